@@ -0,0 +1,453 @@
+//! Batched token transfers.
+//!
+//! An error returned by the token program aborts the transaction, so each
+//! transfer of a batch is checked against the state of its accounts before
+//! it is invoked. A transfer that would fail is classified with
+//! [`classify`]: when [`FailureClass::continues_batch`] holds, the transfer
+//! is skipped and counted in the report, otherwise the batch stops with the
+//! error.
+
+use pinocchio::{
+    account_info::AccountInfo, instruction::Signer, program_error::ProgramError, pubkey::Pubkey,
+};
+
+use crate::{
+    errors::{classify, FailureClass, TokenError},
+    extensions::{get_extension_data, BaseState, ExtensionType},
+    instructions::TransferChecked,
+    state::{classify_account, Mint, Strictness, TokenAccount, TokenAccountKind},
+    validation::{evaluate_transfer, TransferContext},
+};
+
+/// Outcome of a batch of transfers.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BatchReport {
+    /// Number of transfers invoked.
+    pub transferred: u32,
+    /// Number of transfers skipped.
+    pub skipped: u32,
+    /// Number of skipped transfers whose error is classified as
+    /// [`FailureClass::RetryLater`].
+    pub retryable: u32,
+    /// Total amount transferred.
+    pub amount: u64,
+}
+
+impl BatchReport {
+    /// Record the outcome of a transfer, returning the error if the batch
+    /// cannot continue.
+    #[inline(always)]
+    fn record(&mut self, result: Result<u64, ProgramError>) -> Result<(), ProgramError> {
+        match result {
+            Ok(0) => (),
+            Ok(amount) => {
+                self.transferred += 1;
+                self.amount = self.amount.saturating_add(amount);
+            }
+            Err(error) => {
+                let class = classify(&error);
+
+                if !class.continues_batch() {
+                    return Err(error);
+                }
+
+                self.skipped += 1;
+                if class == FailureClass::RetryLater {
+                    self.retryable += 1;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Transfer the whole balance of each of `sources` to `destination`.
+///
+/// `authority` must be the owner of every source account and `signers` its
+/// signer seeds. Empty sources are ignored. Sources that cannot be swept
+/// because of their state (e.g., frozen or in use) are skipped; a source
+/// that is not a token account of `mint` owned by `authority` stops the
+/// batch with the error.
+///
+/// Returns `InvalidArgument` if `mint` has a transfer hook program, since
+/// the transfers would require the accounts of the hook.
+pub fn sweep(
+    sources: &[&AccountInfo],
+    mint: &AccountInfo,
+    destination: &AccountInfo,
+    authority: &AccountInfo,
+    signers: &[Signer],
+) -> Result<BatchReport, ProgramError> {
+    let decimals = transfer_decimals(mint)?;
+    let mut report = BatchReport::default();
+
+    for source in sources {
+        let result = if source.key() == destination.key() {
+            Ok(0)
+        } else {
+            check_transfer(mint, source, destination, authority.key(), None).and_then(|amount| {
+                transfer(
+                    source,
+                    mint,
+                    destination,
+                    authority,
+                    amount,
+                    decimals,
+                    signers,
+                )
+            })
+        };
+
+        report.record(result)?;
+    }
+
+    Ok(report)
+}
+
+/// Transfer each amount of `transfers` from `source` to its destination.
+///
+/// `authority` must be the owner of `source` and `signers` its signer
+/// seeds. Transfers that cannot be invoked because of the state of their
+/// accounts (e.g., the destination requires a memo or the remaining balance
+/// is insufficient) are skipped; a destination that is not a token account
+/// of `mint` stops the batch with the error.
+///
+/// Returns `InvalidArgument` if `mint` has a transfer hook program, since
+/// the transfers would require the accounts of the hook.
+pub fn batch_transfer(
+    source: &AccountInfo,
+    mint: &AccountInfo,
+    authority: &AccountInfo,
+    transfers: &[(&AccountInfo, u64)],
+    signers: &[Signer],
+) -> Result<BatchReport, ProgramError> {
+    let decimals = transfer_decimals(mint)?;
+    let mut report = BatchReport::default();
+
+    for (destination, amount) in transfers {
+        let result = check_transfer(mint, source, destination, authority.key(), Some(*amount))
+            .and_then(|amount| {
+                transfer(
+                    source,
+                    mint,
+                    destination,
+                    authority,
+                    amount,
+                    decimals,
+                    signers,
+                )
+            });
+
+        report.record(result)?;
+    }
+
+    Ok(report)
+}
+
+#[inline(always)]
+fn transfer(
+    source: &AccountInfo,
+    mint: &AccountInfo,
+    destination: &AccountInfo,
+    authority: &AccountInfo,
+    amount: u64,
+    decimals: u8,
+    signers: &[Signer],
+) -> Result<u64, ProgramError> {
+    if amount > 0 {
+        TransferChecked {
+            from: source,
+            mint,
+            to: destination,
+            authority,
+            amount,
+            decimals,
+            token_program: &crate::ID,
+        }
+        .invoke_signed(signers)?;
+    }
+
+    Ok(amount)
+}
+
+/// Return the decimals of `mint`, validating that tokens can be transferred
+/// without additional accounts.
+fn transfer_decimals(mint: &AccountInfo) -> Result<u8, ProgramError> {
+    if !mint.is_owned_by(&crate::ID) {
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+
+    let data = mint.try_borrow_data()?;
+
+    if classify_account(&data, Strictness::Strict)? != TokenAccountKind::Mint {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    // The transfer hook extension holds the authority and the program id.
+    if get_extension_data(&data, BaseState::Mint, ExtensionType::TransferHook)
+        .is_some_and(|hook| hook.len() == 64 && hook[32..] != [0; 32])
+    {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // SAFETY: The data holds a mint.
+    Ok(unsafe { Mint::from_bytes_unchecked(&data) }.decimals())
+}
+
+/// Return the amount to transfer from `source` to `destination`, or the
+/// error the token program would return for the transfer.
+///
+/// The whole balance of `source` is transferred when `amount` is `None`.
+fn check_transfer(
+    mint: &AccountInfo,
+    source: &AccountInfo,
+    destination: &AccountInfo,
+    authority: &Pubkey,
+    amount: Option<u64>,
+) -> Result<u64, ProgramError> {
+    for account in [source, destination] {
+        if !account.is_owned_by(&crate::ID) {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+    }
+
+    let mint_data = mint.try_borrow_data()?;
+    let source_data = source.try_borrow_data()?;
+    let destination_data = destination.try_borrow_data()?;
+
+    for data in [&source_data, &destination_data] {
+        if classify_account(data, Strictness::Strict)? != TokenAccountKind::TokenAccount {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // SAFETY: The data holds a token account.
+        if unsafe { TokenAccount::from_bytes_unchecked(data) }.mint() != mint.key() {
+            return Err(TokenError::MintMismatch.into());
+        }
+    }
+
+    // SAFETY: The data holds a token account.
+    let token_account = unsafe { TokenAccount::from_bytes_unchecked(&source_data) };
+
+    if token_account.owner() != authority {
+        return Err(TokenError::OwnerMismatch.into());
+    }
+
+    let context = TransferContext {
+        is_cpi: true,
+        ..Default::default()
+    };
+
+    if let Some(blocked) = evaluate_transfer(
+        mint.key(),
+        &mint_data,
+        &source_data,
+        &destination_data,
+        context,
+    )? {
+        return Err(blocked.into());
+    }
+
+    let balance = token_account.amount();
+    let amount = amount.unwrap_or(balance);
+
+    if amount > balance {
+        return Err(TokenError::InsufficientFunds.into());
+    }
+
+    Ok(amount)
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::vec::Vec;
+
+    use super::*;
+    use crate::{
+        state::AccountState,
+        test_utils::{
+            account_with_extensions, mint_base, take_captured, token_account_base, TestAccount,
+        },
+    };
+
+    const AUTHORITY: Pubkey = [1; 32];
+    const MINT: Pubkey = [2; 32];
+
+    fn mint() -> AccountInfo {
+        TestAccount::with_data(MINT, crate::ID, &mint_base(None, 1_000, 6, None)).info()
+    }
+
+    fn token_account(key: u8, mint: &Pubkey, amount: u64, state: AccountState) -> AccountInfo {
+        let mut data = token_account_base(mint, &AUTHORITY, amount);
+        data[108] = state as u8;
+
+        TestAccount::with_data([key; 32], crate::ID, &data)
+            .writable()
+            .info()
+    }
+
+    fn transfers() -> Vec<(Pubkey, u64)> {
+        take_captured()
+            .iter()
+            .map(|instruction| {
+                assert_eq!(instruction.data[0], 12);
+                (
+                    instruction.accounts[0].pubkey,
+                    u64::from_le_bytes(instruction.data[1..9].try_into().unwrap()),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_sweep_skips_accounts_by_state() {
+        let mint = mint();
+        let authority = TestAccount::new(AUTHORITY).signer().info();
+        let destination = token_account(3, &MINT, 0, AccountState::Initialized);
+
+        let funded = token_account(10, &MINT, 5, AccountState::Initialized);
+        let frozen = token_account(11, &MINT, 5, AccountState::Frozen);
+        let empty = token_account(12, &MINT, 0, AccountState::Initialized);
+        let in_use = token_account(13, &MINT, 5, AccountState::Initialized);
+        let other = token_account(14, &MINT, 7, AccountState::Initialized);
+
+        let data = in_use.try_borrow_mut_data().unwrap();
+
+        let report = sweep(
+            &[&funded, &frozen, &empty, &in_use, &other],
+            &mint,
+            &destination,
+            &authority,
+            &[],
+        )
+        .unwrap();
+
+        drop(data);
+
+        assert_eq!(
+            report,
+            BatchReport {
+                transferred: 2,
+                skipped: 2,
+                retryable: 1,
+                amount: 12,
+            }
+        );
+        assert_eq!(transfers(), [([10; 32], 5), ([14; 32], 7)]);
+    }
+
+    #[test]
+    fn test_sweep_stops_on_permanent_failure() {
+        let mint = mint();
+        let authority = TestAccount::new(AUTHORITY).signer().info();
+        let destination = token_account(3, &MINT, 0, AccountState::Initialized);
+
+        let funded = token_account(10, &MINT, 5, AccountState::Initialized);
+        let wrong_mint = token_account(11, &[9; 32], 5, AccountState::Initialized);
+        let other = token_account(12, &MINT, 7, AccountState::Initialized);
+
+        assert_eq!(
+            sweep(
+                &[&funded, &wrong_mint, &other],
+                &mint,
+                &destination,
+                &authority,
+                &[],
+            ),
+            Err(TokenError::MintMismatch.into())
+        );
+        assert_eq!(transfers(), [([10; 32], 5)]);
+    }
+
+    #[test]
+    fn test_batch_transfer() {
+        let mint = mint();
+        let authority = TestAccount::new(AUTHORITY).signer().info();
+        let source = token_account(3, &MINT, 100, AccountState::Initialized);
+
+        let first = token_account(10, &MINT, 0, AccountState::Initialized);
+        let memo_required = TestAccount::with_data(
+            [11; 32],
+            crate::ID,
+            &account_with_extensions(
+                BaseState::TokenAccount,
+                &token_account_base(&MINT, &[4; 32], 0),
+                &[(ExtensionType::MemoTransfer, &[1])],
+            ),
+        )
+        .writable()
+        .info();
+        let second = token_account(12, &MINT, 0, AccountState::Initialized);
+
+        let report = batch_transfer(
+            &source,
+            &mint,
+            &authority,
+            &[
+                (&first, 40),
+                (&memo_required, 10),
+                (&second, 500),
+                (&second, 60),
+            ],
+            &[],
+        )
+        .unwrap();
+
+        // The memo and insufficient funds failures need an action from the
+        // user, so the batch continues.
+        assert_eq!(
+            report,
+            BatchReport {
+                transferred: 2,
+                skipped: 2,
+                retryable: 0,
+                amount: 100,
+            }
+        );
+        assert_eq!(transfers(), [([3; 32], 40), ([3; 32], 60)]);
+
+        let wrong_mint = token_account(13, &[9; 32], 0, AccountState::Initialized);
+
+        assert_eq!(
+            batch_transfer(
+                &source,
+                &mint,
+                &authority,
+                &[(&wrong_mint, 1), (&first, 1)],
+                &[],
+            ),
+            Err(TokenError::MintMismatch.into())
+        );
+        assert!(transfers().is_empty());
+    }
+
+    #[test]
+    fn test_batch_rejects_transfer_hook_mint() {
+        let mut transfer_hook = [0; 64];
+        transfer_hook[32..].copy_from_slice(&[5; 32]);
+        let mint = TestAccount::with_data(
+            MINT,
+            crate::ID,
+            &account_with_extensions(
+                BaseState::Mint,
+                &mint_base(None, 1_000, 6, None),
+                &[(ExtensionType::TransferHook, &transfer_hook)],
+            ),
+        )
+        .info();
+        let authority = TestAccount::new(AUTHORITY).signer().info();
+        let source = token_account(3, &MINT, 100, AccountState::Initialized);
+
+        assert_eq!(
+            batch_transfer(&source, &mint, &authority, &[(&source, 1)], &[]),
+            Err(ProgramError::InvalidArgument)
+        );
+        assert_eq!(
+            sweep(&[&source], &mint, &source, &authority, &[]),
+            Err(ProgramError::InvalidArgument)
+        );
+    }
+}
@@ -0,0 +1,736 @@
+//! Internal helpers to build cross-program invocations with a variable
+//! number of accounts.
+
+use core::{mem::MaybeUninit, slice::from_raw_parts};
+
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{AccountMeta, Instruction, Signer},
+    ProgramResult,
+};
+
+pub(crate) use pinocchio_token::instruction_data::IxData;
+
+/// Stack allocated account metas and account infos for a cross-program
+/// invocation.
+///
+/// Accounts are pushed in instruction order, keeping the 1:1 relationship
+/// between metas and infos required by the runtime.
+pub(crate) struct CpiAccounts<'a, const MAX_ACCOUNTS: usize> {
+    metas: [MaybeUninit<AccountMeta<'a>>; MAX_ACCOUNTS],
+    infos: [MaybeUninit<&'a AccountInfo>; MAX_ACCOUNTS],
+    len: usize,
+}
+
+impl<'a, const MAX_ACCOUNTS: usize> CpiAccounts<'a, MAX_ACCOUNTS> {
+    const UNINIT_META: MaybeUninit<AccountMeta<'a>> = MaybeUninit::uninit();
+
+    const UNINIT_INFO: MaybeUninit<&'a AccountInfo> = MaybeUninit::uninit();
+
+    #[inline(always)]
+    pub fn new() -> Self {
+        Self {
+            metas: [Self::UNINIT_META; MAX_ACCOUNTS],
+            infos: [Self::UNINIT_INFO; MAX_ACCOUNTS],
+            len: 0,
+        }
+    }
+
+    /// Append an account to the invocation.
+    ///
+    /// Panics if more than `MAX_ACCOUNTS` accounts are pushed; callers are
+    /// expected to validate variable length inputs beforehand.
+    #[inline(always)]
+    pub fn push(&mut self, account: &'a AccountInfo, is_writable: bool, is_signer: bool) {
+        self.metas[self.len].write(AccountMeta::new(account.key(), is_writable, is_signer));
+        self.infos[self.len].write(account);
+        self.len += 1;
+    }
+
+    /// Append an authority account followed by its (optional) multisig
+    /// signers.
+    ///
+    /// The authority is a signer only when no multisig signers are provided.
+    #[inline(always)]
+    pub fn push_authority(&mut self, authority: &'a AccountInfo, signers: &[&'a AccountInfo]) {
+        self.push(authority, false, signers.is_empty());
+
+        for signer in signers {
+            self.push(signer, false, true);
+        }
+    }
+
+    #[inline(always)]
+    pub fn metas(&self) -> &[AccountMeta<'a>] {
+        // SAFETY: The first `len` metas have been initialized.
+        unsafe { from_raw_parts(self.metas.as_ptr() as _, self.len) }
+    }
+
+    #[inline(always)]
+    pub fn infos(&self) -> &[&'a AccountInfo] {
+        // SAFETY: The first `len` infos have been initialized.
+        unsafe { from_raw_parts(self.infos.as_ptr() as _, self.len) }
+    }
+}
+
+/// Invoke a cross-program instruction from an array of `AccountInfo`s
+/// without signer seeds.
+#[inline(always)]
+pub(crate) fn invoke<const ACCOUNTS: usize>(
+    instruction: &Instruction,
+    account_infos: &[&AccountInfo; ACCOUNTS],
+) -> ProgramResult {
+    invoke_signed(instruction, account_infos, &[])
+}
+
+/// Invoke a cross-program instruction from an array of `AccountInfo`s.
+///
+/// This is a thin wrapper around [`pinocchio::cpi::invoke_signed`], which
+/// allows tests to inspect the instruction being invoked.
+#[inline(always)]
+pub(crate) fn invoke_signed<const ACCOUNTS: usize>(
+    instruction: &Instruction,
+    account_infos: &[&AccountInfo; ACCOUNTS],
+    signers: &[Signer],
+) -> ProgramResult {
+    #[cfg(test)]
+    crate::test_utils::record(instruction, account_infos)?;
+
+    pinocchio::cpi::invoke_signed(instruction, account_infos, signers)
+}
+
+/// Invoke a cross-program instruction with a slice of `AccountInfo`s.
+///
+/// This is a thin wrapper around [`pinocchio::cpi::invoke_signed_with_bounds`],
+/// which allows tests to inspect the instruction being invoked.
+#[inline(always)]
+pub(crate) fn invoke_signed_with_bounds<const MAX_ACCOUNTS: usize>(
+    instruction: &Instruction,
+    account_infos: &[&AccountInfo],
+    signers: &[Signer],
+) -> ProgramResult {
+    #[cfg(test)]
+    crate::test_utils::record(instruction, account_infos)?;
+
+    pinocchio::cpi::invoke_signed_with_bounds::<MAX_ACCOUNTS>(instruction, account_infos, signers)
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use crate::test_utils::{take_single, TestAccount};
+
+    /// Assert that an instruction was invoked with `len` bytes of data.
+    fn assert_data_len(result: ProgramResult, len: usize) {
+        result.unwrap();
+        assert_eq!(take_single().data.len(), len);
+    }
+
+    #[test]
+    fn test_instruction_data_len() {
+        use crate::{
+            extensions::{
+                cpi_guard::{DisableCpiGuard, EnableCpiGuard},
+                default_account_state::{InitializeDefaultAccountState, UpdateDefaultAccountState},
+                group_member_pointer, group_pointer, interest_bearing_mint,
+                memo_transfer::{DisableRequiredMemoTransfers, EnableRequiredMemoTransfers},
+                metadata_pointer,
+                mint_close_authority::InitializeMintCloseAuthority,
+                pausable::{Pause, Resume},
+                scaled_ui_amount,
+                token_group::{
+                    InitializeGroup, InitializeMember, UpdateGroupAuthority, UpdateGroupMaxSize,
+                },
+                token_metadata::{self, Emit, Field, RemoveKey, UpdateAuthority, UpdateField},
+                transfer_fee::{
+                    HarvestWithheldTokensToMint, InitializeTransferFeeConfig, SetTransferFee,
+                    TransferCheckedWithFee, WithdrawWithheldTokensFromAccounts,
+                    WithdrawWithheldTokensFromMint,
+                },
+                transfer_hook::{self, Execute},
+            },
+            instructions::*,
+            state::AccountState,
+        };
+
+        let a = TestAccount::new([1; 32]).writable().info();
+        let b = TestAccount::new([2; 32]).writable().info();
+        let c = TestAccount::new([3; 32]).writable().info();
+        let d = TestAccount::new([4; 32]).signer().info();
+        let e = TestAccount::new([5; 32]).info();
+        let key = [6; 32];
+        let program = crate::ID;
+
+        // Base instructions.
+        for (new_authority, len) in [(Some(&key), 35), (None, 3)] {
+            let set_authority = SetAuthority {
+                account: &a,
+                authority: &d,
+                authority_type: AuthorityType::MintTokens,
+                new_authority,
+                token_program: &program,
+            };
+            assert_data_len(set_authority.invoke(), len);
+        }
+        for (freeze_authority, len) in [(Some(&key), 67), (None, 35)] {
+            let initialize = InitializeMint {
+                mint: &a,
+                rent_sysvar: &e,
+                decimals: 6,
+                mint_authority: &key,
+                freeze_authority,
+                token_program: &program,
+            };
+            assert_data_len(initialize.invoke(), len);
+
+            let initialize = InitializeMint2 {
+                mint: &a,
+                decimals: 6,
+                mint_authority: &key,
+                freeze_authority,
+                token_program: &program,
+            };
+            assert_data_len(initialize.invoke(), len);
+        }
+        let initialize = InitializeAccount2 {
+            account: &a,
+            mint: &b,
+            rent_sysvar: &e,
+            owner: &key,
+            token_program: &program,
+        };
+        assert_data_len(initialize.invoke(), 33);
+        let initialize = InitializeAccount3 {
+            account: &a,
+            mint: &b,
+            owner: &key,
+            token_program: &program,
+        };
+        assert_data_len(initialize.invoke(), 33);
+        let transfer = Transfer {
+            from: &a,
+            to: &b,
+            authority: &d,
+            amount: 1,
+            token_program: &program,
+        };
+        assert_data_len(transfer.invoke(), 9);
+        let transfer = TransferChecked {
+            from: &a,
+            mint: &b,
+            to: &c,
+            authority: &d,
+            amount: 1,
+            decimals: 6,
+            token_program: &program,
+        };
+        assert_data_len(transfer.invoke(), 10);
+        let approve = Approve {
+            source: &a,
+            delegate: &b,
+            authority: &d,
+            amount: 1,
+            token_program: &program,
+        };
+        assert_data_len(approve.invoke(), 9);
+        let approve = ApproveChecked {
+            source: &a,
+            mint: &b,
+            delegate: &c,
+            authority: &d,
+            amount: 1,
+            decimals: 6,
+            token_program: &program,
+        };
+        assert_data_len(approve.invoke(), 10);
+        let burn = Burn {
+            account: &a,
+            mint: &b,
+            authority: &d,
+            amount: 1,
+            token_program: &program,
+        };
+        assert_data_len(burn.invoke(), 9);
+        let burn = BurnChecked {
+            account: &a,
+            mint: &b,
+            authority: &d,
+            amount: 1,
+            decimals: 6,
+            token_program: &program,
+        };
+        assert_data_len(burn.invoke(), 10);
+        let mint_to = MintTo {
+            mint: &a,
+            account: &b,
+            mint_authority: &d,
+            amount: 1,
+            token_program: &program,
+        };
+        assert_data_len(mint_to.invoke(), 9);
+        let mint_to = MintToChecked {
+            mint: &a,
+            account: &b,
+            mint_authority: &d,
+            amount: 1,
+            decimals: 6,
+            token_program: &program,
+        };
+        assert_data_len(mint_to.invoke(), 10);
+        let initialize = InitializeAccount {
+            account: &a,
+            mint: &b,
+            owner: &e,
+            rent_sysvar: &e,
+            token_program: &program,
+        };
+        assert_data_len(initialize.invoke(), 1);
+        let initialize = InitializeMultisig {
+            multisig: &a,
+            rent_sysvar: &e,
+            signers: &[&e],
+            m: 1,
+            token_program: &program,
+        };
+        assert_data_len(initialize.invoke(), 2);
+        let initialize = InitializeMultisig2 {
+            multisig: &a,
+            signers: &[&e],
+            m: 1,
+            token_program: &program,
+        };
+        assert_data_len(initialize.invoke(), 2);
+        let revoke = Revoke {
+            source: &a,
+            authority: &d,
+            token_program: &program,
+        };
+        assert_data_len(revoke.invoke(), 1);
+        let close = CloseAccount {
+            account: &a,
+            destination: &b,
+            authority: &d,
+            token_program: &program,
+        };
+        assert_data_len(close.invoke(), 1);
+        let freeze = FreezeAccount {
+            account: &a,
+            mint: &b,
+            freeze_authority: &d,
+            token_program: &program,
+        };
+        assert_data_len(freeze.invoke(), 1);
+        let thaw = ThawAccount {
+            account: &a,
+            mint: &b,
+            freeze_authority: &d,
+            token_program: &program,
+        };
+        assert_data_len(thaw.invoke(), 1);
+        let sync = SyncNative {
+            native_token: &a,
+            token_program: &program,
+        };
+        assert_data_len(sync.invoke(), 1);
+
+        // Extension instructions.
+        for (close_authority, len) in [(Some(&key), 34), (None, 2)] {
+            let initialize = InitializeMintCloseAuthority {
+                mint: &a,
+                close_authority,
+            };
+            assert_data_len(initialize.invoke(), len);
+        }
+        for (authority, len) in [(Some(&key), 78), (None, 14)] {
+            let initialize = InitializeTransferFeeConfig {
+                mint: &a,
+                transfer_fee_config_authority: authority,
+                withdraw_withheld_authority: authority,
+                transfer_fee_basis_points: 1,
+                maximum_fee: 1,
+            };
+            assert_data_len(initialize.invoke(), len);
+        }
+        let set_transfer_fee = SetTransferFee {
+            mint: &a,
+            authority: &d,
+            signers: &[],
+            transfer_fee_basis_points: 1,
+            maximum_fee: 1,
+        };
+        assert_data_len(set_transfer_fee.invoke(), 12);
+        let transfer = TransferCheckedWithFee {
+            source: &a,
+            mint: &b,
+            destination: &c,
+            authority: &d,
+            signers: &[],
+            amount: 1,
+            decimals: 6,
+            fee: 0,
+        };
+        assert_data_len(transfer.invoke(), 19);
+        let withdraw = WithdrawWithheldTokensFromMint {
+            mint: &a,
+            destination: &b,
+            authority: &d,
+            signers: &[],
+        };
+        assert_data_len(withdraw.invoke(), 2);
+        let withdraw = WithdrawWithheldTokensFromAccounts {
+            mint: &a,
+            destination: &b,
+            authority: &d,
+            signers: &[],
+            sources: &[&c],
+        };
+        assert_data_len(withdraw.invoke(), 3);
+        let harvest = HarvestWithheldTokensToMint {
+            mint: &a,
+            sources: &[&c],
+        };
+        assert_data_len(harvest.invoke(), 2);
+        let initialize = InitializeDefaultAccountState {
+            mint: &a,
+            state: AccountState::Frozen,
+        };
+        assert_data_len(initialize.invoke(), 3);
+        let update = UpdateDefaultAccountState {
+            mint: &a,
+            freeze_authority: &d,
+            signers: &[],
+            state: AccountState::Initialized,
+        };
+        assert_data_len(update.invoke(), 3);
+        let enable = EnableRequiredMemoTransfers {
+            token_account: &a,
+            owner: &d,
+            signers: &[],
+        };
+        assert_data_len(enable.invoke(), 2);
+        let disable = DisableRequiredMemoTransfers {
+            token_account: &a,
+            owner: &d,
+            signers: &[],
+        };
+        assert_data_len(disable.invoke(), 2);
+        let enable = EnableCpiGuard {
+            token_account: &a,
+            owner: &d,
+            signers: &[],
+        };
+        assert_data_len(enable.invoke(), 2);
+        let disable = DisableCpiGuard {
+            token_account: &a,
+            owner: &d,
+            signers: &[],
+        };
+        assert_data_len(disable.invoke(), 2);
+        let pause = Pause {
+            mint: &a,
+            pause_authority: &d,
+            signers: &[],
+        };
+        assert_data_len(pause.invoke(), 2);
+        let resume = Resume {
+            mint: &a,
+            pause_authority: &d,
+            signers: &[],
+        };
+        assert_data_len(resume.invoke(), 2);
+        let initialize = interest_bearing_mint::Initialize {
+            mint: &a,
+            rate_authority: None,
+            rate: 1,
+        };
+        assert_data_len(initialize.invoke(), 36);
+        let initialize = metadata_pointer::Initialize {
+            mint: &a,
+            authority: None,
+            metadata_address: Some(&key),
+        };
+        assert_data_len(initialize.invoke(), 66);
+        let update = group_pointer::Update {
+            mint: &a,
+            authority: &d,
+            signers: &[],
+            group_address: None,
+        };
+        assert_data_len(update.invoke(), 34);
+        let update = group_member_pointer::Update {
+            mint: &a,
+            authority: &d,
+            signers: &[],
+            member_address: None,
+        };
+        assert_data_len(update.invoke(), 34);
+        let update = transfer_hook::Update {
+            mint: &a,
+            authority: &d,
+            signers: &[],
+            program_id: None,
+        };
+        assert_data_len(update.invoke(), 34);
+        let execute = Execute {
+            source: &a,
+            mint: &b,
+            destination: &c,
+            owner: &d,
+            extra_account_metas: &e,
+            extra_accounts: &[],
+            amount: 1,
+            program_id: &key,
+        };
+        assert_data_len(execute.invoke(), 16);
+        let initialize = scaled_ui_amount::Initialize {
+            mint: &a,
+            authority: None,
+            multiplier: 1.0,
+        };
+        assert_data_len(initialize.invoke(), 42);
+        let update = scaled_ui_amount::UpdateMultiplier {
+            mint: &a,
+            authority: &d,
+            signers: &[],
+            multiplier: 1.0,
+            effective_timestamp: 0,
+        };
+        assert_data_len(update.invoke(), 18);
+        let initialize = InitializeGroup {
+            group: &a,
+            mint: &b,
+            mint_authority: &d,
+            update_authority: None,
+            max_size: 1,
+        };
+        assert_data_len(initialize.invoke(), 48);
+        let initialize = InitializeMember {
+            member: &a,
+            member_mint: &b,
+            member_mint_authority: &d,
+            group: &c,
+            group_update_authority: &d,
+        };
+        assert_data_len(initialize.invoke(), 8);
+        let update = UpdateGroupAuthority {
+            group: &a,
+            update_authority: &d,
+            signers: &[],
+            new_authority: None,
+        };
+        assert_data_len(update.invoke(), 40);
+        let update = UpdateGroupMaxSize {
+            group: &a,
+            update_authority: &d,
+            current: None,
+            max_size: 1,
+        };
+        assert_data_len(update.invoke(), 16);
+
+        // Token metadata instructions, with their variable length data.
+        let initialize = token_metadata::Initialize {
+            metadata: &a,
+            update_authority: &e,
+            mint: &b,
+            mint_authority: &d,
+            name: "name",
+            symbol: "sym",
+            uri: "",
+        };
+        assert_data_len(initialize.invoke(), 8 + 4 + 4 + 4 + 3 + 4);
+        let update = UpdateField {
+            metadata: &a,
+            update_authority: &d,
+            field: Field::Key("key"),
+            value: "value",
+        };
+        assert_data_len(update.invoke(), 8 + 1 + 4 + 3 + 4 + 5);
+        let remove = RemoveKey {
+            metadata: &a,
+            update_authority: &d,
+            idempotent: true,
+            key: "key",
+        };
+        assert_data_len(remove.invoke(), 8 + 1 + 4 + 3);
+        let update = UpdateAuthority {
+            metadata: &a,
+            update_authority: &d,
+            signers: &[],
+            new_authority: None,
+        };
+        assert_data_len(update.invoke(), 40);
+        for (start, end, len) in [
+            (None, None, 10),
+            (Some(1), None, 18),
+            (Some(1), Some(2), 26),
+        ] {
+            let emit = Emit {
+                metadata: &a,
+                start,
+                end,
+            };
+            assert_data_len(emit.invoke(), len);
+        }
+    }
+
+    #[cfg(feature = "confidential")]
+    #[test]
+    fn test_confidential_instruction_data_len() {
+        use crate::extensions::{
+            confidential_mint_burn::ApplyPendingBurn,
+            confidential_transfer::{
+                ApplyPendingBalance, Deposit, DisableConfidentialCredits, EmptyAccount,
+                EnableConfidentialCredits, Transfer, TransferWithFee, Withdraw,
+            },
+            confidential_transfer_fee::{
+                DisableHarvestToMint, EnableHarvestToMint, HarvestWithheldTokensToMint,
+                WithdrawWithheldTokensFromAccounts,
+            },
+        };
+
+        let a = TestAccount::new([1; 32]).writable().info();
+        let b = TestAccount::new([2; 32]).writable().info();
+        let c = TestAccount::new([3; 32]).writable().info();
+        let d = TestAccount::new([4; 32]).signer().info();
+        let e = TestAccount::new([5; 32]).info();
+
+        let deposit = Deposit {
+            token_account: &a,
+            mint: &b,
+            owner: &d,
+            signers: &[],
+            amount: 1,
+            decimals: 6,
+        };
+        assert_data_len(deposit.invoke(), 11);
+        let apply = ApplyPendingBalance {
+            token_account: &a,
+            owner: &d,
+            signers: &[],
+            expected_pending_balance_credit_counter: 1,
+            new_decryptable_available_balance: &[0; 36],
+        };
+        assert_data_len(apply.invoke(), 46);
+        let withdraw = Withdraw {
+            token_account: &a,
+            mint: &b,
+            instructions_sysvar: Some(&e),
+            equality_proof_account: None,
+            range_proof_account: None,
+            owner: &d,
+            signers: &[],
+            amount: 1,
+            decimals: 6,
+            new_decryptable_available_balance: &[0; 36],
+            equality_proof_instruction_offset: 1,
+            range_proof_instruction_offset: 2,
+        };
+        assert_data_len(withdraw.invoke(), 49);
+        let transfer = Transfer {
+            source: &a,
+            mint: &b,
+            destination: &c,
+            instructions_sysvar: Some(&e),
+            equality_proof_account: None,
+            ciphertext_validity_proof_account: None,
+            range_proof_account: None,
+            owner: &d,
+            signers: &[],
+            new_source_decryptable_available_balance: &[0; 36],
+            transfer_amount_auditor_ciphertext_lo: &[0; 64],
+            transfer_amount_auditor_ciphertext_hi: &[0; 64],
+            equality_proof_instruction_offset: 1,
+            ciphertext_validity_proof_instruction_offset: 2,
+            range_proof_instruction_offset: 3,
+        };
+        assert_data_len(transfer.invoke(), 169);
+        let transfer = TransferWithFee {
+            source: &a,
+            mint: &b,
+            destination: &c,
+            instructions_sysvar: Some(&e),
+            equality_proof_account: None,
+            transfer_amount_ciphertext_validity_proof_account: None,
+            fee_sigma_proof_account: None,
+            fee_ciphertext_validity_proof_account: None,
+            range_proof_account: None,
+            owner: &d,
+            signers: &[],
+            new_source_decryptable_available_balance: &[0; 36],
+            transfer_amount_auditor_ciphertext_lo: &[0; 64],
+            transfer_amount_auditor_ciphertext_hi: &[0; 64],
+            equality_proof_instruction_offset: 1,
+            transfer_amount_ciphertext_validity_proof_instruction_offset: 2,
+            fee_sigma_proof_instruction_offset: 3,
+            fee_ciphertext_validity_proof_instruction_offset: 4,
+            range_proof_instruction_offset: 5,
+        };
+        assert_data_len(transfer.invoke(), 171);
+        let withdraw = WithdrawWithheldTokensFromAccounts {
+            mint: &a,
+            destination: &b,
+            proof_account: &e,
+            record_account: None,
+            authority: &d,
+            signers: &[],
+            sources: &[&c],
+            proof_instruction_offset: 1,
+            new_decryptable_available_balance: &[0; 36],
+        };
+        assert_data_len(withdraw.invoke(), 40);
+        let empty = EmptyAccount {
+            token_account: &a,
+            proof_account: &e,
+            record_account: None,
+            owner: &d,
+            signers: &[],
+            proof_instruction_offset: 1,
+        };
+        assert_data_len(empty.invoke(), 3);
+        let enable = EnableConfidentialCredits {
+            token_account: &a,
+            owner: &d,
+            signers: &[],
+        };
+        assert_data_len(enable.invoke(), 2);
+        let disable = DisableConfidentialCredits {
+            token_account: &a,
+            owner: &d,
+            signers: &[],
+        };
+        assert_data_len(disable.invoke(), 2);
+        let harvest = HarvestWithheldTokensToMint {
+            mint: &a,
+            sources: &[&c],
+        };
+        assert_data_len(harvest.invoke(), 2);
+        let enable = EnableHarvestToMint {
+            mint: &a,
+            authority: &d,
+            signers: &[],
+        };
+        assert_data_len(enable.invoke(), 2);
+        let disable = DisableHarvestToMint {
+            mint: &a,
+            authority: &d,
+            signers: &[],
+        };
+        assert_data_len(disable.invoke(), 2);
+        let apply = ApplyPendingBurn {
+            mint: &a,
+            authority: &d,
+            signers: &[],
+        };
+        assert_data_len(apply.invoke(), 2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_ix_data_overflow() {
+        let mut data = IxData::<2>::new();
+        data.write_u8(1);
+        data.write_u16_le(2);
+    }
+}
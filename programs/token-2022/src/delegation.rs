@@ -0,0 +1,241 @@
+//! Helpers for temporary token delegations.
+
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{AccountMeta, Signer},
+    program_error::ProgramError,
+    ProgramResult,
+};
+
+use crate::{
+    ids::which_token_program,
+    instructions::{ApproveChecked, Revoke},
+    plan::CpiPlan,
+};
+
+/// Approve `delegate` for `amount` tokens of `source`, run `body` and revoke
+/// the delegation.
+///
+/// This is the common "approve-and-call" pattern, where a program approves
+/// another program (or its PDA) to pull exactly `amount` tokens, invokes the
+/// program and then clears the approval. The revoke is always attempted
+/// after `body` runs, so the approval does not linger when `body` fails; in
+/// that case the error returned by `body` takes precedence over any error
+/// from the revoke. An error from the approve is returned immediately, since
+/// no delegation was created.
+///
+/// The token program invoked is the owner of the `mint` account, so the helper
+/// works with both SPL Token and SPL Token-2022 accounts. Returns
+/// `IncorrectProgramId` without invoking anything if `mint` is owned by any
+/// other program, so `signers` are never passed to a program chosen by the
+/// caller's accounts. `signers` are used to sign both the approve and the
+/// revoke on behalf of the `owner`.
+///
+/// # Reentrancy
+///
+/// While `body` runs, the delegate is able to move up to `amount` tokens
+/// from `source`, including through nested CPIs back into the calling
+/// program. The revoke only clears any remaining allowance: tokens moved by
+/// the delegate are not restored. Callers should also not rely on the
+/// delegation being in place after `body` returns, and `body` must not
+/// re-approve `source` since the approval would be revoked on return.
+///
+/// When this function returns an error that the calling program propagates,
+/// the runtime rolls back the whole transaction, including the approve, so
+/// the revoke on failure only matters to callers that handle the error and
+/// continue.
+#[allow(clippy::too_many_arguments)]
+pub fn scoped_approval<F>(
+    source: &AccountInfo,
+    mint: &AccountInfo,
+    owner: &AccountInfo,
+    delegate: &AccountInfo,
+    amount: u64,
+    decimals: u8,
+    signers: &[Signer],
+    body: F,
+) -> ProgramResult
+where
+    F: FnOnce() -> ProgramResult,
+{
+    let token_program = which_token_program(mint.owner())
+        .ok_or(ProgramError::IncorrectProgramId)?
+        .id();
+
+    ApproveChecked {
+        source,
+        mint,
+        delegate,
+        authority: owner,
+        amount,
+        decimals,
+        token_program,
+    }
+    .invoke_signed(signers)?;
+
+    let result = body();
+
+    let revoke = Revoke {
+        source,
+        authority: owner,
+        token_program,
+    }
+    .invoke_signed(signers);
+
+    result.and(revoke)
+}
+
+/// Add the CPIs of [`scoped_approval`] to `plan`, without invoking them.
+///
+/// CPIs invoked by `body` are not included. Returns `IncorrectProgramId` if
+/// `mint` is not owned by a token program, as [`scoped_approval`] does.
+pub fn scoped_approval_dry_run<'a, const MAX_ACCOUNTS: usize>(
+    source: &'a AccountInfo,
+    mint: &'a AccountInfo,
+    owner: &'a AccountInfo,
+    delegate: &'a AccountInfo,
+    plan: &mut CpiPlan<'a, MAX_ACCOUNTS>,
+) -> Result<(), ProgramError> {
+    if which_token_program(mint.owner()).is_none() {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    // ApproveChecked
+    plan.add_cpi(&[
+        AccountMeta::writable(source.key()),
+        AccountMeta::readonly(mint.key()),
+        AccountMeta::readonly(delegate.key()),
+        AccountMeta::readonly_signer(owner.key()),
+    ])?;
+    // Revoke
+    plan.add_cpi(&[
+        AccountMeta::writable(source.key()),
+        AccountMeta::readonly_signer(owner.key()),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use pinocchio::program_error::ProgramError;
+
+    use super::*;
+    use crate::test_utils::{take_captured, TestAccount};
+
+    #[test]
+    fn test_scoped_approval_revokes_on_success() {
+        let source = TestAccount::new([1; 32]).writable();
+        let mint = TestAccount::new([2; 32]);
+        let owner = TestAccount::new([3; 32]).signer();
+        let delegate = TestAccount::new([4; 32]);
+
+        let result = scoped_approval(
+            &source.info(),
+            &mint.info(),
+            &owner.info(),
+            &delegate.info(),
+            100,
+            6,
+            &[],
+            || Ok(()),
+        );
+
+        assert_eq!(result, Ok(()));
+
+        let instructions = take_captured();
+        assert_eq!(instructions.len(), 2);
+        // ApproveChecked
+        assert_eq!(instructions[0].program_id, crate::ID);
+        assert_eq!(instructions[0].data[0], 13);
+        assert_eq!(&instructions[0].data[1..9], &100u64.to_le_bytes());
+        // Revoke
+        assert_eq!(instructions[1].program_id, crate::ID);
+        assert_eq!(instructions[1].data, [5]);
+    }
+
+    #[test]
+    fn test_scoped_approval_revokes_on_body_failure() {
+        let source = TestAccount::new([1; 32]).writable();
+        let mint = TestAccount::new([2; 32]);
+        let owner = TestAccount::new([3; 32]).signer();
+        let delegate = TestAccount::new([4; 32]);
+
+        let result = scoped_approval(
+            &source.info(),
+            &mint.info(),
+            &owner.info(),
+            &delegate.info(),
+            100,
+            6,
+            &[],
+            || {
+                // The body observes the approval but not the revoke.
+                assert_eq!(take_captured().len(), 1);
+                Err(ProgramError::Custom(42))
+            },
+        );
+
+        assert_eq!(result, Err(ProgramError::Custom(42)));
+
+        let instructions = take_captured();
+        assert_eq!(instructions.len(), 1);
+        assert_eq!(instructions[0].data, [5]);
+    }
+
+    #[test]
+    fn test_scoped_approval_rejects_foreign_mint() {
+        let source = TestAccount::new([1; 32]).writable().info();
+        let mint = TestAccount::with_data([2; 32], [9; 32], &[]).info();
+        let owner = TestAccount::new([3; 32]).signer().info();
+        let delegate = TestAccount::new([4; 32]).info();
+
+        let result = scoped_approval(&source, &mint, &owner, &delegate, 1, 0, &[], || {
+            panic!("body must not run")
+        });
+
+        assert_eq!(result, Err(ProgramError::IncorrectProgramId));
+        assert!(take_captured().is_empty());
+
+        let mut plan = CpiPlan::<4>::new();
+        assert_eq!(
+            scoped_approval_dry_run(&source, &mint, &owner, &delegate, &mut plan),
+            Err(ProgramError::IncorrectProgramId)
+        );
+        assert_eq!(plan.cpi_count(), 0);
+    }
+
+    #[test]
+    fn test_scoped_approval_dry_run_matches_invocation() {
+        let source = TestAccount::new([1; 32]).writable().info();
+        let mint = TestAccount::new([2; 32]).info();
+        let owner = TestAccount::new([3; 32]).signer().info();
+        let delegate = TestAccount::new([4; 32]).info();
+
+        let mut plan = CpiPlan::<4>::new();
+        scoped_approval_dry_run(&source, &mint, &owner, &delegate, &mut plan).unwrap();
+
+        scoped_approval(&source, &mint, &owner, &delegate, 1, 0, &[], || Ok(())).unwrap();
+        let instructions = take_captured();
+
+        assert_eq!(plan.cpi_count(), instructions.len());
+        assert_eq!(plan.check(), Ok(()));
+
+        for account in plan.unique_accounts() {
+            let metas = instructions
+                .iter()
+                .flat_map(|instruction| instruction.accounts.iter())
+                .filter(|meta| &meta.pubkey == account.key);
+
+            let (is_writable, is_signer) = metas.fold((false, false), |(w, s), meta| {
+                (w || meta.is_writable, s || meta.is_signer)
+            });
+
+            assert_eq!(
+                (account.is_writable, account.is_signer),
+                (is_writable, is_signer)
+            );
+        }
+        assert_eq!(plan.unique_accounts().len(), 4);
+    }
+}
@@ -0,0 +1,653 @@
+//! Errors returned by the Token-2022 program and their classification.
+//!
+//! Token CPIs fail with `ProgramError::Custom` codes defined by the token
+//! program. [`classify`] maps those codes, and the runtime errors returned
+//! before or by a CPI, to whether the operation is worth retrying, which
+//! lets batch helpers decide to continue after an individual failure.
+
+use pinocchio::program_error::ProgramError;
+
+/// Errors of the Token-2022 program, as returned in `ProgramError::Custom`.
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TokenError {
+    /// Lamport balance below rent-exempt threshold.
+    NotRentExempt,
+    /// Insufficient funds for the operation requested.
+    InsufficientFunds,
+    /// Invalid Mint.
+    InvalidMint,
+    /// Account not associated with this Mint.
+    MintMismatch,
+    /// Owner does not match.
+    OwnerMismatch,
+    /// This token's supply is fixed and new tokens cannot be minted.
+    FixedSupply,
+    /// The account cannot be initialized because it is already being used.
+    AlreadyInUse,
+    /// Invalid number of provided signers.
+    InvalidNumberOfProvidedSigners,
+    /// Invalid number of required signers.
+    InvalidNumberOfRequiredSigners,
+    /// State is uninitialized.
+    UninitializedState,
+    /// Instruction does not support native tokens.
+    NativeNotSupported,
+    /// Non-native account can only be closed if its balance is zero.
+    NonNativeHasBalance,
+    /// Invalid instruction.
+    InvalidInstruction,
+    /// State is invalid for requested operation.
+    InvalidState,
+    /// Operation overflowed.
+    Overflow,
+    /// Account does not support specified authority type.
+    AuthorityTypeNotSupported,
+    /// This token mint cannot freeze accounts.
+    MintCannotFreeze,
+    /// The account is frozen.
+    AccountFrozen,
+    /// The provided decimals value different from the Mint decimals.
+    MintDecimalsMismatch,
+    /// Instruction does not support non-native tokens.
+    NonNativeNotSupported,
+    /// Extension type does not match already existing extensions.
+    ExtensionTypeMismatch,
+    /// Extension does not match the base type provided.
+    ExtensionBaseMismatch,
+    /// Extension already initialized on this account.
+    ExtensionAlreadyInitialized,
+    /// An account can only be closed if its confidential balance is zero.
+    ConfidentialTransferAccountHasBalance,
+    /// Account not approved for confidential transfers.
+    ConfidentialTransferAccountNotApproved,
+    /// Account not accepting deposits or transfers.
+    ConfidentialTransferDepositsAndTransfersDisabled,
+    /// ElGamal public key mismatch.
+    ConfidentialTransferElGamalPubkeyMismatch,
+    /// Balance mismatch.
+    ConfidentialTransferBalanceMismatch,
+    /// Mint has non-zero supply. Burn all tokens before closing the mint.
+    MintHasSupply,
+    /// No authority exists to perform the desired operation.
+    NoAuthorityExists,
+    /// Transfer fee exceeds maximum of 10,000 basis points.
+    TransferFeeExceedsMaximum,
+    /// Mint required for this account to transfer tokens, use `transfer_checked` or `transfer_checked_with_fee`.
+    MintRequiredForTransfer,
+    /// Calculated fee does not match expected fee.
+    FeeMismatch,
+    /// Fee parameters associated with confidential transfer zero-knowledge proofs do not match fee parameters in mint.
+    FeeParametersMismatch,
+    /// The owner authority cannot be changed.
+    ImmutableOwner,
+    /// An account can only be closed if its withheld fee balance is zero, harvest fees to the mint and try again.
+    AccountHasWithheldTransferFees,
+    /// No memo in previous instruction; required for recipient to receive a transfer.
+    NoMemo,
+    /// Transfer is disabled for this mint.
+    NonTransferable,
+    /// Non-transferable tokens can't be minted to an account without immutable ownership.
+    NonTransferableNeedsImmutableOwnership,
+    /// The total number of `Deposit` and `Transfer` instructions to an account cannot exceed the associated `maximum_pending_balance_credit_counter`.
+    MaximumPendingBalanceCreditCounterExceeded,
+    /// The deposit amount for the confidential extension exceeds the maximum limit.
+    MaximumDepositAmountExceeded,
+    /// CPI Guard cannot be enabled or disabled in CPI.
+    CpiGuardSettingsLocked,
+    /// CPI Guard is enabled, and a program attempted to transfer user funds without using a delegate.
+    CpiGuardTransferBlocked,
+    /// CPI Guard is enabled, and a program attempted to burn user funds without using a delegate.
+    CpiGuardBurnBlocked,
+    /// CPI Guard is enabled, and a program attempted to close an account without returning lamports to owner.
+    CpiGuardCloseAccountBlocked,
+    /// CPI Guard is enabled, and a program attempted to approve a delegate.
+    CpiGuardApproveBlocked,
+    /// CPI Guard is enabled, and a program attempted to add or replace an authority.
+    CpiGuardSetAuthorityBlocked,
+    /// Account ownership cannot be changed while CPI Guard is enabled.
+    CpiGuardOwnerChangeBlocked,
+    /// Extension not found in account data.
+    ExtensionNotFound,
+    /// Account does not accept non-confidential transfers.
+    NonConfidentialTransfersDisabled,
+    /// An account can only be closed if the confidential withheld fee is zero.
+    ConfidentialTransferFeeAccountHasWithheldFee,
+    /// A mint or an account is initialized to an invalid combination of extensions.
+    InvalidExtensionCombination,
+    /// Extension allocation with overwrite must use the same length.
+    InvalidLengthForAlloc,
+    /// Failed to decrypt a confidential transfer account.
+    AccountDecryption,
+    /// Failed to generate a zero-knowledge proof needed for a token instruction.
+    ProofGeneration,
+    /// An invalid proof instruction offset was provided.
+    InvalidProofInstructionOffset,
+    /// Harvest of withheld tokens to mint is disabled.
+    HarvestToMintDisabled,
+    /// Split proof context state accounts not supported for instruction.
+    SplitProofContextStateAccountsNotSupported,
+    /// Not enough proof context state accounts provided.
+    NotEnoughProofContextStateAccounts,
+    /// Ciphertext is malformed.
+    MalformedCiphertext,
+    /// Ciphertext arithmetic failed.
+    CiphertextArithmeticFailed,
+    /// Pedersen commitment mismatch.
+    PedersenCommitmentMismatch,
+    /// Range proof length mismatch.
+    RangeProofLengthMismatch,
+    /// Illegal transfer amount bit length.
+    IllegalBitLength,
+    /// Fee calculation failed.
+    FeeCalculation,
+    /// Withdraw / Deposit not allowed for confidential-mint-burn.
+    IllegalMintBurnConversion,
+    /// Invalid scale for scaled UI amount.
+    InvalidScale,
+    /// Transferring, minting, and burning is paused on this mint.
+    MintPaused,
+    /// Pending supply is not zero.
+    PendingBalanceNonZero,
+}
+
+impl TryFrom<u32> for TokenError {
+    type Error = ProgramError;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(TokenError::NotRentExempt),
+            1 => Ok(TokenError::InsufficientFunds),
+            2 => Ok(TokenError::InvalidMint),
+            3 => Ok(TokenError::MintMismatch),
+            4 => Ok(TokenError::OwnerMismatch),
+            5 => Ok(TokenError::FixedSupply),
+            6 => Ok(TokenError::AlreadyInUse),
+            7 => Ok(TokenError::InvalidNumberOfProvidedSigners),
+            8 => Ok(TokenError::InvalidNumberOfRequiredSigners),
+            9 => Ok(TokenError::UninitializedState),
+            10 => Ok(TokenError::NativeNotSupported),
+            11 => Ok(TokenError::NonNativeHasBalance),
+            12 => Ok(TokenError::InvalidInstruction),
+            13 => Ok(TokenError::InvalidState),
+            14 => Ok(TokenError::Overflow),
+            15 => Ok(TokenError::AuthorityTypeNotSupported),
+            16 => Ok(TokenError::MintCannotFreeze),
+            17 => Ok(TokenError::AccountFrozen),
+            18 => Ok(TokenError::MintDecimalsMismatch),
+            19 => Ok(TokenError::NonNativeNotSupported),
+            20 => Ok(TokenError::ExtensionTypeMismatch),
+            21 => Ok(TokenError::ExtensionBaseMismatch),
+            22 => Ok(TokenError::ExtensionAlreadyInitialized),
+            23 => Ok(TokenError::ConfidentialTransferAccountHasBalance),
+            24 => Ok(TokenError::ConfidentialTransferAccountNotApproved),
+            25 => Ok(TokenError::ConfidentialTransferDepositsAndTransfersDisabled),
+            26 => Ok(TokenError::ConfidentialTransferElGamalPubkeyMismatch),
+            27 => Ok(TokenError::ConfidentialTransferBalanceMismatch),
+            28 => Ok(TokenError::MintHasSupply),
+            29 => Ok(TokenError::NoAuthorityExists),
+            30 => Ok(TokenError::TransferFeeExceedsMaximum),
+            31 => Ok(TokenError::MintRequiredForTransfer),
+            32 => Ok(TokenError::FeeMismatch),
+            33 => Ok(TokenError::FeeParametersMismatch),
+            34 => Ok(TokenError::ImmutableOwner),
+            35 => Ok(TokenError::AccountHasWithheldTransferFees),
+            36 => Ok(TokenError::NoMemo),
+            37 => Ok(TokenError::NonTransferable),
+            38 => Ok(TokenError::NonTransferableNeedsImmutableOwnership),
+            39 => Ok(TokenError::MaximumPendingBalanceCreditCounterExceeded),
+            40 => Ok(TokenError::MaximumDepositAmountExceeded),
+            41 => Ok(TokenError::CpiGuardSettingsLocked),
+            42 => Ok(TokenError::CpiGuardTransferBlocked),
+            43 => Ok(TokenError::CpiGuardBurnBlocked),
+            44 => Ok(TokenError::CpiGuardCloseAccountBlocked),
+            45 => Ok(TokenError::CpiGuardApproveBlocked),
+            46 => Ok(TokenError::CpiGuardSetAuthorityBlocked),
+            47 => Ok(TokenError::CpiGuardOwnerChangeBlocked),
+            48 => Ok(TokenError::ExtensionNotFound),
+            49 => Ok(TokenError::NonConfidentialTransfersDisabled),
+            50 => Ok(TokenError::ConfidentialTransferFeeAccountHasWithheldFee),
+            51 => Ok(TokenError::InvalidExtensionCombination),
+            52 => Ok(TokenError::InvalidLengthForAlloc),
+            53 => Ok(TokenError::AccountDecryption),
+            54 => Ok(TokenError::ProofGeneration),
+            55 => Ok(TokenError::InvalidProofInstructionOffset),
+            56 => Ok(TokenError::HarvestToMintDisabled),
+            57 => Ok(TokenError::SplitProofContextStateAccountsNotSupported),
+            58 => Ok(TokenError::NotEnoughProofContextStateAccounts),
+            59 => Ok(TokenError::MalformedCiphertext),
+            60 => Ok(TokenError::CiphertextArithmeticFailed),
+            61 => Ok(TokenError::PedersenCommitmentMismatch),
+            62 => Ok(TokenError::RangeProofLengthMismatch),
+            63 => Ok(TokenError::IllegalBitLength),
+            64 => Ok(TokenError::FeeCalculation),
+            65 => Ok(TokenError::IllegalMintBurnConversion),
+            66 => Ok(TokenError::InvalidScale),
+            67 => Ok(TokenError::MintPaused),
+            68 => Ok(TokenError::PendingBalanceNonZero),
+            _ => Err(ProgramError::InvalidArgument),
+        }
+    }
+}
+
+impl From<TokenError> for ProgramError {
+    fn from(error: TokenError) -> Self {
+        ProgramError::Custom(error as u32)
+    }
+}
+
+impl TokenError {
+    /// Return the token error of a failed token CPI.
+    ///
+    /// Returns `None` if the error is not a custom error or its code does not
+    /// correspond to a `TokenError`.
+    #[inline]
+    pub fn from_program_error(err: &ProgramError) -> Option<TokenError> {
+        match err {
+            ProgramError::Custom(code) => TokenError::try_from(*code).ok(),
+            _ => None,
+        }
+    }
+}
+
+/// Whether a failed token operation is worth retrying.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FailureClass {
+    /// The operation can never succeed with the same accounts and
+    /// arguments.
+    Permanent,
+
+    /// The operation may succeed later without any change from the user,
+    /// e.g., once an account is no longer in use or a mint is resumed.
+    RetryLater,
+
+    /// The operation requires an action from the account owner, e.g.,
+    /// funding an account or disabling the CPI guard.
+    NeedsUserAction,
+
+    /// The error is not known by this crate.
+    Unknown,
+}
+
+impl FailureClass {
+    /// Return whether a batch should continue with its next item after an
+    /// item failed with this class.
+    ///
+    /// `RetryLater` and `NeedsUserAction` failures depend on the state of the
+    /// accounts of the failed item, so the item is skipped. `Permanent` and
+    /// `Unknown` failures indicate invalid arguments, which stop the batch.
+    #[inline(always)]
+    pub const fn continues_batch(self) -> bool {
+        matches!(
+            self,
+            FailureClass::RetryLater | FailureClass::NeedsUserAction
+        )
+    }
+}
+
+/// Return the failure class of a token program error.
+pub const fn classify_token_error(error: TokenError) -> FailureClass {
+    match error {
+        TokenError::NotRentExempt => FailureClass::NeedsUserAction,
+        TokenError::InsufficientFunds => FailureClass::NeedsUserAction,
+        TokenError::InvalidMint => FailureClass::Permanent,
+        TokenError::MintMismatch => FailureClass::Permanent,
+        TokenError::OwnerMismatch => FailureClass::Permanent,
+        TokenError::FixedSupply => FailureClass::Permanent,
+        TokenError::AlreadyInUse => FailureClass::Permanent,
+        TokenError::InvalidNumberOfProvidedSigners => FailureClass::Permanent,
+        TokenError::InvalidNumberOfRequiredSigners => FailureClass::Permanent,
+        TokenError::UninitializedState => FailureClass::Permanent,
+        TokenError::NativeNotSupported => FailureClass::Permanent,
+        TokenError::NonNativeHasBalance => FailureClass::NeedsUserAction,
+        TokenError::InvalidInstruction => FailureClass::Permanent,
+        TokenError::InvalidState => FailureClass::Permanent,
+        TokenError::Overflow => FailureClass::Permanent,
+        TokenError::AuthorityTypeNotSupported => FailureClass::Permanent,
+        TokenError::MintCannotFreeze => FailureClass::Permanent,
+        TokenError::AccountFrozen => FailureClass::NeedsUserAction,
+        TokenError::MintDecimalsMismatch => FailureClass::Permanent,
+        TokenError::NonNativeNotSupported => FailureClass::Permanent,
+        TokenError::ExtensionTypeMismatch => FailureClass::Permanent,
+        TokenError::ExtensionBaseMismatch => FailureClass::Permanent,
+        TokenError::ExtensionAlreadyInitialized => FailureClass::Permanent,
+        TokenError::ConfidentialTransferAccountHasBalance => FailureClass::NeedsUserAction,
+        TokenError::ConfidentialTransferAccountNotApproved => FailureClass::NeedsUserAction,
+        TokenError::ConfidentialTransferDepositsAndTransfersDisabled => {
+            FailureClass::NeedsUserAction
+        }
+        TokenError::ConfidentialTransferElGamalPubkeyMismatch => FailureClass::Permanent,
+        TokenError::ConfidentialTransferBalanceMismatch => FailureClass::Permanent,
+        TokenError::MintHasSupply => FailureClass::NeedsUserAction,
+        TokenError::NoAuthorityExists => FailureClass::Permanent,
+        TokenError::TransferFeeExceedsMaximum => FailureClass::Permanent,
+        TokenError::MintRequiredForTransfer => FailureClass::Permanent,
+        TokenError::FeeMismatch => FailureClass::Permanent,
+        TokenError::FeeParametersMismatch => FailureClass::Permanent,
+        TokenError::ImmutableOwner => FailureClass::Permanent,
+        TokenError::AccountHasWithheldTransferFees => FailureClass::NeedsUserAction,
+        TokenError::NoMemo => FailureClass::NeedsUserAction,
+        TokenError::NonTransferable => FailureClass::Permanent,
+        TokenError::NonTransferableNeedsImmutableOwnership => FailureClass::Permanent,
+        TokenError::MaximumPendingBalanceCreditCounterExceeded => FailureClass::NeedsUserAction,
+        TokenError::MaximumDepositAmountExceeded => FailureClass::Permanent,
+        TokenError::CpiGuardSettingsLocked => FailureClass::Permanent,
+        TokenError::CpiGuardTransferBlocked => FailureClass::NeedsUserAction,
+        TokenError::CpiGuardBurnBlocked => FailureClass::NeedsUserAction,
+        TokenError::CpiGuardCloseAccountBlocked => FailureClass::NeedsUserAction,
+        TokenError::CpiGuardApproveBlocked => FailureClass::NeedsUserAction,
+        TokenError::CpiGuardSetAuthorityBlocked => FailureClass::NeedsUserAction,
+        TokenError::CpiGuardOwnerChangeBlocked => FailureClass::NeedsUserAction,
+        TokenError::ExtensionNotFound => FailureClass::Permanent,
+        TokenError::NonConfidentialTransfersDisabled => FailureClass::NeedsUserAction,
+        TokenError::ConfidentialTransferFeeAccountHasWithheldFee => FailureClass::NeedsUserAction,
+        TokenError::InvalidExtensionCombination => FailureClass::Permanent,
+        TokenError::InvalidLengthForAlloc => FailureClass::Permanent,
+        TokenError::AccountDecryption => FailureClass::Permanent,
+        TokenError::ProofGeneration => FailureClass::Permanent,
+        TokenError::InvalidProofInstructionOffset => FailureClass::Permanent,
+        TokenError::HarvestToMintDisabled => FailureClass::NeedsUserAction,
+        TokenError::SplitProofContextStateAccountsNotSupported => FailureClass::Permanent,
+        TokenError::NotEnoughProofContextStateAccounts => FailureClass::Permanent,
+        TokenError::MalformedCiphertext => FailureClass::Permanent,
+        TokenError::CiphertextArithmeticFailed => FailureClass::Permanent,
+        TokenError::PedersenCommitmentMismatch => FailureClass::Permanent,
+        TokenError::RangeProofLengthMismatch => FailureClass::Permanent,
+        TokenError::IllegalBitLength => FailureClass::Permanent,
+        TokenError::FeeCalculation => FailureClass::Permanent,
+        TokenError::IllegalMintBurnConversion => FailureClass::Permanent,
+        TokenError::InvalidScale => FailureClass::Permanent,
+        TokenError::MintPaused => FailureClass::RetryLater,
+        TokenError::PendingBalanceNonZero => FailureClass::NeedsUserAction,
+    }
+}
+
+/// Classify the error of a failed token CPI.
+///
+/// Custom errors are interpreted as Token-2022 errors; codes that do not
+/// correspond to a [`TokenError`] are [`FailureClass::Unknown`].
+pub fn classify(err: &ProgramError) -> FailureClass {
+    match err {
+        ProgramError::Custom(_) => match TokenError::from_program_error(err) {
+            Some(error) => classify_token_error(error),
+            None => FailureClass::Unknown,
+        },
+        // The account is in use by another borrow or the transaction is
+        // over a per-transaction limit.
+        ProgramError::AccountBorrowFailed
+        | ProgramError::MaxAccountsDataAllocationsExceeded
+        | ProgramError::MaxInstructionTraceLengthExceeded => FailureClass::RetryLater,
+        // Lamport balances need to be topped up.
+        ProgramError::InsufficientFunds | ProgramError::AccountNotRentExempt => {
+            FailureClass::NeedsUserAction
+        }
+        ProgramError::InvalidArgument
+        | ProgramError::InvalidInstructionData
+        | ProgramError::InvalidAccountData
+        | ProgramError::AccountDataTooSmall
+        | ProgramError::IncorrectProgramId
+        | ProgramError::MissingRequiredSignature
+        | ProgramError::AccountAlreadyInitialized
+        | ProgramError::UninitializedAccount
+        | ProgramError::NotEnoughAccountKeys
+        | ProgramError::MaxSeedLengthExceeded
+        | ProgramError::InvalidSeeds
+        | ProgramError::UnsupportedSysvar
+        | ProgramError::IllegalOwner
+        | ProgramError::InvalidRealloc
+        | ProgramError::InvalidAccountOwner
+        | ProgramError::ArithmeticOverflow
+        | ProgramError::Immutable
+        | ProgramError::IncorrectAuthority => FailureClass::Permanent,
+        ProgramError::BorshIoError | ProgramError::BuiltinProgramsMustConsumeComputeUnits => {
+            FailureClass::Unknown
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Expected class of every token error, in discriminant order.
+    const CLASSIFICATION: &[(TokenError, FailureClass)] = &[
+        (TokenError::NotRentExempt, FailureClass::NeedsUserAction),
+        (TokenError::InsufficientFunds, FailureClass::NeedsUserAction),
+        (TokenError::InvalidMint, FailureClass::Permanent),
+        (TokenError::MintMismatch, FailureClass::Permanent),
+        (TokenError::OwnerMismatch, FailureClass::Permanent),
+        (TokenError::FixedSupply, FailureClass::Permanent),
+        (TokenError::AlreadyInUse, FailureClass::Permanent),
+        (
+            TokenError::InvalidNumberOfProvidedSigners,
+            FailureClass::Permanent,
+        ),
+        (
+            TokenError::InvalidNumberOfRequiredSigners,
+            FailureClass::Permanent,
+        ),
+        (TokenError::UninitializedState, FailureClass::Permanent),
+        (TokenError::NativeNotSupported, FailureClass::Permanent),
+        (
+            TokenError::NonNativeHasBalance,
+            FailureClass::NeedsUserAction,
+        ),
+        (TokenError::InvalidInstruction, FailureClass::Permanent),
+        (TokenError::InvalidState, FailureClass::Permanent),
+        (TokenError::Overflow, FailureClass::Permanent),
+        (
+            TokenError::AuthorityTypeNotSupported,
+            FailureClass::Permanent,
+        ),
+        (TokenError::MintCannotFreeze, FailureClass::Permanent),
+        (TokenError::AccountFrozen, FailureClass::NeedsUserAction),
+        (TokenError::MintDecimalsMismatch, FailureClass::Permanent),
+        (TokenError::NonNativeNotSupported, FailureClass::Permanent),
+        (TokenError::ExtensionTypeMismatch, FailureClass::Permanent),
+        (TokenError::ExtensionBaseMismatch, FailureClass::Permanent),
+        (
+            TokenError::ExtensionAlreadyInitialized,
+            FailureClass::Permanent,
+        ),
+        (
+            TokenError::ConfidentialTransferAccountHasBalance,
+            FailureClass::NeedsUserAction,
+        ),
+        (
+            TokenError::ConfidentialTransferAccountNotApproved,
+            FailureClass::NeedsUserAction,
+        ),
+        (
+            TokenError::ConfidentialTransferDepositsAndTransfersDisabled,
+            FailureClass::NeedsUserAction,
+        ),
+        (
+            TokenError::ConfidentialTransferElGamalPubkeyMismatch,
+            FailureClass::Permanent,
+        ),
+        (
+            TokenError::ConfidentialTransferBalanceMismatch,
+            FailureClass::Permanent,
+        ),
+        (TokenError::MintHasSupply, FailureClass::NeedsUserAction),
+        (TokenError::NoAuthorityExists, FailureClass::Permanent),
+        (
+            TokenError::TransferFeeExceedsMaximum,
+            FailureClass::Permanent,
+        ),
+        (TokenError::MintRequiredForTransfer, FailureClass::Permanent),
+        (TokenError::FeeMismatch, FailureClass::Permanent),
+        (TokenError::FeeParametersMismatch, FailureClass::Permanent),
+        (TokenError::ImmutableOwner, FailureClass::Permanent),
+        (
+            TokenError::AccountHasWithheldTransferFees,
+            FailureClass::NeedsUserAction,
+        ),
+        (TokenError::NoMemo, FailureClass::NeedsUserAction),
+        (TokenError::NonTransferable, FailureClass::Permanent),
+        (
+            TokenError::NonTransferableNeedsImmutableOwnership,
+            FailureClass::Permanent,
+        ),
+        (
+            TokenError::MaximumPendingBalanceCreditCounterExceeded,
+            FailureClass::NeedsUserAction,
+        ),
+        (
+            TokenError::MaximumDepositAmountExceeded,
+            FailureClass::Permanent,
+        ),
+        (TokenError::CpiGuardSettingsLocked, FailureClass::Permanent),
+        (
+            TokenError::CpiGuardTransferBlocked,
+            FailureClass::NeedsUserAction,
+        ),
+        (
+            TokenError::CpiGuardBurnBlocked,
+            FailureClass::NeedsUserAction,
+        ),
+        (
+            TokenError::CpiGuardCloseAccountBlocked,
+            FailureClass::NeedsUserAction,
+        ),
+        (
+            TokenError::CpiGuardApproveBlocked,
+            FailureClass::NeedsUserAction,
+        ),
+        (
+            TokenError::CpiGuardSetAuthorityBlocked,
+            FailureClass::NeedsUserAction,
+        ),
+        (
+            TokenError::CpiGuardOwnerChangeBlocked,
+            FailureClass::NeedsUserAction,
+        ),
+        (TokenError::ExtensionNotFound, FailureClass::Permanent),
+        (
+            TokenError::NonConfidentialTransfersDisabled,
+            FailureClass::NeedsUserAction,
+        ),
+        (
+            TokenError::ConfidentialTransferFeeAccountHasWithheldFee,
+            FailureClass::NeedsUserAction,
+        ),
+        (
+            TokenError::InvalidExtensionCombination,
+            FailureClass::Permanent,
+        ),
+        (TokenError::InvalidLengthForAlloc, FailureClass::Permanent),
+        (TokenError::AccountDecryption, FailureClass::Permanent),
+        (TokenError::ProofGeneration, FailureClass::Permanent),
+        (
+            TokenError::InvalidProofInstructionOffset,
+            FailureClass::Permanent,
+        ),
+        (
+            TokenError::HarvestToMintDisabled,
+            FailureClass::NeedsUserAction,
+        ),
+        (
+            TokenError::SplitProofContextStateAccountsNotSupported,
+            FailureClass::Permanent,
+        ),
+        (
+            TokenError::NotEnoughProofContextStateAccounts,
+            FailureClass::Permanent,
+        ),
+        (TokenError::MalformedCiphertext, FailureClass::Permanent),
+        (
+            TokenError::CiphertextArithmeticFailed,
+            FailureClass::Permanent,
+        ),
+        (
+            TokenError::PedersenCommitmentMismatch,
+            FailureClass::Permanent,
+        ),
+        (
+            TokenError::RangeProofLengthMismatch,
+            FailureClass::Permanent,
+        ),
+        (TokenError::IllegalBitLength, FailureClass::Permanent),
+        (TokenError::FeeCalculation, FailureClass::Permanent),
+        (
+            TokenError::IllegalMintBurnConversion,
+            FailureClass::Permanent,
+        ),
+        (TokenError::InvalidScale, FailureClass::Permanent),
+        (TokenError::MintPaused, FailureClass::RetryLater),
+        (
+            TokenError::PendingBalanceNonZero,
+            FailureClass::NeedsUserAction,
+        ),
+    ];
+
+    #[test]
+    fn test_classify_token_errors() {
+        for (code, (error, class)) in CLASSIFICATION.iter().enumerate() {
+            assert_eq!(TokenError::try_from(code as u32), Ok(*error));
+            assert_eq!(*error as u32, code as u32);
+            assert_eq!(
+                classify(&ProgramError::Custom(code as u32)),
+                *class,
+                "{error:?}"
+            );
+        }
+
+        assert_eq!(
+            classify(&ProgramError::Custom(CLASSIFICATION.len() as u32)),
+            FailureClass::Unknown
+        );
+    }
+
+    #[test]
+    fn test_token_error_round_trip() {
+        for code in 0..CLASSIFICATION.len() as u32 {
+            let error = TokenError::try_from(code).unwrap();
+            let program_error = ProgramError::from(error);
+
+            assert_eq!(program_error, ProgramError::Custom(code));
+            assert_eq!(TokenError::from_program_error(&program_error), Some(error));
+        }
+
+        for code in [CLASSIFICATION.len() as u32, u32::MAX] {
+            assert_eq!(
+                TokenError::try_from(code),
+                Err(ProgramError::InvalidArgument)
+            );
+            assert_eq!(
+                TokenError::from_program_error(&ProgramError::Custom(code)),
+                None
+            );
+        }
+
+        assert_eq!(
+            TokenError::from_program_error(&ProgramError::InvalidAccountData),
+            None
+        );
+    }
+
+    #[test]
+    fn test_classify_runtime_errors() {
+        assert_eq!(
+            classify(&ProgramError::AccountBorrowFailed),
+            FailureClass::RetryLater
+        );
+        assert_eq!(
+            classify(&ProgramError::InsufficientFunds),
+            FailureClass::NeedsUserAction
+        );
+        assert_eq!(
+            classify(&ProgramError::MissingRequiredSignature),
+            FailureClass::Permanent
+        );
+        assert_eq!(classify(&ProgramError::BorshIoError), FailureClass::Unknown);
+    }
+
+    #[test]
+    fn test_continues_batch() {
+        assert!(FailureClass::RetryLater.continues_batch());
+        assert!(FailureClass::NeedsUserAction.continues_batch());
+        assert!(!FailureClass::Permanent.continues_batch());
+        assert!(!FailureClass::Unknown.continues_batch());
+    }
+}
@@ -0,0 +1,214 @@
+//! Confidential mint and burn extension.
+
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Instruction, Signer},
+    program_error::ProgramError,
+    ProgramResult,
+};
+
+use crate::{
+    cpi::{invoke_signed_with_bounds, CpiAccounts, IxData},
+    extensions::{
+        confidential_transfer::{DecryptableBalance, ElGamalPubkey, PodElGamalCiphertext},
+        BaseState, Extension, ExtensionDiscriminator, ExtensionType,
+    },
+    instructions::MAX_MULTISIG_SIGNERS,
+};
+
+/// Maximum number of accounts of the `ApplyPendingBurn` instruction.
+const MAX_APPLY_PENDING_BURN_ACCOUNTS: usize = 2 + MAX_MULTISIG_SIGNERS;
+
+/// Instruction discriminators of the confidential mint and burn extension.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConfidentialMintBurnInstruction {
+    InitializeMint = 0,
+    RotateSupplyElGamalPubkey = 1,
+    UpdateDecryptableSupply = 2,
+    Mint = 3,
+    Burn = 4,
+    ApplyPendingBurn = 5,
+}
+
+/// Confidential supply of a mint.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ConfidentialMintBurn {
+    /// The confidential supply of the mint, encrypted under
+    /// `supply_elgamal_pubkey`.
+    pub confidential_supply: PodElGamalCiphertext,
+    /// The decryptable confidential supply of the mint.
+    pub decryptable_supply: DecryptableBalance,
+    /// The ElGamal public key used to encrypt the confidential supply.
+    pub supply_elgamal_pubkey: ElGamalPubkey,
+    /// The amount of burn amounts not yet aggregated into the confidential
+    /// supply.
+    pub pending_burn: PodElGamalCiphertext,
+}
+
+unsafe impl Extension for ConfidentialMintBurn {
+    const TYPE: ExtensionType = ExtensionType::ConfidentialMintBurn;
+    const LEN: usize = core::mem::size_of::<Self>();
+    const BASE_STATE: BaseState = BaseState::Mint;
+}
+
+const _: () = assert!(ConfidentialMintBurn::LEN == 196);
+
+// `Default` cannot be derived for the ciphertext arrays.
+impl Default for ConfidentialMintBurn {
+    fn default() -> Self {
+        Self {
+            confidential_supply: [0; 64],
+            decryptable_supply: [0; 36],
+            supply_elgamal_pubkey: ElGamalPubkey::default(),
+            pending_burn: [0; 64],
+        }
+    }
+}
+
+/// Aggregate the pending burn amounts into the confidential supply.
+///
+/// ### Accounts:
+///   * Single authority
+///   0. `[WRITE]` The mint.
+///   1. `[SIGNER]` The mint authority.
+///
+///   * Multisignature authority
+///   0. `[WRITE]` The mint.
+///   1. `[]` The multisig mint authority.
+///   2. `..2+M` `[SIGNER]` M signer accounts.
+pub struct ApplyPendingBurn<'a, 'b> {
+    /// Mint Account.
+    pub mint: &'a AccountInfo,
+    /// The mint authority.
+    pub authority: &'a AccountInfo,
+    /// Signer accounts when the mint authority is a multisig.
+    pub signers: &'b [&'a AccountInfo],
+}
+
+impl ApplyPendingBurn<'_, '_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    #[inline(always)]
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        if self.signers.len() > MAX_MULTISIG_SIGNERS {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        // Account metadata
+        let mut accounts = CpiAccounts::<MAX_APPLY_PENDING_BURN_ACCOUNTS>::new();
+
+        accounts.push(self.mint, true, false);
+        accounts.push_authority(self.authority, self.signers);
+
+        // Instruction data layout:
+        // -  [0]: extension instruction discriminator (1 byte, u8)
+        // -  [1]: instruction discriminator (1 byte, u8)
+        let mut instruction_data = IxData::<2>::new();
+
+        instruction_data.write_u8(ExtensionDiscriminator::ConfidentialMintBurn as u8);
+        instruction_data.write_u8(ConfidentialMintBurnInstruction::ApplyPendingBurn as u8);
+
+        let instruction = Instruction {
+            program_id: &crate::ID,
+            accounts: accounts.metas(),
+            data: instruction_data.finish(),
+        };
+
+        invoke_signed_with_bounds::<MAX_APPLY_PENDING_BURN_ACCOUNTS>(
+            &instruction,
+            accounts.infos(),
+            signers,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::{vec, vec::Vec};
+
+    use super::*;
+    use crate::test_utils::{take_single, CapturedMeta, TestAccount};
+
+    #[test]
+    fn test_apply_pending_burn() {
+        let mint = TestAccount::new([1; 32]).writable().info();
+        let authority = TestAccount::new([2; 32]).signer().info();
+
+        ApplyPendingBurn {
+            mint: &mint,
+            authority: &authority,
+            signers: &[],
+        }
+        .invoke()
+        .unwrap();
+
+        let instruction = take_single();
+
+        assert_eq!(instruction.data, vec![42, 5]);
+        assert_eq!(
+            instruction.accounts,
+            vec![
+                CapturedMeta::new([1; 32], true, false),
+                CapturedMeta::new([2; 32], false, true),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_apply_pending_burn_multisig() {
+        let mint = TestAccount::new([1; 32]).writable().info();
+        let authority = TestAccount::new([2; 32]).info();
+        // 2-of-3 multisig: only two of the three members sign.
+        let signer_1 = TestAccount::new([3; 32]).signer().info();
+        let signer_2 = TestAccount::new([4; 32]).signer().info();
+
+        ApplyPendingBurn {
+            mint: &mint,
+            authority: &authority,
+            signers: &[&signer_1, &signer_2],
+        }
+        .invoke()
+        .unwrap();
+
+        let instruction = take_single();
+
+        assert_eq!(instruction.data, vec![42, 5]);
+        assert_eq!(
+            instruction.accounts,
+            vec![
+                CapturedMeta::new([1; 32], true, false),
+                CapturedMeta::new([2; 32], false, false),
+                CapturedMeta::new([3; 32], false, true),
+                CapturedMeta::new([4; 32], false, true),
+            ]
+        );
+        assert_eq!(
+            instruction.account_infos,
+            vec![[1; 32], [2; 32], [3; 32], [4; 32]]
+        );
+    }
+
+    #[test]
+    fn test_apply_pending_burn_too_many_signers() {
+        let mint = TestAccount::new([1; 32]).writable().info();
+        let authority = TestAccount::new([2; 32]).info();
+        let signers = (0..=MAX_MULTISIG_SIGNERS as u8)
+            .map(|i| TestAccount::new([100 + i; 32]).signer().info())
+            .collect::<Vec<_>>();
+        let signers = signers.iter().collect::<Vec<_>>();
+
+        let apply = ApplyPendingBurn {
+            mint: &mint,
+            authority: &authority,
+            signers: &signers,
+        };
+        assert_eq!(apply.invoke(), Err(ProgramError::InvalidArgument));
+    }
+}
@@ -0,0 +1,130 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Instruction, Signer},
+    program_error::ProgramError,
+    ProgramResult,
+};
+
+use crate::{
+    cpi::{invoke_signed_with_bounds, CpiAccounts, IxData},
+    extensions::{
+        confidential_transfer::{ConfidentialTransferInstruction, DecryptableBalance},
+        ExtensionDiscriminator,
+    },
+    instructions::MAX_MULTISIG_SIGNERS,
+};
+
+/// Apply the pending balance of a confidential token account to its
+/// available balance.
+///
+/// ### Accounts:
+///   * Single owner
+///   0. `[WRITE]` The token account.
+///   1. `[SIGNER]` The token account owner.
+///
+///   * Multisignature owner
+///   0. `[WRITE]` The token account.
+///   1. `[]` The multisig account owner.
+///   2. `..2+M` `[SIGNER]` M signer accounts.
+pub struct ApplyPendingBalance<'a, 'b> {
+    /// Token Account.
+    pub token_account: &'a AccountInfo,
+    /// Owner of the token account.
+    pub owner: &'a AccountInfo,
+    /// Signer accounts when the owner is a multisig.
+    pub signers: &'b [&'a AccountInfo],
+    /// The expected number of pending balance credits since the last
+    /// successful `ApplyPendingBalance` instruction.
+    pub expected_pending_balance_credit_counter: u64,
+    /// The new decryptable balance if the pending balance is applied
+    /// successfully.
+    pub new_decryptable_available_balance: &'b DecryptableBalance,
+}
+
+impl ApplyPendingBalance<'_, '_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    #[inline(always)]
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        if self.signers.len() > MAX_MULTISIG_SIGNERS {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        // Account metadata
+        let mut accounts = CpiAccounts::<{ 2 + MAX_MULTISIG_SIGNERS }>::new();
+
+        accounts.push(self.token_account, true, false);
+        accounts.push_authority(self.owner, self.signers);
+
+        // Instruction data layout:
+        // -  [0]: extension instruction discriminator (1 byte, u8)
+        // -  [1]: instruction discriminator (1 byte, u8)
+        // -  [2..10]: expected_pending_balance_credit_counter (8 bytes, u64)
+        // -  [10..46]: new_decryptable_available_balance (36 bytes, AeCiphertext)
+        let mut instruction_data = IxData::<46>::new();
+
+        // Set discriminators as u8 at offsets [0..2]
+        instruction_data.write_u8(ExtensionDiscriminator::ConfidentialTransfer as u8);
+        instruction_data.write_u8(ConfidentialTransferInstruction::ApplyPendingBalance as u8);
+        // Set expected_pending_balance_credit_counter as u64 at offset [2..10]
+        instruction_data.write_u64_le(self.expected_pending_balance_credit_counter);
+        // Set new_decryptable_available_balance as AeCiphertext at offset [10..46]
+        instruction_data.write_bytes(self.new_decryptable_available_balance);
+
+        let instruction = Instruction {
+            program_id: &crate::ID,
+            accounts: accounts.metas(),
+            data: instruction_data.finish(),
+        };
+
+        invoke_signed_with_bounds::<{ 2 + MAX_MULTISIG_SIGNERS }>(
+            &instruction,
+            accounts.infos(),
+            signers,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::vec;
+
+    use super::*;
+    use crate::test_utils::{take_single, CapturedMeta, TestAccount};
+
+    #[test]
+    fn test_apply_pending_balance() {
+        let token_account = TestAccount::new([1; 32]).writable().info();
+        let owner = TestAccount::new([2; 32]).signer().info();
+
+        ApplyPendingBalance {
+            token_account: &token_account,
+            owner: &owner,
+            signers: &[],
+            expected_pending_balance_credit_counter: 0x0102,
+            new_decryptable_available_balance: &[3; 36],
+        }
+        .invoke()
+        .unwrap();
+
+        let instruction = take_single();
+
+        let mut expected = vec![27, 8, 2, 1, 0, 0, 0, 0, 0, 0];
+        expected.extend_from_slice(&[3; 36]);
+
+        assert_eq!(instruction.data.len(), 1 + 1 + 8 + 36);
+        assert_eq!(instruction.data, expected);
+        assert_eq!(
+            instruction.accounts,
+            vec![
+                CapturedMeta::new([1; 32], true, false),
+                CapturedMeta::new([2; 32], false, true),
+            ]
+        );
+    }
+}
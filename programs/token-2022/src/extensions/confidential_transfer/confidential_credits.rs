@@ -0,0 +1,202 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Instruction, Signer},
+    program_error::ProgramError,
+    ProgramResult,
+};
+
+use crate::{
+    cpi::{invoke_signed_with_bounds, CpiAccounts, IxData},
+    extensions::{confidential_transfer::ConfidentialTransferInstruction, ExtensionDiscriminator},
+    instructions::MAX_MULTISIG_SIGNERS,
+};
+
+/// Allow incoming confidential transfers into a token account.
+///
+/// ### Accounts:
+///   * Single owner
+///   0. `[WRITE]` The token account.
+///   1. `[SIGNER]` The token account owner.
+///
+///   * Multisignature owner
+///   0. `[WRITE]` The token account.
+///   1. `[]` The multisig account owner.
+///   2. `..2+M` `[SIGNER]` M signer accounts.
+pub struct EnableConfidentialCredits<'a, 'b> {
+    /// Token Account.
+    pub token_account: &'a AccountInfo,
+    /// Owner of the token account.
+    pub owner: &'a AccountInfo,
+    /// Signer accounts when the owner is a multisig.
+    pub signers: &'b [&'a AccountInfo],
+}
+
+impl EnableConfidentialCredits<'_, '_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    #[inline(always)]
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        invoke_confidential_credits(
+            ConfidentialTransferInstruction::EnableConfidentialCredits,
+            self.token_account,
+            self.owner,
+            self.signers,
+            signers,
+        )
+    }
+}
+
+/// Reject incoming confidential transfers into a token account.
+///
+/// ### Accounts:
+///   * Single owner
+///   0. `[WRITE]` The token account.
+///   1. `[SIGNER]` The token account owner.
+///
+///   * Multisignature owner
+///   0. `[WRITE]` The token account.
+///   1. `[]` The multisig account owner.
+///   2. `..2+M` `[SIGNER]` M signer accounts.
+pub struct DisableConfidentialCredits<'a, 'b> {
+    /// Token Account.
+    pub token_account: &'a AccountInfo,
+    /// Owner of the token account.
+    pub owner: &'a AccountInfo,
+    /// Signer accounts when the owner is a multisig.
+    pub signers: &'b [&'a AccountInfo],
+}
+
+impl DisableConfidentialCredits<'_, '_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    #[inline(always)]
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        invoke_confidential_credits(
+            ConfidentialTransferInstruction::DisableConfidentialCredits,
+            self.token_account,
+            self.owner,
+            self.signers,
+            signers,
+        )
+    }
+}
+
+#[inline(always)]
+fn invoke_confidential_credits(
+    instruction: ConfidentialTransferInstruction,
+    token_account: &AccountInfo,
+    owner: &AccountInfo,
+    multisig_signers: &[&AccountInfo],
+    signers: &[Signer],
+) -> ProgramResult {
+    if multisig_signers.len() > MAX_MULTISIG_SIGNERS {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Account metadata
+    let mut accounts = CpiAccounts::<{ 2 + MAX_MULTISIG_SIGNERS }>::new();
+
+    accounts.push(token_account, true, false);
+    accounts.push_authority(owner, multisig_signers);
+
+    // Instruction data layout:
+    // -  [0]: extension instruction discriminator (1 byte, u8)
+    // -  [1]: instruction discriminator (1 byte, u8)
+    let mut instruction_data = IxData::<2>::new();
+
+    instruction_data.write_u8(ExtensionDiscriminator::ConfidentialTransfer as u8);
+    instruction_data.write_u8(instruction as u8);
+
+    let instruction = Instruction {
+        program_id: &crate::ID,
+        accounts: accounts.metas(),
+        data: instruction_data.finish(),
+    };
+
+    invoke_signed_with_bounds::<{ 2 + MAX_MULTISIG_SIGNERS }>(
+        &instruction,
+        accounts.infos(),
+        signers,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::vec;
+
+    use super::*;
+    use crate::test_utils::{take_captured, CapturedMeta, TestAccount};
+
+    #[test]
+    fn test_confidential_credits() {
+        let token_account = TestAccount::new([1; 32]).writable().info();
+        let owner = TestAccount::new([2; 32]).signer().info();
+
+        EnableConfidentialCredits {
+            token_account: &token_account,
+            owner: &owner,
+            signers: &[],
+        }
+        .invoke()
+        .unwrap();
+
+        DisableConfidentialCredits {
+            token_account: &token_account,
+            owner: &owner,
+            signers: &[],
+        }
+        .invoke()
+        .unwrap();
+
+        let instructions = take_captured();
+
+        assert_eq!(instructions[0].data, vec![27, 9]);
+        assert_eq!(instructions[1].data, vec![27, 10]);
+
+        for instruction in instructions {
+            assert_eq!(
+                instruction.accounts,
+                vec![
+                    CapturedMeta::new([1; 32], true, false),
+                    CapturedMeta::new([2; 32], false, true),
+                ]
+            );
+        }
+    }
+
+    #[test]
+    fn test_confidential_credits_multisig() {
+        let token_account = TestAccount::new([1; 32]).writable().info();
+        let owner = TestAccount::new([2; 32]).info();
+        let signer_1 = TestAccount::new([3; 32]).signer().info();
+        let signer_2 = TestAccount::new([4; 32]).signer().info();
+
+        DisableConfidentialCredits {
+            token_account: &token_account,
+            owner: &owner,
+            signers: &[&signer_1, &signer_2],
+        }
+        .invoke()
+        .unwrap();
+
+        let instructions = take_captured();
+
+        assert_eq!(
+            instructions[0].accounts,
+            vec![
+                CapturedMeta::new([1; 32], true, false),
+                CapturedMeta::new([2; 32], false, false),
+                CapturedMeta::new([3; 32], false, true),
+                CapturedMeta::new([4; 32], false, true),
+            ]
+        );
+    }
+}
@@ -0,0 +1,168 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Instruction, Signer},
+    program_error::ProgramError,
+    ProgramResult,
+};
+
+use crate::{
+    cpi::{invoke_signed_with_bounds, CpiAccounts, IxData},
+    extensions::{confidential_transfer::ConfidentialTransferInstruction, ExtensionDiscriminator},
+    instructions::MAX_MULTISIG_SIGNERS,
+};
+
+/// Deposit SPL Tokens into the pending balance of a confidential token
+/// account.
+///
+/// The deposited tokens are not available for confidential transfers until
+/// the pending balance is applied with the `ApplyPendingBalance` instruction.
+///
+/// ### Accounts:
+///   * Single owner
+///   0. `[WRITE]` The token account.
+///   1. `[]` The token mint.
+///   2. `[SIGNER]` The token account owner.
+///
+///   * Multisignature owner
+///   0. `[WRITE]` The token account.
+///   1. `[]` The token mint.
+///   2. `[]` The multisig account owner.
+///   3. `..3+M` `[SIGNER]` M signer accounts.
+pub struct Deposit<'a, 'b> {
+    /// Token Account.
+    pub token_account: &'a AccountInfo,
+    /// Mint Account.
+    pub mint: &'a AccountInfo,
+    /// Owner of the token account.
+    pub owner: &'a AccountInfo,
+    /// Signer accounts when the owner is a multisig.
+    pub signers: &'b [&'a AccountInfo],
+    /// Amount of tokens to deposit.
+    pub amount: u64,
+    /// Expected number of base 10 digits to the right of the decimal place.
+    pub decimals: u8,
+}
+
+impl Deposit<'_, '_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    #[inline(always)]
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        if self.signers.len() > MAX_MULTISIG_SIGNERS {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        // Account metadata
+        let mut accounts = CpiAccounts::<{ 3 + MAX_MULTISIG_SIGNERS }>::new();
+
+        accounts.push(self.token_account, true, false);
+        accounts.push(self.mint, false, false);
+        accounts.push_authority(self.owner, self.signers);
+
+        // Instruction data layout:
+        // -  [0]: extension instruction discriminator (1 byte, u8)
+        // -  [1]: instruction discriminator (1 byte, u8)
+        // -  [2..10]: amount (8 bytes, u64)
+        // -  [10]: decimals (1 byte, u8)
+        let mut instruction_data = IxData::<11>::new();
+
+        // Set discriminators as u8 at offsets [0..2]
+        instruction_data.write_u8(ExtensionDiscriminator::ConfidentialTransfer as u8);
+        instruction_data.write_u8(ConfidentialTransferInstruction::Deposit as u8);
+        // Set amount as u64 at offset [2..10]
+        instruction_data.write_u64_le(self.amount);
+        // Set decimals as u8 at offset [10]
+        instruction_data.write_u8(self.decimals);
+
+        let instruction = Instruction {
+            program_id: &crate::ID,
+            accounts: accounts.metas(),
+            data: instruction_data.finish(),
+        };
+
+        invoke_signed_with_bounds::<{ 3 + MAX_MULTISIG_SIGNERS }>(
+            &instruction,
+            accounts.infos(),
+            signers,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::vec;
+
+    use super::*;
+    use crate::test_utils::{take_single, CapturedMeta, TestAccount};
+
+    #[test]
+    fn test_deposit() {
+        let token_account = TestAccount::new([1; 32]).writable();
+        let mint = TestAccount::new([2; 32]);
+        let owner = TestAccount::new([3; 32]).signer();
+
+        Deposit {
+            token_account: &token_account.info(),
+            mint: &mint.info(),
+            owner: &owner.info(),
+            signers: &[],
+            amount: 1_000_000_000,
+            decimals: 9,
+        }
+        .invoke()
+        .unwrap();
+
+        let instruction = take_single();
+
+        // Hand-encoded layout of `DepositInstructionData` of spl-token-2022,
+        // prefixed by the discriminators.
+        assert_eq!(
+            instruction.data,
+            vec![27, 5, 0x00, 0xca, 0x9a, 0x3b, 0x00, 0x00, 0x00, 0x00, 9]
+        );
+        assert_eq!(
+            instruction.accounts,
+            vec![
+                CapturedMeta::new([1; 32], true, false),
+                CapturedMeta::new([2; 32], false, false),
+                CapturedMeta::new([3; 32], false, true),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_deposit_multisig() {
+        let token_account = TestAccount::new([1; 32]).writable();
+        let mint = TestAccount::new([2; 32]);
+        let owner = TestAccount::new([3; 32]);
+        let signer = TestAccount::new([4; 32]).signer();
+
+        Deposit {
+            token_account: &token_account.info(),
+            mint: &mint.info(),
+            owner: &owner.info(),
+            signers: &[&signer.info()],
+            amount: 1,
+            decimals: 0,
+        }
+        .invoke()
+        .unwrap();
+
+        let instruction = take_single();
+
+        assert_eq!(
+            instruction.accounts,
+            vec![
+                CapturedMeta::new([1; 32], true, false),
+                CapturedMeta::new([2; 32], false, false),
+                CapturedMeta::new([3; 32], false, false),
+                CapturedMeta::new([4; 32], false, true),
+            ]
+        );
+    }
+}
@@ -0,0 +1,182 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Instruction, Signer},
+    program_error::ProgramError,
+    ProgramResult,
+};
+
+use crate::{
+    cpi::{invoke_signed_with_bounds, CpiAccounts},
+    extensions::{confidential_transfer::ConfidentialTransferInstruction, ExtensionDiscriminator},
+    instructions::MAX_MULTISIG_SIGNERS,
+};
+
+/// Empty the available balance in a confidential token account.
+///
+/// A token account that is extended for confidential transfers can only be
+/// closed if the pending and available balance ciphertexts are emptied. The
+/// pending balance can be emptied via the `ApplyPendingBalance` instruction.
+///
+/// ### Accounts:
+///   * Single owner
+///   0. `[WRITE]` The token account.
+///   1. `[]` Instructions sysvar if `VerifyZeroCiphertext` is included in
+///      the same transaction or context state account if
+///      `VerifyZeroCiphertext` is pre-verified into a context state account.
+///   2. `[]` (Optional) Record account if the accompanying proof is to be
+///      read from a record account.
+///   3. `[SIGNER]` The token account owner.
+///
+///   * Multisignature owner
+///   0. `[WRITE]` The token account.
+///   1. `[]` Instructions sysvar or context state account.
+///   2. `[]` (Optional) Record account.
+///   3. `[]` The multisig account owner.
+///   4. `..4+M` `[SIGNER]` M signer accounts.
+pub struct EmptyAccount<'a, 'b> {
+    /// Token Account.
+    pub token_account: &'a AccountInfo,
+    /// Instructions sysvar or `VerifyZeroCiphertext` context state account.
+    pub proof_account: &'a AccountInfo,
+    /// Record account holding the proof data, when the proof is read from
+    /// a record account.
+    pub record_account: Option<&'a AccountInfo>,
+    /// Owner of the token account.
+    pub owner: &'a AccountInfo,
+    /// Signer accounts when the owner is a multisig.
+    pub signers: &'b [&'a AccountInfo],
+    /// Relative offset of the `VerifyZeroCiphertext` instruction, or `0`
+    /// when the proof is provided as a context state account.
+    pub proof_instruction_offset: i8,
+}
+
+impl EmptyAccount<'_, '_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    #[inline(always)]
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        if self.signers.len() > MAX_MULTISIG_SIGNERS {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        // Account metadata
+        let mut accounts = CpiAccounts::<{ 4 + MAX_MULTISIG_SIGNERS }>::new();
+
+        accounts.push(self.token_account, true, false);
+        accounts.push(self.proof_account, false, false);
+
+        if let Some(record_account) = self.record_account {
+            accounts.push(record_account, false, false);
+        }
+
+        accounts.push_authority(self.owner, self.signers);
+
+        // Instruction data layout:
+        // -  [0]: extension instruction discriminator (1 byte, u8)
+        // -  [1]: instruction discriminator (1 byte, u8)
+        // -  [2]: proof_instruction_offset (1 byte, i8)
+        let instruction_data = [
+            ExtensionDiscriminator::ConfidentialTransfer as u8,
+            ConfidentialTransferInstruction::EmptyAccount as u8,
+            self.proof_instruction_offset as u8,
+        ];
+
+        let instruction = Instruction {
+            program_id: &crate::ID,
+            accounts: accounts.metas(),
+            data: &instruction_data,
+        };
+
+        invoke_signed_with_bounds::<{ 4 + MAX_MULTISIG_SIGNERS }>(
+            &instruction,
+            accounts.infos(),
+            signers,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::vec;
+
+    use pinocchio::sysvars::instructions::INSTRUCTIONS_ID;
+
+    use super::*;
+    use crate::test_utils::{take_single, CapturedMeta, TestAccount};
+
+    #[test]
+    fn test_empty_account_with_instructions_sysvar() {
+        let token_account = TestAccount::new([1; 32]).writable();
+        let sysvar = TestAccount::new(INSTRUCTIONS_ID);
+        let record = TestAccount::new([2; 32]);
+        let owner = TestAccount::new([3; 32]).signer();
+
+        EmptyAccount {
+            token_account: &token_account.info(),
+            proof_account: &sysvar.info(),
+            record_account: Some(&record.info()),
+            owner: &owner.info(),
+            signers: &[],
+            proof_instruction_offset: -1,
+        }
+        .invoke()
+        .unwrap();
+
+        let instruction = take_single();
+
+        assert_eq!(instruction.program_id, crate::ID);
+        assert_eq!(instruction.data, vec![27, 4, 0xff]);
+        assert_eq!(
+            instruction.accounts,
+            vec![
+                CapturedMeta::new([1; 32], true, false),
+                CapturedMeta::new(INSTRUCTIONS_ID, false, false),
+                CapturedMeta::new([2; 32], false, false),
+                CapturedMeta::new([3; 32], false, true),
+            ]
+        );
+        assert_eq!(
+            instruction.account_infos,
+            vec![[1; 32], INSTRUCTIONS_ID, [2; 32], [3; 32]]
+        );
+    }
+
+    #[test]
+    fn test_empty_account_with_context_account_and_multisig() {
+        let token_account = TestAccount::new([1; 32]).writable();
+        let context = TestAccount::new([4; 32]);
+        let owner = TestAccount::new([3; 32]);
+        let signer_1 = TestAccount::new([5; 32]).signer();
+        let signer_2 = TestAccount::new([6; 32]).signer();
+
+        EmptyAccount {
+            token_account: &token_account.info(),
+            proof_account: &context.info(),
+            record_account: None,
+            owner: &owner.info(),
+            signers: &[&signer_1.info(), &signer_2.info()],
+            proof_instruction_offset: 0,
+        }
+        .invoke()
+        .unwrap();
+
+        let instruction = take_single();
+
+        assert_eq!(instruction.data, vec![27, 4, 0]);
+        assert_eq!(
+            instruction.accounts,
+            vec![
+                CapturedMeta::new([1; 32], true, false),
+                CapturedMeta::new([4; 32], false, false),
+                CapturedMeta::new([3; 32], false, false),
+                CapturedMeta::new([5; 32], false, true),
+                CapturedMeta::new([6; 32], false, true),
+            ]
+        );
+    }
+}
@@ -0,0 +1,326 @@
+//! Confidential transfer extension.
+//!
+//! Instructions that require a zero-knowledge proof accept the proof either
+//! as an instruction in the same transaction, referenced through the
+//! instructions sysvar and a relative `proof_instruction_offset`, or as a
+//! pre-verified context state account, in which case the offset must be `0`.
+
+mod apply_pending_balance;
+mod confidential_credits;
+mod deposit;
+mod empty_account;
+mod transfer;
+mod transfer_with_fee;
+mod withdraw;
+
+pub use apply_pending_balance::*;
+pub use confidential_credits::*;
+pub use deposit::*;
+pub use empty_account::*;
+pub use transfer::*;
+pub use transfer_with_fee::*;
+pub use withdraw::*;
+
+use pinocchio::pubkey::Pubkey;
+
+use crate::{
+    extensions::{BaseState, Extension, ExtensionType},
+    pod::{PodBool, PodU64},
+};
+
+/// Authenticated encryption of a balance, decryptable by the account owner.
+pub type DecryptableBalance = [u8; 36];
+
+/// ElGamal ciphertext.
+pub type PodElGamalCiphertext = [u8; 64];
+
+/// ElGamal public key.
+///
+/// Optional keys, such as the auditor key of a mint, are stored as the zero
+/// key when absent.
+#[repr(transparent)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ElGamalPubkey(pub [u8; 32]);
+
+impl ElGamalPubkey {
+    /// Return the bytes of the key.
+    #[inline(always)]
+    pub const fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+
+    /// Return whether the key is the zero key, which stands for `None` in
+    /// optional keys.
+    #[inline(always)]
+    pub fn is_zero(&self) -> bool {
+        self.0 == [0; 32]
+    }
+
+    /// Return the key, or `None` for the zero key.
+    #[inline(always)]
+    pub fn get(&self) -> Option<&Self> {
+        if self.is_zero() {
+            None
+        } else {
+            Some(self)
+        }
+    }
+}
+
+impl From<[u8; 32]> for ElGamalPubkey {
+    #[inline(always)]
+    fn from(bytes: [u8; 32]) -> Self {
+        Self(bytes)
+    }
+}
+
+impl PartialEq<[u8; 32]> for ElGamalPubkey {
+    #[inline(always)]
+    fn eq(&self, other: &[u8; 32]) -> bool {
+        &self.0 == other
+    }
+}
+
+/// Confidential transfer configuration of a mint.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ConfidentialTransferMint {
+    /// Authority to modify the `ConfidentialTransferMint` configuration and
+    /// to approve new accounts.
+    pub authority: Pubkey,
+    /// Whether new token accounts are approved automatically.
+    pub auto_approve_new_accounts: PodBool,
+    /// Authority to decode any transfer amount in a confidential transfer.
+    pub auditor_elgamal_pubkey: ElGamalPubkey,
+}
+
+unsafe impl Extension for ConfidentialTransferMint {
+    const TYPE: ExtensionType = ExtensionType::ConfidentialTransferMint;
+    const LEN: usize = core::mem::size_of::<Self>();
+    const BASE_STATE: BaseState = BaseState::Mint;
+}
+
+const _: () = assert!(ConfidentialTransferMint::LEN == 65);
+
+impl ConfidentialTransferMint {
+    /// Return the auditor ElGamal public key, or `None` if transfers are not
+    /// audited.
+    #[inline(always)]
+    pub fn auditor_elgamal_pubkey(&self) -> Option<&ElGamalPubkey> {
+        self.auditor_elgamal_pubkey.get()
+    }
+}
+
+/// Confidential transfer state of a token account.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ConfidentialTransferAccount {
+    /// Whether the account has been approved to make confidential transfers.
+    pub approved: PodBool,
+    /// ElGamal public key of the account, used to encrypt its balances.
+    pub elgamal_pubkey: ElGamalPubkey,
+    /// Low 16 bits of the pending balance.
+    pub pending_balance_lo: PodElGamalCiphertext,
+    /// High 48 bits of the pending balance.
+    pub pending_balance_hi: PodElGamalCiphertext,
+    /// Available balance.
+    pub available_balance: PodElGamalCiphertext,
+    /// Available balance, decryptable by the account owner.
+    pub decryptable_available_balance: DecryptableBalance,
+    /// Whether incoming confidential transfers are accepted.
+    pub allow_confidential_credits: PodBool,
+    /// Whether incoming non-confidential transfers are accepted.
+    pub allow_non_confidential_credits: PodBool,
+    /// Number of incoming transfers credited to the pending balance since the
+    /// last `ApplyPendingBalance`.
+    pub pending_balance_credit_counter: PodU64,
+    /// Maximum value of `pending_balance_credit_counter` before
+    /// `ApplyPendingBalance` must be invoked.
+    pub maximum_pending_balance_credit_counter: PodU64,
+    /// Expected value of `pending_balance_credit_counter`, as provided to the
+    /// last `ApplyPendingBalance`.
+    pub expected_pending_balance_credit_counter: PodU64,
+    /// Value of `pending_balance_credit_counter` when the last
+    /// `ApplyPendingBalance` was processed.
+    pub actual_pending_balance_credit_counter: PodU64,
+}
+
+unsafe impl Extension for ConfidentialTransferAccount {
+    const TYPE: ExtensionType = ExtensionType::ConfidentialTransferAccount;
+    const LEN: usize = core::mem::size_of::<Self>();
+    const BASE_STATE: BaseState = BaseState::TokenAccount;
+}
+
+const _: () = assert!(ConfidentialTransferAccount::LEN == 295);
+
+// `Default` cannot be derived for the ciphertext arrays.
+impl Default for ConfidentialTransferAccount {
+    fn default() -> Self {
+        Self {
+            approved: PodBool::default(),
+            elgamal_pubkey: ElGamalPubkey::default(),
+            pending_balance_lo: [0; 64],
+            pending_balance_hi: [0; 64],
+            available_balance: [0; 64],
+            decryptable_available_balance: [0; 36],
+            allow_confidential_credits: PodBool::default(),
+            allow_non_confidential_credits: PodBool::default(),
+            pending_balance_credit_counter: PodU64::default(),
+            maximum_pending_balance_credit_counter: PodU64::default(),
+            expected_pending_balance_credit_counter: PodU64::default(),
+            actual_pending_balance_credit_counter: PodU64::default(),
+        }
+    }
+}
+
+impl ConfidentialTransferAccount {
+    /// Return whether the account has been approved to make confidential
+    /// transfers.
+    #[inline(always)]
+    pub fn approved(&self) -> bool {
+        self.approved.get()
+    }
+
+    /// Return whether incoming confidential transfers are accepted.
+    #[inline(always)]
+    pub fn allow_confidential_credits(&self) -> bool {
+        self.allow_confidential_credits.get()
+    }
+
+    /// Return whether incoming non-confidential transfers are accepted.
+    #[inline(always)]
+    pub fn allow_non_confidential_credits(&self) -> bool {
+        self.allow_non_confidential_credits.get()
+    }
+
+    /// Return the number of incoming transfers credited to the pending
+    /// balance since the last `ApplyPendingBalance`.
+    #[inline(always)]
+    pub fn pending_balance_credit_counter(&self) -> u64 {
+        self.pending_balance_credit_counter.get()
+    }
+
+    /// Return the maximum number of incoming transfers credited to the
+    /// pending balance before `ApplyPendingBalance` must be invoked.
+    #[inline(always)]
+    pub fn maximum_pending_balance_credit_counter(&self) -> u64 {
+        self.maximum_pending_balance_credit_counter.get()
+    }
+
+    /// Return the expected pending balance credit counter of the last
+    /// `ApplyPendingBalance`.
+    #[inline(always)]
+    pub fn expected_pending_balance_credit_counter(&self) -> u64 {
+        self.expected_pending_balance_credit_counter.get()
+    }
+
+    /// Return the pending balance credit counter when the last
+    /// `ApplyPendingBalance` was processed.
+    #[inline(always)]
+    pub fn actual_pending_balance_credit_counter(&self) -> u64 {
+        self.actual_pending_balance_credit_counter.get()
+    }
+}
+
+/// Instruction discriminators of the confidential transfer extension.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConfidentialTransferInstruction {
+    InitializeMint = 0,
+    UpdateMint = 1,
+    ConfigureAccount = 2,
+    ApproveAccount = 3,
+    EmptyAccount = 4,
+    Deposit = 5,
+    Withdraw = 6,
+    Transfer = 7,
+    ApplyPendingBalance = 8,
+    EnableConfidentialCredits = 9,
+    DisableConfidentialCredits = 10,
+    EnableNonConfidentialCredits = 11,
+    DisableNonConfidentialCredits = 12,
+    TransferWithFee = 13,
+    ConfigureAccountWithRegistry = 14,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        extensions::try_get_extension,
+        test_utils::{account_with_extensions, pyusd_mint_data, token_account_base, PYUSD_MINT},
+    };
+
+    #[test]
+    fn test_elgamal_pubkey() {
+        let key = ElGamalPubkey::from([3; 32]);
+
+        assert_eq!(key.as_bytes(), &[3; 32]);
+        assert_eq!(key, [3; 32]);
+        assert!(!key.is_zero());
+        assert_eq!(key.get(), Some(&key));
+
+        assert!(ElGamalPubkey::default().is_zero());
+        assert_eq!(ElGamalPubkey::default().get(), None);
+    }
+
+    #[test]
+    fn test_auditor_elgamal_pubkey() {
+        let data = pyusd_mint_data();
+        let mint = try_get_extension::<ConfidentialTransferMint>(&data).unwrap();
+
+        // PYUSD does not audit confidential transfers.
+        assert!(mint.auditor_elgamal_pubkey.is_zero());
+        assert_eq!(mint.auditor_elgamal_pubkey(), None);
+
+        let mint = ConfidentialTransferMint {
+            auditor_elgamal_pubkey: [4; 32].into(),
+            ..*mint
+        };
+        assert_eq!(mint.auditor_elgamal_pubkey(), Some(&ElGamalPubkey([4; 32])));
+    }
+
+    #[test]
+    fn test_confidential_transfer_account() {
+        let mut confidential_transfer_account = [0; 295];
+        // approved
+        confidential_transfer_account[0] = 1;
+        // elgamal_pubkey
+        confidential_transfer_account[1..33].copy_from_slice(&[5; 32]);
+        // decryptable_available_balance
+        confidential_transfer_account[225..261].copy_from_slice(&[6; 36]);
+        // allow_confidential_credits, allow_non_confidential_credits
+        confidential_transfer_account[261] = 1;
+        confidential_transfer_account[262] = 0;
+        // pending, maximum, expected and actual pending balance credit counters
+        confidential_transfer_account[263..271].copy_from_slice(&3u64.to_le_bytes());
+        confidential_transfer_account[271..279].copy_from_slice(&65_536u64.to_le_bytes());
+        confidential_transfer_account[279..287].copy_from_slice(&2u64.to_le_bytes());
+        confidential_transfer_account[287..295].copy_from_slice(&1u64.to_le_bytes());
+
+        let data = account_with_extensions(
+            BaseState::TokenAccount,
+            &token_account_base(&PYUSD_MINT, &[2; 32], 0),
+            &[
+                (ExtensionType::ImmutableOwner, &[]),
+                (
+                    ExtensionType::ConfidentialTransferAccount,
+                    &confidential_transfer_account,
+                ),
+            ],
+        );
+        let account = try_get_extension::<ConfidentialTransferAccount>(&data).unwrap();
+
+        assert!(account.approved());
+        assert_eq!(account.elgamal_pubkey, [5; 32]);
+        assert_eq!(account.pending_balance_lo, [0; 64]);
+        assert_eq!(account.available_balance, [0; 64]);
+        assert_eq!(account.decryptable_available_balance, [6; 36]);
+        assert!(account.allow_confidential_credits());
+        assert!(!account.allow_non_confidential_credits());
+        assert_eq!(account.pending_balance_credit_counter(), 3);
+        assert_eq!(account.maximum_pending_balance_credit_counter(), 65_536);
+        assert_eq!(account.expected_pending_balance_credit_counter(), 2);
+        assert_eq!(account.actual_pending_balance_credit_counter(), 1);
+    }
+}
@@ -0,0 +1,355 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Instruction, Signer},
+    program_error::ProgramError,
+    ProgramResult,
+};
+
+use crate::{
+    cpi::{invoke_signed_with_bounds, CpiAccounts, IxData},
+    extensions::{
+        confidential_transfer::{
+            ConfidentialTransferInstruction, DecryptableBalance, PodElGamalCiphertext,
+        },
+        ExtensionDiscriminator,
+    },
+    instructions::MAX_MULTISIG_SIGNERS,
+};
+
+/// Length of the `TransferWithFee` instruction data.
+const DATA_LEN: usize = 171;
+
+/// Transfer tokens confidentially from a mint with a transfer fee.
+///
+/// Required instead of `Transfer` for mints with both the transfer fee and
+/// the confidential transfer extensions. The transfer fee is proven with two
+/// additional proofs, the fee sigma proof and the fee ciphertext validity
+/// proof.
+///
+/// Each proof is provided either as an instruction in the same transaction,
+/// in which case the instructions sysvar is required and the proof account
+/// is an optional record account holding the proof data, or as a
+/// pre-verified context state account, in which case the proof instruction
+/// offset must be `0`.
+///
+/// ### Accounts:
+///   * Single owner
+///   0. `[WRITE]` The source token account.
+///   1. `[]` The token mint.
+///   2. `[WRITE]` The destination token account.
+///   3. `[]` (Optional) Instructions sysvar if at least one of the proof
+///      instructions is included in the same transaction.
+///   4. `[]` (Optional) Equality proof record account or context state
+///      account.
+///   5. `[]` (Optional) Transfer amount ciphertext validity proof record
+///      account or context state account.
+///   6. `[]` (Optional) Fee sigma proof record account or context state
+///      account.
+///   7. `[]` (Optional) Fee ciphertext validity proof record account or
+///      context state account.
+///   8. `[]` (Optional) Range proof record account or context state account.
+///   9. `[SIGNER]` The source account's owner.
+///
+///   * Multisignature owner
+///   0. `[WRITE]` The source token account.
+///   1. `[]` The token mint.
+///   2. `[WRITE]` The destination token account.
+///   3. `[]` (Optional) Instructions sysvar.
+///   4. `[]` (Optional) Equality proof record account or context state
+///      account.
+///   5. `[]` (Optional) Transfer amount ciphertext validity proof record
+///      account or context state account.
+///   6. `[]` (Optional) Fee sigma proof record account or context state
+///      account.
+///   7. `[]` (Optional) Fee ciphertext validity proof record account or
+///      context state account.
+///   8. `[]` (Optional) Range proof record account or context state account.
+///   9. `[]` The multisig source account owner.
+///   10. `..10+M` `[SIGNER]` M signer accounts.
+pub struct TransferWithFee<'a, 'b> {
+    /// Source Token Account.
+    pub source: &'a AccountInfo,
+    /// Mint Account.
+    pub mint: &'a AccountInfo,
+    /// Destination Token Account.
+    pub destination: &'a AccountInfo,
+    /// Instructions sysvar, required when at least one of the proofs is
+    /// provided as an instruction in the same transaction.
+    pub instructions_sysvar: Option<&'a AccountInfo>,
+    /// Equality proof record account or context state account.
+    pub equality_proof_account: Option<&'a AccountInfo>,
+    /// Transfer amount ciphertext validity proof record account or context
+    /// state account.
+    pub transfer_amount_ciphertext_validity_proof_account: Option<&'a AccountInfo>,
+    /// Fee sigma proof record account or context state account.
+    pub fee_sigma_proof_account: Option<&'a AccountInfo>,
+    /// Fee ciphertext validity proof record account or context state account.
+    pub fee_ciphertext_validity_proof_account: Option<&'a AccountInfo>,
+    /// Range proof record account or context state account.
+    pub range_proof_account: Option<&'a AccountInfo>,
+    /// Owner of the source account.
+    pub owner: &'a AccountInfo,
+    /// Signer accounts when the owner is a multisig.
+    pub signers: &'b [&'a AccountInfo],
+    /// The new source decryptable balance if the transfer succeeds.
+    pub new_source_decryptable_available_balance: &'b DecryptableBalance,
+    /// The transfer amount encrypted under the auditor ElGamal public key
+    /// (low bits).
+    pub transfer_amount_auditor_ciphertext_lo: &'b PodElGamalCiphertext,
+    /// The transfer amount encrypted under the auditor ElGamal public key
+    /// (high bits).
+    pub transfer_amount_auditor_ciphertext_hi: &'b PodElGamalCiphertext,
+    /// Relative offset of the `VerifyCiphertextCommitmentEquality`
+    /// instruction, or `0` when the proof is a context state account.
+    pub equality_proof_instruction_offset: i8,
+    /// Relative offset of the
+    /// `VerifyBatchedGroupedCiphertext3HandlesValidity` instruction for the
+    /// transfer amount, or `0` when the proof is a context state account.
+    pub transfer_amount_ciphertext_validity_proof_instruction_offset: i8,
+    /// Relative offset of the `VerifyPercentageWithCap` instruction, or `0`
+    /// when the proof is a context state account.
+    pub fee_sigma_proof_instruction_offset: i8,
+    /// Relative offset of the
+    /// `VerifyBatchedGroupedCiphertext2HandlesValidity` instruction for the
+    /// fee, or `0` when the proof is a context state account.
+    pub fee_ciphertext_validity_proof_instruction_offset: i8,
+    /// Relative offset of the `VerifyBatchedRangeProofU128` instruction, or
+    /// `0` when the proof is a context state account.
+    pub range_proof_instruction_offset: i8,
+}
+
+impl TransferWithFee<'_, '_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    #[inline(always)]
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        if self.signers.len() > MAX_MULTISIG_SIGNERS {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        // Account metadata
+        let mut accounts = CpiAccounts::<{ 10 + MAX_MULTISIG_SIGNERS }>::new();
+
+        accounts.push(self.source, true, false);
+        accounts.push(self.mint, false, false);
+        accounts.push(self.destination, true, false);
+
+        for account in [
+            self.instructions_sysvar,
+            self.equality_proof_account,
+            self.transfer_amount_ciphertext_validity_proof_account,
+            self.fee_sigma_proof_account,
+            self.fee_ciphertext_validity_proof_account,
+            self.range_proof_account,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            accounts.push(account, false, false);
+        }
+
+        accounts.push_authority(self.owner, self.signers);
+
+        // Instruction data layout:
+        // -  [0]: extension instruction discriminator (1 byte, u8)
+        // -  [1]: instruction discriminator (1 byte, u8)
+        // -  [2..38]: new_source_decryptable_available_balance (36 bytes, AeCiphertext)
+        // -  [38..102]: transfer_amount_auditor_ciphertext_lo (64 bytes, ElGamalCiphertext)
+        // -  [102..166]: transfer_amount_auditor_ciphertext_hi (64 bytes, ElGamalCiphertext)
+        // -  [166]: equality_proof_instruction_offset (1 byte, i8)
+        // -  [167]: transfer_amount_ciphertext_validity_proof_instruction_offset (1 byte, i8)
+        // -  [168]: fee_sigma_proof_instruction_offset (1 byte, i8)
+        // -  [169]: fee_ciphertext_validity_proof_instruction_offset (1 byte, i8)
+        // -  [170]: range_proof_instruction_offset (1 byte, i8)
+        let mut instruction_data = IxData::<DATA_LEN>::new();
+
+        // Set discriminators as u8 at offsets [0..2]
+        instruction_data.write_u8(ExtensionDiscriminator::ConfidentialTransfer as u8);
+        instruction_data.write_u8(ConfidentialTransferInstruction::TransferWithFee as u8);
+        // Set new_source_decryptable_available_balance as AeCiphertext at offset [2..38]
+        instruction_data.write_bytes(self.new_source_decryptable_available_balance);
+        // Set transfer_amount_auditor_ciphertext_lo as ElGamalCiphertext at offset [38..102]
+        instruction_data.write_bytes(self.transfer_amount_auditor_ciphertext_lo);
+        // Set transfer_amount_auditor_ciphertext_hi as ElGamalCiphertext at offset [102..166]
+        instruction_data.write_bytes(self.transfer_amount_auditor_ciphertext_hi);
+        // Set proof instruction offsets as i8 at offsets [166..171]
+        instruction_data.write_u8(self.equality_proof_instruction_offset as u8);
+        instruction_data
+            .write_u8(self.transfer_amount_ciphertext_validity_proof_instruction_offset as u8);
+        instruction_data.write_u8(self.fee_sigma_proof_instruction_offset as u8);
+        instruction_data.write_u8(self.fee_ciphertext_validity_proof_instruction_offset as u8);
+        instruction_data.write_u8(self.range_proof_instruction_offset as u8);
+
+        let instruction = Instruction {
+            program_id: &crate::ID,
+            accounts: accounts.metas(),
+            data: instruction_data.finish(),
+        };
+
+        invoke_signed_with_bounds::<{ 10 + MAX_MULTISIG_SIGNERS }>(
+            &instruction,
+            accounts.infos(),
+            signers,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::vec;
+
+    use pinocchio::sysvars::instructions::INSTRUCTIONS_ID;
+
+    use super::*;
+    use crate::test_utils::{take_single, CapturedMeta, TestAccount};
+
+    #[test]
+    fn test_transfer_with_fee_with_proof_instructions() {
+        let source = TestAccount::new([1; 32]).writable().info();
+        let mint = TestAccount::new([2; 32]).info();
+        let destination = TestAccount::new([3; 32]).writable().info();
+        let sysvar = TestAccount::new(INSTRUCTIONS_ID).info();
+        let owner = TestAccount::new([4; 32]).signer().info();
+
+        TransferWithFee {
+            source: &source,
+            mint: &mint,
+            destination: &destination,
+            instructions_sysvar: Some(&sysvar),
+            equality_proof_account: None,
+            transfer_amount_ciphertext_validity_proof_account: None,
+            fee_sigma_proof_account: None,
+            fee_ciphertext_validity_proof_account: None,
+            range_proof_account: None,
+            owner: &owner,
+            signers: &[],
+            new_source_decryptable_available_balance: &[5; 36],
+            transfer_amount_auditor_ciphertext_lo: &[6; 64],
+            transfer_amount_auditor_ciphertext_hi: &[7; 64],
+            equality_proof_instruction_offset: 1,
+            transfer_amount_ciphertext_validity_proof_instruction_offset: 2,
+            fee_sigma_proof_instruction_offset: 3,
+            fee_ciphertext_validity_proof_instruction_offset: 4,
+            range_proof_instruction_offset: -1,
+        }
+        .invoke()
+        .unwrap();
+
+        let instruction = take_single();
+
+        // Hand-encoded layout of `TransferWithFeeInstructionData` of
+        // spl-token-2022, prefixed by the discriminators.
+        let mut expected = vec![27, 13];
+        expected.extend_from_slice(&[5; 36]);
+        expected.extend_from_slice(&[6; 64]);
+        expected.extend_from_slice(&[7; 64]);
+        expected.extend_from_slice(&[1, 2, 3, 4, 0xff]);
+
+        assert_eq!(instruction.data, expected);
+        assert_eq!(
+            instruction.accounts,
+            vec![
+                CapturedMeta::new([1; 32], true, false),
+                CapturedMeta::new([2; 32], false, false),
+                CapturedMeta::new([3; 32], true, false),
+                CapturedMeta::new(INSTRUCTIONS_ID, false, false),
+                CapturedMeta::new([4; 32], false, true),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_transfer_with_fee_with_context_accounts() {
+        let source = TestAccount::new([1; 32]).writable().info();
+        let mint = TestAccount::new([2; 32]).info();
+        let destination = TestAccount::new([3; 32]).writable().info();
+        let equality = TestAccount::new([8; 32]).info();
+        let validity = TestAccount::new([9; 32]).info();
+        let fee_sigma = TestAccount::new([10; 32]).info();
+        let fee_validity = TestAccount::new([11; 32]).info();
+        let range = TestAccount::new([12; 32]).info();
+        let owner = TestAccount::new([4; 32]).info();
+        let signer = TestAccount::new([13; 32]).signer().info();
+
+        TransferWithFee {
+            source: &source,
+            mint: &mint,
+            destination: &destination,
+            instructions_sysvar: None,
+            equality_proof_account: Some(&equality),
+            transfer_amount_ciphertext_validity_proof_account: Some(&validity),
+            fee_sigma_proof_account: Some(&fee_sigma),
+            fee_ciphertext_validity_proof_account: Some(&fee_validity),
+            range_proof_account: Some(&range),
+            owner: &owner,
+            signers: &[&signer],
+            new_source_decryptable_available_balance: &[0; 36],
+            transfer_amount_auditor_ciphertext_lo: &[0; 64],
+            transfer_amount_auditor_ciphertext_hi: &[0; 64],
+            equality_proof_instruction_offset: 0,
+            transfer_amount_ciphertext_validity_proof_instruction_offset: 0,
+            fee_sigma_proof_instruction_offset: 0,
+            fee_ciphertext_validity_proof_instruction_offset: 0,
+            range_proof_instruction_offset: 0,
+        }
+        .invoke()
+        .unwrap();
+
+        let instruction = take_single();
+
+        assert_eq!(instruction.data.len(), DATA_LEN);
+        assert_eq!(&instruction.data[..2], &[27, 13]);
+        assert_eq!(&instruction.data[166..], &[0; 5]);
+        assert_eq!(
+            instruction.accounts,
+            vec![
+                CapturedMeta::new([1; 32], true, false),
+                CapturedMeta::new([2; 32], false, false),
+                CapturedMeta::new([3; 32], true, false),
+                CapturedMeta::new([8; 32], false, false),
+                CapturedMeta::new([9; 32], false, false),
+                CapturedMeta::new([10; 32], false, false),
+                CapturedMeta::new([11; 32], false, false),
+                CapturedMeta::new([12; 32], false, false),
+                CapturedMeta::new([4; 32], false, false),
+                CapturedMeta::new([13; 32], false, true),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_transfer_with_fee_too_many_signers() {
+        let account = TestAccount::new([1; 32]).info();
+        let signers = [&account; MAX_MULTISIG_SIGNERS + 1];
+
+        let result = TransferWithFee {
+            source: &account,
+            mint: &account,
+            destination: &account,
+            instructions_sysvar: None,
+            equality_proof_account: None,
+            transfer_amount_ciphertext_validity_proof_account: None,
+            fee_sigma_proof_account: None,
+            fee_ciphertext_validity_proof_account: None,
+            range_proof_account: None,
+            owner: &account,
+            signers: &signers,
+            new_source_decryptable_available_balance: &[0; 36],
+            transfer_amount_auditor_ciphertext_lo: &[0; 64],
+            transfer_amount_auditor_ciphertext_hi: &[0; 64],
+            equality_proof_instruction_offset: 0,
+            transfer_amount_ciphertext_validity_proof_instruction_offset: 0,
+            fee_sigma_proof_instruction_offset: 0,
+            fee_ciphertext_validity_proof_instruction_offset: 0,
+            range_proof_instruction_offset: 0,
+        }
+        .invoke();
+
+        assert_eq!(result, Err(ProgramError::InvalidArgument));
+    }
+}
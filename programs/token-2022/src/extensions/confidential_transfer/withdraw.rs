@@ -0,0 +1,278 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Instruction, Signer},
+    program_error::ProgramError,
+    ProgramResult,
+};
+
+use crate::{
+    cpi::{invoke_signed_with_bounds, CpiAccounts, IxData},
+    extensions::{
+        confidential_transfer::{ConfidentialTransferInstruction, DecryptableBalance},
+        ExtensionDiscriminator,
+    },
+    instructions::MAX_MULTISIG_SIGNERS,
+};
+
+/// Withdraw SPL Tokens from the available balance of a confidential token
+/// account.
+///
+/// Each proof is provided either as an instruction in the same transaction,
+/// in which case the instructions sysvar is required and the proof account
+/// is an optional record account holding the proof data, or as a
+/// pre-verified context state account, in which case the proof instruction
+/// offset must be `0`.
+///
+/// ### Accounts:
+///   * Single owner
+///   0. `[WRITE]` The token account.
+///   1. `[]` The token mint.
+///   2. `[]` (Optional) Instructions sysvar if at least one of the proof
+///      instructions is included in the same transaction.
+///   3. `[]` (Optional) Equality proof record account or context state
+///      account.
+///   4. `[]` (Optional) Range proof record account or context state account.
+///   5. `[SIGNER]` The token account owner.
+///
+///   * Multisignature owner
+///   0. `[WRITE]` The token account.
+///   1. `[]` The token mint.
+///   2. `[]` (Optional) Instructions sysvar.
+///   3. `[]` (Optional) Equality proof record account or context state
+///      account.
+///   4. `[]` (Optional) Range proof record account or context state account.
+///   5. `[]` The multisig account owner.
+///   6. `..6+M` `[SIGNER]` M signer accounts.
+pub struct Withdraw<'a, 'b> {
+    /// Token Account.
+    pub token_account: &'a AccountInfo,
+    /// Mint Account.
+    pub mint: &'a AccountInfo,
+    /// Instructions sysvar, required when at least one of the proofs is
+    /// provided as an instruction in the same transaction.
+    pub instructions_sysvar: Option<&'a AccountInfo>,
+    /// Equality proof record account or context state account.
+    pub equality_proof_account: Option<&'a AccountInfo>,
+    /// Range proof record account or context state account.
+    pub range_proof_account: Option<&'a AccountInfo>,
+    /// Owner of the token account.
+    pub owner: &'a AccountInfo,
+    /// Signer accounts when the owner is a multisig.
+    pub signers: &'b [&'a AccountInfo],
+    /// Amount of tokens to withdraw.
+    pub amount: u64,
+    /// Expected number of base 10 digits to the right of the decimal place.
+    pub decimals: u8,
+    /// The new decryptable balance if the withdrawal succeeds.
+    pub new_decryptable_available_balance: &'b DecryptableBalance,
+    /// Relative offset of the `VerifyCiphertextCommitmentEquality`
+    /// instruction, or `0` when the proof is a context state account.
+    pub equality_proof_instruction_offset: i8,
+    /// Relative offset of the `VerifyBatchedRangeProofU64` instruction, or
+    /// `0` when the proof is a context state account.
+    pub range_proof_instruction_offset: i8,
+}
+
+impl Withdraw<'_, '_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    #[inline(always)]
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        if self.signers.len() > MAX_MULTISIG_SIGNERS {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        // Account metadata
+        let mut accounts = CpiAccounts::<{ 6 + MAX_MULTISIG_SIGNERS }>::new();
+
+        accounts.push(self.token_account, true, false);
+        accounts.push(self.mint, false, false);
+
+        for account in [
+            self.instructions_sysvar,
+            self.equality_proof_account,
+            self.range_proof_account,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            accounts.push(account, false, false);
+        }
+
+        accounts.push_authority(self.owner, self.signers);
+
+        // Instruction data layout:
+        // -  [0]: extension instruction discriminator (1 byte, u8)
+        // -  [1]: instruction discriminator (1 byte, u8)
+        // -  [2..10]: amount (8 bytes, u64)
+        // -  [10]: decimals (1 byte, u8)
+        // -  [11..47]: new_decryptable_available_balance (36 bytes, AeCiphertext)
+        // -  [47]: equality_proof_instruction_offset (1 byte, i8)
+        // -  [48]: range_proof_instruction_offset (1 byte, i8)
+        let mut instruction_data = IxData::<49>::new();
+
+        // Set discriminators as u8 at offsets [0..2]
+        instruction_data.write_u8(ExtensionDiscriminator::ConfidentialTransfer as u8);
+        instruction_data.write_u8(ConfidentialTransferInstruction::Withdraw as u8);
+        // Set amount as u64 at offset [2..10]
+        instruction_data.write_u64_le(self.amount);
+        // Set decimals as u8 at offset [10]
+        instruction_data.write_u8(self.decimals);
+        // Set new_decryptable_available_balance as AeCiphertext at offset [11..47]
+        instruction_data.write_bytes(self.new_decryptable_available_balance);
+        // Set proof instruction offsets as i8 at offsets [47..49]
+        instruction_data.write_u8(self.equality_proof_instruction_offset as u8);
+        instruction_data.write_u8(self.range_proof_instruction_offset as u8);
+
+        let instruction = Instruction {
+            program_id: &crate::ID,
+            accounts: accounts.metas(),
+            data: instruction_data.finish(),
+        };
+
+        invoke_signed_with_bounds::<{ 6 + MAX_MULTISIG_SIGNERS }>(
+            &instruction,
+            accounts.infos(),
+            signers,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::vec;
+
+    use pinocchio::sysvars::instructions::INSTRUCTIONS_ID;
+
+    use super::*;
+    use crate::test_utils::{take_single, CapturedMeta, TestAccount};
+
+    #[test]
+    fn test_withdraw_with_proof_instructions() {
+        let token_account = TestAccount::new([1; 32]).writable();
+        let mint = TestAccount::new([2; 32]);
+        let sysvar = TestAccount::new(INSTRUCTIONS_ID);
+        let owner = TestAccount::new([3; 32]).signer();
+
+        Withdraw {
+            token_account: &token_account.info(),
+            mint: &mint.info(),
+            instructions_sysvar: Some(&sysvar.info()),
+            equality_proof_account: None,
+            range_proof_account: None,
+            owner: &owner.info(),
+            signers: &[],
+            amount: 42,
+            decimals: 6,
+            new_decryptable_available_balance: &[9; 36],
+            equality_proof_instruction_offset: 1,
+            range_proof_instruction_offset: 2,
+        }
+        .invoke()
+        .unwrap();
+
+        let instruction = take_single();
+
+        let mut expected = vec![27, 6, 42, 0, 0, 0, 0, 0, 0, 0, 6];
+        expected.extend_from_slice(&[9; 36]);
+        expected.extend_from_slice(&[1, 2]);
+
+        assert_eq!(instruction.data, expected);
+        assert_eq!(
+            instruction.accounts,
+            vec![
+                CapturedMeta::new([1; 32], true, false),
+                CapturedMeta::new([2; 32], false, false),
+                CapturedMeta::new(INSTRUCTIONS_ID, false, false),
+                CapturedMeta::new([3; 32], false, true),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_withdraw_with_context_accounts() {
+        let token_account = TestAccount::new([1; 32]).writable();
+        let mint = TestAccount::new([2; 32]);
+        let equality = TestAccount::new([4; 32]);
+        let range = TestAccount::new([5; 32]);
+        let owner = TestAccount::new([3; 32]);
+        let signer = TestAccount::new([6; 32]).signer();
+
+        Withdraw {
+            token_account: &token_account.info(),
+            mint: &mint.info(),
+            instructions_sysvar: None,
+            equality_proof_account: Some(&equality.info()),
+            range_proof_account: Some(&range.info()),
+            owner: &owner.info(),
+            signers: &[&signer.info()],
+            amount: u64::MAX,
+            decimals: 0,
+            new_decryptable_available_balance: &[0; 36],
+            equality_proof_instruction_offset: 0,
+            range_proof_instruction_offset: 0,
+        }
+        .invoke()
+        .unwrap();
+
+        let instruction = take_single();
+
+        assert_eq!(instruction.data.len(), 49);
+        assert_eq!(&instruction.data[2..10], &[0xff; 8]);
+        assert_eq!(&instruction.data[47..], &[0, 0]);
+        assert_eq!(
+            instruction.accounts,
+            vec![
+                CapturedMeta::new([1; 32], true, false),
+                CapturedMeta::new([2; 32], false, false),
+                CapturedMeta::new([4; 32], false, false),
+                CapturedMeta::new([5; 32], false, false),
+                CapturedMeta::new([3; 32], false, false),
+                CapturedMeta::new([6; 32], false, true),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_withdraw_with_max_accounts() {
+        let token_account = TestAccount::new([1; 32]).writable().info();
+        let mint = TestAccount::new([2; 32]).info();
+        let sysvar = TestAccount::new(INSTRUCTIONS_ID).info();
+        let record = TestAccount::new([4; 32]).info();
+        let range = TestAccount::new([5; 32]).info();
+        let owner = TestAccount::new([3; 32]).info();
+        let signer = TestAccount::new([6; 32]).signer().info();
+        let signers = [&signer; MAX_MULTISIG_SIGNERS];
+
+        let withdraw = Withdraw {
+            token_account: &token_account,
+            mint: &mint,
+            instructions_sysvar: Some(&sysvar),
+            equality_proof_account: Some(&record),
+            range_proof_account: Some(&range),
+            owner: &owner,
+            signers: &signers,
+            amount: 1,
+            decimals: 0,
+            new_decryptable_available_balance: &[0; 36],
+            equality_proof_instruction_offset: 1,
+            range_proof_instruction_offset: 0,
+        };
+
+        withdraw.invoke().unwrap();
+        assert_eq!(take_single().accounts.len(), 6 + MAX_MULTISIG_SIGNERS);
+
+        let signers = [&signer; MAX_MULTISIG_SIGNERS + 1];
+        let withdraw = Withdraw {
+            signers: &signers,
+            ..withdraw
+        };
+
+        assert_eq!(withdraw.invoke(), Err(ProgramError::InvalidArgument));
+    }
+}
@@ -0,0 +1,202 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Instruction, Signer},
+    program_error::ProgramError,
+    ProgramResult,
+};
+
+use crate::{
+    cpi::{invoke_signed_with_bounds, CpiAccounts},
+    extensions::{
+        confidential_transfer_fee::ConfidentialTransferFeeInstruction, ExtensionDiscriminator,
+    },
+    instructions::MAX_MULTISIG_SIGNERS,
+};
+
+/// Allow withheld confidential fees to be harvested to the mint.
+///
+/// ### Accounts:
+///   * Single authority
+///   0. `[WRITE]` The token mint.
+///   1. `[SIGNER]` The confidential transfer fee authority.
+///
+///   * Multisignature authority
+///   0. `[WRITE]` The token mint.
+///   1. `[]` The confidential transfer fee multisig authority.
+///   2. `..2+M` `[SIGNER]` M signer accounts.
+pub struct EnableHarvestToMint<'a, 'b> {
+    /// Mint Account.
+    pub mint: &'a AccountInfo,
+    /// The confidential transfer fee authority.
+    pub authority: &'a AccountInfo,
+    /// Signer accounts when the authority is a multisig.
+    pub signers: &'b [&'a AccountInfo],
+}
+
+impl EnableHarvestToMint<'_, '_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    #[inline(always)]
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        invoke_harvest_to_mint(
+            ConfidentialTransferFeeInstruction::EnableHarvestToMint,
+            self.mint,
+            self.authority,
+            self.signers,
+            signers,
+        )
+    }
+}
+
+/// Prevent withheld confidential fees from being harvested to the mint.
+///
+/// ### Accounts:
+///   * Single authority
+///   0. `[WRITE]` The token mint.
+///   1. `[SIGNER]` The confidential transfer fee authority.
+///
+///   * Multisignature authority
+///   0. `[WRITE]` The token mint.
+///   1. `[]` The confidential transfer fee multisig authority.
+///   2. `..2+M` `[SIGNER]` M signer accounts.
+pub struct DisableHarvestToMint<'a, 'b> {
+    /// Mint Account.
+    pub mint: &'a AccountInfo,
+    /// The confidential transfer fee authority.
+    pub authority: &'a AccountInfo,
+    /// Signer accounts when the authority is a multisig.
+    pub signers: &'b [&'a AccountInfo],
+}
+
+impl DisableHarvestToMint<'_, '_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    #[inline(always)]
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        invoke_harvest_to_mint(
+            ConfidentialTransferFeeInstruction::DisableHarvestToMint,
+            self.mint,
+            self.authority,
+            self.signers,
+            signers,
+        )
+    }
+}
+
+#[inline(always)]
+fn invoke_harvest_to_mint(
+    instruction: ConfidentialTransferFeeInstruction,
+    mint: &AccountInfo,
+    authority: &AccountInfo,
+    multisig_signers: &[&AccountInfo],
+    signers: &[Signer],
+) -> ProgramResult {
+    if multisig_signers.len() > MAX_MULTISIG_SIGNERS {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Account metadata
+    let mut accounts = CpiAccounts::<{ 2 + MAX_MULTISIG_SIGNERS }>::new();
+
+    accounts.push(mint, true, false);
+    accounts.push_authority(authority, multisig_signers);
+
+    // Instruction data layout:
+    // -  [0]: extension instruction discriminator (1 byte, u8)
+    // -  [1]: instruction discriminator (1 byte, u8)
+    let instruction = Instruction {
+        program_id: &crate::ID,
+        accounts: accounts.metas(),
+        data: &[
+            ExtensionDiscriminator::ConfidentialTransferFee as u8,
+            instruction as u8,
+        ],
+    };
+
+    invoke_signed_with_bounds::<{ 2 + MAX_MULTISIG_SIGNERS }>(
+        &instruction,
+        accounts.infos(),
+        signers,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::vec;
+
+    use super::*;
+    use crate::test_utils::{take_captured, CapturedMeta, TestAccount};
+
+    #[test]
+    fn test_harvest_to_mint() {
+        let mint = TestAccount::new([1; 32]).writable().info();
+        let authority = TestAccount::new([2; 32]).signer().info();
+
+        EnableHarvestToMint {
+            mint: &mint,
+            authority: &authority,
+            signers: &[],
+        }
+        .invoke()
+        .unwrap();
+
+        DisableHarvestToMint {
+            mint: &mint,
+            authority: &authority,
+            signers: &[],
+        }
+        .invoke()
+        .unwrap();
+
+        let instructions = take_captured();
+
+        assert_eq!(instructions[0].data, vec![37, 4]);
+        assert_eq!(instructions[1].data, vec![37, 5]);
+
+        for instruction in instructions {
+            assert_eq!(
+                instruction.accounts,
+                vec![
+                    CapturedMeta::new([1; 32], true, false),
+                    CapturedMeta::new([2; 32], false, true),
+                ]
+            );
+        }
+    }
+
+    #[test]
+    fn test_harvest_to_mint_multisig() {
+        let mint = TestAccount::new([1; 32]).writable().info();
+        let authority = TestAccount::new([2; 32]).info();
+        let signer_1 = TestAccount::new([3; 32]).signer().info();
+        let signer_2 = TestAccount::new([4; 32]).signer().info();
+
+        DisableHarvestToMint {
+            mint: &mint,
+            authority: &authority,
+            signers: &[&signer_1, &signer_2],
+        }
+        .invoke()
+        .unwrap();
+
+        let instructions = take_captured();
+
+        assert_eq!(
+            instructions[0].accounts,
+            vec![
+                CapturedMeta::new([1; 32], true, false),
+                CapturedMeta::new([2; 32], false, false),
+                CapturedMeta::new([3; 32], false, true),
+                CapturedMeta::new([4; 32], false, true),
+            ]
+        );
+    }
+}
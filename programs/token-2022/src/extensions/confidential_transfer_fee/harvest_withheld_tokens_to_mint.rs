@@ -0,0 +1,131 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Instruction, Signer},
+    program_error::ProgramError,
+    ProgramResult,
+};
+
+use crate::{
+    cpi::{invoke_signed_with_bounds, CpiAccounts},
+    extensions::{
+        confidential_transfer_fee::{ConfidentialTransferFeeInstruction, MAX_SOURCE_ACCOUNTS},
+        ExtensionDiscriminator,
+    },
+};
+
+/// Maximum number of accounts of the instruction.
+const MAX_ACCOUNTS: usize = 1 + MAX_SOURCE_ACCOUNTS;
+
+/// Permissionless instruction to transfer all withheld confidential tokens
+/// to the mint.
+///
+/// Source accounts that cannot be harvested (e.g., accounts of a different
+/// mint) are skipped by the token program.
+///
+/// ### Accounts:
+///   0. `[WRITE]` The mint.
+///   1. `..1+N` `[WRITE]` The source accounts to harvest from.
+pub struct HarvestWithheldTokensToMint<'a, 'b> {
+    /// Mint Account.
+    pub mint: &'a AccountInfo,
+    /// Token accounts to harvest the withheld tokens from, up to
+    /// [`MAX_SOURCE_ACCOUNTS`].
+    pub sources: &'b [&'a AccountInfo],
+}
+
+impl HarvestWithheldTokensToMint<'_, '_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    #[inline(always)]
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        if self.sources.len() > MAX_SOURCE_ACCOUNTS {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        // Account metadata
+        let mut accounts = CpiAccounts::<MAX_ACCOUNTS>::new();
+
+        accounts.push(self.mint, true, false);
+
+        for source in self.sources {
+            accounts.push(source, true, false);
+        }
+
+        // Instruction data layout:
+        // -  [0]: extension instruction discriminator (1 byte, u8)
+        // -  [1]: instruction discriminator (1 byte, u8)
+        let instruction = Instruction {
+            program_id: &crate::ID,
+            accounts: accounts.metas(),
+            data: &[
+                ExtensionDiscriminator::ConfidentialTransferFee as u8,
+                ConfidentialTransferFeeInstruction::HarvestWithheldTokensToMint as u8,
+            ],
+        };
+
+        invoke_signed_with_bounds::<MAX_ACCOUNTS>(&instruction, accounts.infos(), signers)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::{vec, vec::Vec};
+
+    use super::*;
+    use crate::test_utils::{take_single, CapturedMeta, TestAccount};
+
+    #[test]
+    fn test_harvest_withheld_tokens_to_mint_without_sources() {
+        let mint = TestAccount::new([1; 32]).writable().info();
+
+        HarvestWithheldTokensToMint {
+            mint: &mint,
+            sources: &[],
+        }
+        .invoke()
+        .unwrap();
+
+        let instruction = take_single();
+
+        assert_eq!(instruction.data, vec![37, 3]);
+        assert_eq!(
+            instruction.accounts,
+            vec![CapturedMeta::new([1; 32], true, false)]
+        );
+    }
+
+    #[test]
+    fn test_harvest_withheld_tokens_to_mint_with_max_sources() {
+        let mint = TestAccount::new([1; 32]).writable().info();
+        let sources = (0..=MAX_SOURCE_ACCOUNTS as u8)
+            .map(|i| TestAccount::new([100 + i; 32]).writable().info())
+            .collect::<Vec<_>>();
+        let sources = sources.iter().collect::<Vec<_>>();
+
+        HarvestWithheldTokensToMint {
+            mint: &mint,
+            sources: &sources[..MAX_SOURCE_ACCOUNTS],
+        }
+        .invoke()
+        .unwrap();
+
+        let instruction = take_single();
+
+        assert_eq!(instruction.data, vec![37, 3]);
+        assert_eq!(instruction.accounts.len(), 1 + MAX_SOURCE_ACCOUNTS);
+        for (meta, i) in instruction.accounts[1..].iter().zip(0u8..) {
+            assert_eq!(meta, &CapturedMeta::new([100 + i; 32], true, false));
+        }
+
+        let harvest = HarvestWithheldTokensToMint {
+            mint: &mint,
+            sources: &sources,
+        };
+        assert_eq!(harvest.invoke(), Err(ProgramError::InvalidArgument));
+    }
+}
@@ -0,0 +1,25 @@
+//! Confidential transfer fee extension.
+
+mod harvest_to_mint;
+mod harvest_withheld_tokens_to_mint;
+mod withdraw_withheld_tokens_from_accounts;
+
+pub use harvest_to_mint::*;
+pub use harvest_withheld_tokens_to_mint::*;
+pub use withdraw_withheld_tokens_from_accounts::*;
+
+/// Maximum number of source token accounts of the instructions that collect
+/// withheld fees from token accounts.
+pub const MAX_SOURCE_ACCOUNTS: usize = 20;
+
+/// Instruction discriminators of the confidential transfer fee extension.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConfidentialTransferFeeInstruction {
+    InitializeConfidentialTransferFeeConfig = 0,
+    WithdrawWithheldTokensFromMint = 1,
+    WithdrawWithheldTokensFromAccounts = 2,
+    HarvestWithheldTokensToMint = 3,
+    EnableHarvestToMint = 4,
+    DisableHarvestToMint = 5,
+}
@@ -0,0 +1,215 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Instruction, Signer},
+    program_error::ProgramError,
+    ProgramResult,
+};
+
+use crate::{
+    cpi::{invoke_signed_with_bounds, CpiAccounts, IxData},
+    extensions::{
+        confidential_transfer::DecryptableBalance,
+        confidential_transfer_fee::{ConfidentialTransferFeeInstruction, MAX_SOURCE_ACCOUNTS},
+        ExtensionDiscriminator,
+    },
+    instructions::MAX_MULTISIG_SIGNERS,
+};
+
+/// Maximum number of accounts of the instruction.
+const MAX_ACCOUNTS: usize = 5 + MAX_MULTISIG_SIGNERS + MAX_SOURCE_ACCOUNTS;
+
+/// Transfer all withheld confidential tokens from token accounts to the
+/// fee receiver account.
+///
+/// The proof that the withheld amount re-encrypted under the destination
+/// ElGamal public key matches is provided either as a
+/// `VerifyCiphertextCiphertextEquality` instruction in the same transaction,
+/// in which case the proof account is the instructions sysvar, or as a
+/// pre-verified context state account, in which case the proof instruction
+/// offset must be `0`.
+///
+/// ### Accounts:
+///   * Single owner
+///   0. `[WRITE]` The token mint.
+///   1. `[WRITE]` The fee receiver account.
+///   2. `[]` Instructions sysvar or context state account.
+///   3. `[]` (Optional) Record account if the accompanying proof is to be
+///      read from a record account.
+///   4. `[SIGNER]` The mint's `withdraw_withheld_authority`.
+///   5. `..5+N` `[WRITE]` The source accounts to withdraw from.
+///
+///   * Multisignature owner
+///   0. `[WRITE]` The token mint.
+///   1. `[WRITE]` The fee receiver account.
+///   2. `[]` Instructions sysvar or context state account.
+///   3. `[]` (Optional) Record account.
+///   4. `[]` The mint's multisig `withdraw_withheld_authority`.
+///   5. `..5+M` `[SIGNER]` M signer accounts.
+///   6. `5+M+1..5+M+N` `[WRITE]` The source accounts to withdraw from.
+pub struct WithdrawWithheldTokensFromAccounts<'a, 'b> {
+    /// Mint Account.
+    pub mint: &'a AccountInfo,
+    /// Fee receiver token account.
+    pub destination: &'a AccountInfo,
+    /// Instructions sysvar or `VerifyCiphertextCiphertextEquality` context
+    /// state account.
+    pub proof_account: &'a AccountInfo,
+    /// Record account holding the proof data, when the proof is read from
+    /// a record account.
+    pub record_account: Option<&'a AccountInfo>,
+    /// The mint's `withdraw_withheld_authority`.
+    pub authority: &'a AccountInfo,
+    /// Signer accounts when the authority is a multisig.
+    pub signers: &'b [&'a AccountInfo],
+    /// Token accounts to withdraw the withheld tokens from, up to
+    /// [`MAX_SOURCE_ACCOUNTS`].
+    pub sources: &'b [&'a AccountInfo],
+    /// Relative offset of the `VerifyCiphertextCiphertextEquality`
+    /// instruction, or `0` when the proof is a context state account.
+    pub proof_instruction_offset: i8,
+    /// The new decryptable balance of the destination account.
+    pub new_decryptable_available_balance: &'b DecryptableBalance,
+}
+
+impl WithdrawWithheldTokensFromAccounts<'_, '_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    #[inline(always)]
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        if self.signers.len() > MAX_MULTISIG_SIGNERS || self.sources.len() > MAX_SOURCE_ACCOUNTS {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        // Account metadata
+        let mut accounts = CpiAccounts::<MAX_ACCOUNTS>::new();
+
+        accounts.push(self.mint, true, false);
+        accounts.push(self.destination, true, false);
+        accounts.push(self.proof_account, false, false);
+
+        if let Some(record_account) = self.record_account {
+            accounts.push(record_account, false, false);
+        }
+
+        accounts.push_authority(self.authority, self.signers);
+
+        for source in self.sources {
+            accounts.push(source, true, false);
+        }
+
+        // Instruction data layout:
+        // -  [0]: extension instruction discriminator (1 byte, u8)
+        // -  [1]: instruction discriminator (1 byte, u8)
+        // -  [2]: num_token_accounts (1 byte, u8)
+        // -  [3]: proof_instruction_offset (1 byte, i8)
+        // -  [4..40]: new_decryptable_available_balance (36 bytes, AeCiphertext)
+        let mut instruction_data = IxData::<40>::new();
+
+        // Set discriminators, num_token_accounts and proof_instruction_offset
+        // at offsets [0..4]
+        instruction_data.write_u8(ExtensionDiscriminator::ConfidentialTransferFee as u8);
+        instruction_data
+            .write_u8(ConfidentialTransferFeeInstruction::WithdrawWithheldTokensFromAccounts as u8);
+        instruction_data.write_u8(self.sources.len() as u8);
+        instruction_data.write_u8(self.proof_instruction_offset as u8);
+        // Set new_decryptable_available_balance as AeCiphertext at offset [4..40]
+        instruction_data.write_bytes(self.new_decryptable_available_balance);
+
+        let instruction = Instruction {
+            program_id: &crate::ID,
+            accounts: accounts.metas(),
+            data: instruction_data.finish(),
+        };
+
+        invoke_signed_with_bounds::<MAX_ACCOUNTS>(&instruction, accounts.infos(), signers)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::{vec, vec::Vec};
+
+    use super::*;
+    use crate::test_utils::{take_single, CapturedMeta, TestAccount};
+
+    fn withdraw(sources: &[&AccountInfo]) -> ProgramResult {
+        let mint = TestAccount::new([1; 32]).writable().info();
+        let destination = TestAccount::new([2; 32]).writable().info();
+        let proof = TestAccount::new([3; 32]).info();
+        let authority = TestAccount::new([4; 32]).signer().info();
+
+        WithdrawWithheldTokensFromAccounts {
+            mint: &mint,
+            destination: &destination,
+            proof_account: &proof,
+            record_account: None,
+            authority: &authority,
+            signers: &[],
+            sources,
+            proof_instruction_offset: 0,
+            new_decryptable_available_balance: &[5; 36],
+        }
+        .invoke()
+    }
+
+    #[test]
+    fn test_withdraw_withheld_tokens_from_no_accounts() {
+        withdraw(&[]).unwrap();
+
+        let instruction = take_single();
+
+        let mut expected = vec![37, 2, 0, 0];
+        expected.extend_from_slice(&[5; 36]);
+
+        assert_eq!(instruction.data, expected);
+        assert_eq!(
+            instruction.accounts,
+            vec![
+                CapturedMeta::new([1; 32], true, false),
+                CapturedMeta::new([2; 32], true, false),
+                CapturedMeta::new([3; 32], false, false),
+                CapturedMeta::new([4; 32], false, true),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_withdraw_withheld_tokens_from_one_account() {
+        let source = TestAccount::new([10; 32]).writable().info();
+
+        withdraw(&[&source]).unwrap();
+
+        let instruction = take_single();
+
+        assert_eq!(instruction.data[2], 1);
+        assert_eq!(
+            instruction.accounts.last(),
+            Some(&CapturedMeta::new([10; 32], true, false))
+        );
+    }
+
+    #[test]
+    fn test_withdraw_withheld_tokens_from_many_accounts() {
+        let sources = (0..=MAX_SOURCE_ACCOUNTS as u8)
+            .map(|i| TestAccount::new([100 + i; 32]).writable().info())
+            .collect::<Vec<_>>();
+        let sources = sources.iter().collect::<Vec<_>>();
+
+        withdraw(&sources[..MAX_SOURCE_ACCOUNTS]).unwrap();
+
+        let instruction = take_single();
+
+        assert_eq!(instruction.data[2], MAX_SOURCE_ACCOUNTS as u8);
+        assert_eq!(instruction.accounts.len(), 4 + MAX_SOURCE_ACCOUNTS);
+        for (meta, i) in instruction.accounts[4..].iter().zip(0u8..) {
+            assert_eq!(meta, &CapturedMeta::new([100 + i; 32], true, false));
+        }
+
+        assert_eq!(withdraw(&sources), Err(ProgramError::InvalidArgument));
+    }
+}
@@ -0,0 +1,361 @@
+//! CPI guard extension.
+
+use pinocchio::{
+    account_info::{AccountInfo, Ref},
+    instruction::{Instruction, Signer},
+    program_error::ProgramError,
+    ProgramResult,
+};
+
+use crate::{
+    cpi::{invoke_signed_with_bounds, CpiAccounts, IxData},
+    extensions::{
+        get_extension_from_account_info, get_extension_from_account_info_unchecked, BaseState,
+        Extension, ExtensionDiscriminator, ExtensionType,
+    },
+    instructions::MAX_MULTISIG_SIGNERS,
+    pod::PodBool,
+};
+
+/// Instruction discriminators of the CPI guard extension.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CpiGuardInstruction {
+    Enable = 0,
+    Disable = 1,
+}
+
+/// CPI guard state of a token account.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CpiGuard {
+    /// Whether privileged token operations are locked from happening via CPI.
+    pub lock_cpi: PodBool,
+}
+
+unsafe impl Extension for CpiGuard {
+    const TYPE: ExtensionType = ExtensionType::CpiGuard;
+    const LEN: usize = core::mem::size_of::<Self>();
+    const BASE_STATE: BaseState = BaseState::TokenAccount;
+}
+
+const _: () = assert!(CpiGuard::LEN == 1);
+
+impl CpiGuard {
+    /// Return the `CpiGuard` extension of the given token account.
+    ///
+    /// This method performs owner validation on `AccountInfo`, safe borrowing
+    /// the account data.
+    ///
+    /// Returns the `ExtensionError` code if the account is not a token account
+    /// or does not have the extension.
+    /// Returns `AccountBorrowFailed` if the account data is mutably borrowed.
+    #[inline]
+    pub fn from_account_info(account_info: &AccountInfo) -> Result<Ref<Self>, ProgramError> {
+        get_extension_from_account_info(account_info)
+    }
+
+    /// Return the `CpiGuard` extension of the given token account.
+    ///
+    /// This method performs owner validation on `AccountInfo`, but does not
+    /// perform the borrow check.
+    ///
+    /// Returns the `ExtensionError` code if the account is not a token account
+    /// or does not have the extension.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that it is safe to borrow the account data (e.g., there are
+    /// no mutable borrows of the account data).
+    #[inline]
+    pub unsafe fn from_account_info_unchecked(
+        account_info: &AccountInfo,
+    ) -> Result<&Self, ProgramError> {
+        get_extension_from_account_info_unchecked(account_info)
+    }
+
+    /// Return whether privileged token operations are locked from CPIs.
+    #[inline(always)]
+    pub fn is_cpi_locked(&self) -> bool {
+        self.lock_cpi.get()
+    }
+}
+
+/// Lock privileged token operations from happening via CPI.
+///
+/// The token program rejects the instruction when invoked through a CPI, so
+/// it can only be used to build instructions submitted directly.
+///
+/// ### Accounts:
+///   * Single owner
+///   0. `[WRITE]` The token account.
+///   1. `[SIGNER]` The token account owner.
+///
+///   * Multisignature owner
+///   0. `[WRITE]` The token account.
+///   1. `[]` The multisig account owner.
+///   2. `..2+M` `[SIGNER]` M signer accounts.
+pub struct EnableCpiGuard<'a, 'b> {
+    /// Token Account.
+    pub token_account: &'a AccountInfo,
+    /// Owner of the token account.
+    pub owner: &'a AccountInfo,
+    /// Signer accounts when the owner is a multisig.
+    pub signers: &'b [&'a AccountInfo],
+}
+
+impl EnableCpiGuard<'_, '_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    #[inline(always)]
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        invoke_cpi_guard(
+            CpiGuardInstruction::Enable,
+            self.token_account,
+            self.owner,
+            self.signers,
+            signers,
+        )
+    }
+}
+
+/// Allow privileged token operations to happen via CPI.
+///
+/// The token program rejects the instruction when invoked through a CPI, so
+/// it can only be used to build instructions submitted directly.
+///
+/// ### Accounts:
+///   * Single owner
+///   0. `[WRITE]` The token account.
+///   1. `[SIGNER]` The token account owner.
+///
+///   * Multisignature owner
+///   0. `[WRITE]` The token account.
+///   1. `[]` The multisig account owner.
+///   2. `..2+M` `[SIGNER]` M signer accounts.
+pub struct DisableCpiGuard<'a, 'b> {
+    /// Token Account.
+    pub token_account: &'a AccountInfo,
+    /// Owner of the token account.
+    pub owner: &'a AccountInfo,
+    /// Signer accounts when the owner is a multisig.
+    pub signers: &'b [&'a AccountInfo],
+}
+
+impl DisableCpiGuard<'_, '_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    #[inline(always)]
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        invoke_cpi_guard(
+            CpiGuardInstruction::Disable,
+            self.token_account,
+            self.owner,
+            self.signers,
+            signers,
+        )
+    }
+}
+
+#[inline(always)]
+fn invoke_cpi_guard(
+    instruction: CpiGuardInstruction,
+    token_account: &AccountInfo,
+    owner: &AccountInfo,
+    multisig_signers: &[&AccountInfo],
+    signers: &[Signer],
+) -> ProgramResult {
+    if multisig_signers.len() > MAX_MULTISIG_SIGNERS {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Account metadata
+    let mut accounts = CpiAccounts::<{ 2 + MAX_MULTISIG_SIGNERS }>::new();
+
+    accounts.push(token_account, true, false);
+    accounts.push_authority(owner, multisig_signers);
+
+    // Instruction data layout:
+    // -  [0]: extension instruction discriminator (1 byte, u8)
+    // -  [1]: instruction discriminator (1 byte, u8)
+    let mut instruction_data = IxData::<2>::new();
+
+    instruction_data.write_u8(ExtensionDiscriminator::CpiGuard as u8);
+    instruction_data.write_u8(instruction as u8);
+
+    let instruction = Instruction {
+        program_id: &crate::ID,
+        accounts: accounts.metas(),
+        data: instruction_data.finish(),
+    };
+
+    invoke_signed_with_bounds::<{ 2 + MAX_MULTISIG_SIGNERS }>(
+        &instruction,
+        accounts.infos(),
+        signers,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::{vec, vec::Vec};
+
+    use super::*;
+    use crate::{
+        extensions::{ExtensionError, ExtensionType},
+        test_utils::{
+            account_with_extensions, take_captured, token_account_base, CapturedMeta, TestAccount,
+        },
+    };
+
+    fn cpi_guard_token_account_data(enabled: bool) -> Vec<u8> {
+        account_with_extensions(
+            BaseState::TokenAccount,
+            &token_account_base(&[1; 32], &[2; 32], 1),
+            &[
+                (ExtensionType::ImmutableOwner, &[]),
+                (ExtensionType::CpiGuard, &[enabled as u8]),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_cpi_guard_from_account_info() {
+        for enabled in [false, true] {
+            let account =
+                TestAccount::with_data([1; 32], crate::ID, &cpi_guard_token_account_data(enabled))
+                    .info();
+
+            let extension = unsafe { CpiGuard::from_account_info_unchecked(&account) }.unwrap();
+            assert_eq!(extension.is_cpi_locked(), enabled);
+        }
+
+        let account =
+            TestAccount::with_data([1; 32], [9; 32], &cpi_guard_token_account_data(true)).info();
+        assert_eq!(
+            unsafe { CpiGuard::from_account_info_unchecked(&account) },
+            Err(ProgramError::InvalidAccountOwner)
+        );
+
+        let account = TestAccount::with_data(
+            [1; 32],
+            crate::ID,
+            &token_account_base(&[3; 32], &[2; 32], 0),
+        )
+        .info();
+        assert_eq!(
+            unsafe { CpiGuard::from_account_info_unchecked(&account) },
+            Err(ExtensionError::NotFound.into())
+        );
+    }
+
+    #[test]
+    fn test_cpi_guard_from_account_info_borrow() {
+        let account =
+            TestAccount::with_data([1; 32], crate::ID, &cpi_guard_token_account_data(true)).info();
+
+        {
+            let extension = CpiGuard::from_account_info(&account).unwrap();
+            assert!(extension.is_cpi_locked());
+
+            // Shared borrows can coexist, but the data cannot be mutably
+            // borrowed while the extension is held.
+            assert!(CpiGuard::from_account_info(&account).is_ok());
+            assert_eq!(
+                account.try_borrow_mut_data().err(),
+                Some(ProgramError::AccountBorrowFailed)
+            );
+        }
+
+        {
+            let _data = account.try_borrow_mut_data().unwrap();
+            assert_eq!(
+                CpiGuard::from_account_info(&account).err(),
+                Some(ProgramError::AccountBorrowFailed)
+            );
+        }
+
+        // Borrows are released when the guards are dropped.
+        assert!(CpiGuard::from_account_info(&account).is_ok());
+
+        let account =
+            TestAccount::with_data([1; 32], [9; 32], &cpi_guard_token_account_data(true)).info();
+        assert_eq!(
+            CpiGuard::from_account_info(&account).err(),
+            Some(ProgramError::InvalidAccountOwner)
+        );
+    }
+
+    #[test]
+    fn test_cpi_guard() {
+        let token_account = TestAccount::new([1; 32]).writable().info();
+        let owner = TestAccount::new([2; 32]).signer().info();
+
+        EnableCpiGuard {
+            token_account: &token_account,
+            owner: &owner,
+            signers: &[],
+        }
+        .invoke()
+        .unwrap();
+
+        DisableCpiGuard {
+            token_account: &token_account,
+            owner: &owner,
+            signers: &[],
+        }
+        .invoke()
+        .unwrap();
+
+        let instructions = take_captured();
+
+        assert_eq!(instructions[0].data, vec![34, 0]);
+        assert_eq!(instructions[1].data, vec![34, 1]);
+
+        for instruction in instructions {
+            assert_eq!(
+                instruction.accounts,
+                vec![
+                    CapturedMeta::new([1; 32], true, false),
+                    CapturedMeta::new([2; 32], false, true),
+                ]
+            );
+        }
+    }
+
+    #[test]
+    fn test_cpi_guard_multisig() {
+        let token_account = TestAccount::new([1; 32]).writable().info();
+        let owner = TestAccount::new([2; 32]).info();
+        let signer_1 = TestAccount::new([3; 32]).signer().info();
+        let signer_2 = TestAccount::new([4; 32]).signer().info();
+
+        DisableCpiGuard {
+            token_account: &token_account,
+            owner: &owner,
+            signers: &[&signer_1, &signer_2],
+        }
+        .invoke()
+        .unwrap();
+
+        let instructions = take_captured();
+
+        assert_eq!(
+            instructions[0].accounts,
+            vec![
+                CapturedMeta::new([1; 32], true, false),
+                CapturedMeta::new([2; 32], false, false),
+                CapturedMeta::new([3; 32], false, true),
+                CapturedMeta::new([4; 32], false, true),
+            ]
+        );
+    }
+}
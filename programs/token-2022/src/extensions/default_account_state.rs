@@ -0,0 +1,279 @@
+//! Default account state extension.
+
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{AccountMeta, Instruction, Signer},
+    program_error::ProgramError,
+    ProgramResult,
+};
+
+use crate::{
+    cpi::{invoke_signed, invoke_signed_with_bounds, CpiAccounts, IxData},
+    extensions::{BaseState, Extension, ExtensionDiscriminator, ExtensionType},
+    instructions::MAX_MULTISIG_SIGNERS,
+    state::AccountState,
+};
+
+/// Maximum number of accounts of the `Update` instruction.
+const MAX_UPDATE_ACCOUNTS: usize = 2 + MAX_MULTISIG_SIGNERS;
+
+/// Instruction discriminators of the default account state extension.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DefaultAccountStateInstruction {
+    Initialize = 0,
+    Update = 1,
+}
+
+/// Default state of new token accounts of a mint.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DefaultAccountState {
+    /// Default `AccountState` in which new accounts are initialized.
+    ///
+    /// Stored as a raw byte, since the account data may hold any value; use
+    /// [`DefaultAccountState::state`] to read it.
+    pub state: u8,
+}
+
+unsafe impl Extension for DefaultAccountState {
+    const TYPE: ExtensionType = ExtensionType::DefaultAccountState;
+    const LEN: usize = core::mem::size_of::<Self>();
+    const BASE_STATE: BaseState = BaseState::Mint;
+}
+
+const _: () = assert!(DefaultAccountState::LEN == 1);
+
+impl DefaultAccountState {
+    /// Return the default state of new token accounts.
+    ///
+    /// Returns `InvalidAccountData` if the stored value is not an
+    /// `AccountState`.
+    #[inline]
+    pub fn state(&self) -> Result<AccountState, ProgramError> {
+        if self.state > AccountState::Frozen as u8 {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(AccountState::from(self.state))
+    }
+}
+
+/// Initialize the default account state extension on a new mint.
+///
+/// Must be invoked before `InitializeMint`, since the token program rejects
+/// extension initialization on an initialized mint.
+///
+/// ### Accounts:
+///   0. `[WRITE]` The mint to initialize.
+pub struct InitializeDefaultAccountState<'a> {
+    /// Mint Account.
+    pub mint: &'a AccountInfo,
+    /// Default state of new token accounts, which cannot be
+    /// `Uninitialized`.
+    pub state: AccountState,
+}
+
+impl<'a> InitializeDefaultAccountState<'a> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    /// Invoke the instruction.
+    ///
+    /// Returns `InvalidInstructionData` if the state is `Uninitialized`.
+    #[inline(always)]
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        validate_state(self.state)?;
+
+        let account_metas = self.account_metas();
+        let instruction_data = self.instruction_data();
+
+        let instruction = Instruction {
+            program_id: &crate::ID,
+            accounts: &account_metas,
+            data: instruction_data.finish(),
+        };
+
+        invoke_signed(&instruction, &[self.mint], signers)
+    }
+
+    /// Return the account metas of the instruction.
+    #[inline(always)]
+    pub fn account_metas(&self) -> [AccountMeta<'a>; 1] {
+        [AccountMeta::writable(self.mint.key())]
+    }
+
+    /// Return the instruction data.
+    #[inline(always)]
+    pub fn instruction_data(&self) -> IxData<3> {
+        // Instruction data layout:
+        // -  [0]: extension instruction discriminator (1 byte, u8)
+        // -  [1]: instruction discriminator (1 byte, u8)
+        // -  [2]: state (1 byte, u8)
+        let mut instruction_data = IxData::<3>::new();
+
+        instruction_data.write_u8(ExtensionDiscriminator::DefaultAccountState as u8);
+        instruction_data.write_u8(DefaultAccountStateInstruction::Initialize as u8);
+        instruction_data.write_u8(self.state as u8);
+
+        instruction_data
+    }
+}
+
+/// Update the default state of new token accounts of a mint.
+///
+/// ### Accounts:
+///   * Single authority
+///   0. `[WRITE]` The mint.
+///   1. `[SIGNER]` The mint freeze authority.
+///
+///   * Multisignature authority
+///   0. `[WRITE]` The mint.
+///   1. `[]` The mint multisig freeze authority.
+///   2. `..2+M` `[SIGNER]` M signer accounts.
+pub struct UpdateDefaultAccountState<'a, 'b> {
+    /// Mint Account.
+    pub mint: &'a AccountInfo,
+    /// The mint freeze authority.
+    pub freeze_authority: &'a AccountInfo,
+    /// Signer accounts when the freeze authority is a multisig.
+    pub signers: &'b [&'a AccountInfo],
+    /// Default state of new token accounts, which cannot be
+    /// `Uninitialized`.
+    pub state: AccountState,
+}
+
+impl UpdateDefaultAccountState<'_, '_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    /// Invoke the instruction.
+    ///
+    /// Returns `InvalidInstructionData` if the state is `Uninitialized`.
+    #[inline(always)]
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        if self.signers.len() > MAX_MULTISIG_SIGNERS {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        validate_state(self.state)?;
+
+        // Account metadata
+        let mut accounts = CpiAccounts::<MAX_UPDATE_ACCOUNTS>::new();
+
+        accounts.push(self.mint, true, false);
+        accounts.push_authority(self.freeze_authority, self.signers);
+
+        // Instruction data layout:
+        // -  [0]: extension instruction discriminator (1 byte, u8)
+        // -  [1]: instruction discriminator (1 byte, u8)
+        // -  [2]: state (1 byte, u8)
+        let mut instruction_data = IxData::<3>::new();
+
+        instruction_data.write_u8(ExtensionDiscriminator::DefaultAccountState as u8);
+        instruction_data.write_u8(DefaultAccountStateInstruction::Update as u8);
+        instruction_data.write_u8(self.state as u8);
+
+        let instruction = Instruction {
+            program_id: &crate::ID,
+            accounts: accounts.metas(),
+            data: instruction_data.finish(),
+        };
+
+        invoke_signed_with_bounds::<MAX_UPDATE_ACCOUNTS>(&instruction, accounts.infos(), signers)
+    }
+}
+
+/// Check that a default account state is accepted by the token program.
+#[inline(always)]
+fn validate_state(state: AccountState) -> Result<(), ProgramError> {
+    if state == AccountState::Uninitialized {
+        Err(ProgramError::InvalidInstructionData)
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::vec;
+
+    use super::*;
+    use crate::test_utils::{take_single, CapturedMeta, TestAccount};
+
+    #[test]
+    fn test_state() {
+        for (state, expected) in [
+            (0, AccountState::Uninitialized),
+            (1, AccountState::Initialized),
+            (2, AccountState::Frozen),
+        ] {
+            assert_eq!(DefaultAccountState { state }.state(), Ok(expected));
+        }
+
+        for state in [3, u8::MAX] {
+            assert_eq!(
+                DefaultAccountState { state }.state(),
+                Err(ProgramError::InvalidAccountData)
+            );
+        }
+    }
+
+    #[test]
+    fn test_initialize_default_account_state() {
+        let mint = TestAccount::new([1; 32]).writable().info();
+
+        let mut initialize = InitializeDefaultAccountState {
+            mint: &mint,
+            state: AccountState::Frozen,
+        };
+        initialize.invoke().unwrap();
+
+        let instruction = take_single();
+
+        assert_eq!(instruction.data, vec![28, 0, 2]);
+        assert_eq!(
+            instruction.accounts,
+            vec![CapturedMeta::new([1; 32], true, false)]
+        );
+
+        initialize.state = AccountState::Uninitialized;
+        assert_eq!(
+            initialize.invoke(),
+            Err(ProgramError::InvalidInstructionData)
+        );
+    }
+
+    #[test]
+    fn test_update_default_account_state() {
+        let mint = TestAccount::new([1; 32]).writable().info();
+        let freeze_authority = TestAccount::new([2; 32]).signer().info();
+
+        let mut update = UpdateDefaultAccountState {
+            mint: &mint,
+            freeze_authority: &freeze_authority,
+            signers: &[],
+            state: AccountState::Initialized,
+        };
+        update.invoke().unwrap();
+
+        let instruction = take_single();
+
+        assert_eq!(instruction.data, vec![28, 1, 1]);
+        assert_eq!(
+            instruction.accounts,
+            vec![
+                CapturedMeta::new([1; 32], true, false),
+                CapturedMeta::new([2; 32], false, true),
+            ]
+        );
+
+        update.state = AccountState::Uninitialized;
+        assert_eq!(update.invoke(), Err(ProgramError::InvalidInstructionData));
+    }
+}
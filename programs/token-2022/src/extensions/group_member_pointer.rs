@@ -0,0 +1,200 @@
+//! Group member pointer extension.
+
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Instruction, Signer},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    ProgramResult,
+};
+
+use crate::{
+    cpi::{invoke_signed_with_bounds, CpiAccounts, IxData},
+    extensions::{BaseState, Extension, ExtensionDiscriminator, ExtensionType},
+    instructions::MAX_MULTISIG_SIGNERS,
+    pod::non_zero,
+};
+
+/// Maximum number of accounts of the `Update` instruction.
+const MAX_UPDATE_ACCOUNTS: usize = 2 + MAX_MULTISIG_SIGNERS;
+
+/// Instruction discriminators of the group member pointer extension.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GroupMemberPointerInstruction {
+    Initialize = 0,
+    Update = 1,
+}
+
+/// Group member pointer of a mint.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct GroupMemberPointer {
+    /// Authority that can set the address.
+    pub authority: Pubkey,
+    /// Account address that holds the member.
+    pub member_address: Pubkey,
+}
+
+unsafe impl Extension for GroupMemberPointer {
+    const TYPE: ExtensionType = ExtensionType::GroupMemberPointer;
+    const LEN: usize = core::mem::size_of::<Self>();
+    const BASE_STATE: BaseState = BaseState::Mint;
+}
+
+const _: () = assert!(GroupMemberPointer::LEN == 64);
+
+impl GroupMemberPointer {
+    /// Return the authority that can set the address, or `None` if the
+    /// address cannot be updated.
+    #[inline(always)]
+    pub fn authority(&self) -> Option<&Pubkey> {
+        non_zero(&self.authority)
+    }
+
+    /// Return the address of the account that holds the member, or `None`
+    /// if it is not set.
+    #[inline(always)]
+    pub fn member_address(&self) -> Option<&Pubkey> {
+        non_zero(&self.member_address)
+    }
+
+    /// Return whether the member is stored in the mint `mint_key` itself.
+    #[inline(always)]
+    pub fn is_self_referential(&self, mint_key: &Pubkey) -> bool {
+        self.member_address() == Some(mint_key)
+    }
+}
+
+/// Update the member address of the group member pointer.
+///
+/// ### Accounts:
+///   * Single authority
+///   0. `[WRITE]` The mint.
+///   1. `[SIGNER]` The group member pointer authority.
+///
+///   * Multisignature authority
+///   0. `[WRITE]` The mint.
+///   1. `[]` The group member pointer multisig authority.
+///   2. `..2+M` `[SIGNER]` M signer accounts.
+pub struct Update<'a, 'b> {
+    /// Mint Account.
+    pub mint: &'a AccountInfo,
+    /// The group member pointer authority.
+    pub authority: &'a AccountInfo,
+    /// Signer accounts when the authority is a multisig.
+    pub signers: &'b [&'a AccountInfo],
+    /// New member address, or `None` to clear it.
+    pub member_address: Option<&'a Pubkey>,
+}
+
+impl Update<'_, '_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    #[inline(always)]
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        if self.signers.len() > MAX_MULTISIG_SIGNERS {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        // Account metadata
+        let mut accounts = CpiAccounts::<MAX_UPDATE_ACCOUNTS>::new();
+
+        accounts.push(self.mint, true, false);
+        accounts.push_authority(self.authority, self.signers);
+
+        // Instruction data layout:
+        // -  [0]: extension instruction discriminator (1 byte, u8)
+        // -  [1]: instruction discriminator (1 byte, u8)
+        // -  [2..34]: member_address (32 bytes, Pubkey, zeroed if `None`)
+        let mut instruction_data = IxData::<34>::new();
+
+        instruction_data.write_u8(ExtensionDiscriminator::GroupMemberPointer as u8);
+        instruction_data.write_u8(GroupMemberPointerInstruction::Update as u8);
+        instruction_data.write_pubkey(self.member_address.unwrap_or(&Pubkey::default()));
+
+        let instruction = Instruction {
+            program_id: &crate::ID,
+            accounts: accounts.metas(),
+            data: instruction_data.finish(),
+        };
+
+        invoke_signed_with_bounds::<MAX_UPDATE_ACCOUNTS>(&instruction, accounts.infos(), signers)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::vec;
+
+    use super::*;
+    use crate::{
+        extensions::try_get_extension,
+        test_utils::{
+            account_with_extensions, mint_base, take_captured, CapturedMeta, TestAccount,
+        },
+    };
+
+    #[test]
+    fn test_accessors() {
+        let mut group_member_pointer = [1; 64];
+        group_member_pointer[..32].copy_from_slice(&[2; 32]);
+        let data = account_with_extensions(
+            BaseState::Mint,
+            &mint_base(Some(&[2; 32]), 0, 0, None),
+            &[(ExtensionType::GroupMemberPointer, &group_member_pointer)],
+        );
+        let pointer = try_get_extension::<GroupMemberPointer>(&data).unwrap();
+
+        assert_eq!(pointer.authority(), Some(&[2; 32]));
+        assert_eq!(pointer.member_address(), Some(&[1; 32]));
+        assert!(pointer.is_self_referential(&[1; 32]));
+        assert!(!pointer.is_self_referential(&[3; 32]));
+
+        let pointer = GroupMemberPointer {
+            authority: [0; 32],
+            member_address: [0; 32],
+        };
+
+        assert_eq!(pointer.authority(), None);
+        assert_eq!(pointer.member_address(), None);
+        assert!(!pointer.is_self_referential(&[0; 32]));
+    }
+
+    #[test]
+    fn test_update_group_member_pointer() {
+        let mint = TestAccount::new([1; 32]).writable().info();
+        let authority = TestAccount::new([2; 32]).signer().info();
+
+        for member_address in [Some(&[3; 32]), Some(&[4; 32]), None] {
+            Update {
+                mint: &mint,
+                authority: &authority,
+                signers: &[],
+                member_address,
+            }
+            .invoke()
+            .unwrap();
+        }
+
+        let instructions = take_captured();
+
+        assert_eq!(instructions.len(), 3);
+        for (instruction, address) in instructions.iter().zip([[3; 32], [4; 32], [0; 32]]) {
+            assert_eq!(&instruction.data[..2], &[41, 1]);
+            assert_eq!(&instruction.data[2..], &address);
+            assert_eq!(
+                instruction.accounts,
+                vec![
+                    CapturedMeta::new([1; 32], true, false),
+                    CapturedMeta::new([2; 32], false, true),
+                ]
+            );
+        }
+    }
+}
@@ -0,0 +1,219 @@
+//! Group pointer extension.
+
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Instruction, Signer},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    ProgramResult,
+};
+
+use crate::{
+    cpi::{invoke_signed_with_bounds, CpiAccounts, IxData},
+    extensions::{BaseState, Extension, ExtensionDiscriminator, ExtensionType},
+    instructions::MAX_MULTISIG_SIGNERS,
+    pod::non_zero,
+};
+
+/// Maximum number of accounts of the `Update` instruction.
+const MAX_UPDATE_ACCOUNTS: usize = 2 + MAX_MULTISIG_SIGNERS;
+
+/// Instruction discriminators of the group pointer extension.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GroupPointerInstruction {
+    Initialize = 0,
+    Update = 1,
+}
+
+/// Group pointer of a mint.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct GroupPointer {
+    /// Authority that can set the address.
+    pub authority: Pubkey,
+    /// Account address that holds the group.
+    pub group_address: Pubkey,
+}
+
+unsafe impl Extension for GroupPointer {
+    const TYPE: ExtensionType = ExtensionType::GroupPointer;
+    const LEN: usize = core::mem::size_of::<Self>();
+    const BASE_STATE: BaseState = BaseState::Mint;
+}
+
+const _: () = assert!(GroupPointer::LEN == 64);
+
+impl GroupPointer {
+    /// Return the authority that can set the address, or `None` if the
+    /// address cannot be updated.
+    #[inline(always)]
+    pub fn authority(&self) -> Option<&Pubkey> {
+        non_zero(&self.authority)
+    }
+
+    /// Return the address of the account that holds the group, or `None`
+    /// if it is not set.
+    #[inline(always)]
+    pub fn group_address(&self) -> Option<&Pubkey> {
+        non_zero(&self.group_address)
+    }
+
+    /// Return whether the group is stored in the mint `mint_key` itself.
+    #[inline(always)]
+    pub fn is_self_referential(&self, mint_key: &Pubkey) -> bool {
+        self.group_address() == Some(mint_key)
+    }
+}
+
+/// Update the group address of the group pointer.
+///
+/// ### Accounts:
+///   * Single authority
+///   0. `[WRITE]` The mint.
+///   1. `[SIGNER]` The group pointer authority.
+///
+///   * Multisignature authority
+///   0. `[WRITE]` The mint.
+///   1. `[]` The group pointer multisig authority.
+///   2. `..2+M` `[SIGNER]` M signer accounts.
+pub struct Update<'a, 'b> {
+    /// Mint Account.
+    pub mint: &'a AccountInfo,
+    /// The group pointer authority.
+    pub authority: &'a AccountInfo,
+    /// Signer accounts when the authority is a multisig.
+    pub signers: &'b [&'a AccountInfo],
+    /// New group address, or `None` to clear it.
+    pub group_address: Option<&'a Pubkey>,
+}
+
+impl Update<'_, '_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    #[inline(always)]
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        if self.signers.len() > MAX_MULTISIG_SIGNERS {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        // Account metadata
+        let mut accounts = CpiAccounts::<MAX_UPDATE_ACCOUNTS>::new();
+
+        accounts.push(self.mint, true, false);
+        accounts.push_authority(self.authority, self.signers);
+
+        // Instruction data layout:
+        // -  [0]: extension instruction discriminator (1 byte, u8)
+        // -  [1]: instruction discriminator (1 byte, u8)
+        // -  [2..34]: group_address (32 bytes, Pubkey, zeroed if `None`)
+        let mut instruction_data = IxData::<34>::new();
+
+        instruction_data.write_u8(ExtensionDiscriminator::GroupPointer as u8);
+        instruction_data.write_u8(GroupPointerInstruction::Update as u8);
+        instruction_data.write_pubkey(self.group_address.unwrap_or(&Pubkey::default()));
+
+        let instruction = Instruction {
+            program_id: &crate::ID,
+            accounts: accounts.metas(),
+            data: instruction_data.finish(),
+        };
+
+        invoke_signed_with_bounds::<MAX_UPDATE_ACCOUNTS>(&instruction, accounts.infos(), signers)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::vec;
+
+    use super::*;
+    use crate::test_utils::{take_single, CapturedMeta, TestAccount};
+
+    #[test]
+    fn test_accessors() {
+        let pointer = GroupPointer {
+            authority: [2; 32],
+            group_address: [1; 32],
+        };
+
+        assert_eq!(pointer.authority(), Some(&[2; 32]));
+        assert_eq!(pointer.group_address(), Some(&[1; 32]));
+        assert!(pointer.is_self_referential(&[1; 32]));
+        assert!(!pointer.is_self_referential(&[3; 32]));
+
+        let pointer = GroupPointer {
+            authority: [0; 32],
+            group_address: [0; 32],
+        };
+
+        assert_eq!(pointer.authority(), None);
+        assert_eq!(pointer.group_address(), None);
+        assert!(!pointer.is_self_referential(&[0; 32]));
+    }
+
+    #[test]
+    fn test_update_group_pointer_multisig() {
+        let mint = TestAccount::new([1; 32]).writable().info();
+        let multisig = TestAccount::new([2; 32]).info();
+        let signer_1 = TestAccount::new([3; 32]).signer().info();
+        let signer_2 = TestAccount::new([4; 32]).signer().info();
+
+        Update {
+            mint: &mint,
+            authority: &multisig,
+            signers: &[&signer_1, &signer_2],
+            group_address: Some(&[5; 32]),
+        }
+        .invoke()
+        .unwrap();
+
+        let instruction = take_single();
+
+        let mut data = vec![40, 1];
+        data.extend_from_slice(&[5; 32]);
+
+        assert_eq!(instruction.data, data);
+        assert_eq!(
+            instruction.accounts,
+            vec![
+                CapturedMeta::new([1; 32], true, false),
+                CapturedMeta::new([2; 32], false, false),
+                CapturedMeta::new([3; 32], false, true),
+                CapturedMeta::new([4; 32], false, true),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_update_group_pointer_clear() {
+        let mint = TestAccount::new([1; 32]).writable().info();
+        let authority = TestAccount::new([2; 32]).signer().info();
+
+        Update {
+            mint: &mint,
+            authority: &authority,
+            signers: &[],
+            group_address: None,
+        }
+        .invoke()
+        .unwrap();
+
+        let instruction = take_single();
+
+        assert_eq!(&instruction.data[..2], &[40, 1]);
+        assert_eq!(&instruction.data[2..], &[0; 32]);
+        assert_eq!(
+            instruction.accounts,
+            vec![
+                CapturedMeta::new([1; 32], true, false),
+                CapturedMeta::new([2; 32], false, true),
+            ]
+        );
+    }
+}
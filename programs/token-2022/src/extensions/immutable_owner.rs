@@ -0,0 +1,122 @@
+//! Immutable owner extension.
+
+use pinocchio::{
+    account_info::{AccountInfo, Ref},
+    program_error::ProgramError,
+};
+
+use crate::extensions::{
+    get_extension_from_account_info, get_extension_from_account_info_unchecked, BaseState,
+    Extension, ExtensionType,
+};
+
+/// Marker for token accounts whose owner cannot be changed.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ImmutableOwner;
+
+unsafe impl Extension for ImmutableOwner {
+    const TYPE: ExtensionType = ExtensionType::ImmutableOwner;
+    const LEN: usize = core::mem::size_of::<Self>();
+    const BASE_STATE: BaseState = BaseState::TokenAccount;
+}
+
+const _: () = assert!(ImmutableOwner::LEN == 0);
+
+impl ImmutableOwner {
+    /// Return the `ImmutableOwner` extension of the given token account.
+    ///
+    /// This method performs owner validation on `AccountInfo`, safe borrowing
+    /// the account data.
+    ///
+    /// Returns the `ExtensionError` code if the account is not a token account
+    /// or its owner is not immutable.
+    /// Returns `AccountBorrowFailed` if the account data is mutably borrowed.
+    #[inline]
+    pub fn from_account_info(account_info: &AccountInfo) -> Result<Ref<Self>, ProgramError> {
+        get_extension_from_account_info(account_info)
+    }
+
+    /// Return the `ImmutableOwner` extension of the given token account.
+    ///
+    /// This method performs owner validation on `AccountInfo`, but does not
+    /// perform the borrow check.
+    ///
+    /// Returns the `ExtensionError` code if the account is not a token account
+    /// or its owner is not immutable.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that it is safe to borrow the account data (e.g., there are
+    /// no mutable borrows of the account data).
+    #[inline]
+    pub unsafe fn from_account_info_unchecked(
+        account_info: &AccountInfo,
+    ) -> Result<&Self, ProgramError> {
+        get_extension_from_account_info_unchecked(account_info)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        extensions::{try_get_extension, ExtensionError},
+        test_utils::{account_with_extensions, token_account_base, TestAccount},
+    };
+
+    #[test]
+    fn test_immutable_owner_from_account_info() {
+        // Zero-length entry followed by another zero-length entry, and
+        // zero-length entry at the end of the data.
+        let extensions: [&[(ExtensionType, &[u8])]; 2] = [
+            &[
+                (ExtensionType::ImmutableOwner, &[]),
+                (ExtensionType::NonTransferableAccount, &[]),
+            ],
+            &[
+                (ExtensionType::TransferFeeAmount, &[7; 8]),
+                (ExtensionType::ImmutableOwner, &[]),
+            ],
+        ];
+
+        for extensions in extensions {
+            let data = account_with_extensions(
+                BaseState::TokenAccount,
+                &token_account_base(&[1; 32], &[2; 32], 1),
+                extensions,
+            );
+            let account = TestAccount::with_data([1; 32], crate::ID, &data).info();
+            assert_eq!(
+                unsafe { ImmutableOwner::from_account_info_unchecked(&account) },
+                Ok(&ImmutableOwner)
+            );
+        }
+
+        let account = TestAccount::with_data(
+            [1; 32],
+            crate::ID,
+            &token_account_base(&[3; 32], &[2; 32], 0),
+        )
+        .info();
+        assert_eq!(
+            unsafe { ImmutableOwner::from_account_info_unchecked(&account) },
+            Err(ExtensionError::NotFound.into())
+        );
+    }
+
+    #[test]
+    fn test_immutable_owner_with_data() {
+        // An `ImmutableOwner` entry with data is not a valid extension.
+        let data = account_with_extensions(
+            BaseState::TokenAccount,
+            &token_account_base(&[3; 32], &[2; 32], 0),
+            &[(ExtensionType::ImmutableOwner, &[1])],
+        );
+
+        assert_eq!(
+            try_get_extension::<ImmutableOwner>(&data),
+            Err(ExtensionError::InvalidData)
+        );
+    }
+}
@@ -0,0 +1,131 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{AccountMeta, Instruction, Signer},
+    pubkey::Pubkey,
+    ProgramResult,
+};
+
+use crate::{
+    cpi::{invoke_signed, IxData},
+    extensions::{interest_bearing_mint::InterestBearingMintInstruction, ExtensionDiscriminator},
+};
+
+/// Initialize the interest-bearing extension on a new mint.
+///
+/// Must be invoked before `InitializeMint`, since the token program rejects
+/// extension initialization on an initialized mint.
+///
+/// ### Accounts:
+///   0. `[WRITE]` The mint to initialize.
+pub struct Initialize<'a> {
+    /// Mint Account.
+    pub mint: &'a AccountInfo,
+    /// Authority that can update the rate, or `None` if the rate cannot be
+    /// updated.
+    pub rate_authority: Option<&'a Pubkey>,
+    /// Initial interest rate, in basis points.
+    pub rate: i16,
+}
+
+impl<'a> Initialize<'a> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    #[inline(always)]
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        let account_metas = self.account_metas();
+        let instruction_data = self.instruction_data();
+
+        let instruction = Instruction {
+            program_id: &crate::ID,
+            accounts: &account_metas,
+            data: instruction_data.finish(),
+        };
+
+        invoke_signed(&instruction, &[self.mint], signers)
+    }
+
+    /// Return the account metas of the instruction.
+    #[inline(always)]
+    pub fn account_metas(&self) -> [AccountMeta<'a>; 1] {
+        [AccountMeta::writable(self.mint.key())]
+    }
+
+    /// Return the instruction data.
+    #[inline(always)]
+    pub fn instruction_data(&self) -> IxData<36> {
+        // Instruction data layout:
+        // -  [0]: extension instruction discriminator (1 byte, u8)
+        // -  [1]: instruction discriminator (1 byte, u8)
+        // -  [2..34]: rate_authority (32 bytes, Pubkey, zeroed if `None`)
+        // -  [34..36]: rate (2 bytes, i16)
+        let mut instruction_data = IxData::<36>::new();
+
+        instruction_data.write_u8(ExtensionDiscriminator::InterestBearingMint as u8);
+        instruction_data.write_u8(InterestBearingMintInstruction::Initialize as u8);
+        instruction_data.write_pubkey(self.rate_authority.unwrap_or(&Pubkey::default()));
+        instruction_data.write_bytes(&self.rate.to_le_bytes());
+
+        instruction_data
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::vec;
+
+    use super::*;
+    use crate::test_utils::{take_single, CapturedMeta, TestAccount};
+
+    #[test]
+    fn test_initialize_interest_bearing_mint() {
+        let mint = TestAccount::new([1; 32]).writable().info();
+
+        Initialize {
+            mint: &mint,
+            rate_authority: Some(&[2; 32]),
+            rate: 500,
+        }
+        .invoke()
+        .unwrap();
+
+        let instruction = take_single();
+
+        let mut data = vec![33, 0];
+        data.extend_from_slice(&[2; 32]);
+        data.extend_from_slice(&[0xf4, 0x01]);
+
+        assert_eq!(instruction.data, data);
+        assert_eq!(
+            instruction.accounts,
+            vec![CapturedMeta::new([1; 32], true, false)]
+        );
+    }
+
+    #[test]
+    fn test_initialize_interest_bearing_mint_negative_rate() {
+        let mint = TestAccount::new([1; 32]).writable().info();
+
+        Initialize {
+            mint: &mint,
+            rate_authority: None,
+            rate: -300,
+        }
+        .invoke()
+        .unwrap();
+
+        let instruction = take_single();
+
+        assert_eq!(&instruction.data[2..34], &[0; 32]);
+        // Two's complement of 300 (0x012c).
+        assert_eq!(&instruction.data[34..], &[0xd4, 0xfe]);
+        assert_eq!(
+            i16::from_le_bytes([instruction.data[34], instruction.data[35]]),
+            -300
+        );
+    }
+}
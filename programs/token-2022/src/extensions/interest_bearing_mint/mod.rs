@@ -0,0 +1,266 @@
+//! Interest-bearing mint extension.
+
+mod initialize;
+
+pub use initialize::*;
+
+use pinocchio::pubkey::Pubkey;
+
+use crate::{
+    extensions::{BaseState, Extension, ExtensionType},
+    math::{exp, pow10},
+    pod::{non_zero, PodI16, PodI64},
+};
+
+/// Number of seconds in a year, as used by the token program (365.24 days).
+pub const SECONDS_PER_YEAR: f64 = 60.0 * 60.0 * 24.0 * 365.24;
+
+/// One, expressed in basis points.
+const ONE_IN_BASIS_POINTS: f64 = 10_000.0;
+
+/// Instruction discriminators of the interest-bearing mint extension.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InterestBearingMintInstruction {
+    Initialize = 0,
+    UpdateRate = 1,
+}
+
+/// Interest rate configuration of a mint.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct InterestBearingConfig {
+    /// Authority that can set the interest rate.
+    pub rate_authority: Pubkey,
+    /// Timestamp of initialization, from which to base interest
+    /// calculations.
+    pub initialization_timestamp: PodI64,
+    /// Average rate from initialization until the last time it was updated,
+    /// in basis points.
+    pub pre_update_average_rate: PodI16,
+    /// Timestamp of the last update, used to calculate the total amount
+    /// accrued.
+    pub last_update_timestamp: PodI64,
+    /// Current rate, since the last update, in basis points.
+    pub current_rate: PodI16,
+}
+
+unsafe impl Extension for InterestBearingConfig {
+    const TYPE: ExtensionType = ExtensionType::InterestBearingConfig;
+    const LEN: usize = core::mem::size_of::<Self>();
+    const BASE_STATE: BaseState = BaseState::Mint;
+}
+
+const _: () = assert!(InterestBearingConfig::LEN == 52);
+
+impl InterestBearingConfig {
+    /// Return the authority that can set the interest rate, or `None` if
+    /// the rate cannot be updated.
+    #[inline(always)]
+    pub fn authority(&self) -> Option<&Pubkey> {
+        non_zero(&self.rate_authority)
+    }
+
+    /// Return the timestamp of initialization.
+    #[inline(always)]
+    pub fn initialization_timestamp(&self) -> i64 {
+        self.initialization_timestamp.get()
+    }
+
+    /// Return the average rate from initialization until the last update,
+    /// in basis points.
+    #[inline(always)]
+    pub fn pre_update_average_rate(&self) -> i16 {
+        self.pre_update_average_rate.get()
+    }
+
+    /// Return the timestamp of the last update.
+    #[inline(always)]
+    pub fn last_update_timestamp(&self) -> i64 {
+        self.last_update_timestamp.get()
+    }
+
+    /// Return the current rate, in basis points.
+    #[inline(always)]
+    pub fn current_rate(&self) -> i16 {
+        self.current_rate.get()
+    }
+
+    /// Return the interest accrued between the initialization and the last
+    /// update, as a scale factor of the amount.
+    #[inline]
+    fn pre_update_exp(&self) -> Option<f64> {
+        let timespan = self
+            .last_update_timestamp()
+            .checked_sub(self.initialization_timestamp())?;
+        Some(continuous_compounding(
+            self.pre_update_average_rate(),
+            timespan,
+        ))
+    }
+
+    /// Return the interest accrued between the last update and
+    /// `unix_timestamp`, as a scale factor of the amount.
+    ///
+    /// The scale factor is lower than `1` for timestamps before the last
+    /// update when the rate is positive, as in the token program.
+    #[inline]
+    fn post_update_exp(&self, unix_timestamp: i64) -> Option<f64> {
+        let timespan = unix_timestamp.checked_sub(self.last_update_timestamp())?;
+        Some(continuous_compounding(self.current_rate(), timespan))
+    }
+
+    /// Convert a raw amount to its UI amount at `unix_timestamp`, including
+    /// the interest accrued since initialization.
+    ///
+    /// Replicates the conversion of the token program: the interest is
+    /// compounded continuously, at the average rate before the last update
+    /// and at the current rate after it. Amounts that overflow an `f64` are
+    /// saturated to `u64::MAX`.
+    ///
+    /// Returns `None` if a timespan overflows an `i64`.
+    pub fn amount_to_ui_amount(
+        &self,
+        amount: u64,
+        decimals: u8,
+        unix_timestamp: i64,
+    ) -> Option<f64> {
+        let total_scale =
+            self.pre_update_exp()? * self.post_update_exp(unix_timestamp)? / pow10(decimals);
+        let ui_amount = amount as f64 * total_scale;
+
+        if ui_amount.is_infinite() {
+            Some(u64::MAX as f64)
+        } else {
+            Some(ui_amount)
+        }
+    }
+}
+
+/// Return the scale factor of continuously compounding `rate` basis points
+/// per year over `timespan` seconds.
+#[inline(always)]
+fn continuous_compounding(rate: i16, timespan: i64) -> f64 {
+    // The product of an `i16` and an `i64` always fits in an `i128`.
+    let numerator = (rate as i128 * timespan as i128) as f64;
+    exp(numerator / SECONDS_PER_YEAR / ONE_IN_BASIS_POINTS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::extensions::try_from_bytes_ref;
+
+    const ONE_YEAR: i64 = SECONDS_PER_YEAR as i64;
+
+    fn config(
+        initialization_timestamp: i64,
+        pre_update_average_rate: i16,
+        last_update_timestamp: i64,
+        current_rate: i16,
+    ) -> InterestBearingConfig {
+        InterestBearingConfig {
+            rate_authority: [1; 32],
+            initialization_timestamp: initialization_timestamp.into(),
+            pre_update_average_rate: pre_update_average_rate.into(),
+            last_update_timestamp: last_update_timestamp.into(),
+            current_rate: current_rate.into(),
+        }
+    }
+
+    fn assert_ui_amount(value: Option<f64>, expected: f64) {
+        let value = value.unwrap();
+        assert!(
+            ((value - expected) / expected).abs() < 1e-14,
+            "ui amount {value}, expected {expected}"
+        );
+    }
+
+    #[test]
+    fn test_accessors() {
+        let mut data = [0; InterestBearingConfig::LEN];
+        data[..32].copy_from_slice(&[1; 32]);
+        data[32..40].copy_from_slice(&1_700_000_000i64.to_le_bytes());
+        data[40..42].copy_from_slice(&250i16.to_le_bytes());
+        data[42..50].copy_from_slice(&(-1i64).to_le_bytes());
+        data[50..52].copy_from_slice(&(-500i16).to_le_bytes());
+
+        let config = try_from_bytes_ref::<InterestBearingConfig>(&data).unwrap();
+
+        assert_eq!(config.authority(), Some(&[1; 32]));
+        assert_eq!(config.initialization_timestamp(), 1_700_000_000);
+        assert_eq!(config.pre_update_average_rate(), 250);
+        assert_eq!(config.last_update_timestamp(), -1);
+        assert_eq!(config.current_rate(), -500);
+
+        data[..32].copy_from_slice(&[0; 32]);
+        let config = try_from_bytes_ref::<InterestBearingConfig>(&data).unwrap();
+
+        assert_eq!(config.authority(), None);
+    }
+
+    #[test]
+    fn test_amount_to_ui_amount() {
+        // Expected values are computed by `spl-token-2022`.
+
+        // 1 year at 5%.
+        let constant = config(0, 500, ONE_YEAR, 500);
+        assert_ui_amount(
+            constant.amount_to_ui_amount(1, 0, ONE_YEAR),
+            1.051_271_096_376_024_1,
+        );
+        assert_ui_amount(
+            constant.amount_to_ui_amount(1, 1, ONE_YEAR),
+            0.105_127_109_637_602_41,
+        );
+        assert_ui_amount(
+            constant.amount_to_ui_amount(1, 10, ONE_YEAR),
+            0.000_000_000_105_127_109_637_602_42,
+        );
+        assert_ui_amount(
+            constant.amount_to_ui_amount(10_000_000_000, 10, ONE_YEAR),
+            1.051_271_096_376_024_1,
+        );
+
+        // 1 year at -5%.
+        let negative = config(0, -500, ONE_YEAR, -500);
+        assert_ui_amount(
+            negative.amount_to_ui_amount(1, 0, ONE_YEAR),
+            0.951_229_424_500_714,
+        );
+
+        // 1 year at -5% followed by 1 year at 5%.
+        let net_out = config(0, -500, ONE_YEAR, 500);
+        assert_ui_amount(net_out.amount_to_ui_amount(1, 0, ONE_YEAR * 2), 1.0);
+
+        // Timestamp before the last update.
+        assert_ui_amount(constant.amount_to_ui_amount(1, 0, 0), 1.0);
+
+        assert_ui_amount(
+            constant.amount_to_ui_amount(u64::MAX, 0, ONE_YEAR * 2),
+            20_386_805_083_448_100_000.0,
+        );
+    }
+
+    #[test]
+    fn test_amount_to_ui_amount_saturation() {
+        let constant = config(0, 500, ONE_YEAR, 500);
+        let maximum = config(0, 500, ONE_YEAR, i16::MAX);
+
+        assert_eq!(
+            maximum.amount_to_ui_amount(u64::MAX, 0, ONE_YEAR * 10_000),
+            Some(u64::MAX as f64)
+        );
+        assert_eq!(
+            config(0, i16::MAX, i64::MAX, i16::MAX).amount_to_ui_amount(1, 0, i64::MAX),
+            Some(u64::MAX as f64)
+        );
+
+        // Timespans overflowing an `i64`.
+        assert_eq!(
+            config(i64::MIN, 500, 1, 500).amount_to_ui_amount(1, 0, 1),
+            None
+        );
+        assert_eq!(constant.amount_to_ui_amount(1, 0, i64::MIN), None);
+    }
+}
@@ -0,0 +1,322 @@
+//! Memo transfer extension.
+
+use pinocchio::{
+    account_info::{AccountInfo, Ref},
+    instruction::{Instruction, Signer},
+    program_error::ProgramError,
+    ProgramResult,
+};
+
+use crate::{
+    cpi::{invoke_signed_with_bounds, CpiAccounts, IxData},
+    extensions::{
+        get_extension_from_account_info, get_extension_from_account_info_unchecked, BaseState,
+        Extension, ExtensionDiscriminator, ExtensionType,
+    },
+    instructions::MAX_MULTISIG_SIGNERS,
+    pod::PodBool,
+};
+
+/// Instruction discriminators of the memo transfer extension.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RequiredMemoTransfersInstruction {
+    Enable = 0,
+    Disable = 1,
+}
+
+/// Memo requirement of a token account.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MemoTransfer {
+    /// Whether incoming transfers must be preceded by a memo instruction.
+    pub require_incoming_transfer_memos: PodBool,
+}
+
+unsafe impl Extension for MemoTransfer {
+    const TYPE: ExtensionType = ExtensionType::MemoTransfer;
+    const LEN: usize = core::mem::size_of::<Self>();
+    const BASE_STATE: BaseState = BaseState::TokenAccount;
+}
+
+const _: () = assert!(MemoTransfer::LEN == 1);
+
+impl MemoTransfer {
+    /// Return the `MemoTransfer` extension of the given token account.
+    ///
+    /// This method performs owner validation on `AccountInfo`, safe borrowing
+    /// the account data.
+    ///
+    /// Returns the `ExtensionError` code if the account is not a token account
+    /// or does not have the extension.
+    /// Returns `AccountBorrowFailed` if the account data is mutably borrowed.
+    #[inline]
+    pub fn from_account_info(account_info: &AccountInfo) -> Result<Ref<Self>, ProgramError> {
+        get_extension_from_account_info(account_info)
+    }
+
+    /// Return the `MemoTransfer` extension of the given token account.
+    ///
+    /// This method performs owner validation on `AccountInfo`, but does not
+    /// perform the borrow check.
+    ///
+    /// Returns the `ExtensionError` code if the account is not a token account
+    /// or does not have the extension.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that it is safe to borrow the account data (e.g., there are
+    /// no mutable borrows of the account data).
+    #[inline]
+    pub unsafe fn from_account_info_unchecked(
+        account_info: &AccountInfo,
+    ) -> Result<&Self, ProgramError> {
+        get_extension_from_account_info_unchecked(account_info)
+    }
+
+    /// Return whether incoming transfers require a memo.
+    #[inline(always)]
+    pub fn is_memo_required(&self) -> bool {
+        self.require_incoming_transfer_memos.get()
+    }
+}
+
+/// Require memos for transfers into a token account.
+///
+/// ### Accounts:
+///   * Single owner
+///   0. `[WRITE]` The token account.
+///   1. `[SIGNER]` The token account owner.
+///
+///   * Multisignature owner
+///   0. `[WRITE]` The token account.
+///   1. `[]` The multisig account owner.
+///   2. `..2+M` `[SIGNER]` M signer accounts.
+pub struct EnableRequiredMemoTransfers<'a, 'b> {
+    /// Token Account.
+    pub token_account: &'a AccountInfo,
+    /// Owner of the token account.
+    pub owner: &'a AccountInfo,
+    /// Signer accounts when the owner is a multisig.
+    pub signers: &'b [&'a AccountInfo],
+}
+
+impl EnableRequiredMemoTransfers<'_, '_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    #[inline(always)]
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        invoke_required_memo_transfers(
+            RequiredMemoTransfersInstruction::Enable,
+            self.token_account,
+            self.owner,
+            self.signers,
+            signers,
+        )
+    }
+}
+
+/// Stop requiring memos for transfers into a token account.
+///
+/// ### Accounts:
+///   * Single owner
+///   0. `[WRITE]` The token account.
+///   1. `[SIGNER]` The token account owner.
+///
+///   * Multisignature owner
+///   0. `[WRITE]` The token account.
+///   1. `[]` The multisig account owner.
+///   2. `..2+M` `[SIGNER]` M signer accounts.
+pub struct DisableRequiredMemoTransfers<'a, 'b> {
+    /// Token Account.
+    pub token_account: &'a AccountInfo,
+    /// Owner of the token account.
+    pub owner: &'a AccountInfo,
+    /// Signer accounts when the owner is a multisig.
+    pub signers: &'b [&'a AccountInfo],
+}
+
+impl DisableRequiredMemoTransfers<'_, '_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    #[inline(always)]
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        invoke_required_memo_transfers(
+            RequiredMemoTransfersInstruction::Disable,
+            self.token_account,
+            self.owner,
+            self.signers,
+            signers,
+        )
+    }
+}
+
+#[inline(always)]
+fn invoke_required_memo_transfers(
+    instruction: RequiredMemoTransfersInstruction,
+    token_account: &AccountInfo,
+    owner: &AccountInfo,
+    multisig_signers: &[&AccountInfo],
+    signers: &[Signer],
+) -> ProgramResult {
+    if multisig_signers.len() > MAX_MULTISIG_SIGNERS {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Account metadata
+    let mut accounts = CpiAccounts::<{ 2 + MAX_MULTISIG_SIGNERS }>::new();
+
+    accounts.push(token_account, true, false);
+    accounts.push_authority(owner, multisig_signers);
+
+    // Instruction data layout:
+    // -  [0]: extension instruction discriminator (1 byte, u8)
+    // -  [1]: instruction discriminator (1 byte, u8)
+    let mut instruction_data = IxData::<2>::new();
+
+    instruction_data.write_u8(ExtensionDiscriminator::MemoTransfer as u8);
+    instruction_data.write_u8(instruction as u8);
+
+    let instruction = Instruction {
+        program_id: &crate::ID,
+        accounts: accounts.metas(),
+        data: instruction_data.finish(),
+    };
+
+    invoke_signed_with_bounds::<{ 2 + MAX_MULTISIG_SIGNERS }>(
+        &instruction,
+        accounts.infos(),
+        signers,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::{vec, vec::Vec};
+
+    use super::*;
+    use crate::{
+        extensions::ExtensionError,
+        test_utils::{
+            account_with_extensions, take_captured, token_account_base, CapturedMeta, TestAccount,
+        },
+    };
+
+    fn memo_transfer_token_account_data(enabled: bool) -> Vec<u8> {
+        account_with_extensions(
+            BaseState::TokenAccount,
+            &token_account_base(&[1; 32], &[2; 32], 1),
+            &[
+                (ExtensionType::ImmutableOwner, &[]),
+                (ExtensionType::MemoTransfer, &[enabled as u8]),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_memo_transfer_from_account_info() {
+        for enabled in [false, true] {
+            let account = TestAccount::with_data(
+                [1; 32],
+                crate::ID,
+                &memo_transfer_token_account_data(enabled),
+            )
+            .info();
+
+            let extension = unsafe { MemoTransfer::from_account_info_unchecked(&account) }.unwrap();
+            assert_eq!(extension.is_memo_required(), enabled);
+        }
+
+        let account =
+            TestAccount::with_data([1; 32], [9; 32], &memo_transfer_token_account_data(true))
+                .info();
+        assert_eq!(
+            unsafe { MemoTransfer::from_account_info_unchecked(&account) },
+            Err(ProgramError::InvalidAccountOwner)
+        );
+
+        let account = TestAccount::with_data(
+            [1; 32],
+            crate::ID,
+            &token_account_base(&[3; 32], &[2; 32], 0),
+        )
+        .info();
+        assert_eq!(
+            unsafe { MemoTransfer::from_account_info_unchecked(&account) },
+            Err(ExtensionError::NotFound.into())
+        );
+    }
+
+    #[test]
+    fn test_required_memo_transfers() {
+        let token_account = TestAccount::new([1; 32]).writable().info();
+        let owner = TestAccount::new([2; 32]).signer().info();
+
+        EnableRequiredMemoTransfers {
+            token_account: &token_account,
+            owner: &owner,
+            signers: &[],
+        }
+        .invoke()
+        .unwrap();
+
+        DisableRequiredMemoTransfers {
+            token_account: &token_account,
+            owner: &owner,
+            signers: &[],
+        }
+        .invoke()
+        .unwrap();
+
+        let instructions = take_captured();
+
+        assert_eq!(instructions[0].data, vec![30, 0]);
+        assert_eq!(instructions[1].data, vec![30, 1]);
+
+        for instruction in instructions {
+            assert_eq!(
+                instruction.accounts,
+                vec![
+                    CapturedMeta::new([1; 32], true, false),
+                    CapturedMeta::new([2; 32], false, true),
+                ]
+            );
+        }
+    }
+
+    #[test]
+    fn test_required_memo_transfers_multisig() {
+        let token_account = TestAccount::new([1; 32]).writable().info();
+        let owner = TestAccount::new([2; 32]).info();
+        let signer_1 = TestAccount::new([3; 32]).signer().info();
+        let signer_2 = TestAccount::new([4; 32]).signer().info();
+
+        DisableRequiredMemoTransfers {
+            token_account: &token_account,
+            owner: &owner,
+            signers: &[&signer_1, &signer_2],
+        }
+        .invoke()
+        .unwrap();
+
+        let instructions = take_captured();
+
+        assert_eq!(
+            instructions[0].accounts,
+            vec![
+                CapturedMeta::new([1; 32], true, false),
+                CapturedMeta::new([2; 32], false, false),
+                CapturedMeta::new([3; 32], false, true),
+                CapturedMeta::new([4; 32], false, true),
+            ]
+        );
+    }
+}
@@ -0,0 +1,235 @@
+//! Metadata pointer extension.
+
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{AccountMeta, Instruction, Signer},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    ProgramResult,
+};
+
+use crate::{
+    cpi::{invoke_signed, IxData},
+    extensions::{BaseState, Extension, ExtensionDiscriminator, ExtensionType},
+    pod::non_zero,
+};
+
+/// Instruction discriminators of the metadata pointer extension.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MetadataPointerInstruction {
+    Initialize = 0,
+    Update = 1,
+}
+
+/// Metadata pointer of a mint.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MetadataPointer {
+    /// Authority that can set the address.
+    pub authority: Pubkey,
+    /// Account address that holds the metadata.
+    pub metadata_address: Pubkey,
+}
+
+unsafe impl Extension for MetadataPointer {
+    const TYPE: ExtensionType = ExtensionType::MetadataPointer;
+    const LEN: usize = core::mem::size_of::<Self>();
+    const BASE_STATE: BaseState = BaseState::Mint;
+}
+
+const _: () = assert!(MetadataPointer::LEN == 64);
+
+impl MetadataPointer {
+    /// Return the authority that can set the address, or `None` if the
+    /// address cannot be updated.
+    #[inline(always)]
+    pub fn authority(&self) -> Option<&Pubkey> {
+        non_zero(&self.authority)
+    }
+
+    /// Return the address of the account that holds the metadata, or `None`
+    /// if it is not set.
+    #[inline(always)]
+    pub fn metadata_address(&self) -> Option<&Pubkey> {
+        non_zero(&self.metadata_address)
+    }
+
+    /// Return whether the metadata is stored in the mint `mint_key` itself,
+    /// in which case the `TokenMetadata` extension is read from the same
+    /// account.
+    #[inline(always)]
+    pub fn is_self_referential(&self, mint_key: &Pubkey) -> bool {
+        self.metadata_address() == Some(mint_key)
+    }
+}
+
+/// Initialize the metadata pointer on a new mint.
+///
+/// Must be invoked before `InitializeMint`, since the token program rejects
+/// extension initialization on an initialized mint. At least one of
+/// `authority` and `metadata_address` must be set.
+///
+/// ### Accounts:
+///   0. `[WRITE]` The mint to initialize.
+pub struct Initialize<'a> {
+    /// Mint Account.
+    pub mint: &'a AccountInfo,
+    /// Authority that can set the metadata address.
+    pub authority: Option<&'a Pubkey>,
+    /// Account address that holds the metadata, which may be the mint
+    /// itself when it holds the token metadata extension.
+    pub metadata_address: Option<&'a Pubkey>,
+}
+
+impl<'a> Initialize<'a> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    #[inline(always)]
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        if self.authority.is_none() && self.metadata_address.is_none() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let account_metas = self.account_metas();
+        let instruction_data = self.instruction_data();
+
+        let instruction = Instruction {
+            program_id: &crate::ID,
+            accounts: &account_metas,
+            data: instruction_data.finish(),
+        };
+
+        invoke_signed(&instruction, &[self.mint], signers)
+    }
+
+    /// Return the account metas of the instruction.
+    #[inline(always)]
+    pub fn account_metas(&self) -> [AccountMeta<'a>; 1] {
+        [AccountMeta::writable(self.mint.key())]
+    }
+
+    /// Return the instruction data.
+    #[inline(always)]
+    pub fn instruction_data(&self) -> IxData<66> {
+        // Instruction data layout:
+        // -  [0]: extension instruction discriminator (1 byte, u8)
+        // -  [1]: instruction discriminator (1 byte, u8)
+        // -  [2..34]: authority (32 bytes, Pubkey, zeroed if `None`)
+        // -  [34..66]: metadata_address (32 bytes, Pubkey, zeroed if `None`)
+        let mut instruction_data = IxData::<66>::new();
+
+        instruction_data.write_u8(ExtensionDiscriminator::MetadataPointer as u8);
+        instruction_data.write_u8(MetadataPointerInstruction::Initialize as u8);
+        instruction_data.write_pubkey(self.authority.unwrap_or(&Pubkey::default()));
+        instruction_data.write_pubkey(self.metadata_address.unwrap_or(&Pubkey::default()));
+
+        instruction_data
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::vec;
+
+    use super::*;
+    use crate::{
+        extensions::try_get_extension,
+        test_utils::{
+            pyusd_mint_data, take_single, CapturedMeta, TestAccount, PYUSD_MINT,
+            PYUSD_UPDATE_AUTHORITY,
+        },
+    };
+
+    #[test]
+    fn test_self_referential_pointer() {
+        let data = pyusd_mint_data();
+        let pointer = try_get_extension::<MetadataPointer>(&data).unwrap();
+
+        assert_eq!(pointer.authority(), Some(&PYUSD_UPDATE_AUTHORITY));
+        assert_eq!(pointer.metadata_address(), Some(&PYUSD_MINT));
+        assert!(pointer.is_self_referential(&PYUSD_MINT));
+        assert!(!pointer.is_self_referential(&[1; 32]));
+    }
+
+    #[test]
+    fn test_external_pointer() {
+        let data = pyusd_mint_data();
+        let pointer = MetadataPointer {
+            authority: [0; 32],
+            metadata_address: [1; 32],
+        };
+
+        assert_eq!(pointer.authority(), None);
+        assert_eq!(pointer.metadata_address(), Some(&[1; 32]));
+        assert!(!pointer.is_self_referential(&PYUSD_MINT));
+        assert!(pointer.is_self_referential(&[1; 32]));
+
+        let pointer = MetadataPointer {
+            metadata_address: [0; 32],
+            ..*try_get_extension::<MetadataPointer>(&data).unwrap()
+        };
+
+        assert_eq!(pointer.metadata_address(), None);
+        // The zero key is never a self-referential pointer.
+        assert!(!pointer.is_self_referential(&[0; 32]));
+    }
+
+    #[test]
+    fn test_initialize_metadata_pointer_to_mint() {
+        let mint = TestAccount::new([1; 32]).writable().info();
+
+        Initialize {
+            mint: &mint,
+            authority: Some(&[2; 32]),
+            metadata_address: Some(mint.key()),
+        }
+        .invoke()
+        .unwrap();
+
+        let instruction = take_single();
+
+        let mut data = vec![39, 0];
+        data.extend_from_slice(&[2; 32]);
+        data.extend_from_slice(&[1; 32]);
+
+        assert_eq!(instruction.data, data);
+        assert_eq!(
+            instruction.accounts,
+            vec![CapturedMeta::new([1; 32], true, false)]
+        );
+    }
+
+    #[test]
+    fn test_initialize_metadata_pointer_without_authority() {
+        let mint = TestAccount::new([1; 32]).writable().info();
+
+        Initialize {
+            mint: &mint,
+            authority: None,
+            metadata_address: Some(&[3; 32]),
+        }
+        .invoke()
+        .unwrap();
+
+        let instruction = take_single();
+
+        assert_eq!(&instruction.data[2..34], &[0; 32]);
+        assert_eq!(&instruction.data[34..], &[3; 32]);
+
+        let initialize = Initialize {
+            mint: &mint,
+            authority: None,
+            metadata_address: None,
+        };
+        assert_eq!(
+            initialize.invoke(),
+            Err(ProgramError::InvalidInstructionData)
+        );
+    }
+}
@@ -0,0 +1,156 @@
+//! Mint close authority extension.
+
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{AccountMeta, Instruction, Signer},
+    pubkey::Pubkey,
+    ProgramResult,
+};
+
+use crate::{
+    cpi::{invoke_signed, IxData},
+    extensions::{BaseState, Extension, ExtensionType},
+    pod::non_zero,
+};
+
+/// Close authority of a mint.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MintCloseAuthority {
+    /// Optional authority to close the mint.
+    pub close_authority: Pubkey,
+}
+
+unsafe impl Extension for MintCloseAuthority {
+    const TYPE: ExtensionType = ExtensionType::MintCloseAuthority;
+    const LEN: usize = core::mem::size_of::<Self>();
+    const BASE_STATE: BaseState = BaseState::Mint;
+}
+
+const _: () = assert!(MintCloseAuthority::LEN == 32);
+
+impl MintCloseAuthority {
+    /// Return the authority that can close the mint, or `None` if the mint
+    /// cannot be closed.
+    #[inline(always)]
+    pub fn close_authority(&self) -> Option<&Pubkey> {
+        non_zero(&self.close_authority)
+    }
+}
+
+/// Initialize the close authority on a new mint.
+///
+/// Must be invoked before `InitializeMint`, since the token program rejects
+/// extension initialization on an initialized mint.
+///
+/// ### Accounts:
+///   0. `[WRITE]` The mint to initialize.
+pub struct InitializeMintCloseAuthority<'a> {
+    /// Mint Account.
+    pub mint: &'a AccountInfo,
+    /// Authority that can close the mint, or `None` if the mint cannot be
+    /// closed.
+    pub close_authority: Option<&'a Pubkey>,
+}
+
+impl<'a> InitializeMintCloseAuthority<'a> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    #[inline(always)]
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        let account_metas = self.account_metas();
+        let instruction_data = self.instruction_data();
+
+        let instruction = Instruction {
+            program_id: &crate::ID,
+            accounts: &account_metas,
+            data: instruction_data.finish(),
+        };
+
+        invoke_signed(&instruction, &[self.mint], signers)
+    }
+
+    /// Return the account metas of the instruction.
+    #[inline(always)]
+    pub fn account_metas(&self) -> [AccountMeta<'a>; 1] {
+        [AccountMeta::writable(self.mint.key())]
+    }
+
+    /// Return the instruction data.
+    #[inline(always)]
+    pub fn instruction_data(&self) -> IxData<34> {
+        // Instruction data layout:
+        // -  [0]: instruction discriminator (1 byte, u8)
+        // -  [1]: close_authority presence flag (1 byte, u8)
+        // -  [2..34]: close_authority (optional, 32 bytes, Pubkey)
+        let mut instruction_data = IxData::<34>::new();
+
+        // Set discriminator as u8 at offset [0]
+        instruction_data.write_u8(25);
+
+        // Set close_authority as COption<Pubkey> at offset [1..34]
+        instruction_data.write_coption_pubkey(self.close_authority);
+
+        instruction_data
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::vec;
+
+    use super::*;
+    use crate::{
+        extensions::{try_from_bytes_ref, try_get_extension},
+        test_utils::{
+            pyusd_mint_data, take_single, CapturedMeta, TestAccount, PYUSD_UPDATE_AUTHORITY,
+        },
+    };
+
+    #[test]
+    fn test_close_authority() {
+        let data = pyusd_mint_data();
+        let extension = try_get_extension::<MintCloseAuthority>(&data).unwrap();
+        assert_eq!(extension.close_authority(), Some(&PYUSD_UPDATE_AUTHORITY));
+
+        let extension = try_from_bytes_ref::<MintCloseAuthority>(&[0; 32]).unwrap();
+        assert_eq!(extension.close_authority(), None);
+    }
+
+    #[test]
+    fn test_initialize_mint_close_authority() {
+        let mint = TestAccount::new([1; 32]).writable().info();
+
+        InitializeMintCloseAuthority {
+            mint: &mint,
+            close_authority: Some(&[2; 32]),
+        }
+        .invoke()
+        .unwrap();
+
+        let instruction = take_single();
+
+        let mut data = vec![25, 1];
+        data.extend_from_slice(&[2; 32]);
+
+        assert_eq!(instruction.data, data);
+        assert_eq!(
+            instruction.accounts,
+            vec![CapturedMeta::new([1; 32], true, false)]
+        );
+
+        InitializeMintCloseAuthority {
+            mint: &mint,
+            close_authority: None,
+        }
+        .invoke()
+        .unwrap();
+
+        assert_eq!(take_single().data, vec![25, 0]);
+    }
+}
@@ -0,0 +1,2382 @@
+//! Helpers for Token-2022 extensions.
+//!
+//! Each extension is defined in its own module, containing the CPI helpers
+//! for the extension instructions and the types to read the extension data.
+//!
+//! Extensions are stored after the base state of the account using a
+//! type-length-value (TLV) encoding: a `u16` extension type, a `u16` length
+//! and the extension data. Mints with extensions are padded to the length of
+//! a token account, so the TLV data always starts after the account type
+//! discriminator.
+//!
+//! The confidential extensions are only available with the `confidential`
+//! feature (enabled by default). Their extension types are always known, so
+//! accounts with confidential extensions can still be parsed without it.
+
+#[cfg(feature = "confidential")]
+pub mod confidential_mint_burn;
+#[cfg(feature = "confidential")]
+pub mod confidential_transfer;
+#[cfg(feature = "confidential")]
+pub mod confidential_transfer_fee;
+pub mod cpi_guard;
+pub mod default_account_state;
+pub mod group_member_pointer;
+pub mod group_pointer;
+pub mod immutable_owner;
+pub mod interest_bearing_mint;
+pub mod memo_transfer;
+pub mod metadata_pointer;
+pub mod mint_close_authority;
+pub mod non_transferable;
+pub mod pausable;
+pub mod permanent_delegate;
+pub mod scaled_ui_amount;
+pub mod token_group;
+pub mod token_metadata;
+pub mod transfer_fee;
+pub mod transfer_hook;
+
+use pinocchio::{
+    account_info::{AccountInfo, Ref},
+    program_error::ProgramError,
+};
+
+use crate::{
+    errors::TokenError,
+    state::{
+        classify_account, window, AccountType, Mint, Strictness, TokenAccount, TokenAccountKind,
+        ACCOUNT_TYPE_OFFSET, MULTISIG_LEN,
+    },
+};
+
+/// Offset of the first TLV entry on accounts with extensions.
+pub const EXTENSION_START_OFFSET: usize = ACCOUNT_TYPE_OFFSET + 1;
+
+/// Length of the type and length fields of a TLV entry.
+pub const EXTENSION_HEADER_LEN: usize = 4;
+
+/// Instruction discriminators of the Token-2022 extensions.
+///
+/// Extension instructions are encoded as the extension discriminator followed
+/// by the discriminator of the extension instruction.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExtensionDiscriminator {
+    TransferFee = 26,
+    ConfidentialTransfer = 27,
+    DefaultAccountState = 28,
+    MemoTransfer = 30,
+    InterestBearingMint = 33,
+    CpiGuard = 34,
+    TransferHook = 36,
+    ConfidentialTransferFee = 37,
+    MetadataPointer = 39,
+    GroupPointer = 40,
+    GroupMemberPointer = 41,
+    ConfidentialMintBurn = 42,
+    ScaledUiAmount = 43,
+    Pausable = 44,
+}
+
+/// Extension types, as stored in the TLV data of an account.
+#[repr(u16)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExtensionType {
+    /// Used as padding if the account size would otherwise be 355, same as a
+    /// multisig.
+    Uninitialized,
+    TransferFeeConfig,
+    TransferFeeAmount,
+    MintCloseAuthority,
+    ConfidentialTransferMint,
+    ConfidentialTransferAccount,
+    DefaultAccountState,
+    ImmutableOwner,
+    MemoTransfer,
+    NonTransferable,
+    InterestBearingConfig,
+    CpiGuard,
+    PermanentDelegate,
+    NonTransferableAccount,
+    TransferHook,
+    TransferHookAccount,
+    ConfidentialTransferFeeConfig,
+    ConfidentialTransferFeeAmount,
+    MetadataPointer,
+    TokenMetadata,
+    GroupPointer,
+    TokenGroup,
+    GroupMemberPointer,
+    TokenGroupMember,
+    ConfidentialMintBurn,
+    ScaledUiAmount,
+    Pausable,
+    PausableAccount,
+}
+
+impl ExtensionType {
+    /// Return the extension type from its little-endian `u16` representation.
+    ///
+    /// Returns `None` for values that do not correspond to an extension
+    /// known by this crate.
+    #[inline]
+    pub fn from_bytes(bytes: [u8; 2]) -> Option<Self> {
+        match u16::from_le_bytes(bytes) {
+            0 => Some(Self::Uninitialized),
+            1 => Some(Self::TransferFeeConfig),
+            2 => Some(Self::TransferFeeAmount),
+            3 => Some(Self::MintCloseAuthority),
+            4 => Some(Self::ConfidentialTransferMint),
+            5 => Some(Self::ConfidentialTransferAccount),
+            6 => Some(Self::DefaultAccountState),
+            7 => Some(Self::ImmutableOwner),
+            8 => Some(Self::MemoTransfer),
+            9 => Some(Self::NonTransferable),
+            10 => Some(Self::InterestBearingConfig),
+            11 => Some(Self::CpiGuard),
+            12 => Some(Self::PermanentDelegate),
+            13 => Some(Self::NonTransferableAccount),
+            14 => Some(Self::TransferHook),
+            15 => Some(Self::TransferHookAccount),
+            16 => Some(Self::ConfidentialTransferFeeConfig),
+            17 => Some(Self::ConfidentialTransferFeeAmount),
+            18 => Some(Self::MetadataPointer),
+            19 => Some(Self::TokenMetadata),
+            20 => Some(Self::GroupPointer),
+            21 => Some(Self::TokenGroup),
+            22 => Some(Self::GroupMemberPointer),
+            23 => Some(Self::TokenGroupMember),
+            24 => Some(Self::ConfidentialMintBurn),
+            25 => Some(Self::ScaledUiAmount),
+            26 => Some(Self::Pausable),
+            27 => Some(Self::PausableAccount),
+            _ => None,
+        }
+    }
+
+    /// Return the length of the extension data.
+    ///
+    /// Returns `None` for `TokenMetadata`, whose length depends on its
+    /// content.
+    pub const fn fixed_len(self) -> Option<usize> {
+        let len = match self {
+            Self::Uninitialized
+            | Self::ImmutableOwner
+            | Self::NonTransferable
+            | Self::NonTransferableAccount
+            | Self::PausableAccount => 0,
+            Self::DefaultAccountState
+            | Self::MemoTransfer
+            | Self::CpiGuard
+            | Self::TransferHookAccount => 1,
+            Self::TransferFeeAmount => 8,
+            Self::MintCloseAuthority | Self::PermanentDelegate => 32,
+            Self::Pausable => 33,
+            Self::InterestBearingConfig => 52,
+            Self::ScaledUiAmount => 56,
+            Self::TransferHook
+            | Self::MetadataPointer
+            | Self::GroupPointer
+            | Self::GroupMemberPointer
+            | Self::ConfidentialTransferFeeAmount => 64,
+            Self::ConfidentialTransferMint => 65,
+            Self::TokenGroupMember => 72,
+            Self::TokenGroup => 80,
+            Self::TransferFeeConfig => 108,
+            Self::ConfidentialTransferFeeConfig => 129,
+            Self::ConfidentialMintBurn => 196,
+            Self::ConfidentialTransferAccount => 295,
+            Self::TokenMetadata => return None,
+        };
+
+        Some(len)
+    }
+}
+
+/// Base state of an account with extensions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BaseState {
+    Mint,
+    TokenAccount,
+}
+
+impl BaseState {
+    /// Return the account type discriminator for the base state.
+    #[inline(always)]
+    pub const fn account_type(self) -> AccountType {
+        match self {
+            BaseState::Mint => AccountType::Mint,
+            BaseState::TokenAccount => AccountType::Account,
+        }
+    }
+}
+
+/// Header of a TLV entry, with the position of its value in the account data.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct TlvEntry {
+    /// Extension type, possibly unknown to this crate.
+    extension_type: u16,
+    /// Offset of the value.
+    offset: usize,
+    /// Length of the value, which may extend past the end of the data.
+    length: usize,
+}
+
+impl TlvEntry {
+    /// Return the offset of the next entry.
+    #[inline(always)]
+    const fn end(&self) -> usize {
+        self.offset + self.length
+    }
+}
+
+/// Return the offset of the first TLV entry of the account data, or the
+/// length of the data when the account type does not match `base_state`.
+///
+/// Multisig accounts have a signer key where the account type would be, so
+/// data with the length of a multisig account has no TLV entries.
+#[inline(always)]
+const fn tlv_start(data: &[u8], base_state: BaseState) -> usize {
+    if data.len() > ACCOUNT_TYPE_OFFSET
+        && data.len() != MULTISIG_LEN
+        && data[ACCOUNT_TYPE_OFFSET] == base_state.account_type() as u8
+    {
+        EXTENSION_START_OFFSET
+    } else {
+        data.len()
+    }
+}
+
+/// Read the header of the TLV entry at `offset`.
+///
+/// Every reader of TLV data walks the entries with this function. Returns
+/// `None` at the end of the TLV data: the end of `data`, an `Uninitialized`
+/// entry or zeroed bytes too short to hold a header (accounts padded to avoid
+/// the length of a multisig end with the two zeroed bytes of an
+/// `Uninitialized` type). Other bytes too short to hold a header are
+/// [`ExtensionError::Truncated`]. The value of the entry is not required to
+/// be within `data`.
+const fn read_entry(data: &[u8], offset: usize) -> Result<Option<TlvEntry>, ExtensionError> {
+    if offset + EXTENSION_HEADER_LEN > data.len() {
+        let mut index = offset;
+
+        while index < data.len() {
+            if data[index] != 0 {
+                return Err(ExtensionError::Truncated);
+            }
+            index += 1;
+        }
+
+        return Ok(None);
+    }
+
+    let extension_type = u16::from_le_bytes([data[offset], data[offset + 1]]);
+
+    if extension_type == ExtensionType::Uninitialized as u16 {
+        return Ok(None);
+    }
+
+    Ok(Some(TlvEntry {
+        extension_type,
+        offset: offset + EXTENSION_HEADER_LEN,
+        length: u16::from_le_bytes([data[offset + 2], data[offset + 3]]) as usize,
+    }))
+}
+
+/// Find the TLV entry of `extension_type`, walking the entries from
+/// `offset`.
+///
+/// Only the entries before it are required to be within `data`; callers
+/// reading the value of the entry must bounds-check it.
+const fn find_entry(
+    data: &[u8],
+    mut offset: usize,
+    extension_type: ExtensionType,
+) -> Result<TlvEntry, ExtensionError> {
+    loop {
+        match read_entry(data, offset) {
+            Ok(Some(entry)) if entry.extension_type == extension_type as u16 => return Ok(entry),
+            Ok(Some(entry)) if entry.end() <= data.len() => offset = entry.end(),
+            Ok(Some(_)) => return Err(ExtensionError::Truncated),
+            Ok(None) => return Err(ExtensionError::NotFound),
+            Err(error) => return Err(error),
+        }
+    }
+}
+
+/// Return the data of the `extension_type` TLV entry.
+///
+/// Returns `None` when the extension is not present or its data is not
+/// fully contained in `data`.
+///
+/// This is a `const fn`, so fixed-size extensions can be read at compile
+/// time. Typed references to extensions and the variable-length
+/// `TokenMetadata` fields (which require UTF-8 validation) are not available
+/// in `const` contexts.
+pub const fn get_extension_data(
+    data: &[u8],
+    base_state: BaseState,
+    extension_type: ExtensionType,
+) -> Option<&[u8]> {
+    match find_entry(data, tlv_start(data, base_state), extension_type) {
+        Ok(entry) if entry.end() <= data.len() => {
+            Some(data.split_at(entry.offset).1.split_at(entry.length).0)
+        }
+        _ => None,
+    }
+}
+
+/// Iterator over the TLV entries of an account with extensions.
+///
+/// Yields the type and data of each entry, in the order they are stored.
+/// Entries with an extension type unknown to this crate are skipped. The
+/// iteration ends at the first `Uninitialized` entry or zeroed bytes too
+/// short to hold a TLV header, which mark the end of the TLV data, or at the
+/// first entry that is not fully contained in the data, in which case
+/// [`ExtensionIterator::is_truncated`] returns `true`.
+#[derive(Clone, Debug)]
+pub struct ExtensionIterator<'a> {
+    data: &'a [u8],
+    offset: usize,
+    truncated: bool,
+}
+
+impl<'a> ExtensionIterator<'a> {
+    /// Create an iterator over the TLV entries of the account data.
+    ///
+    /// The iterator is empty when the account type of `data` does not match
+    /// `base_state` or `data` has the length of a multisig account.
+    #[inline]
+    pub fn new(data: &'a [u8], base_state: BaseState) -> Self {
+        Self {
+            data,
+            offset: tlv_start(data, base_state),
+            truncated: false,
+        }
+    }
+
+    /// Whether the iteration ended on an entry that is not fully contained
+    /// in the data.
+    #[inline(always)]
+    pub fn is_truncated(&self) -> bool {
+        self.truncated
+    }
+
+    #[inline(always)]
+    fn finish(&mut self, truncated: bool) -> Option<(ExtensionType, &'a [u8])> {
+        self.offset = self.data.len();
+        self.truncated = truncated;
+        None
+    }
+}
+
+impl<'a> Iterator for ExtensionIterator<'a> {
+    type Item = (ExtensionType, &'a [u8]);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.offset < self.data.len() {
+            match read_entry(self.data, self.offset) {
+                Ok(Some(entry)) if entry.end() <= self.data.len() => {
+                    self.offset = entry.end();
+
+                    if let Some(extension_type) =
+                        ExtensionType::from_bytes(entry.extension_type.to_le_bytes())
+                    {
+                        return Some((extension_type, &self.data[entry.offset..entry.end()]));
+                    }
+                }
+                Ok(Some(_)) | Err(_) => return self.finish(true),
+                Ok(None) => return self.finish(false),
+            }
+        }
+
+        None
+    }
+}
+
+impl core::iter::FusedIterator for ExtensionIterator<'_> {}
+
+/// Return the mutable data of the `extension_type` TLV entry.
+///
+/// Mutable counterpart of [`get_extension_data`].
+#[inline]
+pub fn get_extension_data_mut(
+    data: &mut [u8],
+    base_state: BaseState,
+    extension_type: ExtensionType,
+) -> Option<&mut [u8]> {
+    let entry = find_entry(data, tlv_start(data, base_state), extension_type).ok()?;
+    data.get_mut(entry.offset..entry.end())
+}
+
+/// Writer of TLV entries into the data of an account with extensions.
+///
+/// Entries are appended after the existing entries of the account, so a
+/// writer can be created on new (zeroed) or partially written account data.
+/// The base state itself is not written.
+#[derive(Debug)]
+pub struct ExtensionWriter<'a> {
+    data: &'a mut [u8],
+    base_state: BaseState,
+    offset: usize,
+}
+
+impl<'a> ExtensionWriter<'a> {
+    /// Create a writer for the account data, setting its account type.
+    ///
+    /// Returns `InvalidAccountData` if `data` is not long enough to hold the
+    /// account type, it already has the account type of a different base
+    /// state, or its existing entries are truncated.
+    pub fn new(data: &'a mut [u8], base_state: BaseState) -> Result<Self, ProgramError> {
+        let account_type = base_state.account_type() as u8;
+
+        match data.get_mut(ACCOUNT_TYPE_OFFSET) {
+            Some(current) if *current == AccountType::Uninitialized as u8 => {
+                *current = account_type
+            }
+            Some(current) if *current == account_type => (),
+            _ => return Err(ProgramError::InvalidAccountData),
+        }
+
+        let mut offset = EXTENSION_START_OFFSET;
+
+        // Skip the existing entries.
+        loop {
+            match read_entry(data, offset) {
+                Ok(Some(entry)) if entry.end() <= data.len() => offset = entry.end(),
+                Ok(None) => break,
+                _ => return Err(ProgramError::InvalidAccountData),
+            }
+        }
+
+        Ok(Self {
+            data,
+            base_state,
+            offset,
+        })
+    }
+
+    /// Append a TLV entry with the given extension data.
+    ///
+    /// Returns `AccountDataTooSmall` if the entry does not fit in the data,
+    /// `InvalidArgument` for `Uninitialized` entries or values longer than
+    /// `u16::MAX`, and the `ExtensionAlreadyInitialized` token error if the
+    /// account already has the extension.
+    pub fn write(
+        &mut self,
+        extension_type: ExtensionType,
+        value: &[u8],
+    ) -> Result<(), ProgramError> {
+        if extension_type == ExtensionType::Uninitialized || value.len() > u16::MAX as usize {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        if find_entry(
+            &self.data[..self.offset],
+            EXTENSION_START_OFFSET,
+            extension_type,
+        )
+        .is_ok()
+        {
+            return Err(TokenError::ExtensionAlreadyInitialized.into());
+        }
+
+        let start = self.offset + EXTENSION_HEADER_LEN;
+        let entry = self
+            .data
+            .get_mut(self.offset..start + value.len())
+            .ok_or(ProgramError::AccountDataTooSmall)?;
+
+        entry[..2].copy_from_slice(&(extension_type as u16).to_le_bytes());
+        entry[2..4].copy_from_slice(&(value.len() as u16).to_le_bytes());
+        entry[EXTENSION_HEADER_LEN..].copy_from_slice(value);
+
+        self.offset = start + value.len();
+
+        Ok(())
+    }
+
+    /// Append the TLV entry of the extension `T`.
+    ///
+    /// Returns `InvalidArgument` if `T` is an extension of a different base
+    /// state, otherwise the same errors as [`ExtensionWriter::write`].
+    pub fn write_extension<T: Extension>(&mut self, extension: &T) -> Result<(), ProgramError> {
+        if T::BASE_STATE != self.base_state {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        // SAFETY: Extensions are `repr(C)` structs of `T::LEN` bytes without
+        // padding.
+        let value =
+            unsafe { core::slice::from_raw_parts(extension as *const T as *const u8, T::LEN) };
+
+        self.write(T::TYPE, value)
+    }
+
+    /// Return the length of the account data up to the end of the last
+    /// entry.
+    #[inline(always)]
+    pub fn data_len(&self) -> usize {
+        self.offset
+    }
+}
+
+/// Return the length of an account with the given extensions, as allocated
+/// by Token-2022.
+///
+/// Accounts without extensions only hold the base state. Otherwise, the base
+/// state is padded to the length of a token account and followed by the
+/// account type and the TLV entries; duplicated extension types are counted
+/// once. Returns `InvalidArgument` for `TokenMetadata`, whose length depends
+/// on its content.
+pub fn try_calculate_account_len(
+    base_state: BaseState,
+    extension_types: &[ExtensionType],
+) -> Result<usize, ProgramError> {
+    if extension_types.is_empty() {
+        return Ok(match base_state {
+            BaseState::Mint => Mint::BASE_LEN,
+            BaseState::TokenAccount => TokenAccount::BASE_LEN,
+        });
+    }
+
+    let mut len = EXTENSION_START_OFFSET;
+
+    for (index, extension_type) in extension_types.iter().enumerate() {
+        if extension_types[..index].contains(extension_type) {
+            continue;
+        }
+
+        len += EXTENSION_HEADER_LEN
+            + extension_type
+                .fixed_len()
+                .ok_or(ProgramError::InvalidArgument)?;
+    }
+
+    // Token-2022 pads accounts that would have the length of a multisig with
+    // the type of an `Uninitialized` entry, without its length.
+    if len == MULTISIG_LEN {
+        len += core::mem::size_of::<ExtensionType>();
+    }
+
+    Ok(len)
+}
+
+/// Return the data of several extensions, walking the TLV data once.
+///
+/// Each element of the result is the data of the extension type at the same
+/// index of `extension_types`, or `None` when the account does not have the
+/// extension. Typed references can be taken with [`cast_extension`]. The walk
+/// stops as soon as every requested extension is found.
+#[inline]
+pub fn get_extensions_data<'a, const N: usize>(
+    data: &'a [u8],
+    base_state: BaseState,
+    extension_types: &[ExtensionType; N],
+) -> [Option<&'a [u8]>; N] {
+    collect_extensions_data(ExtensionIterator::new(data, base_state), extension_types)
+}
+
+#[inline(always)]
+fn collect_extensions_data<'a, const N: usize>(
+    extensions: impl Iterator<Item = (ExtensionType, &'a [u8])>,
+    extension_types: &[ExtensionType; N],
+) -> [Option<&'a [u8]>; N] {
+    let mut found = [None; N];
+    let mut remaining = N;
+
+    if remaining == 0 {
+        return found;
+    }
+
+    for (extension_type, value) in extensions {
+        for (requested, slot) in extension_types.iter().zip(found.iter_mut()) {
+            if *requested == extension_type && slot.is_none() {
+                *slot = Some(value);
+                remaining -= 1;
+            }
+        }
+
+        if remaining == 0 {
+            break;
+        }
+    }
+
+    found
+}
+
+/// Return a reference to the extension `T` from its TLV entry data, as
+/// returned by [`get_extensions_data`].
+///
+/// Returns `None` when the length of `value` is not the length of `T`.
+#[inline(always)]
+pub fn cast_extension<T: Extension>(value: &[u8]) -> Option<&T> {
+    try_from_bytes_ref(value).ok()
+}
+
+/// Return a reference to the extension `T` from its data.
+///
+/// Returns `InvalidAccountData` when the length of `data` is not the length
+/// of `T`.
+#[inline(always)]
+pub fn try_from_bytes_ref<T: Extension>(data: &[u8]) -> Result<&T, ProgramError> {
+    if data.len() != T::LEN {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    // SAFETY: `data` is `T::LEN` bytes long.
+    Ok(unsafe { from_bytes_ref_unchecked(data) })
+}
+
+/// Return a copy of the extension `T` from its data.
+///
+/// Returns `InvalidAccountData` when the length of `data` is not the length
+/// of `T`.
+#[inline(always)]
+pub fn try_from_bytes<T: Extension + Copy>(data: &[u8]) -> Result<T, ProgramError> {
+    try_from_bytes_ref(data).copied()
+}
+
+/// Return a reference to the extension `T` from its data, without checking
+/// the length of the data.
+///
+/// The alignment of `T` is checked at compile time.
+///
+/// # Safety
+///
+/// The caller must ensure that `data` is at least `T::LEN` bytes long.
+#[inline(always)]
+pub unsafe fn from_bytes_ref_unchecked<T: Extension>(data: &[u8]) -> &T {
+    const { assert!(core::mem::align_of::<T>() == 1) };
+
+    // SAFETY: `T` has an alignment of 1 and is valid for any byte pattern, and
+    // the caller guarantees that `data` holds `T::LEN` bytes.
+    &*(data.as_ptr() as *const T)
+}
+
+/// Fixed-length extension stored in the TLV data of an account.
+///
+/// # Safety
+///
+/// Extensions are read by casting the account data, so implementors must be
+/// `#[repr(C)]` structs with an alignment of `1` for which any byte pattern
+/// is valid, and `LEN` must be their size.
+pub unsafe trait Extension: Sized {
+    /// Extension type of the TLV entry.
+    const TYPE: ExtensionType;
+
+    /// Length of the extension data.
+    ///
+    /// Must be the length defined by spl-token-2022, since extensions are
+    /// only read from TLV entries of exactly this length; implementations
+    /// pin it with a compile-time assertion.
+    const LEN: usize;
+
+    /// Base state of the accounts holding the extension.
+    const BASE_STATE: BaseState;
+}
+
+/// Errors returned when reading an extension from account data.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExtensionError {
+    /// The data is not the data of a mint or a token account.
+    InvalidAccount,
+
+    /// The extension is defined for accounts with a different base state.
+    WrongBaseState {
+        expected: BaseState,
+        found: BaseState,
+    },
+
+    /// The account does not have the extension.
+    NotFound,
+
+    /// The length of the extension data does not match the length of the
+    /// extension.
+    InvalidData,
+
+    /// The TLV data ends in the middle of an entry before the extension is
+    /// found.
+    Truncated,
+}
+
+impl ExtensionError {
+    /// First custom error code of extension errors.
+    ///
+    /// Codes start above the codes of [`crate::errors::TokenError`], so they
+    /// are never mistaken for errors of the token program.
+    pub const CODE_BASE: u32 = 0x1000;
+
+    /// Return the custom error code of the error.
+    pub const fn code(&self) -> u32 {
+        Self::CODE_BASE
+            + match self {
+                ExtensionError::InvalidAccount => 0,
+                ExtensionError::WrongBaseState { .. } => 1,
+                ExtensionError::NotFound => 2,
+                ExtensionError::InvalidData => 3,
+                ExtensionError::Truncated => 4,
+            }
+    }
+
+    /// Return the error as a `ProgramError::Custom` with its distinct code.
+    ///
+    /// Same as the `From` conversion, usable in `const` contexts.
+    #[inline(always)]
+    pub const fn into_custom(self) -> ProgramError {
+        ProgramError::Custom(self.code())
+    }
+}
+
+impl From<ExtensionError> for ProgramError {
+    fn from(error: ExtensionError) -> Self {
+        error.into_custom()
+    }
+}
+
+/// Return the base state of an account from its data.
+#[inline(always)]
+fn base_state(data: &[u8]) -> Result<BaseState, ExtensionError> {
+    match classify_account(data, Strictness::Strict) {
+        Ok(TokenAccountKind::Mint) => Ok(BaseState::Mint),
+        Ok(TokenAccountKind::TokenAccount) => Ok(BaseState::TokenAccount),
+        _ => Err(ExtensionError::InvalidAccount),
+    }
+}
+
+/// Write the types of the extensions of an account to `out`, in the order
+/// they are stored, and return the number of extensions.
+///
+/// Returns the [`ExtensionError::InvalidAccount`] code if the data is not the
+/// data of a mint or a token account, the [`ExtensionError::Truncated`] code
+/// if its TLV data is truncated, and `InvalidArgument` if `out` cannot hold
+/// all the extension types.
+pub fn get_extension_types(data: &[u8], out: &mut [ExtensionType]) -> Result<usize, ProgramError> {
+    let mut extensions = ExtensionIterator::new(data, base_state(data)?);
+    let mut count = 0;
+
+    for (extension_type, _) in extensions.by_ref() {
+        *out.get_mut(count).ok_or(ProgramError::InvalidArgument)? = extension_type;
+        count += 1;
+    }
+
+    if extensions.is_truncated() {
+        return Err(ExtensionError::Truncated.into());
+    }
+
+    Ok(count)
+}
+
+/// Whether the account has the extension `T`.
+///
+/// Only the TLV headers are read, so this is cheaper than reading the
+/// extension. Returns `false` for data that is not the data of an account
+/// with the base state of `T` or whose TLV data is malformed.
+#[inline]
+pub fn has_extension<T: Extension>(data: &[u8]) -> bool {
+    base_state(data) == Ok(T::BASE_STATE)
+        && get_extension_data(data, T::BASE_STATE, T::TYPE).is_some()
+}
+
+/// Whether the account has an extension of type `extension_type`.
+///
+/// Only the TLV headers are read; see [`has_extension`].
+#[inline]
+pub fn has_extension_type(data: &[u8], extension_type: ExtensionType) -> bool {
+    match base_state(data) {
+        Ok(base_state) => get_extension_data(data, base_state, extension_type).is_some(),
+        Err(_) => false,
+    }
+}
+
+/// Return a reference to the extension `T` in the account data.
+///
+/// The base state of the account is validated before looking for the
+/// extension, so requesting a mint extension from a token account (or the
+/// other way around) returns [`ExtensionError::WrongBaseState`].
+#[inline]
+pub fn try_get_extension<T: Extension>(data: &[u8]) -> Result<&T, ExtensionError> {
+    try_get_extension_at(data, 0, data.len())
+}
+
+/// Return a reference to the extension `T` of the account stored in the
+/// `len` bytes at `offset` of `buf`.
+///
+/// All bounds are relative to the window, so the data of neighbouring
+/// accounts in `buf` is never read.
+#[inline]
+pub fn try_get_extension_at<T: Extension>(
+    buf: &[u8],
+    offset: usize,
+    len: usize,
+) -> Result<&T, ExtensionError> {
+    let data = window(buf, offset, len).ok_or(ExtensionError::InvalidAccount)?;
+
+    let found = base_state(data)?;
+
+    if found != T::BASE_STATE {
+        return Err(ExtensionError::WrongBaseState {
+            expected: T::BASE_STATE,
+            found,
+        });
+    }
+
+    find_typed_extension(data)
+}
+
+/// Return a reference to the extension `T` of an account whose base state
+/// has already been validated to be `T::BASE_STATE`.
+#[inline(always)]
+fn find_typed_extension<T: Extension>(data: &[u8]) -> Result<&T, ExtensionError> {
+    let mut extensions = ExtensionIterator::new(data, T::BASE_STATE);
+
+    match extensions.find(|(extension_type, _)| *extension_type == T::TYPE) {
+        Some((_, value)) => try_from_bytes_ref(value).map_err(|_| ExtensionError::InvalidData),
+        // The extension may be in the truncated entry.
+        None if extensions.is_truncated() => Err(ExtensionError::Truncated),
+        None => Err(ExtensionError::NotFound),
+    }
+}
+
+/// View of the TLV data of an account classified with a given
+/// [`Strictness`].
+///
+/// Extensions are only read from mints and token accounts: multisig accounts
+/// and, with [`Strictness::Lenient`], accounts of unknown kinds have no
+/// extensions, so their data is never parsed as TLV entries.
+#[derive(Clone, Copy, Debug)]
+pub struct TlvView<'a> {
+    data: &'a [u8],
+    kind: TokenAccountKind,
+}
+
+impl<'a> TlvView<'a> {
+    /// Create a view of the account data.
+    ///
+    /// Returns `InvalidAccountData` if the account cannot be classified with
+    /// `strictness`.
+    #[inline]
+    pub fn new(data: &'a [u8], strictness: Strictness) -> Result<Self, ProgramError> {
+        Ok(Self {
+            data,
+            kind: classify_account(data, strictness)?,
+        })
+    }
+
+    /// Return the kind of the account.
+    #[inline(always)]
+    pub fn kind(&self) -> TokenAccountKind {
+        self.kind
+    }
+
+    /// Return the base state of the account, or `None` for accounts that
+    /// cannot have extensions.
+    #[inline(always)]
+    pub fn base_state(&self) -> Option<BaseState> {
+        match self.kind {
+            TokenAccountKind::Mint => Some(BaseState::Mint),
+            TokenAccountKind::TokenAccount => Some(BaseState::TokenAccount),
+            TokenAccountKind::Multisig | TokenAccountKind::Unknown(_) => None,
+        }
+    }
+
+    /// Return an iterator over the TLV entries of the account.
+    ///
+    /// The iterator is empty for accounts that cannot have extensions.
+    #[inline]
+    pub fn iter(&self) -> ExtensionIterator<'a> {
+        match self.base_state() {
+            Some(base_state) => ExtensionIterator::new(self.data, base_state),
+            None => ExtensionIterator::new(&[], BaseState::Mint),
+        }
+    }
+
+    /// Return a reference to the extension `T`.
+    ///
+    /// Returns [`ExtensionError::InvalidAccount`] for accounts that cannot
+    /// have extensions, otherwise the same errors as [`try_get_extension`].
+    #[inline]
+    pub fn get<T: Extension>(&self) -> Result<&'a T, ExtensionError> {
+        let found = self.base_state().ok_or(ExtensionError::InvalidAccount)?;
+
+        if found != T::BASE_STATE {
+            return Err(ExtensionError::WrongBaseState {
+                expected: T::BASE_STATE,
+                found,
+            });
+        }
+
+        find_typed_extension(self.data)
+    }
+}
+
+/// Return a reference to the extension `T` in the account data.
+///
+/// Returns `None` when the account does not have the extension; use
+/// [`try_get_extension`] to know why the extension could not be read.
+#[inline]
+pub fn get_extension_from_bytes<T: Extension>(data: &[u8]) -> Option<&T> {
+    try_get_extension(data).ok()
+}
+
+/// Return a reference to the extension `T` of the given account.
+///
+/// This function performs owner validation on `AccountInfo`, safe borrowing
+/// the account data.
+///
+/// Returns `InvalidAccountOwner` if the account is not owned by the token
+/// program, `AccountBorrowFailed` if the account data is mutably borrowed and
+/// the [`ExtensionError`] code if the extension cannot be read.
+#[inline]
+pub fn get_extension_from_account_info<T: Extension>(
+    account_info: &AccountInfo,
+) -> Result<Ref<T>, ProgramError> {
+    if !account_info.is_owned_by(&crate::ID) {
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+
+    let data = account_info.try_borrow_data()?;
+    let offset = {
+        let extension = try_get_extension::<T>(&data)?;
+        extension as *const T as usize - data.as_ptr() as usize
+    };
+
+    // SAFETY: `try_get_extension` validated that the `T::LEN` bytes at
+    // `offset` are within `data` and hold a `T`.
+    Ok(Ref::map(data, |data| unsafe {
+        from_bytes_ref_unchecked(&data[offset..offset + T::LEN])
+    }))
+}
+
+/// Return a reference to the extension `T` of the given account.
+///
+/// This function performs owner validation on `AccountInfo`, but does not
+/// perform the borrow check.
+///
+/// Returns `InvalidAccountOwner` if the account is not owned by the token
+/// program and the [`ExtensionError`] code if the extension cannot be read.
+///
+/// # Safety
+///
+/// The caller must ensure that it is safe to borrow the account data (e.g., there are
+/// no mutable borrows of the account data).
+#[inline]
+pub unsafe fn get_extension_from_account_info_unchecked<T: Extension>(
+    account_info: &AccountInfo,
+) -> Result<&T, ProgramError> {
+    if !account_info.is_owned_by(&crate::ID) {
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+    Ok(try_get_extension(account_info.borrow_data_unchecked())?)
+}
+
+/// Return a mutable reference to the extension `T` in the account data.
+///
+/// Mutable counterpart of [`try_get_extension`], with the same validation of
+/// the base state and bounds of the extension.
+#[inline]
+pub fn try_get_extension_mut<T: Extension>(data: &mut [u8]) -> Result<&mut T, ExtensionError> {
+    let offset = {
+        let extension = try_get_extension::<T>(data)?;
+        extension as *const T as usize - data.as_ptr() as usize
+    };
+
+    // SAFETY: `try_get_extension` validated that the `T::LEN` bytes at
+    // `offset` are within `data` and hold a `T`.
+    Ok(unsafe { &mut *(data.as_mut_ptr().add(offset) as *mut T) })
+}
+
+/// Return a mutable reference to the extension `T` in the account data.
+///
+/// Returns `None` when the account does not have the extension; use
+/// [`try_get_extension_mut`] to know why the extension could not be read.
+#[inline]
+pub fn get_extension_from_bytes_mut<T: Extension>(data: &mut [u8]) -> Option<&mut T> {
+    try_get_extension_mut(data).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::{vec, vec::Vec};
+
+    use super::*;
+    use crate::{
+        extensions::{
+            cpi_guard::CpiGuard,
+            default_account_state::DefaultAccountState,
+            group_member_pointer::GroupMemberPointer,
+            group_pointer::GroupPointer,
+            immutable_owner::ImmutableOwner,
+            interest_bearing_mint::InterestBearingConfig,
+            memo_transfer::MemoTransfer,
+            metadata_pointer::MetadataPointer,
+            mint_close_authority::MintCloseAuthority,
+            non_transferable::{NonTransferable, NonTransferableAccount},
+            pausable::{PausableAccount, PausableConfig},
+            permanent_delegate::PermanentDelegate,
+            scaled_ui_amount::ScaledUiAmountConfig,
+            token_group::{TokenGroup, TokenGroupMember},
+            transfer_fee::{TransferFee, TransferFeeAmount, TransferFeeConfig},
+            transfer_hook::{TransferHook, TransferHookAccount},
+        },
+        state::{Mint, TokenAccount},
+        test_utils::{account_with_extensions, mint_base, pyusd_mint_data},
+    };
+
+    /// Assert that each extension is rejected with a `WrongBaseState` error.
+    macro_rules! assert_wrong_base_state {
+        ($data:expr, $found:expr, [$($extension:ty),+ $(,)?]) => {
+            $(
+                assert_eq!(
+                    try_get_extension::<$extension>($data).map(|_| ()),
+                    Err(ExtensionError::WrongBaseState {
+                        expected: <$extension>::BASE_STATE,
+                        found: $found,
+                    }),
+                    "{}",
+                    stringify!($extension),
+                );
+                assert!(get_extension_from_bytes::<$extension>($data).is_none());
+            )+
+        };
+    }
+
+    /// Token account with token account extensions.
+    fn token_account_data() -> Vec<u8> {
+        account_with_extensions(
+            BaseState::TokenAccount,
+            &[0; TokenAccount::BASE_LEN],
+            &[
+                (ExtensionType::TransferHookAccount, &[1]),
+                (ExtensionType::PausableAccount, &[]),
+            ],
+        )
+    }
+
+    const TRANSFER_FEE_MINT: &[u8] = include_bytes!("../../tests/fixtures/transfer_fee_mint.bin");
+
+    /// Basis points of the newer transfer fee, read at compile time.
+    static KNOWN_FEE_BPS: u16 = {
+        match get_extension_data(
+            TRANSFER_FEE_MINT,
+            BaseState::Mint,
+            ExtensionType::TransferFeeConfig,
+        ) {
+            // The newer transfer fee basis points are the last 2 bytes.
+            Some(data) => u16::from_le_bytes([data[106], data[107]]),
+            None => panic!("missing transfer fee config"),
+        }
+    };
+
+    static KNOWN_DECIMALS: u8 = crate::state::const_read_decimals(TRANSFER_FEE_MINT);
+
+    #[test]
+    fn test_get_extension_data_after_confidential_extension() {
+        let data = pyusd_mint_data();
+
+        // `ConfidentialTransferMint` precedes `MetadataPointer` in the fixture,
+        // so this requires skipping over it, with or without the
+        // `confidential` feature.
+        let confidential = get_extension_data(
+            &data,
+            BaseState::Mint,
+            ExtensionType::ConfidentialTransferMint,
+        )
+        .unwrap();
+        let metadata_pointer =
+            get_extension_data(&data, BaseState::Mint, ExtensionType::MetadataPointer).unwrap();
+
+        assert!(confidential.as_ptr() < metadata_pointer.as_ptr());
+        assert_eq!(metadata_pointer.len(), 64);
+    }
+
+    #[test]
+    fn test_const_reads_match_runtime_parsing() {
+        let data = TRANSFER_FEE_MINT.to_vec();
+        let mint = unsafe { crate::state::Mint::from_bytes_unchecked(&data) };
+
+        assert_eq!(KNOWN_DECIMALS, mint.decimals());
+        assert_eq!(crate::state::const_read_supply(&data), mint.supply());
+
+        let transfer_fee_config =
+            get_extension_data(&data, BaseState::Mint, ExtensionType::TransferFeeConfig).unwrap();
+        let newer_fee_bps = &transfer_fee_config[transfer_fee_config.len() - 2..];
+
+        assert_eq!(
+            KNOWN_FEE_BPS,
+            u16::from_le_bytes(newer_fee_bps.try_into().unwrap())
+        );
+        assert_eq!(KNOWN_FEE_BPS, 100);
+    }
+
+    #[test]
+    fn test_extension_iterator() {
+        let data = pyusd_mint_data();
+
+        let extension_types = ExtensionIterator::new(&data, BaseState::Mint)
+            .map(|(extension_type, _)| extension_type)
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            extension_types,
+            [
+                ExtensionType::TransferFeeConfig,
+                ExtensionType::MintCloseAuthority,
+                ExtensionType::ConfidentialTransferMint,
+                ExtensionType::PermanentDelegate,
+                ExtensionType::TransferHook,
+                ExtensionType::MetadataPointer,
+                ExtensionType::TokenMetadata,
+            ]
+        );
+
+        let mut extensions = ExtensionIterator::new(TRANSFER_FEE_MINT, BaseState::Mint);
+        let (extension_type, value) = extensions.next().unwrap();
+        assert_eq!(extension_type, ExtensionType::TransferFeeConfig);
+        assert_eq!(value.len(), TransferFeeConfig::LEN);
+        assert!(extensions.next().is_none());
+        assert!(!extensions.is_truncated());
+
+        // Wrong base state.
+        assert!(ExtensionIterator::new(&data, BaseState::TokenAccount)
+            .next()
+            .is_none());
+    }
+
+    #[test]
+    fn test_extension_iterator_truncated() {
+        let data = pyusd_mint_data();
+
+        // Cut the data in the middle of the last entry.
+        let mut extensions = ExtensionIterator::new(&data[..data.len() - 1], BaseState::Mint);
+        assert_eq!(extensions.by_ref().count(), 6);
+        assert!(extensions.is_truncated());
+        assert!(extensions.next().is_none());
+
+        // Cut the data in the middle of a TLV header.
+        let mut extensions = ExtensionIterator::new(
+            &TRANSFER_FEE_MINT[..EXTENSION_START_OFFSET + 2],
+            BaseState::Mint,
+        );
+        assert!(extensions.next().is_none());
+        assert!(extensions.is_truncated());
+    }
+
+    #[test]
+    fn test_extension_iterator_skips_unknown_and_stops_at_uninitialized() {
+        let mut data = account_with_extensions(
+            BaseState::TokenAccount,
+            &[0; TokenAccount::BASE_LEN],
+            &[(ExtensionType::TransferHookAccount, &[1])],
+        );
+        // Unknown extension type, followed by padding and an entry that must
+        // not be read.
+        data.extend_from_slice(&[0xff, 0xff, 1, 0, 9]);
+        data.extend_from_slice(&[0, 0, 0, 0]);
+        data.extend_from_slice(&(ExtensionType::PausableAccount as u16).to_le_bytes());
+        data.extend_from_slice(&[0, 0]);
+
+        let mut extensions = ExtensionIterator::new(&data, BaseState::TokenAccount);
+        assert_eq!(
+            extensions.next(),
+            Some((ExtensionType::TransferHookAccount, &[1][..]))
+        );
+        assert!(extensions.next().is_none());
+        assert!(!extensions.is_truncated());
+    }
+
+    #[test]
+    fn test_get_extension_types() {
+        let data = pyusd_mint_data();
+        let mut out = [ExtensionType::Uninitialized; 10];
+
+        let count = get_extension_types(&data, &mut out).unwrap();
+
+        assert_eq!(
+            out[..count],
+            [
+                ExtensionType::TransferFeeConfig,
+                ExtensionType::MintCloseAuthority,
+                ExtensionType::ConfidentialTransferMint,
+                ExtensionType::PermanentDelegate,
+                ExtensionType::TransferHook,
+                ExtensionType::MetadataPointer,
+                ExtensionType::TokenMetadata,
+            ]
+        );
+
+        assert_eq!(
+            get_extension_types(&data, &mut out[..6]),
+            Err(ProgramError::InvalidArgument)
+        );
+        assert_eq!(
+            get_extension_types(&data[..data.len() - 1], &mut out),
+            Err(ExtensionError::Truncated.into())
+        );
+        assert_eq!(
+            get_extension_types(&[0; 100], &mut out),
+            Err(ExtensionError::InvalidAccount.into())
+        );
+    }
+
+    /// Read every extension of `data` with each of the readers.
+    fn read_all_extensions(data: &[u8]) {
+        let mut out = [ExtensionType::Uninitialized; 32];
+        let _ = get_extension_types(data, &mut out);
+        let _ = ExtensionIterator::new(data, BaseState::Mint).count();
+        let _ = ExtensionIterator::new(data, BaseState::TokenAccount).count();
+
+        let _ = get_extension_data(data, BaseState::Mint, ExtensionType::TokenMetadata);
+        let _ = get_extension_data(data, BaseState::Mint, ExtensionType::TransferFeeConfig);
+        let _ = get_extension_data(
+            data,
+            BaseState::TokenAccount,
+            ExtensionType::TransferHookAccount,
+        );
+
+        let _ = try_get_extension::<TransferFeeConfig>(data);
+        let _ = try_get_extension::<TransferHook>(data);
+        let _ = try_get_extension::<MetadataPointer>(data);
+        let _ = try_get_extension::<TransferHookAccount>(data);
+        let _ = get_extension_from_bytes::<PausableAccount>(data);
+
+        let _ = crate::extensions::token_metadata::TokenMetadata::from_mint_data(data);
+        let _ = crate::extensions::token_metadata::probe(data);
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore = "too slow under Miri")]
+    fn test_truncated_prefixes_do_not_panic() {
+        let fixtures = [
+            pyusd_mint_data(),
+            token_account_data(),
+            TRANSFER_FEE_MINT.to_vec(),
+        ];
+
+        for data in &fixtures {
+            for len in 0..=data.len() {
+                read_all_extensions(&data[..len]);
+            }
+        }
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore = "too slow under Miri")]
+    fn test_lookups_match_iterator() {
+        let mut padded = TRANSFER_FEE_MINT.to_vec();
+        padded.extend_from_slice(&[0; 2]);
+        let mut trailing = TRANSFER_FEE_MINT.to_vec();
+        trailing.extend_from_slice(&[1, 0]);
+
+        let fixtures = [pyusd_mint_data(), token_account_data(), padded, trailing];
+        let extension_types = (1..=u8::MAX)
+            .filter_map(|extension_type| ExtensionType::from_bytes([extension_type, 0]))
+            .collect::<Vec<_>>();
+
+        for data in &fixtures {
+            for len in 0..=data.len() {
+                let data = &data[..len];
+
+                for base_state in [BaseState::Mint, BaseState::TokenAccount] {
+                    let entries = ExtensionIterator::new(data, base_state).collect::<Vec<_>>();
+
+                    for extension_type in &extension_types {
+                        let expected = entries
+                            .iter()
+                            .find(|(current, _)| current == extension_type)
+                            .map(|(_, value)| *value);
+
+                        assert_eq!(
+                            get_extension_data(data, base_state, *extension_type),
+                            expected
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_extension_length_past_end_of_data() {
+        let mut data = token_account_data();
+        // The length of the `TransferHookAccount` entry claims more bytes than
+        // the rest of the data.
+        data[EXTENSION_START_OFFSET + 2..EXTENSION_START_OFFSET + 4]
+            .copy_from_slice(&u16::MAX.to_le_bytes());
+
+        read_all_extensions(&data);
+
+        assert_eq!(
+            try_get_extension::<TransferHookAccount>(&data),
+            Err(ExtensionError::Truncated)
+        );
+        assert_eq!(
+            get_extension_data(
+                &data,
+                BaseState::TokenAccount,
+                ExtensionType::TransferHookAccount
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_unknown_extension_between_known_extensions() {
+        let mut data = account_with_extensions(
+            BaseState::TokenAccount,
+            &[0; TokenAccount::BASE_LEN],
+            &[(ExtensionType::TransferHookAccount, &[1])],
+        );
+        // Extension type 200 is not known by this crate.
+        data.extend_from_slice(&200u16.to_le_bytes());
+        data.extend_from_slice(&3u16.to_le_bytes());
+        data.extend_from_slice(&[1, 2, 3]);
+        data.extend_from_slice(&(ExtensionType::PausableAccount as u16).to_le_bytes());
+        data.extend_from_slice(&0u16.to_le_bytes());
+
+        let extension_types = ExtensionIterator::new(&data, BaseState::TokenAccount)
+            .map(|(extension_type, _)| extension_type)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            extension_types,
+            [
+                ExtensionType::TransferHookAccount,
+                ExtensionType::PausableAccount
+            ]
+        );
+
+        assert_eq!(
+            try_get_extension::<TransferHookAccount>(&data).map(|hook| hook.transferring.get()),
+            Ok(true)
+        );
+        assert!(get_extension_from_bytes::<PausableAccount>(&data).is_some());
+        assert!(get_extension_data(
+            &data,
+            BaseState::TokenAccount,
+            ExtensionType::PausableAccount
+        )
+        .is_some());
+    }
+
+    #[test]
+    fn test_get_extension_from_bytes_mut() {
+        // Authority `[7; 32]`, not paused.
+        let mut config = [7; PausableConfig::LEN];
+        config[32] = 0;
+
+        let mut data = account_with_extensions(
+            BaseState::Mint,
+            &crate::test_utils::mint_base(None, 0, 6, None),
+            &[(ExtensionType::Pausable, &config)],
+        );
+
+        get_extension_from_bytes_mut::<PausableConfig>(&mut data)
+            .unwrap()
+            .paused
+            .set(true);
+
+        let config = get_extension_from_bytes::<PausableConfig>(&data).unwrap();
+        assert!(config.paused.get());
+        assert_eq!(config.authority, [7; 32]);
+
+        let value =
+            get_extension_data_mut(&mut data, BaseState::Mint, ExtensionType::Pausable).unwrap();
+        assert_eq!(value.len(), PausableConfig::LEN);
+        value[..32].copy_from_slice(&[8; 32]);
+
+        assert_eq!(
+            get_extension_from_bytes::<PausableConfig>(&data)
+                .unwrap()
+                .authority,
+            [8; 32]
+        );
+
+        // Same validation as the read path.
+        assert_eq!(
+            try_get_extension_mut::<PausableAccount>(&mut data).map(|_| ()),
+            Err(ExtensionError::WrongBaseState {
+                expected: BaseState::TokenAccount,
+                found: BaseState::Mint,
+            })
+        );
+        assert!(get_extension_from_bytes_mut::<TransferHook>(&mut data).is_none());
+        assert!(
+            get_extension_data_mut(&mut data, BaseState::Mint, ExtensionType::TransferHook)
+                .is_none()
+        );
+
+        let len = data.len();
+        assert!(get_extension_from_bytes_mut::<PausableConfig>(&mut data[..len - 1]).is_none());
+        assert!(get_extension_data_mut(
+            &mut data[..len - 1],
+            BaseState::Mint,
+            ExtensionType::Pausable
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn test_fixed_len_matches_extension_len() {
+        macro_rules! assert_fixed_len {
+            ($($extension:ty),+ $(,)?) => {
+                $(
+                    assert_eq!(
+                        <$extension>::TYPE.fixed_len(),
+                        Some(<$extension>::LEN),
+                        "{}",
+                        stringify!($extension),
+                    );
+                )+
+            };
+        }
+
+        assert_fixed_len!(
+            TransferFeeConfig,
+            TransferFeeAmount,
+            MintCloseAuthority,
+            DefaultAccountState,
+            ImmutableOwner,
+            NonTransferable,
+            NonTransferableAccount,
+            InterestBearingConfig,
+            MemoTransfer,
+            PermanentDelegate,
+            CpiGuard,
+            TransferHook,
+            TransferHookAccount,
+            MetadataPointer,
+            GroupPointer,
+            TokenGroup,
+            TokenGroupMember,
+            GroupMemberPointer,
+            ScaledUiAmountConfig,
+            PausableConfig,
+            PausableAccount,
+        );
+
+        #[cfg(feature = "confidential")]
+        assert_fixed_len!(
+            confidential_mint_burn::ConfidentialMintBurn,
+            confidential_transfer::ConfidentialTransferMint,
+            confidential_transfer::ConfidentialTransferAccount,
+        );
+
+        assert_eq!(ExtensionType::TokenMetadata.fixed_len(), None);
+    }
+
+    #[test]
+    fn test_extension_type_and_len() {
+        /// Assert the extension type, base state and length of each
+        /// extension, as defined by spl-token-2022, and that it can be read
+        /// from any offset.
+        macro_rules! assert_extensions {
+            ($(($extension:ty, $type:ident, $base_state:ident, $len:expr)),+ $(,)?) => {
+                $(
+                    assert_eq!(
+                        (<$extension>::TYPE, <$extension>::BASE_STATE, <$extension>::LEN),
+                        (ExtensionType::$type, BaseState::$base_state, $len),
+                        "{}",
+                        stringify!($extension),
+                    );
+                    // Extensions are read from unaligned offsets.
+                    assert_eq!(core::mem::align_of::<$extension>(), 1);
+                )+
+            };
+        }
+
+        assert_extensions!(
+            (TransferFeeConfig, TransferFeeConfig, Mint, 108),
+            (TransferFeeAmount, TransferFeeAmount, TokenAccount, 8),
+            (MintCloseAuthority, MintCloseAuthority, Mint, 32),
+            (DefaultAccountState, DefaultAccountState, Mint, 1),
+            (ImmutableOwner, ImmutableOwner, TokenAccount, 0),
+            (NonTransferable, NonTransferable, Mint, 0),
+            (
+                NonTransferableAccount,
+                NonTransferableAccount,
+                TokenAccount,
+                0
+            ),
+            (InterestBearingConfig, InterestBearingConfig, Mint, 52),
+            (MemoTransfer, MemoTransfer, TokenAccount, 1),
+            (PermanentDelegate, PermanentDelegate, Mint, 32),
+            (CpiGuard, CpiGuard, TokenAccount, 1),
+            (TransferHook, TransferHook, Mint, 64),
+            (TransferHookAccount, TransferHookAccount, TokenAccount, 1),
+            (MetadataPointer, MetadataPointer, Mint, 64),
+            (GroupPointer, GroupPointer, Mint, 64),
+            (TokenGroup, TokenGroup, Mint, 80),
+            (TokenGroupMember, TokenGroupMember, Mint, 72),
+            (GroupMemberPointer, GroupMemberPointer, Mint, 64),
+            (ScaledUiAmountConfig, ScaledUiAmount, Mint, 56),
+            (PausableConfig, Pausable, Mint, 33),
+            (PausableAccount, PausableAccount, TokenAccount, 0),
+        );
+
+        #[cfg(feature = "confidential")]
+        assert_extensions!(
+            (
+                confidential_transfer::ConfidentialTransferMint,
+                ConfidentialTransferMint,
+                Mint,
+                65
+            ),
+            (
+                confidential_transfer::ConfidentialTransferAccount,
+                ConfidentialTransferAccount,
+                TokenAccount,
+                295
+            ),
+            (
+                confidential_mint_burn::ConfidentialMintBurn,
+                ConfidentialMintBurn,
+                Mint,
+                196
+            ),
+        );
+    }
+
+    #[test]
+    fn test_default_extension_round_trip() {
+        /// Assert that the default value of each extension is all zeros and
+        /// is read back unchanged once written.
+        macro_rules! assert_default_round_trip {
+            ($($extension:ty),+ $(,)?) => {
+                $(
+                    let mut data =
+                        vec![0; EXTENSION_START_OFFSET + EXTENSION_HEADER_LEN + <$extension>::LEN];
+                    ExtensionWriter::new(&mut data, <$extension>::BASE_STATE)
+                        .unwrap()
+                        .write_extension(&<$extension>::default())
+                        .unwrap();
+
+                    assert!(
+                        data[EXTENSION_START_OFFSET + EXTENSION_HEADER_LEN..]
+                            .iter()
+                            .all(|byte| *byte == 0),
+                        "{}",
+                        stringify!($extension),
+                    );
+                    assert_eq!(
+                        try_get_extension::<$extension>(&data),
+                        Ok(&<$extension>::default()),
+                        "{}",
+                        stringify!($extension),
+                    );
+                )+
+            };
+        }
+
+        assert_default_round_trip!(
+            TransferFeeConfig,
+            TransferFeeAmount,
+            MintCloseAuthority,
+            ImmutableOwner,
+            NonTransferable,
+            NonTransferableAccount,
+            InterestBearingConfig,
+            MemoTransfer,
+            CpiGuard,
+            PermanentDelegate,
+            TransferHook,
+            TransferHookAccount,
+            MetadataPointer,
+            GroupPointer,
+            TokenGroup,
+            TokenGroupMember,
+            GroupMemberPointer,
+            PausableConfig,
+            PausableAccount,
+        );
+
+        #[cfg(feature = "confidential")]
+        assert_default_round_trip!(
+            confidential_mint_burn::ConfidentialMintBurn,
+            confidential_transfer::ConfidentialTransferMint,
+            confidential_transfer::ConfidentialTransferAccount,
+        );
+    }
+
+    #[test]
+    fn test_try_calculate_account_len() {
+        // Lengths of accounts created by spl-token-2022.
+        let cases: &[(BaseState, &[ExtensionType], usize)] = &[
+            (BaseState::Mint, &[], 82),
+            (BaseState::TokenAccount, &[], 165),
+            (BaseState::Mint, &[ExtensionType::TransferFeeConfig], 278),
+            (BaseState::Mint, &[ExtensionType::MintCloseAuthority], 202),
+            (
+                BaseState::TokenAccount,
+                &[ExtensionType::ImmutableOwner],
+                170,
+            ),
+            (
+                BaseState::TokenAccount,
+                &[
+                    ExtensionType::TransferFeeAmount,
+                    ExtensionType::ImmutableOwner,
+                ],
+                182,
+            ),
+            (
+                BaseState::Mint,
+                &[
+                    ExtensionType::MetadataPointer,
+                    ExtensionType::GroupPointer,
+                    ExtensionType::MetadataPointer,
+                ],
+                302,
+            ),
+            (
+                BaseState::TokenAccount,
+                &[
+                    ExtensionType::ConfidentialTransferAccount,
+                    ExtensionType::ConfidentialTransferFeeAmount,
+                    ExtensionType::TransferFeeAmount,
+                ],
+                545,
+            ),
+        ];
+
+        for (base_state, extension_types, len) in cases {
+            assert_eq!(
+                try_calculate_account_len(*base_state, extension_types),
+                Ok(*len),
+                "{extension_types:?}"
+            );
+        }
+
+        assert_eq!(
+            try_calculate_account_len(BaseState::Mint, &[ExtensionType::TokenMetadata]),
+            Err(ProgramError::InvalidArgument)
+        );
+    }
+
+    #[test]
+    fn test_try_calculate_account_len_with_multisig_len() {
+        // The TLV entries take 189 bytes, so the account would be 355 bytes.
+        let extension_types = [
+            ExtensionType::TransferFeeConfig,
+            ExtensionType::MintCloseAuthority,
+            ExtensionType::PermanentDelegate,
+            ExtensionType::DefaultAccountState,
+        ];
+
+        assert_eq!(
+            try_calculate_account_len(BaseState::Mint, &extension_types),
+            Ok(357)
+        );
+    }
+
+    #[test]
+    fn test_read_account_padded_from_multisig_len() {
+        let extension_types = [
+            ExtensionType::TransferFeeConfig,
+            ExtensionType::MintCloseAuthority,
+            ExtensionType::PermanentDelegate,
+            ExtensionType::DefaultAccountState,
+        ];
+        let mut data = vec![0; 357];
+        data[..Mint::BASE_LEN].copy_from_slice(&mint_base(Some(&[1; 32]), 0, 6, None));
+
+        let mut writer = ExtensionWriter::new(&mut data, BaseState::Mint).unwrap();
+        writer
+            .write(ExtensionType::TransferFeeConfig, &[0; 108])
+            .unwrap();
+        writer
+            .write(ExtensionType::MintCloseAuthority, &[2; 32])
+            .unwrap();
+        writer
+            .write(ExtensionType::PermanentDelegate, &[3; 32])
+            .unwrap();
+        writer
+            .write(ExtensionType::DefaultAccountState, &[2])
+            .unwrap();
+
+        let mut out = [ExtensionType::Uninitialized; 4];
+        assert_eq!(get_extension_types(&data, &mut out), Ok(4));
+        assert_eq!(out, extension_types);
+        assert_eq!(
+            try_get_extension::<DefaultAccountState>(&data)
+                .unwrap()
+                .state,
+            2
+        );
+        assert_eq!(
+            try_get_extension::<PausableConfig>(&data),
+            Err(ExtensionError::NotFound)
+        );
+
+        // Trailing bytes that are not zeroed are a truncated entry.
+        data[356] = 1;
+        assert_eq!(
+            try_get_extension::<PausableConfig>(&data),
+            Err(ExtensionError::Truncated)
+        );
+    }
+
+    #[test]
+    fn test_try_calculate_account_len_matches_fixtures() {
+        let data = pyusd_mint_data();
+        let metadata =
+            get_extension_data(&data, BaseState::Mint, ExtensionType::TokenMetadata).unwrap();
+        let metadata_offset = metadata.as_ptr() as usize - data.as_ptr() as usize;
+
+        assert_eq!(
+            try_calculate_account_len(
+                BaseState::Mint,
+                &[
+                    ExtensionType::TransferFeeConfig,
+                    ExtensionType::MintCloseAuthority,
+                    ExtensionType::ConfidentialTransferMint,
+                    ExtensionType::PermanentDelegate,
+                    ExtensionType::TransferHook,
+                    ExtensionType::MetadataPointer,
+                ]
+            ),
+            Ok(metadata_offset - EXTENSION_HEADER_LEN)
+        );
+        assert_eq!(
+            try_calculate_account_len(
+                BaseState::TokenAccount,
+                &[
+                    ExtensionType::TransferHookAccount,
+                    ExtensionType::PausableAccount
+                ]
+            ),
+            Ok(token_account_data().len())
+        );
+    }
+
+    #[test]
+    fn test_extension_writer_round_trip() {
+        let mut data = vec![0; 300];
+        data[..TokenAccount::BASE_LEN].copy_from_slice(&crate::test_utils::token_account_base(
+            &[1; 32], &[2; 32], 3,
+        ));
+
+        let mut writer = ExtensionWriter::new(&mut data, BaseState::TokenAccount).unwrap();
+        writer
+            .write_extension(&TransferHookAccount {
+                transferring: true.into(),
+            })
+            .unwrap();
+        writer.write(ExtensionType::ImmutableOwner, &[]).unwrap();
+        let len = writer.data_len();
+        data.truncate(len);
+
+        assert_eq!(
+            len,
+            try_calculate_account_len(
+                BaseState::TokenAccount,
+                &[
+                    ExtensionType::TransferHookAccount,
+                    ExtensionType::ImmutableOwner
+                ]
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            get_extension_from_bytes::<TransferHookAccount>(&data)
+                .map(|hook| hook.transferring.get()),
+            Some(true)
+        );
+
+        // Appends after the existing entries.
+        data.extend_from_slice(&[0; 4]);
+        let mut writer = ExtensionWriter::new(&mut data, BaseState::TokenAccount).unwrap();
+        writer.write_extension(&PausableAccount).unwrap();
+        assert!(get_extension_from_bytes::<PausableAccount>(&data).is_some());
+        assert_eq!(
+            ExtensionIterator::new(&data, BaseState::TokenAccount).count(),
+            3
+        );
+    }
+
+    #[test]
+    fn test_extension_writer_rebuilds_fixture() {
+        let mut data = vec![0; TRANSFER_FEE_MINT.len()];
+        data[..Mint::BASE_LEN].copy_from_slice(&mint_base(
+            Some(&[7; 32]),
+            1_000_000_000_000,
+            6,
+            None,
+        ));
+
+        let mut writer = ExtensionWriter::new(&mut data, BaseState::Mint).unwrap();
+        writer
+            .write_extension(&TransferFeeConfig {
+                transfer_fee_config_authority: [7; 32],
+                withdraw_withheld_authority: [7; 32],
+                withheld_amount: 0.into(),
+                older_transfer_fee: TransferFee {
+                    epoch: 0.into(),
+                    maximum_fee: 5_000.into(),
+                    transfer_fee_basis_points: 50.into(),
+                },
+                newer_transfer_fee: TransferFee {
+                    epoch: 500.into(),
+                    maximum_fee: 5_000.into(),
+                    transfer_fee_basis_points: 100.into(),
+                },
+            })
+            .unwrap();
+
+        assert_eq!(writer.data_len(), TRANSFER_FEE_MINT.len());
+        assert_eq!(data, TRANSFER_FEE_MINT);
+    }
+
+    #[test]
+    fn test_extension_writer_errors() {
+        let mut data = vec![0; EXTENSION_START_OFFSET + EXTENSION_HEADER_LEN + 1];
+        let mut writer = ExtensionWriter::new(&mut data, BaseState::TokenAccount).unwrap();
+
+        assert_eq!(
+            writer.write(ExtensionType::MintCloseAuthority, &[0; 32]),
+            Err(ProgramError::AccountDataTooSmall)
+        );
+        assert_eq!(
+            writer.write(ExtensionType::Uninitialized, &[]),
+            Err(ProgramError::InvalidArgument)
+        );
+        assert_eq!(
+            writer.write_extension(&PausableConfig {
+                authority: [0; 32],
+                paused: false.into()
+            }),
+            Err(ProgramError::InvalidArgument)
+        );
+
+        writer.write(ExtensionType::MemoTransfer, &[1]).unwrap();
+        assert_eq!(
+            writer.write(ExtensionType::MemoTransfer, &[1]),
+            Err(TokenError::ExtensionAlreadyInitialized.into())
+        );
+
+        // The account type is already set to a token account.
+        assert_eq!(
+            ExtensionWriter::new(&mut data, BaseState::Mint).map(|_| ()),
+            Err(ProgramError::InvalidAccountData)
+        );
+        assert_eq!(
+            ExtensionWriter::new(&mut [0; ACCOUNT_TYPE_OFFSET], BaseState::Mint).map(|_| ()),
+            Err(ProgramError::InvalidAccountData)
+        );
+
+        // The existing entries are truncated.
+        let mut truncated = TRANSFER_FEE_MINT[..TRANSFER_FEE_MINT.len() - 1].to_vec();
+        assert_eq!(
+            ExtensionWriter::new(&mut truncated, BaseState::Mint).map(|_| ()),
+            Err(ProgramError::InvalidAccountData)
+        );
+    }
+
+    #[test]
+    fn test_try_from_bytes() {
+        let mut data = [0; TransferHook::LEN + 1];
+        data[..32].copy_from_slice(&[7; 32]);
+
+        let hook = try_from_bytes_ref::<TransferHook>(&data[..TransferHook::LEN]).unwrap();
+        assert_eq!(hook.authority, [7; 32]);
+        assert_eq!(
+            try_from_bytes::<TransferHook>(&data[..TransferHook::LEN]),
+            Ok(*hook)
+        );
+
+        // Short and long buffers.
+        for len in [0, 1, TransferHook::LEN - 1, TransferHook::LEN + 1] {
+            assert_eq!(
+                try_from_bytes_ref::<TransferHook>(&data[..len]).map(|_| ()),
+                Err(ProgramError::InvalidAccountData)
+            );
+            assert_eq!(
+                try_from_bytes::<TransferHook>(&data[..len]).map(|_| ()),
+                Err(ProgramError::InvalidAccountData)
+            );
+        }
+
+        // Zero-length extensions.
+        assert!(try_from_bytes::<PausableAccount>(&[]).is_ok());
+        assert_eq!(
+            try_from_bytes::<PausableAccount>(&[0]),
+            Err(ProgramError::InvalidAccountData)
+        );
+    }
+
+    #[test]
+    fn test_get_extensions_data() {
+        let data = pyusd_mint_data();
+
+        let [transfer_fee, transfer_hook, pausable, non_transferable] = get_extensions_data(
+            &data,
+            BaseState::Mint,
+            &[
+                ExtensionType::TransferFeeConfig,
+                ExtensionType::TransferHook,
+                ExtensionType::Pausable,
+                ExtensionType::NonTransferable,
+            ],
+        );
+
+        assert_eq!(
+            transfer_fee.and_then(cast_extension::<TransferFeeConfig>),
+            get_extension_from_bytes::<TransferFeeConfig>(&data)
+        );
+        assert_eq!(
+            transfer_hook
+                .and_then(cast_extension::<TransferHook>)
+                .map(|hook| hook.authority),
+            Some([7; 32])
+        );
+        assert!(pausable.is_none());
+        assert!(non_transferable.is_none());
+
+        // Wrong length.
+        assert!(cast_extension::<TransferHook>(transfer_fee.unwrap()).is_none());
+
+        assert_eq!(
+            get_extensions_data(
+                &data,
+                BaseState::TokenAccount,
+                &[ExtensionType::TransferHook]
+            ),
+            [None]
+        );
+    }
+
+    #[test]
+    fn test_get_extensions_data_single_pass() {
+        let data = pyusd_mint_data();
+        let entries = ExtensionIterator::new(&data, BaseState::Mint).count();
+        let requested = [
+            ExtensionType::TransferFeeConfig,
+            ExtensionType::TransferHook,
+            ExtensionType::Pausable,
+            ExtensionType::NonTransferable,
+        ];
+
+        // Looking up each extension on its own walks the TLV data up to the
+        // extension, or entirely when it is missing.
+        let separate_visits = requested
+            .iter()
+            .map(|requested| {
+                let mut visits = 0;
+                collect_extensions_data(
+                    ExtensionIterator::new(&data, BaseState::Mint).inspect(|_| visits += 1),
+                    &[*requested],
+                );
+                visits
+            })
+            .sum::<usize>();
+
+        let mut visits = 0;
+        collect_extensions_data(
+            ExtensionIterator::new(&data, BaseState::Mint).inspect(|_| visits += 1),
+            &requested,
+        );
+
+        // Each entry is visited once, instead of once per lookup:
+        // `TransferFeeConfig` is the first entry, `TransferHook` the fifth and
+        // the missing extensions walk every entry.
+        assert_eq!(visits, entries);
+        assert_eq!(separate_visits, 1 + 5 + 2 * entries);
+
+        // The walk stops once every requested extension is found.
+        let mut visits = 0;
+        collect_extensions_data(
+            ExtensionIterator::new(&data, BaseState::Mint).inspect(|_| visits += 1),
+            &[ExtensionType::TransferFeeConfig],
+        );
+        assert_eq!(visits, 1);
+    }
+
+    #[test]
+    fn test_has_extension() {
+        let mint = pyusd_mint_data();
+        let token_account = token_account_data();
+
+        assert!(has_extension::<TransferHook>(&mint));
+        assert!(has_extension::<MintCloseAuthority>(&mint));
+        assert!(has_extension::<PausableAccount>(&token_account));
+        assert!(has_extension_type(&mint, ExtensionType::TokenMetadata));
+        assert!(has_extension_type(
+            &token_account,
+            ExtensionType::PausableAccount
+        ));
+
+        // Absent.
+        assert!(!has_extension::<NonTransferable>(&mint));
+        assert!(!has_extension::<PausableConfig>(&mint));
+        assert!(!has_extension_type(&mint, ExtensionType::Pausable));
+
+        // Wrong base state.
+        assert!(!has_extension::<TransferHookAccount>(&mint));
+        assert!(!has_extension::<TransferHook>(&token_account));
+
+        // Malformed data.
+        assert!(!has_extension::<TransferHook>(&[0; 100]));
+        assert!(!has_extension_type(&[0; 100], ExtensionType::TransferHook));
+        assert!(!has_extension::<TransferHookAccount>(
+            &token_account[..token_account.len() - 5]
+        ));
+        assert!(!has_extension_type(
+            &mint[..mint.len() - 1],
+            ExtensionType::TokenMetadata
+        ));
+
+        for len in 0..=mint.len() {
+            let _ = has_extension::<MetadataPointer>(&mint[..len]);
+            let _ = has_extension_type(&mint[..len], ExtensionType::TokenMetadata);
+        }
+    }
+
+    #[test]
+    fn test_get_extension_data_truncated() {
+        let data = TRANSFER_FEE_MINT;
+
+        assert!(
+            get_extension_data(data, BaseState::Mint, ExtensionType::TransferFeeConfig).is_some()
+        );
+        assert!(get_extension_data(
+            &data[..data.len() - 1],
+            BaseState::Mint,
+            ExtensionType::TransferFeeConfig
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn test_get_extension_data_with_wrong_base_state() {
+        let data = pyusd_mint_data();
+
+        assert_eq!(
+            get_extension_data(
+                &data,
+                BaseState::TokenAccount,
+                ExtensionType::MetadataPointer
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_get_extension_from_bytes() {
+        let mint = pyusd_mint_data();
+
+        let transfer_fee_config = get_extension_from_bytes::<TransferFeeConfig>(&mint).unwrap();
+        assert_eq!(transfer_fee_config.transfer_fee_config_authority, [7; 32]);
+        assert_eq!(transfer_fee_config.withdraw_withheld_authority, [7; 32]);
+
+        let without_extension = account_with_extensions(
+            BaseState::Mint,
+            &mint[..Mint::BASE_LEN],
+            &[(ExtensionType::MintCloseAuthority, &[0; 32])],
+        );
+        assert_eq!(
+            try_get_extension::<TransferFeeConfig>(&without_extension),
+            Err(ExtensionError::NotFound)
+        );
+        assert_eq!(
+            try_get_extension::<TransferFeeConfig>(
+                &TRANSFER_FEE_MINT[..TRANSFER_FEE_MINT.len() - 1]
+            ),
+            Err(ExtensionError::Truncated)
+        );
+        assert_eq!(
+            try_get_extension::<TransferFeeConfig>(&[0; 100]),
+            Err(ExtensionError::InvalidAccount)
+        );
+    }
+
+    #[test]
+    fn test_extension_error_codes() {
+        let errors = [
+            ExtensionError::InvalidAccount,
+            ExtensionError::WrongBaseState {
+                expected: BaseState::Mint,
+                found: BaseState::TokenAccount,
+            },
+            ExtensionError::NotFound,
+            ExtensionError::InvalidData,
+            ExtensionError::Truncated,
+        ];
+
+        for (i, error) in errors.iter().enumerate() {
+            assert_eq!(
+                error.into_custom(),
+                ProgramError::Custom(ExtensionError::CODE_BASE + i as u32)
+            );
+            assert_eq!(ProgramError::from(*error), error.into_custom());
+        }
+
+        // Extension error codes are not token program errors.
+        assert!(TokenError::try_from(ExtensionError::CODE_BASE).is_err());
+
+        // A token account queried for a mint extension.
+        let error = try_get_extension::<TransferFeeConfig>(&token_account_data()).unwrap_err();
+        assert_eq!(
+            error,
+            ExtensionError::WrongBaseState {
+                expected: BaseState::Mint,
+                found: BaseState::TokenAccount,
+            }
+        );
+        assert_eq!(
+            error.into_custom(),
+            ProgramError::Custom(ExtensionError::CODE_BASE + 1)
+        );
+
+        // A token account whose TLV data ends in the middle of an entry.
+        let data = token_account_data();
+        let error = try_get_extension::<PausableAccount>(&data[..data.len() - 1]).unwrap_err();
+        assert_eq!(error, ExtensionError::Truncated);
+        assert_eq!(
+            error.into_custom(),
+            ProgramError::Custom(ExtensionError::CODE_BASE + 4)
+        );
+    }
+
+    #[test]
+    fn test_get_extension_from_window() {
+        let mint = pyusd_mint_data();
+
+        let mut buf = mint.clone();
+        buf.extend_from_slice(TRANSFER_FEE_MINT);
+
+        let transfer_fee_config =
+            try_get_extension_at::<TransferFeeConfig>(&buf, 0, mint.len()).unwrap();
+        assert_eq!(transfer_fee_config.transfer_fee_config_authority, [7; 32]);
+
+        let transfer_fee_config =
+            try_get_extension_at::<TransferFeeConfig>(&buf, mint.len(), TRANSFER_FEE_MINT.len())
+                .unwrap();
+        assert_eq!(
+            transfer_fee_config
+                .newer_transfer_fee
+                .transfer_fee_basis_points
+                .get(),
+            100
+        );
+
+        // The window of the second mint ends before its last TLV entry does.
+        assert_eq!(
+            try_get_extension_at::<TransferFeeConfig>(
+                &buf,
+                mint.len(),
+                TRANSFER_FEE_MINT.len() - 1
+            ),
+            Err(ExtensionError::Truncated)
+        );
+        assert_eq!(
+            try_get_extension_at::<TransferFeeConfig>(
+                &buf,
+                mint.len(),
+                TRANSFER_FEE_MINT.len() + 1
+            ),
+            Err(ExtensionError::InvalidAccount)
+        );
+        assert_eq!(
+            try_get_extension_at::<TransferFeeConfig>(&buf, usize::MAX, 2),
+            Err(ExtensionError::InvalidAccount)
+        );
+    }
+
+    #[test]
+    fn test_get_extension_at_unaligned_offset() {
+        let mut token_group = [0; TokenGroup::LEN];
+        token_group[64..72].copy_from_slice(&3u64.to_le_bytes());
+        token_group[72..].copy_from_slice(&10u64.to_le_bytes());
+
+        let mint = account_with_extensions(
+            BaseState::Mint,
+            &crate::test_utils::mint_base(None, 0, 6, None),
+            &[
+                (
+                    ExtensionType::ScaledUiAmount,
+                    &[1; ScaledUiAmountConfig::LEN],
+                ),
+                (ExtensionType::TokenGroup, &token_group),
+            ],
+        );
+
+        // Shifting the account by up to 8 bytes misaligns any extension with
+        // an alignment greater than 1, which Miri reports.
+        for padding in 0..8 {
+            let mut buf = vec![0; padding];
+            buf.extend_from_slice(&mint);
+
+            let group = try_get_extension_at::<TokenGroup>(&buf, padding, mint.len()).unwrap();
+            assert_eq!((group.size(), group.max_size()), (3, 10));
+
+            let config =
+                try_get_extension_at::<ScaledUiAmountConfig>(&buf, padding, mint.len()).unwrap();
+            assert_eq!(config.multiplier, [1; 8]);
+        }
+    }
+
+    #[test]
+    fn test_mint_extensions_on_token_account() {
+        let data = token_account_data();
+
+        assert_wrong_base_state!(
+            &data,
+            BaseState::TokenAccount,
+            [
+                TransferFeeConfig,
+                MintCloseAuthority,
+                DefaultAccountState,
+                NonTransferable,
+                InterestBearingConfig,
+                PermanentDelegate,
+                TransferHook,
+                MetadataPointer,
+                GroupPointer,
+                TokenGroup,
+                GroupMemberPointer,
+                ScaledUiAmountConfig,
+                PausableConfig,
+            ]
+        );
+
+        #[cfg(feature = "confidential")]
+        assert_wrong_base_state!(
+            &data,
+            BaseState::TokenAccount,
+            [
+                confidential_transfer::ConfidentialTransferMint,
+                confidential_mint_burn::ConfidentialMintBurn,
+            ]
+        );
+
+        // Base token accounts without extensions are rejected the same way.
+        let base_token_account = &data[..TokenAccount::BASE_LEN];
+        assert_wrong_base_state!(
+            base_token_account,
+            BaseState::TokenAccount,
+            [TransferFeeConfig]
+        );
+    }
+
+    #[test]
+    fn test_token_account_extensions_on_mint() {
+        let data = pyusd_mint_data();
+
+        assert_wrong_base_state!(
+            &data,
+            BaseState::Mint,
+            [TransferHookAccount, PausableAccount]
+        );
+
+        #[cfg(feature = "confidential")]
+        assert_wrong_base_state!(
+            &data,
+            BaseState::Mint,
+            [confidential_transfer::ConfidentialTransferAccount]
+        );
+
+        // Base mints without extensions are rejected the same way.
+        let base_mint = &data[..Mint::BASE_LEN];
+        assert_wrong_base_state!(base_mint, BaseState::Mint, [TransferHookAccount]);
+    }
+
+    #[test]
+    fn test_extensions_on_multisig() {
+        // A multisig whose second signer key happens to look like the account
+        // type and TLV data of a mint with a `MetadataPointer`.
+        let mut data = vec![0; MULTISIG_LEN];
+        data[ACCOUNT_TYPE_OFFSET] = AccountType::Mint as u8;
+        data[EXTENSION_START_OFFSET..EXTENSION_START_OFFSET + 4].copy_from_slice(&[
+            ExtensionType::MetadataPointer as u8,
+            0,
+            64,
+            0,
+        ]);
+
+        assert_eq!(
+            try_get_extension::<MetadataPointer>(&data).map(|_| ()),
+            Err(ExtensionError::InvalidAccount)
+        );
+        assert!(ExtensionIterator::new(&data, BaseState::Mint)
+            .next()
+            .is_none());
+        assert_eq!(
+            get_extension_data(&data, BaseState::Mint, ExtensionType::MetadataPointer),
+            None
+        );
+        assert_eq!(
+            get_extension_types(&data, &mut [ExtensionType::Uninitialized; 4]),
+            Err(ExtensionError::InvalidAccount.into())
+        );
+
+        // The same data with one more byte is a valid mint with extensions.
+        data.push(0);
+        assert!(try_get_extension::<MetadataPointer>(&data).is_ok());
+    }
+
+    #[test]
+    fn test_tlv_view_strictness() {
+        let mut data = pyusd_mint_data();
+
+        let view = TlvView::new(&data, Strictness::Strict).unwrap();
+        assert_eq!(view.kind(), TokenAccountKind::Mint);
+        assert!(view.get::<MetadataPointer>().is_ok());
+        assert_eq!(
+            view.get::<TransferHookAccount>().err(),
+            Some(ExtensionError::WrongBaseState {
+                expected: BaseState::TokenAccount,
+                found: BaseState::Mint,
+            })
+        );
+        assert!(view.iter().count() > 0);
+
+        // The TLV data of an unknown account type is never parsed.
+        data[ACCOUNT_TYPE_OFFSET] = 7;
+
+        assert_eq!(
+            TlvView::new(&data, Strictness::Strict).err(),
+            Some(ProgramError::InvalidAccountData)
+        );
+        let view = TlvView::new(&data, Strictness::Lenient).unwrap();
+        assert_eq!(view.kind(), TokenAccountKind::Unknown(7));
+        assert_eq!(view.base_state(), None);
+        assert_eq!(view.iter().count(), 0);
+        assert!(!view.iter().is_truncated());
+        assert_eq!(
+            view.get::<MetadataPointer>().err(),
+            Some(ExtensionError::InvalidAccount)
+        );
+
+        // Out-of-bounds data is rejected by both strictness levels.
+        for strictness in [Strictness::Strict, Strictness::Lenient] {
+            assert_eq!(
+                TlvView::new(&data[..100], strictness).err(),
+                Some(ProgramError::InvalidAccountData)
+            );
+        }
+    }
+
+    #[test]
+    fn test_extensions_with_invalid_account_type() {
+        let mut data = pyusd_mint_data();
+
+        for account_type in [AccountType::Uninitialized as u8, 3, u8::MAX] {
+            data[ACCOUNT_TYPE_OFFSET] = account_type;
+
+            assert_eq!(
+                try_get_extension::<MetadataPointer>(&data).map(|_| ()),
+                Err(ExtensionError::InvalidAccount)
+            );
+            assert!(get_extension_from_bytes::<TransferHook>(&data).is_none());
+            assert!(ExtensionIterator::new(&data, BaseState::Mint)
+                .next()
+                .is_none());
+            assert_eq!(
+                get_extension_data(&data, BaseState::Mint, ExtensionType::MetadataPointer),
+                None
+            );
+        }
+    }
+}
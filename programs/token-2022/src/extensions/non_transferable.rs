@@ -0,0 +1,116 @@
+//! Non-transferable extension.
+
+use pinocchio::{
+    account_info::{AccountInfo, Ref},
+    program_error::ProgramError,
+};
+
+use crate::extensions::{
+    get_extension_from_account_info, get_extension_from_account_info_unchecked, BaseState,
+    Extension, ExtensionType,
+};
+
+/// Marker for mints whose tokens cannot be transferred.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct NonTransferable;
+
+unsafe impl Extension for NonTransferable {
+    const TYPE: ExtensionType = ExtensionType::NonTransferable;
+    const LEN: usize = core::mem::size_of::<Self>();
+    const BASE_STATE: BaseState = BaseState::Mint;
+}
+
+const _: () = assert!(NonTransferable::LEN == 0);
+
+/// Marker for token accounts of a non-transferable mint.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct NonTransferableAccount;
+
+unsafe impl Extension for NonTransferableAccount {
+    const TYPE: ExtensionType = ExtensionType::NonTransferableAccount;
+    const LEN: usize = core::mem::size_of::<Self>();
+    const BASE_STATE: BaseState = BaseState::TokenAccount;
+}
+
+const _: () = assert!(NonTransferableAccount::LEN == 0);
+
+impl NonTransferableAccount {
+    /// Return the `NonTransferableAccount` extension of the given token
+    /// account.
+    ///
+    /// This method performs owner validation on `AccountInfo`, safe borrowing
+    /// the account data.
+    ///
+    /// Returns the `ExtensionError` code if the account is not a token account
+    /// of a non-transferable mint.
+    /// Returns `AccountBorrowFailed` if the account data is mutably borrowed.
+    #[inline]
+    pub fn from_account_info(account_info: &AccountInfo) -> Result<Ref<Self>, ProgramError> {
+        get_extension_from_account_info(account_info)
+    }
+
+    /// Return the `NonTransferableAccount` extension of the given token
+    /// account.
+    ///
+    /// This method performs owner validation on `AccountInfo`, but does not
+    /// perform the borrow check.
+    ///
+    /// Returns the `ExtensionError` code if the account is not a token account
+    /// of a non-transferable mint.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that it is safe to borrow the account data (e.g., there are
+    /// no mutable borrows of the account data).
+    #[inline]
+    pub unsafe fn from_account_info_unchecked(
+        account_info: &AccountInfo,
+    ) -> Result<&Self, ProgramError> {
+        get_extension_from_account_info_unchecked(account_info)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        extensions::ExtensionError,
+        test_utils::{account_with_extensions, token_account_base, TestAccount},
+    };
+
+    #[test]
+    fn test_non_transferable_account_from_account_info() {
+        // The zero-length `NonTransferableAccount` entry ends the data.
+        let data = account_with_extensions(
+            BaseState::TokenAccount,
+            &token_account_base(&[1; 32], &[2; 32], 1),
+            &[
+                (ExtensionType::ImmutableOwner, &[]),
+                (ExtensionType::NonTransferableAccount, &[]),
+            ],
+        );
+        assert_eq!(&data[data.len() - 4..], &[13, 0, 0, 0]);
+
+        let account = TestAccount::with_data([1; 32], crate::ID, &data).info();
+        assert_eq!(
+            unsafe { NonTransferableAccount::from_account_info_unchecked(&account) },
+            Ok(&NonTransferableAccount)
+        );
+
+        let data = account_with_extensions(
+            BaseState::TokenAccount,
+            &token_account_base(&[1; 32], &[2; 32], 1),
+            &[
+                (ExtensionType::TransferFeeAmount, &[0; 8]),
+                (ExtensionType::ImmutableOwner, &[]),
+            ],
+        );
+        let account = TestAccount::with_data([1; 32], crate::ID, &data).info();
+        assert_eq!(
+            unsafe { NonTransferableAccount::from_account_info_unchecked(&account) },
+            Err(ExtensionError::NotFound.into())
+        );
+    }
+}
@@ -0,0 +1,279 @@
+//! Pausable extension.
+
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Instruction, Signer},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    ProgramResult,
+};
+
+use crate::{
+    cpi::{invoke_signed_with_bounds, CpiAccounts, IxData},
+    extensions::{BaseState, Extension, ExtensionDiscriminator, ExtensionType},
+    instructions::MAX_MULTISIG_SIGNERS,
+    pod::PodBool,
+};
+
+/// Instruction discriminators of the pausable extension.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PausableInstruction {
+    Initialize = 0,
+    Pause = 1,
+    Resume = 2,
+}
+
+/// Pause configuration of a mint.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PausableConfig {
+    /// Authority that can pause or resume activity on the mint.
+    pub authority: Pubkey,
+    /// Whether minting, burning and transferring are paused.
+    pub paused: PodBool,
+}
+
+unsafe impl Extension for PausableConfig {
+    const TYPE: ExtensionType = ExtensionType::Pausable;
+    const LEN: usize = core::mem::size_of::<Self>();
+    const BASE_STATE: BaseState = BaseState::Mint;
+}
+
+// The layout must match the 33 bytes of the TLV entry written by the token
+// program.
+const _: () = assert!(PausableConfig::LEN == 33);
+
+impl PausableConfig {
+    /// Return whether minting, burning and transferring are paused.
+    ///
+    /// Any nonzero value is treated as paused.
+    #[inline(always)]
+    pub fn paused(&self) -> bool {
+        self.paused.get()
+    }
+}
+
+/// Marker for token accounts of a pausable mint.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PausableAccount;
+
+unsafe impl Extension for PausableAccount {
+    const TYPE: ExtensionType = ExtensionType::PausableAccount;
+    const LEN: usize = core::mem::size_of::<Self>();
+    const BASE_STATE: BaseState = BaseState::TokenAccount;
+}
+
+const _: () = assert!(PausableAccount::LEN == 0);
+
+/// Pause minting, burning and transferring for the mint.
+///
+/// ### Accounts:
+///   * Single authority
+///   0. `[WRITE]` The mint.
+///   1. `[SIGNER]` The pause authority.
+///
+///   * Multisignature authority
+///   0. `[WRITE]` The mint.
+///   1. `[]` The multisig pause authority.
+///   2. `..2+M` `[SIGNER]` M signer accounts.
+pub struct Pause<'a, 'b> {
+    /// Mint Account.
+    pub mint: &'a AccountInfo,
+    /// The pause authority.
+    pub pause_authority: &'a AccountInfo,
+    /// Signer accounts when the pause authority is a multisig.
+    pub signers: &'b [&'a AccountInfo],
+}
+
+impl Pause<'_, '_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    #[inline(always)]
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        invoke_pausable(
+            PausableInstruction::Pause,
+            self.mint,
+            self.pause_authority,
+            self.signers,
+            signers,
+        )
+    }
+}
+
+/// Resume minting, burning and transferring for the mint.
+///
+/// ### Accounts:
+///   * Single authority
+///   0. `[WRITE]` The mint.
+///   1. `[SIGNER]` The pause authority.
+///
+///   * Multisignature authority
+///   0. `[WRITE]` The mint.
+///   1. `[]` The multisig pause authority.
+///   2. `..2+M` `[SIGNER]` M signer accounts.
+pub struct Resume<'a, 'b> {
+    /// Mint Account.
+    pub mint: &'a AccountInfo,
+    /// The pause authority.
+    pub pause_authority: &'a AccountInfo,
+    /// Signer accounts when the pause authority is a multisig.
+    pub signers: &'b [&'a AccountInfo],
+}
+
+impl Resume<'_, '_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    #[inline(always)]
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        invoke_pausable(
+            PausableInstruction::Resume,
+            self.mint,
+            self.pause_authority,
+            self.signers,
+            signers,
+        )
+    }
+}
+
+#[inline(always)]
+fn invoke_pausable(
+    instruction: PausableInstruction,
+    mint: &AccountInfo,
+    pause_authority: &AccountInfo,
+    multisig_signers: &[&AccountInfo],
+    signers: &[Signer],
+) -> ProgramResult {
+    if multisig_signers.len() > MAX_MULTISIG_SIGNERS {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Account metadata
+    let mut accounts = CpiAccounts::<{ 2 + MAX_MULTISIG_SIGNERS }>::new();
+
+    accounts.push(mint, true, false);
+    accounts.push_authority(pause_authority, multisig_signers);
+
+    // Instruction data layout:
+    // -  [0]: extension instruction discriminator (1 byte, u8)
+    // -  [1]: instruction discriminator (1 byte, u8)
+    let mut instruction_data = IxData::<2>::new();
+
+    instruction_data.write_u8(ExtensionDiscriminator::Pausable as u8);
+    instruction_data.write_u8(instruction as u8);
+
+    let instruction = Instruction {
+        program_id: &crate::ID,
+        accounts: accounts.metas(),
+        data: instruction_data.finish(),
+    };
+
+    invoke_signed_with_bounds::<{ 2 + MAX_MULTISIG_SIGNERS }>(
+        &instruction,
+        accounts.infos(),
+        signers,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::vec;
+
+    use super::*;
+    use crate::{
+        extensions::try_from_bytes_ref,
+        test_utils::{take_captured, CapturedMeta, TestAccount},
+    };
+
+    #[test]
+    fn test_paused() {
+        let mut data = [7; PausableConfig::LEN];
+
+        for (value, paused) in [(0, false), (1, true), (2, true), (u8::MAX, true)] {
+            data[32] = value;
+            let config = try_from_bytes_ref::<PausableConfig>(&data).unwrap();
+            assert_eq!(config.paused(), paused);
+            assert_eq!(config.authority, [7; 32]);
+        }
+    }
+
+    #[test]
+    fn test_pause_and_resume() {
+        let mint = TestAccount::new([1; 32]).writable().info();
+        let pause_authority = TestAccount::new([2; 32]).signer().info();
+
+        Pause {
+            mint: &mint,
+            pause_authority: &pause_authority,
+            signers: &[],
+        }
+        .invoke()
+        .unwrap();
+
+        Resume {
+            mint: &mint,
+            pause_authority: &pause_authority,
+            signers: &[],
+        }
+        .invoke()
+        .unwrap();
+
+        let instructions = take_captured();
+
+        assert_eq!(instructions[0].data, vec![44, 1]);
+        assert_eq!(instructions[1].data, vec![44, 2]);
+
+        for instruction in instructions {
+            assert_eq!(
+                instruction.accounts,
+                vec![
+                    CapturedMeta::new([1; 32], true, false),
+                    CapturedMeta::new([2; 32], false, true),
+                ]
+            );
+            // The pause authority must be passed as both meta and info.
+            assert_eq!(instruction.account_infos, vec![[1; 32], [2; 32]]);
+        }
+    }
+
+    #[test]
+    fn test_pause_multisig() {
+        let mint = TestAccount::new([1; 32]).writable().info();
+        let pause_authority = TestAccount::new([2; 32]).info();
+        let signer_1 = TestAccount::new([3; 32]).signer().info();
+        let signer_2 = TestAccount::new([4; 32]).signer().info();
+
+        Pause {
+            mint: &mint,
+            pause_authority: &pause_authority,
+            signers: &[&signer_1, &signer_2],
+        }
+        .invoke()
+        .unwrap();
+
+        let instructions = take_captured();
+
+        assert_eq!(
+            instructions[0].accounts,
+            vec![
+                CapturedMeta::new([1; 32], true, false),
+                CapturedMeta::new([2; 32], false, false),
+                CapturedMeta::new([3; 32], false, true),
+                CapturedMeta::new([4; 32], false, true),
+            ]
+        );
+        assert_eq!(
+            instructions[0].account_infos,
+            vec![[1; 32], [2; 32], [3; 32], [4; 32]]
+        );
+    }
+}
@@ -0,0 +1,51 @@
+//! Permanent delegate extension.
+
+use pinocchio::pubkey::Pubkey;
+
+use crate::{
+    extensions::{BaseState, Extension, ExtensionType},
+    pod::non_zero,
+};
+
+/// Permanent delegate of all token accounts of a mint.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PermanentDelegate {
+    /// Optional permanent delegate for transferring or burning tokens.
+    pub delegate: Pubkey,
+}
+
+unsafe impl Extension for PermanentDelegate {
+    const TYPE: ExtensionType = ExtensionType::PermanentDelegate;
+    const LEN: usize = core::mem::size_of::<Self>();
+    const BASE_STATE: BaseState = BaseState::Mint;
+}
+
+const _: () = assert!(PermanentDelegate::LEN == 32);
+
+impl PermanentDelegate {
+    /// Return the permanent delegate, or `None` if the mint has none.
+    #[inline(always)]
+    pub fn delegate(&self) -> Option<&Pubkey> {
+        non_zero(&self.delegate)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        extensions::{try_from_bytes_ref, try_get_extension},
+        test_utils::{pyusd_mint_data, PYUSD_UPDATE_AUTHORITY},
+    };
+
+    #[test]
+    fn test_delegate() {
+        let data = pyusd_mint_data();
+        let extension = try_get_extension::<PermanentDelegate>(&data).unwrap();
+        assert_eq!(extension.delegate(), Some(&PYUSD_UPDATE_AUTHORITY));
+
+        let extension = try_from_bytes_ref::<PermanentDelegate>(&[0; 32]).unwrap();
+        assert_eq!(extension.delegate(), None);
+    }
+}
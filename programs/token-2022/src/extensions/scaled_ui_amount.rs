@@ -0,0 +1,452 @@
+//! Scaled UI amount extension.
+
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Instruction, Signer},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::clock::UnixTimestamp,
+    ProgramResult,
+};
+
+use crate::{
+    cpi::{invoke_signed_with_bounds, CpiAccounts, IxData},
+    extensions::{BaseState, Extension, ExtensionDiscriminator, ExtensionType},
+    instructions::MAX_MULTISIG_SIGNERS,
+    math::pow10,
+    pod::PodI64,
+};
+
+/// Maximum number of accounts of the `UpdateMultiplier` instruction.
+const MAX_UPDATE_MULTIPLIER_ACCOUNTS: usize = 2 + MAX_MULTISIG_SIGNERS;
+
+/// Instruction discriminators of the scaled UI amount extension.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ScaledUiAmountInstruction {
+    Initialize = 0,
+    UpdateMultiplier = 1,
+}
+
+/// UI amount multiplier configuration of a mint.
+///
+/// The derived `PartialEq` compares the raw bytes of the multipliers, so
+/// `0.0` and `-0.0` differ and identical NaNs are equal; use
+/// [`ScaledUiAmountConfig::multipliers_eq`] to compare their values.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ScaledUiAmountConfig {
+    /// Authority that can set the scaling amount and authority.
+    pub authority: Pubkey,
+    /// Amount to multiply raw amounts by, outside of the decimal (`f64`).
+    pub multiplier: [u8; 8],
+    /// Unix timestamp at which `new_multiplier` comes into effect.
+    pub new_multiplier_effective_timestamp: PodI64,
+    /// Next multiplier, once `new_multiplier_effective_timestamp` is reached
+    /// (`f64`).
+    pub new_multiplier: [u8; 8],
+}
+
+unsafe impl Extension for ScaledUiAmountConfig {
+    const TYPE: ExtensionType = ExtensionType::ScaledUiAmount;
+    const LEN: usize = core::mem::size_of::<Self>();
+    const BASE_STATE: BaseState = BaseState::Mint;
+}
+
+const _: () = assert!(ScaledUiAmountConfig::LEN == 56);
+
+impl ScaledUiAmountConfig {
+    /// Return the multiplier used before `new_multiplier_effective_timestamp`.
+    #[inline(always)]
+    pub fn multiplier(&self) -> f64 {
+        f64::from_le_bytes(self.multiplier)
+    }
+
+    /// Return the multiplier used from `new_multiplier_effective_timestamp`.
+    #[inline(always)]
+    pub fn new_multiplier(&self) -> f64 {
+        f64::from_le_bytes(self.new_multiplier)
+    }
+
+    /// Return whether both configurations have numerically equal
+    /// multipliers, following `f64` comparison rules.
+    #[inline]
+    pub fn multipliers_eq(&self, other: &Self) -> bool {
+        self.multiplier() == other.multiplier() && self.new_multiplier() == other.new_multiplier()
+    }
+
+    /// Return the multiplier in effect at `unix_timestamp`.
+    #[inline]
+    pub fn current_multiplier(&self, unix_timestamp: UnixTimestamp) -> f64 {
+        if unix_timestamp >= self.new_multiplier_effective_timestamp.get() {
+            self.new_multiplier()
+        } else {
+            self.multiplier()
+        }
+    }
+
+    /// Return the factor converting raw amounts to UI amounts at
+    /// `unix_timestamp`.
+    ///
+    /// Returns `None` if the multiplier in effect is not finite and positive,
+    /// which the token program never stores.
+    #[inline]
+    fn total_multiplier(&self, decimals: u8, unix_timestamp: UnixTimestamp) -> Option<f64> {
+        let multiplier = self.current_multiplier(unix_timestamp);
+        validate_multiplier(multiplier).ok()?;
+        Some(multiplier / pow10(decimals))
+    }
+
+    /// Convert a raw amount to its UI amount at `unix_timestamp`.
+    ///
+    /// Amounts that overflow an `f64` are saturated to `u64::MAX`.
+    ///
+    /// Returns `None` if the multiplier in effect is not finite and positive.
+    pub fn amount_to_ui_amount(
+        &self,
+        amount: u64,
+        decimals: u8,
+        unix_timestamp: UnixTimestamp,
+    ) -> Option<f64> {
+        let ui_amount = amount as f64 * self.total_multiplier(decimals, unix_timestamp)?;
+
+        if ui_amount.is_infinite() {
+            Some(u64::MAX as f64)
+        } else {
+            Some(ui_amount)
+        }
+    }
+
+    /// Convert a UI amount to its raw amount at `unix_timestamp`, rounded to
+    /// the nearest integer.
+    ///
+    /// Returns `InvalidAccountData` if the multiplier in effect is not finite
+    /// and positive, and `InvalidArgument` if the raw amount is not a valid
+    /// `u64`.
+    pub fn try_ui_amount_to_amount(
+        &self,
+        ui_amount: f64,
+        decimals: u8,
+        unix_timestamp: UnixTimestamp,
+    ) -> Result<u64, ProgramError> {
+        let total_multiplier = self
+            .total_multiplier(decimals, unix_timestamp)
+            .ok_or(ProgramError::InvalidAccountData)?;
+        let amount = ui_amount / total_multiplier;
+
+        // Negated comparison so that `NaN` is rejected.
+        if !(amount >= 0.0 && amount <= u64::MAX as f64) {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        Ok((amount + 0.5) as u64)
+    }
+}
+
+/// Initialize the scaled UI amount extension on a new mint.
+///
+/// Must be invoked before `InitializeMint`, since the token program rejects
+/// extension initialization on an initialized mint.
+///
+/// ### Accounts:
+///   0. `[WRITE]` The mint to initialize.
+pub struct Initialize<'a> {
+    /// Mint Account.
+    pub mint: &'a AccountInfo,
+    /// Authority that can update the multiplier.
+    pub authority: Option<&'a Pubkey>,
+    /// Initial multiplier, which must be finite and positive.
+    pub multiplier: f64,
+}
+
+impl Initialize<'_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    /// Invoke the instruction.
+    ///
+    /// Returns `InvalidInstructionData` if the multiplier is not finite and
+    /// positive.
+    #[inline(always)]
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        validate_multiplier(self.multiplier)?;
+
+        // Account metadata
+        let mut accounts = CpiAccounts::<1>::new();
+
+        accounts.push(self.mint, true, false);
+
+        // Instruction data layout:
+        // -  [0]: extension instruction discriminator (1 byte, u8)
+        // -  [1]: instruction discriminator (1 byte, u8)
+        // -  [2..34]: authority (32 bytes, Pubkey, zeroed if `None`)
+        // -  [34..42]: multiplier (8 bytes, f64)
+        let mut instruction_data = IxData::<42>::new();
+
+        instruction_data.write_u8(ExtensionDiscriminator::ScaledUiAmount as u8);
+        instruction_data.write_u8(ScaledUiAmountInstruction::Initialize as u8);
+        instruction_data.write_pubkey(self.authority.unwrap_or(&Pubkey::default()));
+        instruction_data.write_bytes(&self.multiplier.to_le_bytes());
+
+        let instruction = Instruction {
+            program_id: &crate::ID,
+            accounts: accounts.metas(),
+            data: instruction_data.finish(),
+        };
+
+        invoke_signed_with_bounds::<1>(&instruction, accounts.infos(), signers)
+    }
+}
+
+/// Update the multiplier of the mint, effective at the given timestamp.
+///
+/// ### Accounts:
+///   * Single authority
+///   0. `[WRITE]` The mint.
+///   1. `[SIGNER]` The multiplier authority.
+///
+///   * Multisignature authority
+///   0. `[WRITE]` The mint.
+///   1. `[]` The multiplier multisig authority.
+///   2. `..2+M` `[SIGNER]` M signer accounts.
+pub struct UpdateMultiplier<'a, 'b> {
+    /// Mint Account.
+    pub mint: &'a AccountInfo,
+    /// The multiplier authority.
+    pub authority: &'a AccountInfo,
+    /// Signer accounts when the authority is a multisig.
+    pub signers: &'b [&'a AccountInfo],
+    /// New multiplier, which must be finite and positive.
+    pub multiplier: f64,
+    /// Unix timestamp at which the new multiplier takes effect.
+    pub effective_timestamp: i64,
+}
+
+impl UpdateMultiplier<'_, '_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    /// Invoke the instruction.
+    ///
+    /// Returns `InvalidInstructionData` if the multiplier is not finite and
+    /// positive.
+    #[inline(always)]
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        if self.signers.len() > MAX_MULTISIG_SIGNERS {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        validate_multiplier(self.multiplier)?;
+
+        // Account metadata
+        let mut accounts = CpiAccounts::<MAX_UPDATE_MULTIPLIER_ACCOUNTS>::new();
+
+        accounts.push(self.mint, true, false);
+        accounts.push_authority(self.authority, self.signers);
+
+        // Instruction data layout:
+        // -  [0]: extension instruction discriminator (1 byte, u8)
+        // -  [1]: instruction discriminator (1 byte, u8)
+        // -  [2..10]: multiplier (8 bytes, f64)
+        // -  [10..18]: effective_timestamp (8 bytes, i64)
+        let mut instruction_data = IxData::<18>::new();
+
+        instruction_data.write_u8(ExtensionDiscriminator::ScaledUiAmount as u8);
+        instruction_data.write_u8(ScaledUiAmountInstruction::UpdateMultiplier as u8);
+        instruction_data.write_bytes(&self.multiplier.to_le_bytes());
+        instruction_data.write_i64_le(self.effective_timestamp);
+
+        let instruction = Instruction {
+            program_id: &crate::ID,
+            accounts: accounts.metas(),
+            data: instruction_data.finish(),
+        };
+
+        invoke_signed_with_bounds::<MAX_UPDATE_MULTIPLIER_ACCOUNTS>(
+            &instruction,
+            accounts.infos(),
+            signers,
+        )
+    }
+}
+
+/// Check that a multiplier is accepted by the token program.
+#[inline(always)]
+fn validate_multiplier(multiplier: f64) -> Result<(), ProgramError> {
+    if multiplier.is_finite() && multiplier > 0.0 {
+        Ok(())
+    } else {
+        Err(ProgramError::InvalidInstructionData)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::vec;
+
+    use super::*;
+    use crate::test_utils::{take_single, CapturedMeta, TestAccount};
+
+    const INVALID_MULTIPLIERS: [f64; 6] =
+        [f64::NAN, f64::INFINITY, f64::NEG_INFINITY, 0.0, -0.0, -1.5];
+
+    fn config(
+        multiplier: f64,
+        effective_timestamp: i64,
+        new_multiplier: f64,
+    ) -> ScaledUiAmountConfig {
+        ScaledUiAmountConfig {
+            authority: [1; 32],
+            multiplier: multiplier.to_le_bytes(),
+            new_multiplier_effective_timestamp: effective_timestamp.into(),
+            new_multiplier: new_multiplier.to_le_bytes(),
+        }
+    }
+
+    #[test]
+    fn test_current_multiplier() {
+        let config = config(2.0, 100, 0.5);
+
+        assert_eq!(config.current_multiplier(i64::MIN), 2.0);
+        assert_eq!(config.current_multiplier(99), 2.0);
+        assert_eq!(config.current_multiplier(100), 0.5);
+        assert_eq!(config.current_multiplier(i64::MAX), 0.5);
+    }
+
+    #[test]
+    fn test_multipliers_eq() {
+        assert!(config(2.0, 100, 0.5).multipliers_eq(&config(2.0, 200, 0.5)));
+        assert!(!config(2.0, 100, 0.5).multipliers_eq(&config(2.0, 100, 1.0)));
+
+        // Numerically equal multipliers with different bytes.
+        assert_ne!(config(0.0, 100, 1.0), config(-0.0, 100, 1.0));
+        assert!(config(0.0, 100, 1.0).multipliers_eq(&config(-0.0, 100, 1.0)));
+
+        // Identical NaN bytes are not numerically equal.
+        assert_eq!(config(f64::NAN, 100, 1.0), config(f64::NAN, 100, 1.0));
+        assert!(!config(f64::NAN, 100, 1.0).multipliers_eq(&config(f64::NAN, 100, 1.0)));
+    }
+
+    #[test]
+    fn test_amount_to_ui_amount() {
+        let config = config(2.0, 100, 0.5);
+
+        assert_eq!(config.amount_to_ui_amount(0, 6, 99), Some(0.0));
+        assert_eq!(config.amount_to_ui_amount(1_500_000, 6, 99), Some(3.0));
+        assert_eq!(config.amount_to_ui_amount(1_500_000, 6, 100), Some(0.75));
+        assert_eq!(config.amount_to_ui_amount(3, 0, 100), Some(1.5));
+        assert_eq!(
+            config.amount_to_ui_amount(u64::MAX, 0, 99),
+            Some(u64::MAX as f64 * 2.0)
+        );
+        assert_eq!(
+            self::config(f64::MAX, 0, f64::MAX).amount_to_ui_amount(u64::MAX, 0, 0),
+            Some(u64::MAX as f64)
+        );
+
+        for multiplier in INVALID_MULTIPLIERS {
+            let config = self::config(2.0, 100, multiplier);
+            assert_eq!(config.amount_to_ui_amount(1, 0, 99), Some(2.0));
+            assert_eq!(config.amount_to_ui_amount(1, 0, 100), None);
+        }
+    }
+
+    #[test]
+    fn test_try_ui_amount_to_amount() {
+        let config = config(2.0, 100, 0.5);
+
+        assert_eq!(config.try_ui_amount_to_amount(3.0, 6, 99), Ok(1_500_000));
+        assert_eq!(config.try_ui_amount_to_amount(0.75, 6, 100), Ok(1_500_000));
+        // Rounded to the nearest raw amount.
+        assert_eq!(config.try_ui_amount_to_amount(0.7, 0, 100), Ok(1));
+        assert_eq!(config.try_ui_amount_to_amount(0.2, 0, 100), Ok(0));
+        assert_eq!(config.try_ui_amount_to_amount(0.0, 0, 100), Ok(0));
+
+        for ui_amount in [-1.0, f64::NAN, f64::INFINITY, u64::MAX as f64 * 4.0] {
+            assert_eq!(
+                config.try_ui_amount_to_amount(ui_amount, 0, 99),
+                Err(ProgramError::InvalidArgument)
+            );
+        }
+
+        for multiplier in INVALID_MULTIPLIERS {
+            assert_eq!(
+                self::config(multiplier, 100, 2.0).try_ui_amount_to_amount(1.0, 0, 0),
+                Err(ProgramError::InvalidAccountData)
+            );
+        }
+    }
+
+    #[test]
+    fn test_initialize() {
+        let mint = TestAccount::new([1; 32]).writable().info();
+
+        let mut initialize = Initialize {
+            mint: &mint,
+            authority: Some(&[2; 32]),
+            multiplier: 1.5,
+        };
+        initialize.invoke().unwrap();
+
+        let instruction = take_single();
+
+        let mut data = vec![43, 0];
+        data.extend_from_slice(&[2; 32]);
+        data.extend_from_slice(&1.5f64.to_le_bytes());
+
+        assert_eq!(instruction.data, data);
+        assert_eq!(
+            instruction.accounts,
+            vec![CapturedMeta::new([1; 32], true, false)]
+        );
+
+        for multiplier in INVALID_MULTIPLIERS {
+            initialize.multiplier = multiplier;
+            assert_eq!(
+                initialize.invoke(),
+                Err(ProgramError::InvalidInstructionData)
+            );
+        }
+    }
+
+    #[test]
+    fn test_update_multiplier() {
+        let mint = TestAccount::new([1; 32]).writable().info();
+        let authority = TestAccount::new([2; 32]).signer().info();
+
+        let mut update = UpdateMultiplier {
+            mint: &mint,
+            authority: &authority,
+            signers: &[],
+            multiplier: 2.0,
+            effective_timestamp: -1,
+        };
+        update.invoke().unwrap();
+
+        let instruction = take_single();
+
+        let mut data = vec![43, 1];
+        data.extend_from_slice(&2.0f64.to_le_bytes());
+        data.extend_from_slice(&[0xff; 8]);
+
+        assert_eq!(instruction.data, data);
+        assert_eq!(
+            instruction.accounts,
+            vec![
+                CapturedMeta::new([1; 32], true, false),
+                CapturedMeta::new([2; 32], false, true),
+            ]
+        );
+
+        for multiplier in INVALID_MULTIPLIERS {
+            update.multiplier = multiplier;
+            assert_eq!(update.invoke(), Err(ProgramError::InvalidInstructionData));
+        }
+    }
+}
@@ -0,0 +1,110 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Instruction, Signer},
+    pubkey::Pubkey,
+    ProgramResult,
+};
+
+use crate::{
+    cpi::{invoke_signed_with_bounds, CpiAccounts, IxData},
+    extensions::token_group::INITIALIZE_GROUP_DISCRIMINATOR,
+};
+
+/// Initialize a new token group.
+///
+/// In Token-2022 the group is stored in the mint, which must have a group
+/// pointer to itself, so `group` and `mint` are the same account.
+///
+/// ### Accounts:
+///   0. `[WRITE]` The group (the mint).
+///   1. `[]` The mint.
+///   2. `[SIGNER]` The mint authority.
+pub struct InitializeGroup<'a> {
+    /// Group Account.
+    pub group: &'a AccountInfo,
+    /// Mint Account.
+    pub mint: &'a AccountInfo,
+    /// Mint authority.
+    pub mint_authority: &'a AccountInfo,
+    /// Authority that can update the group.
+    pub update_authority: Option<&'a Pubkey>,
+    /// Maximum number of group members.
+    pub max_size: u64,
+}
+
+impl InitializeGroup<'_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    #[inline(always)]
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        // Account metadata
+        let mut accounts = CpiAccounts::<3>::new();
+
+        accounts.push(self.group, true, false);
+        accounts.push(self.mint, false, false);
+        accounts.push(self.mint_authority, false, true);
+
+        // Instruction data layout:
+        // -  [0..8]: instruction discriminator (8 bytes, [u8; 8])
+        // -  [8..40]: update_authority (32 bytes, Pubkey, zeroed if `None`)
+        // -  [40..48]: max_size (8 bytes, u64)
+        let mut instruction_data = IxData::<48>::new();
+
+        instruction_data.write_bytes(&INITIALIZE_GROUP_DISCRIMINATOR);
+        instruction_data.write_pubkey(self.update_authority.unwrap_or(&Pubkey::default()));
+        instruction_data.write_u64_le(self.max_size);
+
+        let instruction = Instruction {
+            program_id: &crate::ID,
+            accounts: accounts.metas(),
+            data: instruction_data.finish(),
+        };
+
+        invoke_signed_with_bounds::<3>(&instruction, accounts.infos(), signers)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::vec;
+
+    use super::*;
+    use crate::test_utils::{take_single, CapturedMeta, TestAccount};
+
+    #[test]
+    fn test_initialize_group() {
+        let mint = TestAccount::new([1; 32]).writable().info();
+        let mint_authority = TestAccount::new([2; 32]).signer().info();
+
+        InitializeGroup {
+            group: &mint,
+            mint: &mint,
+            mint_authority: &mint_authority,
+            update_authority: Some(&[3; 32]),
+            max_size: 10,
+        }
+        .invoke()
+        .unwrap();
+
+        let instruction = take_single();
+
+        let mut data = vec![121, 113, 108, 39, 54, 51, 0, 4];
+        data.extend_from_slice(&[3; 32]);
+        data.extend_from_slice(&10u64.to_le_bytes());
+
+        assert_eq!(instruction.data, data);
+        assert_eq!(
+            instruction.accounts,
+            vec![
+                CapturedMeta::new([1; 32], true, false),
+                CapturedMeta::new([1; 32], false, false),
+                CapturedMeta::new([2; 32], false, true),
+            ]
+        );
+    }
+}
@@ -0,0 +1,109 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Instruction, Signer},
+    ProgramResult,
+};
+
+use crate::{
+    cpi::{invoke_signed_with_bounds, CpiAccounts},
+    extensions::token_group::INITIALIZE_MEMBER_DISCRIMINATOR,
+};
+
+/// Initialize a new member of a token group.
+///
+/// In Token-2022 the member is stored in the member mint, which must have a
+/// group member pointer to itself, so `member` and `member_mint` are the
+/// same account.
+///
+/// ### Accounts:
+///   0. `[WRITE]` The member (the member mint).
+///   1. `[]` The member mint.
+///   2. `[SIGNER]` The member mint authority.
+///   3. `[WRITE]` The group.
+///   4. `[SIGNER]` The group update authority.
+pub struct InitializeMember<'a> {
+    /// Member Account.
+    pub member: &'a AccountInfo,
+    /// Member mint Account.
+    pub member_mint: &'a AccountInfo,
+    /// Mint authority of the member mint.
+    pub member_mint_authority: &'a AccountInfo,
+    /// Group Account.
+    pub group: &'a AccountInfo,
+    /// Update authority of the group.
+    pub group_update_authority: &'a AccountInfo,
+}
+
+impl InitializeMember<'_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    #[inline(always)]
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        // Account metadata
+        let mut accounts = CpiAccounts::<5>::new();
+
+        accounts.push(self.member, true, false);
+        accounts.push(self.member_mint, false, false);
+        accounts.push(self.member_mint_authority, false, true);
+        accounts.push(self.group, true, false);
+        accounts.push(self.group_update_authority, false, true);
+
+        // Instruction data layout:
+        // -  [0..8]: instruction discriminator (8 bytes, [u8; 8])
+        let instruction = Instruction {
+            program_id: &crate::ID,
+            accounts: accounts.metas(),
+            data: &INITIALIZE_MEMBER_DISCRIMINATOR,
+        };
+
+        invoke_signed_with_bounds::<5>(&instruction, accounts.infos(), signers)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::vec;
+
+    use super::*;
+    use crate::test_utils::{take_single, CapturedMeta, TestAccount};
+
+    #[test]
+    fn test_initialize_member() {
+        let member_mint = TestAccount::new([1; 32]).writable().info();
+        let member_mint_authority = TestAccount::new([2; 32]).signer().info();
+        let group = TestAccount::new([3; 32]).writable().info();
+        let group_update_authority = TestAccount::new([4; 32]).signer().info();
+
+        InitializeMember {
+            member: &member_mint,
+            member_mint: &member_mint,
+            member_mint_authority: &member_mint_authority,
+            group: &group,
+            group_update_authority: &group_update_authority,
+        }
+        .invoke()
+        .unwrap();
+
+        let instruction = take_single();
+
+        assert_eq!(
+            instruction.data,
+            vec![152, 32, 222, 176, 223, 237, 116, 134]
+        );
+        assert_eq!(
+            instruction.accounts,
+            vec![
+                CapturedMeta::new([1; 32], true, false),
+                CapturedMeta::new([1; 32], false, false),
+                CapturedMeta::new([2; 32], false, true),
+                CapturedMeta::new([3; 32], true, false),
+                CapturedMeta::new([4; 32], false, true),
+            ]
+        );
+    }
+}
@@ -0,0 +1,250 @@
+//! Token group extension.
+//!
+//! The token group instructions follow the SPL token-group interface, which
+//! identifies instructions with an 8-byte discriminator instead of the
+//! Token-2022 instruction discriminators.
+
+mod initialize_group;
+mod initialize_member;
+mod update_group_authority;
+mod update_group_max_size;
+
+pub use initialize_group::*;
+pub use initialize_member::*;
+pub use update_group_authority::*;
+pub use update_group_max_size::*;
+
+use pinocchio::{
+    account_info::{AccountInfo, Ref},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use crate::{
+    extensions::{
+        get_extension_from_account_info, get_extension_from_account_info_unchecked, BaseState,
+        Extension, ExtensionType,
+    },
+    pod::{non_zero, PodU64},
+};
+
+/// Discriminator of the `InitializeGroup` instruction, the first 8 bytes of
+/// the SHA-256 hash of `spl_token_group_interface:initialize_token_group`.
+pub const INITIALIZE_GROUP_DISCRIMINATOR: [u8; 8] = [121, 113, 108, 39, 54, 51, 0, 4];
+
+/// Discriminator of the `UpdateGroupMaxSize` instruction, the first 8 bytes
+/// of the SHA-256 hash of `spl_token_group_interface:update_group_max_size`.
+pub const UPDATE_GROUP_MAX_SIZE_DISCRIMINATOR: [u8; 8] = [108, 37, 171, 143, 248, 30, 18, 110];
+
+/// Discriminator of the `UpdateGroupAuthority` instruction, the first 8
+/// bytes of the SHA-256 hash of `spl_token_group_interface:update_authority`.
+pub const UPDATE_GROUP_AUTHORITY_DISCRIMINATOR: [u8; 8] = [161, 105, 88, 1, 237, 221, 216, 203];
+
+/// Discriminator of the `InitializeMember` instruction, the first 8 bytes of
+/// the SHA-256 hash of `spl_token_group_interface:initialize_member`.
+pub const INITIALIZE_MEMBER_DISCRIMINATOR: [u8; 8] = [152, 32, 222, 176, 223, 237, 116, 134];
+
+/// Group configuration stored in a mint.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TokenGroup {
+    /// Authority that can sign to update the group.
+    pub update_authority: Pubkey,
+    /// Associated mint, used to check that the group is stored in the
+    /// correct account.
+    pub mint: Pubkey,
+    /// Current number of group members.
+    pub size: PodU64,
+    /// Maximum number of group members.
+    pub max_size: PodU64,
+}
+
+unsafe impl Extension for TokenGroup {
+    const TYPE: ExtensionType = ExtensionType::TokenGroup;
+    const LEN: usize = core::mem::size_of::<Self>();
+    const BASE_STATE: BaseState = BaseState::Mint;
+}
+
+const _: () = assert!(TokenGroup::LEN == 80);
+
+impl TokenGroup {
+    /// Return the authority that can sign to update the group, or `None` if
+    /// the group cannot be updated.
+    #[inline(always)]
+    pub fn update_authority(&self) -> Option<&Pubkey> {
+        non_zero(&self.update_authority)
+    }
+
+    /// Return the current number of group members.
+    #[inline(always)]
+    pub fn size(&self) -> u64 {
+        self.size.get()
+    }
+
+    /// Return the maximum number of group members.
+    #[inline(always)]
+    pub fn max_size(&self) -> u64 {
+        self.max_size.get()
+    }
+
+    /// Return whether the group has reached its maximum number of members.
+    #[inline(always)]
+    pub fn is_full(&self) -> bool {
+        self.size() >= self.max_size()
+    }
+
+    /// Return the number of members that can still be added to the group.
+    ///
+    /// Saturates to `0` if the size exceeds the maximum size.
+    #[inline(always)]
+    pub fn remaining_capacity(&self) -> u64 {
+        self.max_size().saturating_sub(self.size())
+    }
+}
+
+/// Group membership stored in a mint.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TokenGroupMember {
+    /// Associated mint, used to check that the member is stored in the
+    /// correct account.
+    pub mint: Pubkey,
+    /// Group the mint belongs to.
+    pub group: Pubkey,
+    /// Number of the member in the group.
+    pub member_number: PodU64,
+}
+
+unsafe impl Extension for TokenGroupMember {
+    const TYPE: ExtensionType = ExtensionType::TokenGroupMember;
+    const LEN: usize = core::mem::size_of::<Self>();
+    const BASE_STATE: BaseState = BaseState::Mint;
+}
+
+const _: () = assert!(TokenGroupMember::LEN == 72);
+
+impl TokenGroupMember {
+    /// Return the `TokenGroupMember` extension of the given mint.
+    ///
+    /// This method performs owner validation on `AccountInfo`, safe borrowing
+    /// the account data.
+    ///
+    /// Returns the `ExtensionError` code if the account is not a mint or does
+    /// not have the extension.
+    /// Returns `AccountBorrowFailed` if the account data is mutably borrowed.
+    #[inline]
+    pub fn from_account_info(account_info: &AccountInfo) -> Result<Ref<Self>, ProgramError> {
+        get_extension_from_account_info(account_info)
+    }
+
+    /// Return the `TokenGroupMember` extension of the given mint.
+    ///
+    /// This method performs owner validation on `AccountInfo`, but does not
+    /// perform the borrow check.
+    ///
+    /// Returns the `ExtensionError` code if the account is not a mint or does
+    /// not have the extension.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that it is safe to borrow the account data (e.g., there are
+    /// no mutable borrows of the account data).
+    #[inline]
+    pub unsafe fn from_account_info_unchecked(
+        account_info: &AccountInfo,
+    ) -> Result<&Self, ProgramError> {
+        get_extension_from_account_info_unchecked(account_info)
+    }
+
+    /// Return the number of the member in the group.
+    #[inline(always)]
+    pub fn member_number(&self) -> u64 {
+        self.member_number.get()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::vec::Vec;
+
+    use super::*;
+    use crate::{
+        extensions::ExtensionError,
+        test_utils::{account_with_extensions, mint_base, pyusd_mint_data, TestAccount},
+    };
+
+    /// Mint `[1; 32]`, member number `member_number` of the group `[3; 32]`.
+    fn group_member_mint_data(member_number: u64) -> Vec<u8> {
+        let mut token_group_member = [1; 72];
+        token_group_member[32..64].copy_from_slice(&[3; 32]);
+        token_group_member[64..].copy_from_slice(&member_number.to_le_bytes());
+
+        account_with_extensions(
+            BaseState::Mint,
+            &mint_base(Some(&[2; 32]), 0, 0, None),
+            &[(ExtensionType::TokenGroupMember, &token_group_member)],
+        )
+    }
+
+    fn token_group(update_authority: Pubkey, size: u64, max_size: u64) -> TokenGroup {
+        TokenGroup {
+            update_authority,
+            mint: [1; 32],
+            size: size.into(),
+            max_size: max_size.into(),
+        }
+    }
+
+    #[test]
+    fn test_token_group_accessors() {
+        let group = token_group([2; 32], 3, 10);
+
+        assert_eq!(group.update_authority(), Some(&[2; 32]));
+        assert_eq!((group.size(), group.max_size()), (3, 10));
+        assert!(!group.is_full());
+        assert_eq!(group.remaining_capacity(), 7);
+
+        let group = token_group([0; 32], 10, 10);
+
+        assert_eq!(group.update_authority(), None);
+        assert!(group.is_full());
+        assert_eq!(group.remaining_capacity(), 0);
+
+        // The size can exceed the maximum size of a corrupted group.
+        let group = token_group([0; 32], 11, 10);
+
+        assert!(group.is_full());
+        assert_eq!(group.remaining_capacity(), 0);
+    }
+
+    #[test]
+    fn test_token_group_member_from_account_info() {
+        for member_number in [1, 42, u64::MAX] {
+            let account =
+                TestAccount::with_data([1; 32], crate::ID, &group_member_mint_data(member_number))
+                    .info();
+
+            let member =
+                unsafe { TokenGroupMember::from_account_info_unchecked(&account) }.unwrap();
+            assert_eq!(member.mint, [1; 32]);
+            assert_eq!(member.group, [3; 32]);
+            assert_eq!(member.member_number(), member_number);
+        }
+
+        let account = TestAccount::with_data([1; 32], crate::ID, &group_member_mint_data(7)).info();
+        let member = TokenGroupMember::from_account_info(&account).unwrap();
+        assert_eq!(member.member_number(), 7);
+
+        let account = TestAccount::with_data([1; 32], crate::ID, &pyusd_mint_data()).info();
+        assert_eq!(
+            unsafe { TokenGroupMember::from_account_info_unchecked(&account) },
+            Err(ExtensionError::NotFound.into())
+        );
+        assert_eq!(
+            TokenGroupMember::from_account_info(&account).err(),
+            Some(ExtensionError::NotFound.into())
+        );
+    }
+}
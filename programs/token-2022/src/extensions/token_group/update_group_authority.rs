@@ -0,0 +1,144 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Instruction, Signer},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    ProgramResult,
+};
+
+use crate::{
+    cpi::{invoke_signed_with_bounds, CpiAccounts, IxData},
+    extensions::token_group::UPDATE_GROUP_AUTHORITY_DISCRIMINATOR,
+    instructions::MAX_MULTISIG_SIGNERS,
+};
+
+/// Maximum number of accounts of the instruction.
+const MAX_ACCOUNTS: usize = 2 + MAX_MULTISIG_SIGNERS;
+
+/// Update the update authority of a group.
+///
+/// ### Accounts:
+///   * Single authority
+///   0. `[WRITE]` The group.
+///   1. `[SIGNER]` The current update authority.
+///
+///   * Multisignature authority
+///   0. `[WRITE]` The group.
+///   1. `[]` The current multisig update authority.
+///   2. `..2+M` `[SIGNER]` M signer accounts.
+pub struct UpdateGroupAuthority<'a, 'b> {
+    /// Group Account.
+    pub group: &'a AccountInfo,
+    /// Current update authority of the group.
+    pub update_authority: &'a AccountInfo,
+    /// Signer accounts when the update authority is a multisig.
+    pub signers: &'b [&'a AccountInfo],
+    /// New update authority, or `None` to make the group immutable.
+    pub new_authority: Option<&'a Pubkey>,
+}
+
+impl UpdateGroupAuthority<'_, '_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    #[inline(always)]
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        if self.signers.len() > MAX_MULTISIG_SIGNERS {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        // Account metadata
+        let mut accounts = CpiAccounts::<MAX_ACCOUNTS>::new();
+
+        accounts.push(self.group, true, false);
+        accounts.push_authority(self.update_authority, self.signers);
+
+        // Instruction data layout:
+        // -  [0..8]: instruction discriminator (8 bytes, [u8; 8])
+        // -  [8..40]: new_authority (32 bytes, Pubkey, zeroed if `None`)
+        let mut instruction_data = IxData::<40>::new();
+
+        instruction_data.write_bytes(&UPDATE_GROUP_AUTHORITY_DISCRIMINATOR);
+        instruction_data.write_pubkey(self.new_authority.unwrap_or(&Pubkey::default()));
+
+        let instruction = Instruction {
+            program_id: &crate::ID,
+            accounts: accounts.metas(),
+            data: instruction_data.finish(),
+        };
+
+        invoke_signed_with_bounds::<MAX_ACCOUNTS>(&instruction, accounts.infos(), signers)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::vec;
+
+    use super::*;
+    use crate::test_utils::{take_captured, take_single, CapturedMeta, TestAccount};
+
+    #[test]
+    fn test_update_group_authority() {
+        let group = TestAccount::new([1; 32]).writable().info();
+        let update_authority = TestAccount::new([2; 32]).signer().info();
+
+        for new_authority in [Some(&[3; 32]), None] {
+            UpdateGroupAuthority {
+                group: &group,
+                update_authority: &update_authority,
+                signers: &[],
+                new_authority,
+            }
+            .invoke()
+            .unwrap();
+        }
+
+        let instructions = take_captured();
+
+        assert_eq!(instructions.len(), 2);
+        for (instruction, authority) in instructions.iter().zip([[3; 32], [0; 32]]) {
+            assert_eq!(instruction.data[..8], UPDATE_GROUP_AUTHORITY_DISCRIMINATOR);
+            assert_eq!(instruction.data[8..], authority);
+            assert_eq!(
+                instruction.accounts,
+                vec![
+                    CapturedMeta::new([1; 32], true, false),
+                    CapturedMeta::new([2; 32], false, true),
+                ]
+            );
+        }
+    }
+
+    #[test]
+    fn test_update_group_authority_multisig() {
+        let group = TestAccount::new([1; 32]).writable().info();
+        let multisig = TestAccount::new([2; 32]).info();
+        let signer = TestAccount::new([3; 32]).signer().info();
+
+        UpdateGroupAuthority {
+            group: &group,
+            update_authority: &multisig,
+            signers: &[&signer],
+            new_authority: Some(&[4; 32]),
+        }
+        .invoke()
+        .unwrap();
+
+        let instruction = take_single();
+
+        assert_eq!(instruction.data.len(), 40);
+        assert_eq!(
+            instruction.accounts,
+            vec![
+                CapturedMeta::new([1; 32], true, false),
+                CapturedMeta::new([2; 32], false, false),
+                CapturedMeta::new([3; 32], false, true),
+            ]
+        );
+    }
+}
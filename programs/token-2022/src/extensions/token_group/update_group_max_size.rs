@@ -0,0 +1,134 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Instruction, Signer},
+    program_error::ProgramError,
+    ProgramResult,
+};
+
+use crate::{
+    cpi::{invoke_signed_with_bounds, CpiAccounts, IxData},
+    extensions::token_group::{TokenGroup, UPDATE_GROUP_MAX_SIZE_DISCRIMINATOR},
+};
+
+/// Update the maximum number of members of a group.
+///
+/// ### Accounts:
+///   0. `[WRITE]` The group.
+///   1. `[SIGNER]` The group update authority.
+pub struct UpdateGroupMaxSize<'a> {
+    /// Group Account.
+    pub group: &'a AccountInfo,
+    /// Update authority of the group.
+    pub update_authority: &'a AccountInfo,
+    /// Current state of the group, used to check `max_size` before invoking
+    /// the instruction.
+    pub current: Option<&'a TokenGroup>,
+    /// New maximum number of group members.
+    pub max_size: u64,
+}
+
+impl UpdateGroupMaxSize<'_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    /// Invoke the instruction.
+    ///
+    /// Returns `InvalidArgument` if `current` is set and the new maximum is
+    /// lower than its number of members, which the token program rejects.
+    #[inline(always)]
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        if self
+            .current
+            .is_some_and(|group| self.max_size < group.size())
+        {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        // Account metadata
+        let mut accounts = CpiAccounts::<2>::new();
+
+        accounts.push(self.group, true, false);
+        accounts.push(self.update_authority, false, true);
+
+        // Instruction data layout:
+        // -  [0..8]: instruction discriminator (8 bytes, [u8; 8])
+        // -  [8..16]: max_size (8 bytes, u64)
+        let mut instruction_data = IxData::<16>::new();
+
+        instruction_data.write_bytes(&UPDATE_GROUP_MAX_SIZE_DISCRIMINATOR);
+        instruction_data.write_u64_le(self.max_size);
+
+        let instruction = Instruction {
+            program_id: &crate::ID,
+            accounts: accounts.metas(),
+            data: instruction_data.finish(),
+        };
+
+        invoke_signed_with_bounds::<2>(&instruction, accounts.infos(), signers)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::vec;
+
+    use super::*;
+    use crate::test_utils::{take_single, CapturedMeta, TestAccount};
+
+    #[test]
+    fn test_update_group_max_size() {
+        let group = TestAccount::new([1; 32]).writable().info();
+        let update_authority = TestAccount::new([2; 32]).signer().info();
+
+        UpdateGroupMaxSize {
+            group: &group,
+            update_authority: &update_authority,
+            current: None,
+            max_size: 0x0102_0304,
+        }
+        .invoke()
+        .unwrap();
+
+        let instruction = take_single();
+
+        assert_eq!(
+            instruction.data,
+            vec![108, 37, 171, 143, 248, 30, 18, 110, 4, 3, 2, 1, 0, 0, 0, 0]
+        );
+        assert_eq!(
+            instruction.accounts,
+            vec![
+                CapturedMeta::new([1; 32], true, false),
+                CapturedMeta::new([2; 32], false, true),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_update_group_max_size_below_size() {
+        let group = TestAccount::new([1; 32]).writable().info();
+        let update_authority = TestAccount::new([2; 32]).signer().info();
+        let current = TokenGroup {
+            update_authority: [2; 32],
+            mint: [1; 32],
+            size: 5.into(),
+            max_size: 10.into(),
+        };
+
+        let mut update = UpdateGroupMaxSize {
+            group: &group,
+            update_authority: &update_authority,
+            current: Some(&current),
+            max_size: 4,
+        };
+        assert_eq!(update.invoke(), Err(ProgramError::InvalidArgument));
+
+        update.max_size = 5;
+        update.invoke().unwrap();
+        assert_eq!(&take_single().data[8..], &5u64.to_le_bytes());
+    }
+}
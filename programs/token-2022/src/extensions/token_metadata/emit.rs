@@ -0,0 +1,119 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Instruction, Signer},
+    ProgramResult,
+};
+
+use crate::{
+    cpi::{invoke_signed_with_bounds, CpiAccounts, IxData},
+    extensions::token_metadata::EMIT_DISCRIMINATOR,
+};
+
+/// Emit the borsh serialized token metadata as return data.
+///
+/// `start` and `end` select a byte range of the serialized metadata, which
+/// allows reading metadata longer than the return data limit in chunks.
+///
+/// ### Accounts:
+///   0. `[]` The metadata account.
+pub struct Emit<'a> {
+    /// Metadata Account.
+    pub metadata: &'a AccountInfo,
+    /// Start of the range of the serialized metadata, inclusive.
+    pub start: Option<u64>,
+    /// End of the range of the serialized metadata, exclusive.
+    pub end: Option<u64>,
+}
+
+impl Emit<'_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    #[inline(always)]
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        // Account metadata
+        let mut accounts = CpiAccounts::<1>::new();
+
+        accounts.push(self.metadata, false, false);
+
+        // Instruction data layout:
+        // -  [0..8]: instruction discriminator (8 bytes, [u8; 8])
+        // -  [..]: start presence flag (1 byte, u8)
+        // -  [..]: start (optional, 8 bytes, u64)
+        // -  [..]: end presence flag (1 byte, u8)
+        // -  [..]: end (optional, 8 bytes, u64)
+        let mut instruction_data = IxData::<26>::new();
+
+        instruction_data.write_bytes(&EMIT_DISCRIMINATOR);
+
+        for offset in [self.start, self.end] {
+            if let Some(offset) = offset {
+                instruction_data.write_u8(1);
+                instruction_data.write_u64_le(offset);
+            } else {
+                instruction_data.write_u8(0);
+            }
+        }
+
+        let instruction = Instruction {
+            program_id: &crate::ID,
+            accounts: accounts.metas(),
+            data: instruction_data.finish(),
+        };
+
+        invoke_signed_with_bounds::<1>(&instruction, accounts.infos(), signers)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::vec;
+
+    use super::*;
+    use crate::test_utils::{take_captured, CapturedMeta, TestAccount};
+
+    const DISCRIMINATOR: [u8; 8] = [250, 166, 180, 250, 13, 12, 184, 70];
+
+    #[test]
+    fn test_emit() {
+        let metadata = TestAccount::new([1; 32]).info();
+
+        Emit {
+            metadata: &metadata,
+            start: None,
+            end: None,
+        }
+        .invoke()
+        .unwrap();
+
+        Emit {
+            metadata: &metadata,
+            start: Some(64),
+            end: Some(0x0102),
+        }
+        .invoke()
+        .unwrap();
+
+        let instructions = take_captured();
+
+        let mut data = DISCRIMINATOR.to_vec();
+        data.extend_from_slice(&[0, 0]);
+        assert_eq!(instructions[0].data, data);
+
+        let mut data = DISCRIMINATOR.to_vec();
+        data.extend_from_slice(&[1, 64, 0, 0, 0, 0, 0, 0, 0]);
+        data.extend_from_slice(&[1, 2, 1, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(instructions[1].data, data);
+
+        for instruction in instructions {
+            assert_eq!(
+                instruction.accounts,
+                vec![CapturedMeta::new([1; 32], false, false)]
+            );
+        }
+    }
+}
@@ -0,0 +1,167 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Instruction, Signer},
+    ProgramResult,
+};
+
+use crate::{
+    cpi::{invoke_signed_with_bounds, CpiAccounts, IxData},
+    extensions::token_metadata::{write_str, INITIALIZE_DISCRIMINATOR, MAX_INSTRUCTION_DATA_LEN},
+};
+
+/// Initialize the token metadata of a mint.
+///
+/// In Token-2022 the metadata is stored in the mint, which must have a
+/// metadata pointer to itself, so `metadata` and `mint` are the same account.
+/// The mint must have enough lamports for the reallocation of the metadata.
+///
+/// ### Accounts:
+///   0. `[WRITE]` The metadata (the mint).
+///   1. `[]` The update authority.
+///   2. `[]` The mint.
+///   3. `[SIGNER]` The mint authority.
+pub struct Initialize<'a, 'b> {
+    /// Metadata Account.
+    pub metadata: &'a AccountInfo,
+    /// Authority that can update the metadata.
+    pub update_authority: &'a AccountInfo,
+    /// Mint Account.
+    pub mint: &'a AccountInfo,
+    /// Mint authority.
+    pub mint_authority: &'a AccountInfo,
+    /// The longer name of the token.
+    pub name: &'b str,
+    /// The shortened symbol of the token.
+    pub symbol: &'b str,
+    /// The URI pointing to richer metadata.
+    pub uri: &'b str,
+}
+
+impl Initialize<'_, '_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    /// Invoke the instruction.
+    ///
+    /// Returns `InvalidArgument` if the instruction data is longer than
+    /// [`MAX_INSTRUCTION_DATA_LEN`].
+    #[inline(always)]
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        // Account metadata
+        let mut accounts = CpiAccounts::<4>::new();
+
+        accounts.push(self.metadata, true, false);
+        accounts.push(self.update_authority, false, false);
+        accounts.push(self.mint, false, false);
+        accounts.push(self.mint_authority, false, true);
+
+        // Instruction data layout:
+        // -  [0..8]: instruction discriminator (8 bytes, [u8; 8])
+        // -  [..]: name (4 bytes length prefix + UTF-8 bytes)
+        // -  [..]: symbol (4 bytes length prefix + UTF-8 bytes)
+        // -  [..]: uri (4 bytes length prefix + UTF-8 bytes)
+        let mut instruction_data = IxData::<MAX_INSTRUCTION_DATA_LEN>::new();
+
+        instruction_data.write_bytes(&INITIALIZE_DISCRIMINATOR);
+
+        write_str(&mut instruction_data, self.name)?;
+        write_str(&mut instruction_data, self.symbol)?;
+        write_str(&mut instruction_data, self.uri)?;
+
+        let instruction = Instruction {
+            program_id: &crate::ID,
+            accounts: accounts.metas(),
+            data: instruction_data.finish(),
+        };
+
+        invoke_signed_with_bounds::<4>(&instruction, accounts.infos(), signers)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::vec;
+
+    use pinocchio::program_error::ProgramError;
+
+    use super::*;
+    use crate::{
+        extensions::token_metadata::TokenMetadata,
+        test_utils::{take_single, CapturedMeta, TestAccount},
+    };
+
+    #[test]
+    fn test_initialize_token_metadata() {
+        let mint = TestAccount::new([1; 32]).writable().info();
+        let update_authority = TestAccount::new([2; 32]).info();
+        let mint_authority = TestAccount::new([3; 32]).signer().info();
+
+        Initialize {
+            metadata: &mint,
+            update_authority: &update_authority,
+            mint: &mint,
+            mint_authority: &mint_authority,
+            name: "PayPal USD",
+            symbol: "PYUSD",
+            uri: "https://example.com/pyusd.json",
+        }
+        .invoke()
+        .unwrap();
+
+        let instruction = take_single();
+
+        assert_eq!(instruction.data[..8], [210, 225, 30, 162, 88, 184, 77, 141]);
+        assert_eq!(
+            instruction.accounts,
+            vec![
+                CapturedMeta::new([1; 32], true, false),
+                CapturedMeta::new([2; 32], false, false),
+                CapturedMeta::new([1; 32], false, false),
+                CapturedMeta::new([3; 32], false, true),
+            ]
+        );
+
+        // The token program stores the strings as they are serialized in the
+        // instruction, after the authority and the mint.
+        let mut extension = vec![2; 32];
+        extension.extend_from_slice(&[1; 32]);
+        extension.extend_from_slice(&instruction.data[8..]);
+        extension.extend_from_slice(&[0; 4]);
+
+        let metadata = TokenMetadata::from_bytes(&extension).unwrap();
+
+        assert_eq!(metadata.update_authority(), Some(&[2; 32]));
+        assert_eq!(metadata.mint(), &[1; 32]);
+        assert_eq!(metadata.name(), "PayPal USD");
+        assert_eq!(metadata.symbol(), "PYUSD");
+        assert_eq!(metadata.uri(), "https://example.com/pyusd.json");
+    }
+
+    #[test]
+    fn test_initialize_token_metadata_too_long() {
+        let mint = TestAccount::new([1; 32]).writable().info();
+        let mint_authority = TestAccount::new([3; 32]).signer().info();
+
+        // Discriminator and three length prefixes.
+        let max_uri = "a".repeat(MAX_INSTRUCTION_DATA_LEN - 8 - 3 * 4);
+        let mut initialize = Initialize {
+            metadata: &mint,
+            update_authority: &mint_authority,
+            mint: &mint,
+            mint_authority: &mint_authority,
+            name: "",
+            symbol: "",
+            uri: &max_uri,
+        };
+        initialize.invoke().unwrap();
+        assert_eq!(take_single().data.len(), MAX_INSTRUCTION_DATA_LEN);
+
+        let uri = max_uri.clone() + "a";
+        initialize.uri = &uri;
+        assert_eq!(initialize.invoke(), Err(ProgramError::InvalidArgument));
+    }
+}
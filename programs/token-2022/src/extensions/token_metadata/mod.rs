@@ -0,0 +1,508 @@
+//! Token metadata extension.
+//!
+//! The token metadata instructions follow the SPL token-metadata interface,
+//! which identifies instructions with an 8-byte discriminator instead of the
+//! Token-2022 instruction discriminators. Their variable length data is
+//! serialized into a stack buffer of [`MAX_INSTRUCTION_DATA_LEN`] bytes.
+
+mod emit;
+mod initialize;
+mod remove_key;
+mod update_authority;
+mod update_field;
+
+pub use emit::*;
+pub use initialize::*;
+pub use remove_key::*;
+pub use update_authority::*;
+pub use update_field::*;
+
+use core::str::from_utf8;
+
+use pinocchio::{program_error::ProgramError, pubkey::Pubkey};
+
+use crate::{
+    cpi::IxData,
+    extensions::{
+        find_entry, get_extension_data, tlv_start, BaseState, ExtensionType, EXTENSION_HEADER_LEN,
+    },
+    pod::non_zero,
+};
+
+/// Maximum length of the data of the token metadata instructions.
+///
+/// Instructions with longer data (e.g., long strings) fail with
+/// `InvalidArgument` before invoking the token program.
+pub const MAX_INSTRUCTION_DATA_LEN: usize = 512;
+
+/// Discriminator of the `Initialize` instruction, the first 8 bytes of the
+/// SHA-256 hash of `spl_token_metadata_interface:initialize_account`.
+pub const INITIALIZE_DISCRIMINATOR: [u8; 8] = [210, 225, 30, 162, 88, 184, 77, 141];
+
+/// Discriminator of the `UpdateField` instruction, the first 8 bytes of the
+/// SHA-256 hash of `spl_token_metadata_interface:updating_field`.
+pub const UPDATE_FIELD_DISCRIMINATOR: [u8; 8] = [221, 233, 49, 45, 181, 202, 220, 200];
+
+/// Discriminator of the `RemoveKey` instruction, the first 8 bytes of the
+/// SHA-256 hash of `spl_token_metadata_interface:remove_key_ix`.
+pub const REMOVE_KEY_DISCRIMINATOR: [u8; 8] = [234, 18, 32, 56, 89, 141, 37, 181];
+
+/// Discriminator of the `UpdateAuthority` instruction, the first 8 bytes of
+/// the SHA-256 hash of `spl_token_metadata_interface:update_the_authority`.
+pub const UPDATE_AUTHORITY_DISCRIMINATOR: [u8; 8] = [215, 228, 166, 228, 84, 100, 86, 123];
+
+/// Discriminator of the `Emit` instruction, the first 8 bytes of the SHA-256
+/// hash of `spl_token_metadata_interface:emitter`.
+pub const EMIT_DISCRIMINATOR: [u8; 8] = [250, 166, 180, 250, 13, 12, 184, 70];
+
+/// Length of the fixed-size fields at the start of the `TokenMetadata`
+/// extension data: the update authority and the mint.
+const FIXED_LEN: usize = 64;
+
+/// Token metadata stored in the mint account.
+///
+/// This is a zero-copy view over the borsh serialized metadata, which is
+/// composed by variable length fields:
+///   - `update_authority` (32 bytes, `Pubkey` with all zeros representing `None`)
+///   - `mint` (32 bytes, `Pubkey`)
+///   - `name` (4 bytes length prefix + UTF-8 bytes)
+///   - `symbol` (4 bytes length prefix + UTF-8 bytes)
+///   - `uri` (4 bytes length prefix + UTF-8 bytes)
+///   - `additional_metadata` (4 bytes count prefix + key/value string pairs)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TokenMetadata<'a> {
+    /// The authority that can sign to update the metadata.
+    update_authority: &'a Pubkey,
+
+    /// The associated mint, used to counter spoofing to be sure that metadata
+    /// belongs to a particular mint.
+    mint: &'a Pubkey,
+
+    /// The longer name of the token.
+    name: &'a str,
+
+    /// The shortened symbol for the token.
+    symbol: &'a str,
+
+    /// The URI pointing to richer metadata.
+    uri: &'a str,
+
+    /// Serialized additional metadata, including the count prefix.
+    additional_metadata: &'a [u8],
+}
+
+impl<'a> TokenMetadata<'a> {
+    /// Create a `TokenMetadata` without additional metadata.
+    ///
+    /// This is the metadata written by the `Initialize` instruction, so its
+    /// size can be computed before creating the mint.
+    pub fn new(
+        update_authority: Option<&'a Pubkey>,
+        mint: &'a Pubkey,
+        name: &'a str,
+        symbol: &'a str,
+        uri: &'a str,
+    ) -> Self {
+        Self {
+            update_authority: update_authority.unwrap_or(&[0; 32]),
+            mint,
+            name,
+            symbol,
+            uri,
+            additional_metadata: &[0; 4],
+        }
+    }
+
+    /// Return a `TokenMetadata` from the extension data.
+    ///
+    /// The `bytes` must contain exactly the data of the `TokenMetadata` TLV
+    /// entry, without the type and length header.
+    pub fn from_bytes(bytes: &'a [u8]) -> Result<Self, ProgramError> {
+        let (update_authority, mint) = read_fixed(bytes).ok_or(ProgramError::InvalidAccountData)?;
+
+        let remaining = &bytes[FIXED_LEN..];
+        let (name, remaining) = read_str(remaining)?;
+        let (symbol, remaining) = read_str(remaining)?;
+        let (uri, additional_metadata) = read_str(remaining)?;
+
+        if additional_metadata.len() < 4 {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok(Self {
+            update_authority,
+            mint,
+            name,
+            symbol,
+            uri,
+            additional_metadata,
+        })
+    }
+
+    /// Return the `TokenMetadata` stored in the given mint account data.
+    pub fn from_mint_data(data: &'a [u8]) -> Result<Self, ProgramError> {
+        Self::from_bytes(
+            get_extension_data(data, BaseState::Mint, ExtensionType::TokenMetadata)
+                .ok_or(ProgramError::InvalidAccountData)?,
+        )
+    }
+
+    /// Return the update authority, treating the all-zeros key as `None`.
+    #[inline(always)]
+    pub fn update_authority(&self) -> Option<&'a Pubkey> {
+        non_zero(self.update_authority)
+    }
+
+    #[inline(always)]
+    pub fn mint(&self) -> &'a Pubkey {
+        self.mint
+    }
+
+    #[inline(always)]
+    pub fn name(&self) -> &'a str {
+        self.name
+    }
+
+    #[inline(always)]
+    pub fn symbol(&self) -> &'a str {
+        self.symbol
+    }
+
+    #[inline(always)]
+    pub fn uri(&self) -> &'a str {
+        self.uri
+    }
+
+    /// Return the serialized additional metadata, starting with the `u32`
+    /// count of key/value pairs.
+    #[inline(always)]
+    pub fn additional_metadata_bytes(&self) -> &'a [u8] {
+        self.additional_metadata
+    }
+
+    /// Return the length of the `TokenMetadata` TLV entry, including the type
+    /// and length header.
+    #[inline]
+    pub fn tlv_size_of(&self) -> usize {
+        EXTENSION_HEADER_LEN + self.packed_len()
+    }
+
+    /// Write the metadata to `out`, using the layout read by
+    /// [`TokenMetadata::from_bytes`], and return the number of bytes written.
+    ///
+    /// Returns `AccountDataTooSmall` if `out` is shorter than the metadata.
+    pub fn pack_into(&self, out: &mut [u8]) -> Result<usize, ProgramError> {
+        let len = self.packed_len();
+        let out = out
+            .get_mut(..len)
+            .ok_or(ProgramError::AccountDataTooSmall)?;
+
+        out[..32].copy_from_slice(self.update_authority);
+        out[32..FIXED_LEN].copy_from_slice(self.mint);
+
+        let mut offset = FIXED_LEN;
+
+        for value in [self.name, self.symbol, self.uri] {
+            out[offset..offset + 4].copy_from_slice(&(value.len() as u32).to_le_bytes());
+            out[offset + 4..offset + 4 + value.len()].copy_from_slice(value.as_bytes());
+            offset += 4 + value.len();
+        }
+
+        out[offset..].copy_from_slice(self.additional_metadata);
+
+        Ok(len)
+    }
+
+    /// Length of the metadata, without the TLV header.
+    #[inline(always)]
+    fn packed_len(&self) -> usize {
+        FIXED_LEN
+            + 3 * 4
+            + self.name.len()
+            + self.symbol.len()
+            + self.uri.len()
+            + self.additional_metadata.len()
+    }
+
+    /// Return an iterator over the additional metadata key/value pairs.
+    ///
+    /// The pairs are parsed lazily, so a malformed pair (truncated or not
+    /// valid UTF-8) is returned as an error, after which the iteration ends.
+    #[inline]
+    pub fn additional_metadata(&self) -> AdditionalMetadata<'a> {
+        // `from_bytes` checked that the count is present.
+        let (count, data) = self.additional_metadata.split_at(4);
+
+        AdditionalMetadata {
+            data,
+            remaining: u32::from_le_bytes([count[0], count[1], count[2], count[3]]),
+        }
+    }
+}
+
+/// Iterator over the additional metadata key/value pairs of a
+/// [`TokenMetadata`].
+#[derive(Clone, Debug)]
+pub struct AdditionalMetadata<'a> {
+    data: &'a [u8],
+    remaining: u32,
+}
+
+impl<'a> Iterator for AdditionalMetadata<'a> {
+    type Item = Result<(&'a str, &'a str), ProgramError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        let pair = read_str(self.data).and_then(|(key, remaining)| {
+            let (value, remaining) = read_str(remaining)?;
+            Ok((key, value, remaining))
+        });
+
+        match pair {
+            Ok((key, value, remaining)) => {
+                self.data = remaining;
+                self.remaining -= 1;
+                Some(Ok((key, value)))
+            }
+            Err(error) => {
+                self.remaining = 0;
+                Some(Err(error))
+            }
+        }
+    }
+}
+
+impl core::iter::FusedIterator for AdditionalMetadata<'_> {}
+
+/// Fixed-size information about the `TokenMetadata` of a mint.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MetadataProbe<'a> {
+    /// Length of the `TokenMetadata` extension data, without the TLV header.
+    pub len: usize,
+
+    /// The authority that can sign to update the metadata.
+    pub update_authority: Option<&'a Pubkey>,
+
+    /// The mint the metadata belongs to.
+    pub mint: &'a Pubkey,
+}
+
+/// Read the fixed-size fields of the `TokenMetadata` of a mint.
+///
+/// This is cheaper than parsing the `TokenMetadata` since none of the
+/// variable length fields are read (or required to be present). Returns `None`
+/// when the mint does not have the extension.
+pub fn probe(mint_data: &[u8]) -> Option<MetadataProbe> {
+    let entry = find_entry(
+        mint_data,
+        tlv_start(mint_data, BaseState::Mint),
+        ExtensionType::TokenMetadata,
+    )
+    .ok()?;
+
+    if entry.length < FIXED_LEN {
+        return None;
+    }
+
+    let (update_authority, mint) = read_fixed(mint_data.get(entry.offset..)?)?;
+
+    Some(MetadataProbe {
+        len: entry.length,
+        update_authority: non_zero(update_authority),
+        mint,
+    })
+}
+
+#[inline(always)]
+fn read_fixed(bytes: &[u8]) -> Option<(&Pubkey, &Pubkey)> {
+    let update_authority = bytes.get(..32)?.try_into().ok()?;
+    let mint = bytes.get(32..FIXED_LEN)?.try_into().ok()?;
+    Some((update_authority, mint))
+}
+
+#[inline(always)]
+fn read_str(bytes: &[u8]) -> Result<(&str, &[u8]), ProgramError> {
+    let length = bytes
+        .get(..4)
+        .map(|length| u32::from_le_bytes([length[0], length[1], length[2], length[3]]) as usize)
+        .ok_or(ProgramError::InvalidAccountData)?;
+
+    let value = bytes
+        .get(4..4 + length)
+        .ok_or(ProgramError::InvalidAccountData)?;
+
+    Ok((
+        from_utf8(value).map_err(|_| ProgramError::InvalidAccountData)?,
+        &bytes[4 + length..],
+    ))
+}
+
+/// Append `value` with its `u32` length prefix to the instruction data.
+///
+/// Returns `InvalidArgument` if the value does not fit in the instruction
+/// data.
+#[inline(always)]
+fn write_str<const MAX_LEN: usize>(
+    instruction_data: &mut IxData<MAX_LEN>,
+    value: &str,
+) -> Result<(), ProgramError> {
+    if value.len() > instruction_data.remaining().saturating_sub(4) {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    instruction_data.write_u32_le(value.len() as u32);
+    instruction_data.write_bytes(value.as_bytes());
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::{vec, vec::Vec};
+
+    use super::*;
+    use crate::test_utils::{
+        pyusd_mint_data, pyusd_mint_data_with_additional_metadata, PYUSD_MINT,
+        PYUSD_UPDATE_AUTHORITY,
+    };
+
+    #[test]
+    fn test_token_metadata() {
+        let data = pyusd_mint_data();
+        let metadata = TokenMetadata::from_mint_data(&data).unwrap();
+
+        assert_eq!(metadata.update_authority(), Some(&PYUSD_UPDATE_AUTHORITY));
+        assert_eq!(metadata.mint(), &PYUSD_MINT);
+        assert_eq!(metadata.name(), "PayPal USD");
+        assert_eq!(metadata.symbol(), "PYUSD");
+        assert_eq!(
+            metadata.uri(),
+            "https://token-metadata.paxos.com/pyusd_metadata/prod/solana/pyusd_metadata.json"
+        );
+        assert_eq!(metadata.additional_metadata_bytes(), &[0, 0, 0, 0]);
+        assert!(metadata.additional_metadata().next().is_none());
+    }
+
+    #[test]
+    fn test_additional_metadata() {
+        let pairs = [("website", "https://paxos.com/pyusd/"), ("", "ünïcødé")];
+        let data = pyusd_mint_data_with_additional_metadata(&pairs);
+        let metadata = TokenMetadata::from_mint_data(&data).unwrap();
+
+        assert_eq!(
+            metadata
+                .additional_metadata()
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap(),
+            pairs
+        );
+    }
+
+    #[test]
+    fn test_additional_metadata_errors() {
+        let data = pyusd_mint_data_with_additional_metadata(&[("a", "b"), ("key", "value")]);
+        let mut value = get_extension_data(&data, BaseState::Mint, ExtensionType::TokenMetadata)
+            .unwrap()
+            .to_vec();
+
+        // Invalid UTF-8 in the last value.
+        let last = value.len() - 1;
+        value[last] = 0xff;
+
+        let metadata = TokenMetadata::from_bytes(&value).unwrap();
+        let mut pairs = metadata.additional_metadata();
+        assert_eq!(pairs.next(), Some(Ok(("a", "b"))));
+        assert_eq!(pairs.next(), Some(Err(ProgramError::InvalidAccountData)));
+        assert_eq!(pairs.next(), None);
+
+        // Truncated last value.
+        let metadata = TokenMetadata::from_bytes(&value[..last]).unwrap();
+        let mut pairs = metadata.additional_metadata();
+        assert_eq!(pairs.next(), Some(Ok(("a", "b"))));
+        assert_eq!(pairs.next(), Some(Err(ProgramError::InvalidAccountData)));
+        assert_eq!(pairs.next(), None);
+    }
+
+    #[test]
+    fn test_pack_round_trip() {
+        let data = pyusd_mint_data_with_additional_metadata(&[("website", "https://paxos.com")]);
+        let value =
+            get_extension_data(&data, BaseState::Mint, ExtensionType::TokenMetadata).unwrap();
+        let pyusd = TokenMetadata::from_bytes(value).unwrap();
+
+        let cases = [
+            pyusd,
+            TokenMetadata::new(None, &[2; 32], "", "", ""),
+            TokenMetadata::new(
+                Some(&[1; 32]),
+                &[2; 32],
+                "Ünïcødé 💵",
+                "€",
+                "https://例え.jp",
+            ),
+        ];
+
+        for metadata in cases {
+            let mut out = vec![0xff; metadata.tlv_size_of() + 1];
+
+            let len = metadata.pack_into(&mut out).unwrap();
+            assert_eq!(len, metadata.tlv_size_of() - EXTENSION_HEADER_LEN);
+            assert_eq!(TokenMetadata::from_bytes(&out[..len]), Ok(metadata));
+
+            assert_eq!(
+                metadata.pack_into(&mut out[..len - 1]),
+                Err(ProgramError::AccountDataTooSmall)
+            );
+        }
+
+        assert_eq!(pyusd.tlv_size_of(), EXTENSION_HEADER_LEN + value.len());
+        // Initialized metadata has an empty additional metadata count.
+        assert_eq!(
+            TokenMetadata::new(None, &[2; 32], "", "", "").tlv_size_of(),
+            EXTENSION_HEADER_LEN + FIXED_LEN + 4 * 4
+        );
+    }
+
+    #[test]
+    fn test_probe_matches_full_parse() {
+        let data = pyusd_mint_data();
+        let metadata = TokenMetadata::from_mint_data(&data).unwrap();
+        let probe = probe(&data).unwrap();
+
+        assert_eq!(probe.update_authority, metadata.update_authority());
+        assert_eq!(probe.mint, metadata.mint());
+        // 64 fixed bytes + 3 strings + additional metadata count.
+        assert_eq!(
+            probe.len,
+            64 + 4 * 4 + metadata.name().len() + metadata.symbol().len() + metadata.uri().len()
+        );
+    }
+
+    #[test]
+    fn test_probe_does_not_read_strings() {
+        let data = pyusd_mint_data();
+        let probe_len = probe(&data).unwrap().len;
+
+        // Truncate the account right after the fixed fields of the metadata.
+        let end = data.len() - probe_len + FIXED_LEN;
+        let truncated = &data[..end];
+
+        assert_eq!(probe(truncated).unwrap().mint, &PYUSD_MINT);
+        assert!(TokenMetadata::from_mint_data(truncated).is_err());
+    }
+
+    #[test]
+    fn test_probe_without_metadata() {
+        let mut data = pyusd_mint_data();
+        // Only keep the base mint.
+        data.truncate(crate::extensions::EXTENSION_START_OFFSET);
+
+        assert_eq!(probe(&data), None);
+        assert_eq!(probe(&data[..crate::state::Mint::BASE_LEN]), None);
+    }
+}
@@ -0,0 +1,111 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Instruction, Signer},
+    ProgramResult,
+};
+
+use crate::{
+    cpi::{invoke_signed_with_bounds, CpiAccounts, IxData},
+    extensions::token_metadata::{write_str, MAX_INSTRUCTION_DATA_LEN, REMOVE_KEY_DISCRIMINATOR},
+};
+
+/// Remove a key from the additional metadata of the token metadata.
+///
+/// ### Accounts:
+///   0. `[WRITE]` The metadata account.
+///   1. `[SIGNER]` The update authority.
+pub struct RemoveKey<'a, 'b> {
+    /// Metadata Account.
+    pub metadata: &'a AccountInfo,
+    /// Update authority of the metadata.
+    pub update_authority: &'a AccountInfo,
+    /// Whether the instruction succeeds when the key does not exist.
+    pub idempotent: bool,
+    /// Key to remove.
+    pub key: &'b str,
+}
+
+impl RemoveKey<'_, '_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    /// Invoke the instruction.
+    ///
+    /// Returns `InvalidArgument` if the instruction data is longer than
+    /// [`MAX_INSTRUCTION_DATA_LEN`].
+    #[inline(always)]
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        // Account metadata
+        let mut accounts = CpiAccounts::<2>::new();
+
+        accounts.push(self.metadata, true, false);
+        accounts.push(self.update_authority, false, true);
+
+        // Instruction data layout:
+        // -  [0..8]: instruction discriminator (8 bytes, [u8; 8])
+        // -  [8]: idempotent (1 byte, bool)
+        // -  [9..]: key (4 bytes length prefix + UTF-8 bytes)
+        let mut instruction_data = IxData::<MAX_INSTRUCTION_DATA_LEN>::new();
+
+        instruction_data.write_bytes(&REMOVE_KEY_DISCRIMINATOR);
+        instruction_data.write_u8(self.idempotent as u8);
+
+        write_str(&mut instruction_data, self.key)?;
+
+        let instruction = Instruction {
+            program_id: &crate::ID,
+            accounts: accounts.metas(),
+            data: instruction_data.finish(),
+        };
+
+        invoke_signed_with_bounds::<2>(&instruction, accounts.infos(), signers)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::vec;
+
+    use super::*;
+    use crate::test_utils::{take_captured, CapturedMeta, TestAccount};
+
+    #[test]
+    fn test_remove_key() {
+        let metadata = TestAccount::new([1; 32]).writable().info();
+        let update_authority = TestAccount::new([2; 32]).signer().info();
+
+        for idempotent in [true, false] {
+            RemoveKey {
+                metadata: &metadata,
+                update_authority: &update_authority,
+                idempotent,
+                key: "émetteur",
+            }
+            .invoke()
+            .unwrap();
+        }
+
+        let instructions = take_captured();
+
+        // The length prefix is the number of bytes, not of characters.
+        let mut key = vec![9, 0, 0, 0];
+        key.extend_from_slice("émetteur".as_bytes());
+
+        for (instruction, idempotent) in instructions.iter().zip([1, 0]) {
+            assert_eq!(instruction.data[..8], [234, 18, 32, 56, 89, 141, 37, 181]);
+            assert_eq!(instruction.data[8], idempotent);
+            assert_eq!(instruction.data[9..], key);
+            assert_eq!(
+                instruction.accounts,
+                vec![
+                    CapturedMeta::new([1; 32], true, false),
+                    CapturedMeta::new([2; 32], false, true),
+                ]
+            );
+        }
+    }
+}
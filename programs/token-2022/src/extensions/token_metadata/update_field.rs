@@ -0,0 +1,166 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Instruction, Signer},
+    ProgramResult,
+};
+
+use crate::{
+    cpi::{invoke_signed_with_bounds, CpiAccounts, IxData},
+    extensions::token_metadata::{write_str, MAX_INSTRUCTION_DATA_LEN, UPDATE_FIELD_DISCRIMINATOR},
+};
+
+/// Field of the token metadata.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Field<'a> {
+    /// The name field.
+    Name,
+    /// The symbol field.
+    Symbol,
+    /// The URI field.
+    Uri,
+    /// A key of the additional metadata.
+    Key(&'a str),
+}
+
+impl Field<'_> {
+    /// Return the borsh enum tag of the field.
+    #[inline(always)]
+    const fn tag(&self) -> u8 {
+        match self {
+            Field::Name => 0,
+            Field::Symbol => 1,
+            Field::Uri => 2,
+            Field::Key(_) => 3,
+        }
+    }
+}
+
+/// Update a field of the token metadata, adding the key to the additional
+/// metadata if it does not exist.
+///
+/// The metadata account must have enough lamports for the reallocation when
+/// the value is longer than the current one.
+///
+/// ### Accounts:
+///   0. `[WRITE]` The metadata account.
+///   1. `[SIGNER]` The update authority.
+pub struct UpdateField<'a, 'b> {
+    /// Metadata Account.
+    pub metadata: &'a AccountInfo,
+    /// Update authority of the metadata.
+    pub update_authority: &'a AccountInfo,
+    /// Field to update.
+    pub field: Field<'b>,
+    /// New value of the field.
+    pub value: &'b str,
+}
+
+impl UpdateField<'_, '_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    /// Invoke the instruction.
+    ///
+    /// Returns `InvalidArgument` if the instruction data is longer than
+    /// [`MAX_INSTRUCTION_DATA_LEN`].
+    #[inline(always)]
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        // Account metadata
+        let mut accounts = CpiAccounts::<2>::new();
+
+        accounts.push(self.metadata, true, false);
+        accounts.push(self.update_authority, false, true);
+
+        // Instruction data layout:
+        // -  [0..8]: instruction discriminator (8 bytes, [u8; 8])
+        // -  [8]: field (1 byte, u8)
+        // -  [..]: key, only for `Field::Key` (4 bytes length prefix + UTF-8 bytes)
+        // -  [..]: value (4 bytes length prefix + UTF-8 bytes)
+        let mut instruction_data = IxData::<MAX_INSTRUCTION_DATA_LEN>::new();
+
+        instruction_data.write_bytes(&UPDATE_FIELD_DISCRIMINATOR);
+        instruction_data.write_u8(self.field.tag());
+
+        if let Field::Key(key) = self.field {
+            write_str(&mut instruction_data, key)?;
+        }
+
+        write_str(&mut instruction_data, self.value)?;
+
+        let instruction = Instruction {
+            program_id: &crate::ID,
+            accounts: accounts.metas(),
+            data: instruction_data.finish(),
+        };
+
+        invoke_signed_with_bounds::<2>(&instruction, accounts.infos(), signers)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::vec;
+
+    use super::*;
+    use crate::test_utils::{borsh_str, take_single, CapturedMeta, TestAccount};
+
+    const DISCRIMINATOR: [u8; 8] = [221, 233, 49, 45, 181, 202, 220, 200];
+
+    #[test]
+    fn test_update_field_name() {
+        let metadata = TestAccount::new([1; 32]).writable().info();
+        let update_authority = TestAccount::new([2; 32]).signer().info();
+
+        UpdateField {
+            metadata: &metadata,
+            update_authority: &update_authority,
+            field: Field::Name,
+            value: "New name",
+        }
+        .invoke()
+        .unwrap();
+
+        let instruction = take_single();
+
+        let mut data = DISCRIMINATOR.to_vec();
+        data.push(0);
+        data.extend(borsh_str("New name"));
+
+        assert_eq!(instruction.data, data);
+        assert_eq!(
+            instruction.accounts,
+            vec![
+                CapturedMeta::new([1; 32], true, false),
+                CapturedMeta::new([2; 32], false, true),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_update_field_key() {
+        let metadata = TestAccount::new([1; 32]).writable().info();
+        let update_authority = TestAccount::new([2; 32]).signer().info();
+
+        UpdateField {
+            metadata: &metadata,
+            update_authority: &update_authority,
+            field: Field::Key("issuer"),
+            value: "Paxos",
+        }
+        .invoke()
+        .unwrap();
+
+        let instruction = take_single();
+
+        let mut data = DISCRIMINATOR.to_vec();
+        data.push(3);
+        data.extend(borsh_str("issuer"));
+        data.extend(borsh_str("Paxos"));
+
+        assert_eq!(instruction.data, data);
+    }
+}
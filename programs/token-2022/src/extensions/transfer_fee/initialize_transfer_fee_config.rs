@@ -0,0 +1,173 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{AccountMeta, Instruction, Signer},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    ProgramResult,
+};
+
+use crate::{
+    cpi::{invoke_signed, IxData},
+    extensions::{
+        transfer_fee::{TransferFeeInstruction, MAX_FEE_BASIS_POINTS},
+        ExtensionDiscriminator,
+    },
+};
+
+/// Initialize the transfer fee on a new mint.
+///
+/// Fails if the mint has already been initialized, so must be included in
+/// the same transaction as the instruction that creates the mint account.
+///
+/// ### Accounts:
+///   0. `[WRITE]` The mint to initialize.
+pub struct InitializeTransferFeeConfig<'a> {
+    /// Mint Account.
+    pub mint: &'a AccountInfo,
+    /// Authority that can set the transfer fee.
+    pub transfer_fee_config_authority: Option<&'a Pubkey>,
+    /// Authority that can withdraw the withheld fees.
+    pub withdraw_withheld_authority: Option<&'a Pubkey>,
+    /// Amount of transfer collected as fees, expressed as basis points of
+    /// the transfer amount, up to [`MAX_FEE_BASIS_POINTS`].
+    pub transfer_fee_basis_points: u16,
+    /// Maximum fee assessed on transfers.
+    pub maximum_fee: u64,
+}
+
+impl<'a> InitializeTransferFeeConfig<'a> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    #[inline(always)]
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        if self.transfer_fee_basis_points > MAX_FEE_BASIS_POINTS {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        let account_metas = self.account_metas();
+        let instruction_data = self.instruction_data();
+
+        let instruction = Instruction {
+            program_id: &crate::ID,
+            accounts: &account_metas,
+            data: instruction_data.finish(),
+        };
+
+        invoke_signed(&instruction, &[self.mint], signers)
+    }
+
+    /// Return the account metas of the instruction.
+    #[inline(always)]
+    pub fn account_metas(&self) -> [AccountMeta<'a>; 1] {
+        [AccountMeta::writable(self.mint.key())]
+    }
+
+    /// Return the instruction data.
+    #[inline(always)]
+    pub fn instruction_data(&self) -> IxData<78> {
+        // Instruction data layout:
+        // -  [0]: extension instruction discriminator (1 byte, u8)
+        // -  [1]: instruction discriminator (1 byte, u8)
+        // -  [2]: transfer_fee_config_authority presence flag (1 byte, u8)
+        // -  [3..35]: transfer_fee_config_authority (optional, 32 bytes, Pubkey)
+        // -  [..]: withdraw_withheld_authority presence flag (1 byte, u8)
+        // -  [..]: withdraw_withheld_authority (optional, 32 bytes, Pubkey)
+        // -  [..]: transfer_fee_basis_points (2 bytes, u16)
+        // -  [..]: maximum_fee (8 bytes, u64)
+        let mut instruction_data = IxData::<78>::new();
+
+        instruction_data.write_u8(ExtensionDiscriminator::TransferFee as u8);
+        instruction_data.write_u8(TransferFeeInstruction::InitializeTransferFeeConfig as u8);
+
+        instruction_data.write_coption_pubkey(self.transfer_fee_config_authority);
+        instruction_data.write_coption_pubkey(self.withdraw_withheld_authority);
+
+        instruction_data.write_u16_le(self.transfer_fee_basis_points);
+        instruction_data.write_u64_le(self.maximum_fee);
+
+        instruction_data
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::{vec, vec::Vec};
+
+    use super::*;
+    use crate::test_utils::{take_single, CapturedMeta, TestAccount};
+
+    const CONFIG_AUTHORITY: Pubkey = [2; 32];
+    const WITHDRAW_AUTHORITY: Pubkey = [3; 32];
+
+    fn expected_data(config: Option<&Pubkey>, withdraw: Option<&Pubkey>) -> Vec<u8> {
+        let mut data = vec![26, 0];
+        for authority in [config, withdraw] {
+            match authority {
+                Some(authority) => {
+                    data.push(1);
+                    data.extend_from_slice(authority);
+                }
+                None => data.push(0),
+            }
+        }
+        data.extend_from_slice(&250u16.to_le_bytes());
+        data.extend_from_slice(&5_000u64.to_le_bytes());
+        data
+    }
+
+    #[test]
+    fn test_initialize_transfer_fee_config() {
+        let mint = TestAccount::new([1; 32]).writable().info();
+
+        let cases = [
+            (Some(&CONFIG_AUTHORITY), Some(&WITHDRAW_AUTHORITY), 78),
+            (Some(&CONFIG_AUTHORITY), None, 46),
+            (None, Some(&WITHDRAW_AUTHORITY), 46),
+            (None, None, 14),
+        ];
+
+        for (config, withdraw, len) in cases {
+            InitializeTransferFeeConfig {
+                mint: &mint,
+                transfer_fee_config_authority: config,
+                withdraw_withheld_authority: withdraw,
+                transfer_fee_basis_points: 250,
+                maximum_fee: 5_000,
+            }
+            .invoke()
+            .unwrap();
+
+            let instruction = take_single();
+
+            assert_eq!(instruction.data.len(), len);
+            assert_eq!(instruction.data, expected_data(config, withdraw));
+            assert_eq!(
+                instruction.accounts,
+                vec![CapturedMeta::new([1; 32], true, false)]
+            );
+        }
+    }
+
+    #[test]
+    fn test_initialize_transfer_fee_config_basis_points() {
+        let mint = TestAccount::new([1; 32]).writable().info();
+
+        let mut initialize = InitializeTransferFeeConfig {
+            mint: &mint,
+            transfer_fee_config_authority: None,
+            withdraw_withheld_authority: None,
+            transfer_fee_basis_points: MAX_FEE_BASIS_POINTS,
+            maximum_fee: u64::MAX,
+        };
+        initialize.invoke().unwrap();
+        assert_eq!(&take_single().data[2..6], &[0, 0, 0x10, 0x27]);
+
+        initialize.transfer_fee_basis_points = MAX_FEE_BASIS_POINTS + 1;
+        assert_eq!(initialize.invoke(), Err(ProgramError::InvalidArgument));
+    }
+}
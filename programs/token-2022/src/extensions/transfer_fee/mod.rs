@@ -0,0 +1,319 @@
+//! Transfer fee extension.
+
+mod harvest_withheld_tokens_to_mint;
+mod initialize_transfer_fee_config;
+mod set_transfer_fee;
+mod transfer_checked_with_fee;
+mod withdraw_withheld_tokens_from_accounts;
+mod withdraw_withheld_tokens_from_mint;
+
+pub use harvest_withheld_tokens_to_mint::*;
+pub use initialize_transfer_fee_config::*;
+pub use set_transfer_fee::*;
+pub use transfer_checked_with_fee::*;
+pub use withdraw_withheld_tokens_from_accounts::*;
+pub use withdraw_withheld_tokens_from_mint::*;
+
+use pinocchio::{
+    account_info::{AccountInfo, Ref},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use crate::{
+    extensions::{
+        get_extension_from_account_info, get_extension_from_account_info_unchecked, BaseState,
+        Extension, ExtensionType,
+    },
+    pod::{PodU16, PodU64},
+};
+
+/// Maximum transfer fee, expressed in basis points (100%).
+pub const MAX_FEE_BASIS_POINTS: u16 = 10_000;
+
+/// Maximum number of source token accounts of the instructions that collect
+/// withheld fees from token accounts.
+pub const MAX_SOURCE_ACCOUNTS: usize = 20;
+
+/// Instruction discriminators of the transfer fee extension.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TransferFeeInstruction {
+    InitializeTransferFeeConfig = 0,
+    TransferCheckedWithFee = 1,
+    WithdrawWithheldTokensFromMint = 2,
+    WithdrawWithheldTokensFromAccounts = 3,
+    HarvestWithheldTokensToMint = 4,
+    SetTransferFee = 5,
+}
+
+/// Transfer fee parameters, effective from `epoch`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TransferFee {
+    /// First epoch where the transfer fee takes effect.
+    pub epoch: PodU64,
+    /// Maximum fee assessed on transfers, expressed as an amount of tokens.
+    pub maximum_fee: PodU64,
+    /// Amount of transfer collected as fees, expressed as basis points of
+    /// the transfer amount (increments of 0.01%).
+    pub transfer_fee_basis_points: PodU16,
+}
+
+impl TransferFee {
+    /// Return the first epoch where the transfer fee takes effect.
+    #[inline(always)]
+    pub fn epoch(&self) -> u64 {
+        self.epoch.get()
+    }
+
+    /// Return the maximum fee assessed on transfers.
+    #[inline(always)]
+    pub fn maximum_fee(&self) -> u64 {
+        self.maximum_fee.get()
+    }
+
+    /// Return the transfer fee, expressed as basis points of the transfer
+    /// amount.
+    #[inline(always)]
+    pub fn transfer_fee_basis_points(&self) -> u16 {
+        self.transfer_fee_basis_points.get()
+    }
+
+    /// Calculate the fee withheld on a transfer of `pre_fee_amount`, the way
+    /// the token program does: rounded up and capped at the maximum fee.
+    ///
+    /// Returns `None` if the fee does not fit in a `u64`.
+    #[inline]
+    pub fn calculate_fee(&self, pre_fee_amount: u64) -> Option<u64> {
+        let basis_points = self.transfer_fee_basis_points() as u128;
+
+        if basis_points == 0 || pre_fee_amount == 0 {
+            return Some(0);
+        }
+
+        let numerator = (pre_fee_amount as u128) * basis_points;
+        let raw_fee = numerator.div_ceil(MAX_FEE_BASIS_POINTS as u128);
+
+        u64::try_from(raw_fee)
+            .ok()
+            .map(|fee| fee.min(self.maximum_fee()))
+    }
+}
+
+/// Transfer fee configuration of a mint.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TransferFeeConfig {
+    /// Optional authority to set the fee.
+    pub transfer_fee_config_authority: Pubkey,
+    /// Withdraw from mint instructions must be signed by this key.
+    pub withdraw_withheld_authority: Pubkey,
+    /// Withheld transfer fee tokens that have been moved to the mint for
+    /// withdrawal.
+    pub withheld_amount: PodU64,
+    /// Older transfer fee, used if the current epoch is lower than the
+    /// newer transfer fee epoch.
+    pub older_transfer_fee: TransferFee,
+    /// Newer transfer fee, used if the current epoch is greater than or equal
+    /// to its epoch.
+    pub newer_transfer_fee: TransferFee,
+}
+
+unsafe impl Extension for TransferFeeConfig {
+    const TYPE: ExtensionType = ExtensionType::TransferFeeConfig;
+    const LEN: usize = core::mem::size_of::<Self>();
+    const BASE_STATE: BaseState = BaseState::Mint;
+}
+
+const _: () = assert!(TransferFeeConfig::LEN == 108);
+
+impl TransferFeeConfig {
+    /// Return the transfer fee in effect at `epoch`.
+    #[inline(always)]
+    pub fn get_epoch_fee(&self, epoch: u64) -> &TransferFee {
+        if epoch >= self.newer_transfer_fee.epoch() {
+            &self.newer_transfer_fee
+        } else {
+            &self.older_transfer_fee
+        }
+    }
+
+    /// Calculate the fee withheld on a transfer of `pre_fee_amount` at
+    /// `epoch`.
+    ///
+    /// The result is the `fee` expected by [`TransferCheckedWithFee`].
+    #[inline(always)]
+    pub fn calculate_epoch_fee(&self, epoch: u64, pre_fee_amount: u64) -> Option<u64> {
+        self.get_epoch_fee(epoch).calculate_fee(pre_fee_amount)
+    }
+}
+
+/// Transfer fees withheld in a token account.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TransferFeeAmount {
+    /// Amount withheld during transfers, to be harvested to the mint.
+    pub withheld_amount: PodU64,
+}
+
+unsafe impl Extension for TransferFeeAmount {
+    const TYPE: ExtensionType = ExtensionType::TransferFeeAmount;
+    const LEN: usize = core::mem::size_of::<Self>();
+    const BASE_STATE: BaseState = BaseState::TokenAccount;
+}
+
+const _: () = assert!(TransferFeeAmount::LEN == 8);
+
+impl TransferFeeAmount {
+    /// Return the `TransferFeeAmount` extension of the given token account.
+    ///
+    /// This method performs owner validation on `AccountInfo`, safe borrowing
+    /// the account data.
+    ///
+    /// Returns the `ExtensionError` code if the account is not a token account
+    /// or does not have the extension.
+    /// Returns `AccountBorrowFailed` if the account data is mutably borrowed.
+    #[inline]
+    pub fn from_account_info(account_info: &AccountInfo) -> Result<Ref<Self>, ProgramError> {
+        get_extension_from_account_info(account_info)
+    }
+
+    /// Return the `TransferFeeAmount` extension of the given token account.
+    ///
+    /// This method performs owner validation on `AccountInfo`, but does not
+    /// perform the borrow check.
+    ///
+    /// Returns the `ExtensionError` code if the account is not a token account
+    /// or does not have the extension.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that it is safe to borrow the account data (e.g., there are
+    /// no mutable borrows of the account data).
+    #[inline]
+    pub unsafe fn from_account_info_unchecked(
+        account_info: &AccountInfo,
+    ) -> Result<&Self, ProgramError> {
+        get_extension_from_account_info_unchecked(account_info)
+    }
+
+    /// Return the amount withheld during transfers.
+    #[inline(always)]
+    pub fn withheld_amount(&self) -> u64 {
+        self.withheld_amount.get()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        extensions::ExtensionError,
+        test_utils::{
+            account_with_extensions, pyusd_mint_data, token_account_base, TestAccount, PYUSD_MINT,
+        },
+    };
+
+    fn transfer_fee(epoch: u64, maximum_fee: u64, basis_points: u16) -> TransferFee {
+        TransferFee {
+            epoch: epoch.into(),
+            maximum_fee: maximum_fee.into(),
+            transfer_fee_basis_points: basis_points.into(),
+        }
+    }
+
+    #[test]
+    fn test_calculate_fee() {
+        let fee = transfer_fee(0, 5_000, 250);
+
+        assert_eq!(fee.calculate_fee(0), Some(0));
+        assert_eq!(fee.calculate_fee(1), Some(1));
+        assert_eq!(fee.calculate_fee(10_000), Some(250));
+        assert_eq!(fee.calculate_fee(10_001), Some(251));
+        assert_eq!(fee.calculate_fee(u64::MAX), Some(5_000));
+        assert_eq!(transfer_fee(0, u64::MAX, 0).calculate_fee(1_000), Some(0));
+        assert_eq!(
+            transfer_fee(0, u64::MAX, MAX_FEE_BASIS_POINTS).calculate_fee(u64::MAX),
+            Some(u64::MAX)
+        );
+        assert_eq!(
+            transfer_fee(0, u64::MAX, u16::MAX).calculate_fee(u64::MAX),
+            None
+        );
+    }
+
+    #[test]
+    fn test_calculate_epoch_fee() {
+        let config = TransferFeeConfig {
+            transfer_fee_config_authority: [1; 32],
+            withdraw_withheld_authority: [2; 32],
+            withheld_amount: 0.into(),
+            older_transfer_fee: transfer_fee(0, u64::MAX, 100),
+            newer_transfer_fee: transfer_fee(10, u64::MAX, 200),
+        };
+
+        assert_eq!(config.calculate_epoch_fee(9, 10_000), Some(100));
+        assert_eq!(config.calculate_epoch_fee(10, 10_000), Some(200));
+        assert_eq!(config.calculate_epoch_fee(11, 10_000), Some(200));
+        assert_eq!(config.calculate_epoch_fee(10, 0), Some(0));
+
+        // The maximum fee of the selected transfer fee applies.
+        let config = TransferFeeConfig {
+            older_transfer_fee: transfer_fee(0, 1_000, MAX_FEE_BASIS_POINTS),
+            newer_transfer_fee: transfer_fee(10, 10, MAX_FEE_BASIS_POINTS),
+            ..config
+        };
+
+        assert_eq!(config.calculate_epoch_fee(9, u64::MAX), Some(1_000));
+        assert_eq!(config.calculate_epoch_fee(10, u64::MAX), Some(10));
+        assert_eq!(config.calculate_epoch_fee(10, 5), Some(5));
+    }
+
+    #[test]
+    fn test_transfer_fee_amount_from_account_info() {
+        let data = account_with_extensions(
+            BaseState::TokenAccount,
+            &token_account_base(&PYUSD_MINT, &[2; 32], 1_000),
+            &[
+                (ExtensionType::TransferFeeAmount, &42u64.to_le_bytes()),
+                (ExtensionType::ImmutableOwner, &[]),
+            ],
+        );
+        let account = TestAccount::with_data([1; 32], crate::ID, &data).info();
+
+        let transfer_fee_amount =
+            unsafe { TransferFeeAmount::from_account_info_unchecked(&account) }.unwrap();
+        assert_eq!(transfer_fee_amount.withheld_amount(), 42);
+
+        // Wrong owner.
+        let account = TestAccount::with_data([1; 32], [9; 32], &data).info();
+        assert_eq!(
+            unsafe { TransferFeeAmount::from_account_info_unchecked(&account) },
+            Err(ProgramError::InvalidAccountOwner)
+        );
+
+        // Mint account.
+        let account = TestAccount::with_data([1; 32], crate::ID, &pyusd_mint_data()).info();
+        assert_eq!(
+            unsafe { TransferFeeAmount::from_account_info_unchecked(&account) },
+            Err(ExtensionError::WrongBaseState {
+                expected: BaseState::TokenAccount,
+                found: BaseState::Mint,
+            }
+            .into())
+        );
+
+        // Token account without extensions.
+        let account = TestAccount::with_data(
+            [1; 32],
+            crate::ID,
+            &token_account_base(&[3; 32], &[2; 32], 0),
+        )
+        .info();
+        assert_eq!(
+            unsafe { TransferFeeAmount::from_account_info_unchecked(&account) },
+            Err(ExtensionError::NotFound.into())
+        );
+    }
+}
@@ -0,0 +1,134 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Instruction, Signer},
+    program_error::ProgramError,
+    ProgramResult,
+};
+
+use crate::{
+    cpi::{invoke_signed_with_bounds, CpiAccounts, IxData},
+    extensions::{
+        transfer_fee::{TransferFeeInstruction, MAX_FEE_BASIS_POINTS},
+        ExtensionDiscriminator,
+    },
+    instructions::MAX_MULTISIG_SIGNERS,
+};
+
+/// Maximum number of accounts of the instruction.
+const MAX_ACCOUNTS: usize = 2 + MAX_MULTISIG_SIGNERS;
+
+/// Set the transfer fee of the mint.
+///
+/// The new fee takes effect two epochs after the instruction is processed.
+///
+/// ### Accounts:
+///   * Single authority
+///   0. `[WRITE]` The mint.
+///   1. `[SIGNER]` The mint's `transfer_fee_config_authority`.
+///
+///   * Multisignature authority
+///   0. `[WRITE]` The mint.
+///   1. `[]` The mint's multisig `transfer_fee_config_authority`.
+///   2. `..2+M` `[SIGNER]` M signer accounts.
+pub struct SetTransferFee<'a, 'b> {
+    /// Mint Account.
+    pub mint: &'a AccountInfo,
+    /// The mint's `transfer_fee_config_authority`.
+    pub authority: &'a AccountInfo,
+    /// Signer accounts when the authority is a multisig.
+    pub signers: &'b [&'a AccountInfo],
+    /// Amount of transfer collected as fees, expressed as basis points of
+    /// the transfer amount, up to [`MAX_FEE_BASIS_POINTS`].
+    pub transfer_fee_basis_points: u16,
+    /// Maximum fee assessed on transfers.
+    pub maximum_fee: u64,
+}
+
+impl SetTransferFee<'_, '_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    #[inline(always)]
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        if self.signers.len() > MAX_MULTISIG_SIGNERS
+            || self.transfer_fee_basis_points > MAX_FEE_BASIS_POINTS
+        {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        // Account metadata
+        let mut accounts = CpiAccounts::<MAX_ACCOUNTS>::new();
+
+        accounts.push(self.mint, true, false);
+        accounts.push_authority(self.authority, self.signers);
+
+        // Instruction data layout:
+        // -  [0]: extension instruction discriminator (1 byte, u8)
+        // -  [1]: instruction discriminator (1 byte, u8)
+        // -  [2..4]: transfer_fee_basis_points (2 bytes, u16)
+        // -  [4..12]: maximum_fee (8 bytes, u64)
+        let mut instruction_data = IxData::<12>::new();
+
+        instruction_data.write_u8(ExtensionDiscriminator::TransferFee as u8);
+        instruction_data.write_u8(TransferFeeInstruction::SetTransferFee as u8);
+        instruction_data.write_u16_le(self.transfer_fee_basis_points);
+        instruction_data.write_u64_le(self.maximum_fee);
+
+        let instruction = Instruction {
+            program_id: &crate::ID,
+            accounts: accounts.metas(),
+            data: instruction_data.finish(),
+        };
+
+        invoke_signed_with_bounds::<MAX_ACCOUNTS>(&instruction, accounts.infos(), signers)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::vec;
+
+    use super::*;
+    use crate::test_utils::{take_single, CapturedMeta, TestAccount};
+
+    #[test]
+    fn test_set_transfer_fee() {
+        let mint = TestAccount::new([1; 32]).writable().info();
+        let multisig = TestAccount::new([2; 32]).info();
+        let signer = TestAccount::new([3; 32]).signer().info();
+
+        let mut set_transfer_fee = SetTransferFee {
+            mint: &mint,
+            authority: &multisig,
+            signers: &[&signer],
+            transfer_fee_basis_points: 0x0102,
+            maximum_fee: 0x0304_0506_0708_090a,
+        };
+        set_transfer_fee.invoke().unwrap();
+
+        let instruction = take_single();
+
+        assert_eq!(
+            instruction.data,
+            vec![26, 5, 0x02, 0x01, 0x0a, 0x09, 0x08, 0x07, 0x06, 0x05, 0x04, 0x03]
+        );
+        assert_eq!(
+            instruction.accounts,
+            vec![
+                CapturedMeta::new([1; 32], true, false),
+                CapturedMeta::new([2; 32], false, false),
+                CapturedMeta::new([3; 32], false, true),
+            ]
+        );
+
+        set_transfer_fee.transfer_fee_basis_points = MAX_FEE_BASIS_POINTS + 1;
+        assert_eq!(
+            set_transfer_fee.invoke(),
+            Err(ProgramError::InvalidArgument)
+        );
+    }
+}
@@ -0,0 +1,183 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Instruction, Signer},
+    program_error::ProgramError,
+    ProgramResult,
+};
+
+use crate::{
+    cpi::{invoke_signed_with_bounds, CpiAccounts, IxData},
+    extensions::{transfer_fee::TransferFeeInstruction, ExtensionDiscriminator},
+    instructions::MAX_MULTISIG_SIGNERS,
+};
+
+/// Maximum number of accounts of the instruction.
+const MAX_ACCOUNTS: usize = 4 + MAX_MULTISIG_SIGNERS;
+
+/// Transfer tokens from one token account to another, asserting the fee
+/// withheld by the mint.
+///
+/// The transfer fails if `fee` does not match the fee calculated by the
+/// token program, which can be obtained with
+/// [`TransferFeeConfig::calculate_epoch_fee`](super::TransferFeeConfig::calculate_epoch_fee).
+///
+/// ### Accounts:
+///   * Single owner/delegate
+///   0. `[WRITE]` The source account.
+///   1. `[]` The token mint.
+///   2. `[WRITE]` The destination account.
+///   3. `[SIGNER]` The source account's owner/delegate.
+///
+///   * Multisignature owner/delegate
+///   0. `[WRITE]` The source account.
+///   1. `[]` The token mint.
+///   2. `[WRITE]` The destination account.
+///   3. `[]` The source account's multisignature owner/delegate.
+///   4. `..4+M` `[SIGNER]` M signer accounts.
+pub struct TransferCheckedWithFee<'a, 'b> {
+    /// Sender account.
+    pub source: &'a AccountInfo,
+    /// Mint Account.
+    pub mint: &'a AccountInfo,
+    /// Recipient account.
+    pub destination: &'a AccountInfo,
+    /// Owner or delegate of the source account.
+    pub authority: &'a AccountInfo,
+    /// Signer accounts when the authority is a multisig.
+    pub signers: &'b [&'a AccountInfo],
+    /// Amount of tokens to transfer, including the fee.
+    pub amount: u64,
+    /// Decimals of the mint.
+    pub decimals: u8,
+    /// Expected fee withheld on the transfer.
+    pub fee: u64,
+}
+
+impl TransferCheckedWithFee<'_, '_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    #[inline(always)]
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        if self.signers.len() > MAX_MULTISIG_SIGNERS {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        // Account metadata
+        let mut accounts = CpiAccounts::<MAX_ACCOUNTS>::new();
+
+        accounts.push(self.source, true, false);
+        accounts.push(self.mint, false, false);
+        accounts.push(self.destination, true, false);
+        accounts.push_authority(self.authority, self.signers);
+
+        // Instruction data layout:
+        // -  [0]: extension instruction discriminator (1 byte, u8)
+        // -  [1]: instruction discriminator (1 byte, u8)
+        // -  [2..10]: amount (8 bytes, u64)
+        // -  [10]: decimals (1 byte, u8)
+        // -  [11..19]: fee (8 bytes, u64)
+        let mut instruction_data = IxData::<19>::new();
+
+        instruction_data.write_u8(ExtensionDiscriminator::TransferFee as u8);
+        instruction_data.write_u8(TransferFeeInstruction::TransferCheckedWithFee as u8);
+        instruction_data.write_u64_le(self.amount);
+        instruction_data.write_u8(self.decimals);
+        instruction_data.write_u64_le(self.fee);
+
+        let instruction = Instruction {
+            program_id: &crate::ID,
+            accounts: accounts.metas(),
+            data: instruction_data.finish(),
+        };
+
+        invoke_signed_with_bounds::<MAX_ACCOUNTS>(&instruction, accounts.infos(), signers)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::vec;
+
+    use super::*;
+    use crate::test_utils::{take_single, CapturedMeta, TestAccount};
+
+    #[test]
+    fn test_transfer_checked_with_fee() {
+        let source = TestAccount::new([1; 32]).writable().info();
+        let mint = TestAccount::new([2; 32]).info();
+        let destination = TestAccount::new([3; 32]).writable().info();
+        let owner = TestAccount::new([4; 32]).signer().info();
+
+        TransferCheckedWithFee {
+            source: &source,
+            mint: &mint,
+            destination: &destination,
+            authority: &owner,
+            signers: &[],
+            amount: 1_000_000,
+            decimals: 6,
+            fee: 2_500,
+        }
+        .invoke()
+        .unwrap();
+
+        let instruction = take_single();
+
+        let mut data = vec![26, 1];
+        data.extend_from_slice(&1_000_000u64.to_le_bytes());
+        data.push(6);
+        data.extend_from_slice(&2_500u64.to_le_bytes());
+
+        assert_eq!(instruction.data, data);
+        assert_eq!(
+            instruction.accounts,
+            vec![
+                CapturedMeta::new([1; 32], true, false),
+                CapturedMeta::new([2; 32], false, false),
+                CapturedMeta::new([3; 32], true, false),
+                CapturedMeta::new([4; 32], false, true),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_transfer_checked_with_fee_multisig() {
+        let source = TestAccount::new([1; 32]).writable().info();
+        let mint = TestAccount::new([2; 32]).info();
+        let destination = TestAccount::new([3; 32]).writable().info();
+        let multisig = TestAccount::new([4; 32]).info();
+        let signer = TestAccount::new([5; 32]).signer().info();
+
+        TransferCheckedWithFee {
+            source: &source,
+            mint: &mint,
+            destination: &destination,
+            authority: &multisig,
+            signers: &[&signer],
+            amount: u64::MAX,
+            decimals: 0,
+            fee: u64::MAX,
+        }
+        .invoke()
+        .unwrap();
+
+        let instruction = take_single();
+
+        assert_eq!(instruction.data.len(), 19);
+        assert_eq!(&instruction.data[2..10], &[0xff; 8]);
+        assert_eq!(instruction.data[10], 0);
+        assert_eq!(&instruction.data[11..], &[0xff; 8]);
+        assert_eq!(
+            &instruction.accounts[3..],
+            &[
+                CapturedMeta::new([4; 32], false, false),
+                CapturedMeta::new([5; 32], false, true),
+            ]
+        );
+    }
+}
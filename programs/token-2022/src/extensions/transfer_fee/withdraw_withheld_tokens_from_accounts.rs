@@ -0,0 +1,179 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Instruction, Signer},
+    program_error::ProgramError,
+    ProgramResult,
+};
+
+use crate::{
+    cpi::{invoke_signed_with_bounds, CpiAccounts, IxData},
+    extensions::{
+        transfer_fee::{TransferFeeInstruction, MAX_SOURCE_ACCOUNTS},
+        ExtensionDiscriminator,
+    },
+    instructions::MAX_MULTISIG_SIGNERS,
+};
+
+/// Maximum number of accounts of the instruction.
+const MAX_ACCOUNTS: usize = 3 + MAX_MULTISIG_SIGNERS + MAX_SOURCE_ACCOUNTS;
+
+/// Transfer all withheld tokens from token accounts to a fee receiver
+/// account.
+///
+/// ### Accounts:
+///   * Single authority
+///   0. `[]` The token mint.
+///   1. `[WRITE]` The fee receiver account.
+///   2. `[SIGNER]` The mint's `withdraw_withheld_authority`.
+///   3. `..3+N` `[WRITE]` The source accounts to withdraw from.
+///
+///   * Multisignature authority
+///   0. `[]` The token mint.
+///   1. `[WRITE]` The fee receiver account.
+///   2. `[]` The mint's multisig `withdraw_withheld_authority`.
+///   3. `..3+M` `[SIGNER]` M signer accounts.
+///   4. `3+M+1..3+M+N` `[WRITE]` The source accounts to withdraw from.
+pub struct WithdrawWithheldTokensFromAccounts<'a, 'b> {
+    /// Mint Account.
+    pub mint: &'a AccountInfo,
+    /// Fee receiver token account.
+    pub destination: &'a AccountInfo,
+    /// The mint's `withdraw_withheld_authority`.
+    pub authority: &'a AccountInfo,
+    /// Signer accounts when the authority is a multisig.
+    pub signers: &'b [&'a AccountInfo],
+    /// Token accounts to withdraw the withheld tokens from, up to
+    /// [`MAX_SOURCE_ACCOUNTS`].
+    pub sources: &'b [&'a AccountInfo],
+}
+
+impl WithdrawWithheldTokensFromAccounts<'_, '_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    #[inline(always)]
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        if self.signers.len() > MAX_MULTISIG_SIGNERS || self.sources.len() > MAX_SOURCE_ACCOUNTS {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        // Account metadata
+        let mut accounts = CpiAccounts::<MAX_ACCOUNTS>::new();
+
+        accounts.push(self.mint, false, false);
+        accounts.push(self.destination, true, false);
+        accounts.push_authority(self.authority, self.signers);
+
+        for source in self.sources {
+            accounts.push(source, true, false);
+        }
+
+        // Instruction data layout:
+        // -  [0]: extension instruction discriminator (1 byte, u8)
+        // -  [1]: instruction discriminator (1 byte, u8)
+        // -  [2]: num_token_accounts (1 byte, u8)
+        let mut instruction_data = IxData::<3>::new();
+
+        instruction_data.write_u8(ExtensionDiscriminator::TransferFee as u8);
+        instruction_data.write_u8(TransferFeeInstruction::WithdrawWithheldTokensFromAccounts as u8);
+        instruction_data.write_u8(self.sources.len() as u8);
+
+        let instruction = Instruction {
+            program_id: &crate::ID,
+            accounts: accounts.metas(),
+            data: instruction_data.finish(),
+        };
+
+        invoke_signed_with_bounds::<MAX_ACCOUNTS>(&instruction, accounts.infos(), signers)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::{vec, vec::Vec};
+
+    use super::*;
+    use crate::test_utils::{take_single, CapturedMeta, TestAccount};
+
+    #[test]
+    fn test_withdraw_withheld_tokens_from_accounts_multisig() {
+        let mint = TestAccount::new([1; 32]).info();
+        let destination = TestAccount::new([2; 32]).writable().info();
+        let multisig = TestAccount::new([3; 32]).info();
+        let signer_1 = TestAccount::new([4; 32]).signer().info();
+        let signer_2 = TestAccount::new([5; 32]).signer().info();
+        let sources = (0..3)
+            .map(|i| TestAccount::new([100 + i; 32]).writable().info())
+            .collect::<Vec<_>>();
+
+        WithdrawWithheldTokensFromAccounts {
+            mint: &mint,
+            destination: &destination,
+            authority: &multisig,
+            signers: &[&signer_1, &signer_2],
+            sources: &sources.iter().collect::<Vec<_>>(),
+        }
+        .invoke()
+        .unwrap();
+
+        let instruction = take_single();
+
+        assert_eq!(instruction.data, vec![26, 3, 3]);
+        assert_eq!(
+            instruction.accounts,
+            vec![
+                CapturedMeta::new([1; 32], false, false),
+                CapturedMeta::new([2; 32], true, false),
+                CapturedMeta::new([3; 32], false, false),
+                CapturedMeta::new([4; 32], false, true),
+                CapturedMeta::new([5; 32], false, true),
+                CapturedMeta::new([100; 32], true, false),
+                CapturedMeta::new([101; 32], true, false),
+                CapturedMeta::new([102; 32], true, false),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_withdraw_withheld_tokens_from_accounts_bounds() {
+        let mint = TestAccount::new([1; 32]).info();
+        let destination = TestAccount::new([2; 32]).writable().info();
+        let authority = TestAccount::new([3; 32]).signer().info();
+        let sources = (0..=MAX_SOURCE_ACCOUNTS as u8)
+            .map(|i| TestAccount::new([100 + i; 32]).writable().info())
+            .collect::<Vec<_>>();
+        let sources = sources.iter().collect::<Vec<_>>();
+
+        WithdrawWithheldTokensFromAccounts {
+            mint: &mint,
+            destination: &destination,
+            authority: &authority,
+            signers: &[],
+            sources: &sources[..MAX_SOURCE_ACCOUNTS],
+        }
+        .invoke()
+        .unwrap();
+
+        let instruction = take_single();
+
+        assert_eq!(instruction.data, vec![26, 3, MAX_SOURCE_ACCOUNTS as u8]);
+        assert_eq!(instruction.accounts.len(), 3 + MAX_SOURCE_ACCOUNTS);
+        assert_eq!(
+            instruction.accounts[2],
+            CapturedMeta::new([3; 32], false, true)
+        );
+
+        let withdraw = WithdrawWithheldTokensFromAccounts {
+            mint: &mint,
+            destination: &destination,
+            authority: &authority,
+            signers: &[],
+            sources: &sources,
+        };
+        assert_eq!(withdraw.invoke(), Err(ProgramError::InvalidArgument));
+    }
+}
@@ -0,0 +1,146 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Instruction, Signer},
+    program_error::ProgramError,
+    ProgramResult,
+};
+
+use crate::{
+    cpi::{invoke_signed_with_bounds, CpiAccounts, IxData},
+    extensions::{transfer_fee::TransferFeeInstruction, ExtensionDiscriminator},
+    instructions::MAX_MULTISIG_SIGNERS,
+};
+
+/// Maximum number of accounts of the instruction.
+const MAX_ACCOUNTS: usize = 3 + MAX_MULTISIG_SIGNERS;
+
+/// Transfer all withheld tokens of the mint to a token account.
+///
+/// ### Accounts:
+///   * Single authority
+///   0. `[WRITE]` The token mint.
+///   1. `[WRITE]` The fee receiver account.
+///   2. `[SIGNER]` The mint's `withdraw_withheld_authority`.
+///
+///   * Multisignature authority
+///   0. `[WRITE]` The token mint.
+///   1. `[WRITE]` The fee receiver account.
+///   2. `[]` The mint's multisig `withdraw_withheld_authority`.
+///   3. `..3+M` `[SIGNER]` M signer accounts.
+pub struct WithdrawWithheldTokensFromMint<'a, 'b> {
+    /// Mint Account.
+    pub mint: &'a AccountInfo,
+    /// Token account receiving the withheld tokens.
+    pub destination: &'a AccountInfo,
+    /// The mint's withdraw withheld authority.
+    pub authority: &'a AccountInfo,
+    /// Signer accounts when the authority is a multisig.
+    pub signers: &'b [&'a AccountInfo],
+}
+
+impl WithdrawWithheldTokensFromMint<'_, '_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    #[inline(always)]
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        if self.signers.len() > MAX_MULTISIG_SIGNERS {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        // Account metadata
+        let mut accounts = CpiAccounts::<MAX_ACCOUNTS>::new();
+
+        accounts.push(self.mint, true, false);
+        accounts.push(self.destination, true, false);
+        accounts.push_authority(self.authority, self.signers);
+
+        // Instruction data layout:
+        // -  [0]: extension instruction discriminator (1 byte, u8)
+        // -  [1]: instruction discriminator (1 byte, u8)
+        let mut instruction_data = IxData::<2>::new();
+
+        instruction_data.write_u8(ExtensionDiscriminator::TransferFee as u8);
+        instruction_data.write_u8(TransferFeeInstruction::WithdrawWithheldTokensFromMint as u8);
+
+        let instruction = Instruction {
+            program_id: &crate::ID,
+            accounts: accounts.metas(),
+            data: instruction_data.finish(),
+        };
+
+        invoke_signed_with_bounds::<MAX_ACCOUNTS>(&instruction, accounts.infos(), signers)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::vec;
+
+    use super::*;
+    use crate::test_utils::{take_single, CapturedMeta, TestAccount};
+
+    #[test]
+    fn test_withdraw_withheld_tokens_from_mint() {
+        let mint = TestAccount::new([1; 32]).writable().info();
+        let destination = TestAccount::new([2; 32]).writable().info();
+        let authority = TestAccount::new([3; 32]).signer().info();
+
+        WithdrawWithheldTokensFromMint {
+            mint: &mint,
+            destination: &destination,
+            authority: &authority,
+            signers: &[],
+        }
+        .invoke()
+        .unwrap();
+
+        let instruction = take_single();
+
+        assert_eq!(instruction.data, vec![26, 2]);
+        assert_eq!(
+            instruction.accounts,
+            vec![
+                CapturedMeta::new([1; 32], true, false),
+                CapturedMeta::new([2; 32], true, false),
+                CapturedMeta::new([3; 32], false, true),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_withdraw_withheld_tokens_from_mint_multisig() {
+        let mint = TestAccount::new([1; 32]).writable().info();
+        let destination = TestAccount::new([2; 32]).writable().info();
+        let multisig = TestAccount::new([3; 32]).info();
+        let signer_1 = TestAccount::new([4; 32]).signer().info();
+        let signer_2 = TestAccount::new([5; 32]).signer().info();
+
+        WithdrawWithheldTokensFromMint {
+            mint: &mint,
+            destination: &destination,
+            authority: &multisig,
+            signers: &[&signer_1, &signer_2],
+        }
+        .invoke()
+        .unwrap();
+
+        let instruction = take_single();
+
+        assert_eq!(instruction.data, vec![26, 2]);
+        assert_eq!(
+            instruction.accounts,
+            vec![
+                CapturedMeta::new([1; 32], true, false),
+                CapturedMeta::new([2; 32], true, false),
+                CapturedMeta::new([3; 32], false, false),
+                CapturedMeta::new([4; 32], false, true),
+                CapturedMeta::new([5; 32], false, true),
+            ]
+        );
+    }
+}
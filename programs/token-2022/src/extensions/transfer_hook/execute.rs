@@ -0,0 +1,165 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Instruction, Signer},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    ProgramResult,
+};
+
+use crate::{
+    cpi::{invoke_signed_with_bounds, CpiAccounts, IxData},
+    extensions::transfer_hook::{EXECUTE_DISCRIMINATOR, MAX_EXTRA_ACCOUNTS},
+};
+
+/// Maximum number of accounts of the instruction.
+const MAX_ACCOUNTS: usize = 5 + MAX_EXTRA_ACCOUNTS;
+
+/// Invoke the `Execute` instruction of a transfer hook program, as the token
+/// program does on transfers.
+///
+/// The extra accounts are passed with the writable and signer flags of their
+/// account infos, in the order of the extra account metas of the hook.
+///
+/// ### Accounts:
+///   0. `[]` The source account.
+///   1. `[]` The token mint.
+///   2. `[]` The destination account.
+///   3. `[]` The source account's owner/delegate.
+///   4. `[]` The validation account holding the extra account metas.
+///   5. `..5+N` The extra accounts required by the hook.
+pub struct Execute<'a, 'b> {
+    /// Sender account.
+    pub source: &'a AccountInfo,
+    /// Mint Account.
+    pub mint: &'a AccountInfo,
+    /// Recipient account.
+    pub destination: &'a AccountInfo,
+    /// Owner or delegate of the source account.
+    pub owner: &'a AccountInfo,
+    /// Validation account holding the extra account metas.
+    pub extra_account_metas: &'a AccountInfo,
+    /// Extra accounts required by the hook, up to [`MAX_EXTRA_ACCOUNTS`].
+    pub extra_accounts: &'b [&'a AccountInfo],
+    /// Amount of tokens transferred.
+    pub amount: u64,
+    /// Transfer hook program.
+    pub program_id: &'b Pubkey,
+}
+
+impl Execute<'_, '_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    #[inline(always)]
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        if self.extra_accounts.len() > MAX_EXTRA_ACCOUNTS {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        // Account metadata
+        let mut accounts = CpiAccounts::<MAX_ACCOUNTS>::new();
+
+        accounts.push(self.source, false, false);
+        accounts.push(self.mint, false, false);
+        accounts.push(self.destination, false, false);
+        accounts.push(self.owner, false, false);
+        accounts.push(self.extra_account_metas, false, false);
+
+        for account in self.extra_accounts {
+            accounts.push(account, account.is_writable(), account.is_signer());
+        }
+
+        // Instruction data layout:
+        // -  [0..8]: instruction discriminator (8 bytes, [u8; 8])
+        // -  [8..16]: amount (8 bytes, u64)
+        let mut instruction_data = IxData::<16>::new();
+
+        instruction_data.write_bytes(&EXECUTE_DISCRIMINATOR);
+        instruction_data.write_u64_le(self.amount);
+
+        let instruction = Instruction {
+            program_id: self.program_id,
+            accounts: accounts.metas(),
+            data: instruction_data.finish(),
+        };
+
+        invoke_signed_with_bounds::<MAX_ACCOUNTS>(&instruction, accounts.infos(), signers)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::{vec, vec::Vec};
+
+    use super::*;
+    use crate::test_utils::{take_single, CapturedMeta, TestAccount};
+
+    #[test]
+    fn test_execute() {
+        let source = TestAccount::new([1; 32]).writable().info();
+        let mint = TestAccount::new([2; 32]).info();
+        let destination = TestAccount::new([3; 32]).writable().info();
+        let owner = TestAccount::new([4; 32]).signer().info();
+        let validation = TestAccount::new([5; 32]).info();
+        let counter = TestAccount::new([6; 32]).writable().info();
+        let config = TestAccount::new([7; 32]).info();
+
+        Execute {
+            source: &source,
+            mint: &mint,
+            destination: &destination,
+            owner: &owner,
+            extra_account_metas: &validation,
+            extra_accounts: &[&counter, &config],
+            amount: 1_000,
+            program_id: &[9; 32],
+        }
+        .invoke()
+        .unwrap();
+
+        let instruction = take_single();
+
+        assert_eq!(instruction.program_id, [9; 32]);
+        assert_eq!(
+            instruction.data,
+            vec![105, 37, 101, 197, 75, 251, 102, 26, 0xe8, 0x03, 0, 0, 0, 0, 0, 0]
+        );
+        assert_eq!(
+            instruction.accounts,
+            vec![
+                CapturedMeta::new([1; 32], false, false),
+                CapturedMeta::new([2; 32], false, false),
+                CapturedMeta::new([3; 32], false, false),
+                CapturedMeta::new([4; 32], false, false),
+                CapturedMeta::new([5; 32], false, false),
+                CapturedMeta::new([6; 32], true, false),
+                CapturedMeta::new([7; 32], false, false),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_execute_too_many_extra_accounts() {
+        let account = TestAccount::new([1; 32]).info();
+        let extra_accounts = (0..=MAX_EXTRA_ACCOUNTS)
+            .map(|_| &account)
+            .collect::<Vec<_>>();
+
+        let execute = Execute {
+            source: &account,
+            mint: &account,
+            destination: &account,
+            owner: &account,
+            extra_account_metas: &account,
+            extra_accounts: &extra_accounts,
+            amount: 0,
+            program_id: &[9; 32],
+        };
+
+        assert_eq!(execute.invoke(), Err(ProgramError::InvalidArgument));
+    }
+}
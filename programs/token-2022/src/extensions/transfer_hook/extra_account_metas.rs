@@ -0,0 +1,217 @@
+use core::slice::from_raw_parts;
+
+use pinocchio::program_error::ProgramError;
+
+use crate::extensions::transfer_hook::EXECUTE_DISCRIMINATOR;
+
+/// Length of the header of a TLV entry of the validation account: an 8-byte
+/// discriminator and a `u32` length.
+const TLV_HEADER_LEN: usize = 12;
+
+/// Extra account required by a transfer hook, as stored in the validation
+/// account.
+///
+/// The address is resolved according to the `discriminator`:
+///   * `0`: `address_config` is the address of the account.
+///   * `1`: `address_config` holds the packed seeds of a PDA of the hook
+///     program.
+///   * `128 + i`: `address_config` holds the packed seeds of a PDA of the
+///     program at the account index `i`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ExtraAccountMeta {
+    /// How the address is resolved.
+    pub discriminator: u8,
+    /// Address or packed seeds of the account.
+    pub address_config: [u8; 32],
+    /// Indicates whether the account is a signer.
+    pub is_signer: u8,
+    /// Indicates whether the account is writable.
+    pub is_writable: u8,
+}
+
+impl ExtraAccountMeta {
+    /// Length of an `ExtraAccountMeta`.
+    pub const LEN: usize = core::mem::size_of::<Self>();
+
+    #[inline(always)]
+    pub fn is_signer(&self) -> bool {
+        self.is_signer != 0
+    }
+
+    #[inline(always)]
+    pub fn is_writable(&self) -> bool {
+        self.is_writable != 0
+    }
+}
+
+/// Zero-copy view of the extra account metas of the `Execute` instruction
+/// stored in the validation account of a transfer hook program.
+///
+/// The validation account holds TLV entries, each with an 8-byte instruction
+/// discriminator, a `u32` length and the value; the value of the `Execute`
+/// entry is a `u32` count followed by the `ExtraAccountMeta`s.
+#[derive(Clone, Copy, Debug)]
+pub struct ExtraAccountMetaList<'a> {
+    metas: &'a [ExtraAccountMeta],
+}
+
+impl<'a> ExtraAccountMetaList<'a> {
+    /// Return the extra account metas of the `Execute` instruction stored in
+    /// the given validation account data.
+    ///
+    /// Returns `InvalidAccountData` if the data does not hold an `Execute`
+    /// entry or the entry is truncated.
+    pub fn from_bytes(data: &'a [u8]) -> Result<Self, ProgramError> {
+        let value = find_execute_entry(data).ok_or(ProgramError::InvalidAccountData)?;
+
+        let count = value
+            .get(..4)
+            .map(|count| u32::from_le_bytes([count[0], count[1], count[2], count[3]]) as usize)
+            .ok_or(ProgramError::InvalidAccountData)?;
+
+        let metas = count
+            .checked_mul(ExtraAccountMeta::LEN)
+            .and_then(|len| value.get(4..4 + len))
+            .ok_or(ProgramError::InvalidAccountData)?;
+
+        Ok(Self {
+            // SAFETY: `ExtraAccountMeta` has an alignment of 1, is valid for
+            // any byte pattern and `metas` holds `count` of them.
+            metas: unsafe { from_raw_parts(metas.as_ptr() as *const ExtraAccountMeta, count) },
+        })
+    }
+
+    /// Return the number of extra account metas.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.metas.len()
+    }
+
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.metas.is_empty()
+    }
+
+    /// Return the extra account metas, in instruction order.
+    #[inline(always)]
+    pub fn metas(&self) -> &'a [ExtraAccountMeta] {
+        self.metas
+    }
+
+    /// Return an iterator over the extra account metas, in instruction order.
+    #[inline(always)]
+    pub fn iter(&self) -> core::slice::Iter<'a, ExtraAccountMeta> {
+        self.metas.iter()
+    }
+}
+
+/// Return the value of the `Execute` TLV entry of the validation account.
+fn find_execute_entry(data: &[u8]) -> Option<&[u8]> {
+    let mut offset = 0;
+
+    while offset < data.len() {
+        let header = data.get(offset..offset + TLV_HEADER_LEN)?;
+        let length = u32::from_le_bytes([header[8], header[9], header[10], header[11]]) as usize;
+        let start = offset + TLV_HEADER_LEN;
+        let end = start.checked_add(length)?;
+
+        if header[..8] == EXECUTE_DISCRIMINATOR {
+            return data.get(start..end);
+        }
+
+        offset = end;
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::vec::Vec;
+
+    use super::*;
+
+    /// Validation account data with two extra account metas, hand-encoded
+    /// following the `ExtraAccountMetaList` layout of
+    /// spl-tlv-account-resolution:
+    ///   * the literal address `[7; 32]`, writable;
+    ///   * a PDA of the hook program with the seeds `Literal(b"counter")`
+    ///     and `AccountKey(0)`, writable.
+    fn validation_data() -> Vec<u8> {
+        let mut data = Vec::new();
+        // TLV header: `Execute` discriminator and the value length.
+        data.extend_from_slice(&[105, 37, 101, 197, 75, 251, 102, 26]);
+        data.extend_from_slice(&74u32.to_le_bytes());
+        // Number of extra account metas.
+        data.extend_from_slice(&2u32.to_le_bytes());
+        // Literal address.
+        data.push(0);
+        data.extend_from_slice(&[7; 32]);
+        data.extend_from_slice(&[0, 1]);
+        // PDA of the hook program.
+        data.push(1);
+        let mut seeds = [0; 32];
+        seeds[..9].copy_from_slice(&[1, 7, b'c', b'o', b'u', b'n', b't', b'e', b'r']);
+        seeds[9..11].copy_from_slice(&[3, 0]);
+        data.extend_from_slice(&seeds);
+        data.extend_from_slice(&[0, 1]);
+        data
+    }
+
+    #[test]
+    fn test_extra_account_meta_list() {
+        let data = validation_data();
+        let list = ExtraAccountMetaList::from_bytes(&data).unwrap();
+
+        assert_eq!(list.len(), 2);
+
+        let metas = list.iter().collect::<Vec<_>>();
+
+        assert_eq!(metas[0].discriminator, 0);
+        assert_eq!(metas[0].address_config, [7; 32]);
+        assert!(!metas[0].is_signer());
+        assert!(metas[0].is_writable());
+
+        assert_eq!(metas[1].discriminator, 1);
+        assert_eq!(&metas[1].address_config[..11], b"\x01\x07counter\x03\x00");
+        assert!(metas[1].is_writable());
+    }
+
+    #[test]
+    fn test_extra_account_meta_list_after_other_entries() {
+        // An entry of another instruction precedes the `Execute` entry.
+        let mut data = [1; 8].to_vec();
+        data.extend_from_slice(&3u32.to_le_bytes());
+        data.extend_from_slice(&[0; 3]);
+        data.extend(validation_data());
+
+        assert_eq!(ExtraAccountMetaList::from_bytes(&data).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_extra_account_meta_list_truncated() {
+        let data = validation_data();
+
+        for len in [0, 11, 15, 16 + ExtraAccountMeta::LEN, data.len() - 1] {
+            assert_eq!(
+                ExtraAccountMetaList::from_bytes(&data[..len]).err(),
+                Some(ProgramError::InvalidAccountData),
+                "length {len}"
+            );
+        }
+
+        // A count larger than the entry.
+        let mut data = validation_data();
+        data[12..16].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(ExtraAccountMetaList::from_bytes(&data).is_err());
+
+        // An empty list.
+        let mut data = [105, 37, 101, 197, 75, 251, 102, 26].to_vec();
+        data.extend_from_slice(&4u32.to_le_bytes());
+        data.extend_from_slice(&0u32.to_le_bytes());
+        assert!(ExtraAccountMetaList::from_bytes(&data).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,182 @@
+//! Transfer hook extension.
+//!
+//! The `Execute` instruction of transfer hook programs follows the SPL
+//! transfer-hook interface, which identifies instructions with an 8-byte
+//! discriminator. The extra accounts required by the hook are stored in the
+//! validation account of the hook program as an [`ExtraAccountMetaList`].
+
+mod execute;
+mod extra_account_metas;
+mod resolve;
+
+pub use execute::*;
+pub use extra_account_metas::*;
+pub use resolve::*;
+
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Instruction, Signer},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    ProgramResult,
+};
+
+use crate::{
+    cpi::{invoke_signed_with_bounds, CpiAccounts, IxData},
+    extensions::{BaseState, Extension, ExtensionDiscriminator, ExtensionType},
+    instructions::MAX_MULTISIG_SIGNERS,
+    pod::PodBool,
+};
+
+/// Discriminator of the `Execute` instruction, the first 8 bytes of the
+/// SHA-256 hash of `spl-transfer-hook-interface:execute`.
+pub const EXECUTE_DISCRIMINATOR: [u8; 8] = [105, 37, 101, 197, 75, 251, 102, 26];
+
+/// Maximum number of extra accounts of a transfer hook.
+pub const MAX_EXTRA_ACCOUNTS: usize = 16;
+
+/// Maximum number of accounts of the `Update` instruction.
+const MAX_UPDATE_ACCOUNTS: usize = 2 + MAX_MULTISIG_SIGNERS;
+
+/// Instruction discriminators of the transfer hook extension.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TransferHookInstruction {
+    Initialize = 0,
+    Update = 1,
+}
+
+/// Transfer hook program of a mint.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TransferHook {
+    /// Authority that can set the transfer hook program id.
+    pub authority: Pubkey,
+    /// Program that authorizes the transfer.
+    pub program_id: Pubkey,
+}
+
+unsafe impl Extension for TransferHook {
+    const TYPE: ExtensionType = ExtensionType::TransferHook;
+    const LEN: usize = core::mem::size_of::<Self>();
+    const BASE_STATE: BaseState = BaseState::Mint;
+}
+
+const _: () = assert!(TransferHook::LEN == 64);
+
+/// Transfer hook state of a token account.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TransferHookAccount {
+    /// Flag to indicate that the account is in the middle of a transfer.
+    pub transferring: PodBool,
+}
+
+unsafe impl Extension for TransferHookAccount {
+    const TYPE: ExtensionType = ExtensionType::TransferHookAccount;
+    const LEN: usize = core::mem::size_of::<Self>();
+    const BASE_STATE: BaseState = BaseState::TokenAccount;
+}
+
+const _: () = assert!(TransferHookAccount::LEN == 1);
+
+/// Update the transfer hook program of a mint.
+///
+/// ### Accounts:
+///   * Single authority
+///   0. `[WRITE]` The mint.
+///   1. `[SIGNER]` The transfer hook authority.
+///
+///   * Multisignature authority
+///   0. `[WRITE]` The mint.
+///   1. `[]` The transfer hook multisig authority.
+///   2. `..2+M` `[SIGNER]` M signer accounts.
+pub struct Update<'a, 'b> {
+    /// Mint Account.
+    pub mint: &'a AccountInfo,
+    /// The transfer hook authority.
+    pub authority: &'a AccountInfo,
+    /// Signer accounts when the authority is a multisig.
+    pub signers: &'b [&'a AccountInfo],
+    /// New transfer hook program, or `None` to disable the transfer hook.
+    pub program_id: Option<&'a Pubkey>,
+}
+
+impl Update<'_, '_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    #[inline(always)]
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        if self.signers.len() > MAX_MULTISIG_SIGNERS {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        // Account metadata
+        let mut accounts = CpiAccounts::<MAX_UPDATE_ACCOUNTS>::new();
+
+        accounts.push(self.mint, true, false);
+        accounts.push_authority(self.authority, self.signers);
+
+        // Instruction data layout:
+        // -  [0]: extension instruction discriminator (1 byte, u8)
+        // -  [1]: instruction discriminator (1 byte, u8)
+        // -  [2..34]: program_id (32 bytes, Pubkey, zeroed if `None`)
+        let mut instruction_data = IxData::<34>::new();
+
+        instruction_data.write_u8(ExtensionDiscriminator::TransferHook as u8);
+        instruction_data.write_u8(TransferHookInstruction::Update as u8);
+        instruction_data.write_pubkey(self.program_id.unwrap_or(&Pubkey::default()));
+
+        let instruction = Instruction {
+            program_id: &crate::ID,
+            accounts: accounts.metas(),
+            data: instruction_data.finish(),
+        };
+
+        invoke_signed_with_bounds::<MAX_UPDATE_ACCOUNTS>(&instruction, accounts.infos(), signers)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::vec;
+
+    use super::*;
+    use crate::test_utils::{take_single, CapturedMeta, TestAccount};
+
+    #[test]
+    fn test_update_transfer_hook() {
+        let mint = TestAccount::new([1; 32]).writable().info();
+        let authority = TestAccount::new([2; 32]).signer().info();
+
+        Update {
+            mint: &mint,
+            authority: &authority,
+            signers: &[],
+            program_id: Some(&[3; 32]),
+        }
+        .invoke()
+        .unwrap();
+
+        let instruction = take_single();
+
+        let mut data = vec![36, 1];
+        data.extend_from_slice(&[3; 32]);
+
+        assert_eq!(instruction.data, data);
+        assert_eq!(
+            instruction.accounts,
+            vec![
+                CapturedMeta::new([1; 32], true, false),
+                CapturedMeta::new([2; 32], false, true),
+            ]
+        );
+        // The authority account info is passed along with the mint.
+        assert_eq!(instruction.account_infos, vec![[1; 32], [2; 32]]);
+    }
+}
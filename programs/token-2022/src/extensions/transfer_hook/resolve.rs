@@ -0,0 +1,481 @@
+use core::mem::MaybeUninit;
+
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::AccountMeta,
+    program_error::ProgramError,
+    pubkey::{Pubkey, MAX_SEEDS, MAX_SEED_LEN},
+};
+
+use crate::{
+    cpi::CpiAccounts,
+    extensions::transfer_hook::{ExtraAccountMeta, ExtraAccountMetaList, MAX_EXTRA_ACCOUNTS},
+};
+
+/// Number of accounts of the `Execute` instruction preceding the extra
+/// accounts.
+const EXECUTE_ACCOUNTS_LEN: usize = 5;
+
+/// Discriminator of extra accounts with a literal address.
+const LITERAL_ADDRESS: u8 = 0;
+
+/// Discriminator of extra accounts that are PDAs of the hook program.
+const HOOK_PROGRAM_PDA: u8 = 1;
+
+/// Offset of the discriminators of extra accounts that are PDAs of the
+/// program at an account index.
+const ACCOUNT_PROGRAM_PDA: u8 = 128;
+
+/// Accounts of the `Execute` instruction preceding the extra accounts.
+///
+/// The seeds of extra accounts reference accounts by their index in the
+/// `Execute` instruction, starting with these accounts.
+#[derive(Clone, Copy)]
+pub struct ExecuteAccounts<'a> {
+    /// Sender account.
+    pub source: &'a AccountInfo,
+    /// Mint Account.
+    pub mint: &'a AccountInfo,
+    /// Recipient account.
+    pub destination: &'a AccountInfo,
+    /// Owner or delegate of the source account.
+    pub owner: &'a AccountInfo,
+    /// Validation account holding the extra account metas.
+    pub extra_account_metas: &'a AccountInfo,
+}
+
+/// Extra accounts of a transfer hook resolved from its extra account metas.
+pub struct ResolvedExtraAccounts<'a> {
+    accounts: CpiAccounts<'a, MAX_EXTRA_ACCOUNTS>,
+}
+
+impl<'a> ResolvedExtraAccounts<'a> {
+    /// Return the account metas of the extra accounts, in instruction order.
+    #[inline(always)]
+    pub fn metas(&self) -> &[AccountMeta<'a>] {
+        self.accounts.metas()
+    }
+
+    /// Return the account infos of the extra accounts, in instruction order.
+    #[inline(always)]
+    pub fn infos(&self) -> &[&'a AccountInfo] {
+        self.accounts.infos()
+    }
+}
+
+/// Resolve the extra accounts of a transfer hook, to be appended to the
+/// accounts of a `TransferChecked` (or `Execute`) CPI.
+///
+/// The address of each extra account is resolved from its meta: literal
+/// addresses are used as is, while PDAs are derived from their seeds, which
+/// may reference the `instruction_data` of the `Execute` instruction and the
+/// key or data of the accounts preceding the extra account. The account with
+/// the resolved address is then looked up in `candidates`.
+///
+/// Returns `NotEnoughAccountKeys` if a resolved address is not in
+/// `candidates`, and `InvalidAccountData` if a meta cannot be resolved (e.g.,
+/// its seeds reference data out of bounds).
+pub fn resolve_extra_account_metas<'a>(
+    extra_account_metas: &ExtraAccountMetaList,
+    execute_accounts: &ExecuteAccounts<'a>,
+    instruction_data: &[u8],
+    program_id: &Pubkey,
+    candidates: &[&'a AccountInfo],
+) -> Result<ResolvedExtraAccounts<'a>, ProgramError> {
+    if extra_account_metas.len() > MAX_EXTRA_ACCOUNTS {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let mut resolved = ResolvedExtraAccounts {
+        accounts: CpiAccounts::new(),
+    };
+
+    for meta in extra_account_metas.iter() {
+        let address = match meta.discriminator {
+            LITERAL_ADDRESS => meta.address_config,
+            HOOK_PROGRAM_PDA => derive_address(
+                meta,
+                execute_accounts,
+                &resolved,
+                instruction_data,
+                program_id,
+            )?,
+            discriminator if discriminator >= ACCOUNT_PROGRAM_PDA => {
+                let index = (discriminator - ACCOUNT_PROGRAM_PDA) as usize;
+                let program = account_at(execute_accounts, &resolved, index)?;
+                derive_address(
+                    meta,
+                    execute_accounts,
+                    &resolved,
+                    instruction_data,
+                    program.key(),
+                )?
+            }
+            _ => return Err(ProgramError::InvalidAccountData),
+        };
+
+        let account = candidates
+            .iter()
+            .find(|candidate| candidate.key() == &address)
+            .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+        resolved
+            .accounts
+            .push(account, meta.is_writable(), meta.is_signer());
+    }
+
+    Ok(resolved)
+}
+
+/// Return the account at `index` of the `Execute` instruction, among the
+/// accounts resolved so far.
+#[inline(always)]
+fn account_at<'a>(
+    execute_accounts: &ExecuteAccounts<'a>,
+    resolved: &ResolvedExtraAccounts<'a>,
+    index: usize,
+) -> Result<&'a AccountInfo, ProgramError> {
+    match index {
+        0 => Ok(execute_accounts.source),
+        1 => Ok(execute_accounts.mint),
+        2 => Ok(execute_accounts.destination),
+        3 => Ok(execute_accounts.owner),
+        4 => Ok(execute_accounts.extra_account_metas),
+        _ => resolved
+            .infos()
+            .get(index - EXECUTE_ACCOUNTS_LEN)
+            .copied()
+            .ok_or(ProgramError::InvalidAccountData),
+    }
+}
+
+/// Derive the address of a PDA extra account from its packed seeds.
+///
+/// Seeds are packed in the address config as a tag followed by its
+/// arguments, until the config ends or a `0` tag is found:
+///   * `1`: a literal, as a `u8` length and the bytes;
+///   * `2`: a slice of the instruction data, as a `u8` index and length;
+///   * `3`: the key of an account, as a `u8` account index;
+///   * `4`: a slice of the data of an account, as a `u8` account index,
+///     data index and length.
+fn derive_address(
+    meta: &ExtraAccountMeta,
+    execute_accounts: &ExecuteAccounts,
+    resolved: &ResolvedExtraAccounts,
+    instruction_data: &[u8],
+    program_id: &Pubkey,
+) -> Result<Pubkey, ProgramError> {
+    let config = &meta.address_config;
+
+    // Seeds are copied since account data cannot stay borrowed.
+    let mut seed_bytes = [[0u8; MAX_SEED_LEN]; MAX_SEEDS];
+    let mut seed_lens = [0usize; MAX_SEEDS];
+    let mut count = 0;
+    let mut offset = 0;
+
+    while offset < config.len() && config[offset] != 0 {
+        if count == MAX_SEEDS {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let args = |len: usize| {
+            config
+                .get(offset + 1..offset + 1 + len)
+                .ok_or(ProgramError::InvalidAccountData)
+        };
+
+        let seed = &mut seed_bytes[count];
+
+        let len = match config[offset] {
+            1 => {
+                let length = args(1)?[0] as usize;
+                let literal = args(1 + length)?;
+                copy_seed(seed, &literal[1..])?;
+                offset += 2 + length;
+                length
+            }
+            2 => {
+                let args = args(2)?;
+                let (index, length) = (args[0] as usize, args[1] as usize);
+                let data = instruction_data
+                    .get(index..index + length)
+                    .ok_or(ProgramError::InvalidAccountData)?;
+                copy_seed(seed, data)?;
+                offset += 3;
+                data.len()
+            }
+            3 => {
+                let account = account_at(execute_accounts, resolved, args(1)?[0] as usize)?;
+                copy_seed(seed, account.key())?;
+                offset += 2;
+                account.key().len()
+            }
+            4 => {
+                let args = args(3)?;
+                let (data_index, length) = (args[1] as usize, args[2] as usize);
+                let account = account_at(execute_accounts, resolved, args[0] as usize)?;
+                let account_data = account.try_borrow_data()?;
+                let data = account_data
+                    .get(data_index..data_index + length)
+                    .ok_or(ProgramError::InvalidAccountData)?;
+                copy_seed(seed, data)?;
+                offset += 4;
+                data.len()
+            }
+            _ => return Err(ProgramError::InvalidAccountData),
+        };
+
+        seed_lens[count] = len;
+        count += 1;
+    }
+
+    let mut seeds = [MaybeUninit::<&[u8]>::uninit(); MAX_SEEDS];
+
+    for (seed, (bytes, len)) in seeds.iter_mut().zip(seed_bytes.iter().zip(seed_lens)) {
+        seed.write(&bytes[..len]);
+    }
+
+    // SAFETY: The first `count` seeds have been initialized.
+    let seeds = unsafe { core::slice::from_raw_parts(seeds.as_ptr() as *const &[u8], count) };
+
+    find_program_address(seeds, program_id)
+}
+
+#[inline(always)]
+fn copy_seed(seed: &mut [u8; MAX_SEED_LEN], bytes: &[u8]) -> Result<(), ProgramError> {
+    seed.get_mut(..bytes.len())
+        .ok_or(ProgramError::InvalidAccountData)?
+        .copy_from_slice(bytes);
+    Ok(())
+}
+
+/// Derive a program address.
+///
+/// Tests use an off-chain implementation of the derivation, since the
+/// syscall is only available on-chain.
+#[inline(always)]
+fn find_program_address(seeds: &[&[u8]], program_id: &Pubkey) -> Result<Pubkey, ProgramError> {
+    #[cfg(test)]
+    use crate::test_utils::try_find_program_address;
+    #[cfg(not(test))]
+    use pinocchio::pubkey::try_find_program_address;
+
+    try_find_program_address(seeds, program_id)
+        .map(|(address, _)| address)
+        .ok_or(ProgramError::InvalidSeeds)
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::{vec, vec::Vec};
+
+    use super::*;
+    use crate::test_utils::{
+        create_program_address, token_account_base, try_find_program_address, TestAccount,
+    };
+
+    const HOOK_PROGRAM: Pubkey = [9; 32];
+
+    /// Validation account data holding the given extra account metas.
+    fn validation_data(metas: &[(u8, [u8; 32], bool)]) -> Vec<u8> {
+        let mut data = vec![105, 37, 101, 197, 75, 251, 102, 26];
+        data.extend_from_slice(&(4 + metas.len() as u32 * 35).to_le_bytes());
+        data.extend_from_slice(&(metas.len() as u32).to_le_bytes());
+        for (discriminator, address_config, is_writable) in metas {
+            data.push(*discriminator);
+            data.extend_from_slice(address_config);
+            data.extend_from_slice(&[0, *is_writable as u8]);
+        }
+        data
+    }
+
+    fn seeds(packed: &[u8]) -> [u8; 32] {
+        let mut config = [0; 32];
+        config[..packed.len()].copy_from_slice(packed);
+        config
+    }
+
+    #[test]
+    fn test_resolve_extra_account_metas() {
+        let source = TestAccount::with_data(
+            [1; 32],
+            crate::ID,
+            &token_account_base(&[2; 32], &[4; 32], 1_000),
+        )
+        .info();
+        let mint = TestAccount::new([2; 32]).info();
+        let destination = TestAccount::new([3; 32]).info();
+        let owner = TestAccount::new([4; 32]).info();
+        let validation = TestAccount::new([5; 32]).info();
+        let execute_accounts = ExecuteAccounts {
+            source: &source,
+            mint: &mint,
+            destination: &destination,
+            owner: &owner,
+            extra_account_metas: &validation,
+        };
+
+        // Execute instruction data: discriminator and amount.
+        let mut instruction_data = vec![105, 37, 101, 197, 75, 251, 102, 26];
+        instruction_data.extend_from_slice(&1_000u64.to_le_bytes());
+
+        let data = validation_data(&[
+            // Literal address, used as the program of the last meta.
+            (0, [7; 32], false),
+            // PDA of the hook program with a literal seed and the mint key.
+            (
+                1,
+                seeds(&[1, 7, b'c', b'o', b'u', b'n', b't', b'e', b'r', 3, 1]),
+                true,
+            ),
+            // PDA of the hook program with the amount and the first extra
+            // account key.
+            (1, seeds(&[2, 8, 8, 3, 5]), false),
+            // PDA of the program at index 5 (the literal address) with the
+            // mint stored in the source account data.
+            (128 + 5, seeds(&[4, 0, 0, 32]), true),
+        ]);
+        let list = ExtraAccountMetaList::from_bytes(&data).unwrap();
+
+        let program_address =
+            |seeds: &[&[u8]], program_id| try_find_program_address(seeds, program_id).unwrap().0;
+        let counter_key = program_address(&[b"counter", &[2; 32]], &HOOK_PROGRAM);
+        let amount_key = program_address(&[&1_000u64.to_le_bytes(), &[7; 32]], &HOOK_PROGRAM);
+        let external_key = program_address(&[&[2; 32]], &[7; 32]);
+
+        let program = TestAccount::new([7; 32]).info();
+        let counter = TestAccount::new(counter_key).info();
+        let amount = TestAccount::new(amount_key).info();
+        let external = TestAccount::new(external_key).info();
+        let decoy = TestAccount::new([8; 32]).info();
+
+        let resolved = resolve_extra_account_metas(
+            &list,
+            &execute_accounts,
+            &instruction_data,
+            &HOOK_PROGRAM,
+            &[&decoy, &external, &amount, &counter, &program],
+        )
+        .unwrap();
+
+        let keys = resolved
+            .metas()
+            .iter()
+            .map(|meta| (*meta.pubkey, meta.is_writable, meta.is_signer))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            keys,
+            vec![
+                ([7; 32], false, false),
+                (counter_key, true, false),
+                (amount_key, false, false),
+                (external_key, true, false),
+            ]
+        );
+        assert!(resolved
+            .infos()
+            .iter()
+            .zip(resolved.metas())
+            .all(|(info, meta)| info.key() == meta.pubkey));
+
+        // The resolved accounts must be among the candidates.
+        assert_eq!(
+            resolve_extra_account_metas(
+                &list,
+                &execute_accounts,
+                &instruction_data,
+                &HOOK_PROGRAM,
+                &[&program, &counter, &external],
+            )
+            .err(),
+            Some(ProgramError::NotEnoughAccountKeys)
+        );
+    }
+
+    #[test]
+    fn test_program_address_vectors() {
+        use pinocchio_pubkey::from_str;
+
+        // Vectors of `Pubkey::create_program_address` in solana-pubkey.
+        let program_id = from_str("BPFLoaderUpgradeab1e11111111111111111111111");
+        let public_key = from_str("SeedPubey1111111111111111111111111111111111");
+
+        for (seeds, address) in [
+            (
+                &[b"".as_slice(), &[1]][..],
+                "BwqrghZA2htAcqq8dzP1WDAhTXYTYWj7CHxF5j7TDBAe",
+            ),
+            (
+                &["☉".as_bytes(), &[0]],
+                "13yWmRpaTR4r5nAktwLqMpRNr28tnVUZw26rTvPSSB19",
+            ),
+            (
+                &[b"Talking", b"Squirrels"],
+                "2fnQrngrQT4SeLcdToJAD96phoEjNL2man2kfRLCASVk",
+            ),
+            (
+                &[&public_key, &[1]],
+                "976ymqVnfE32QFe6NfGDctSvVa36LWnvYxhU6G2232YL",
+            ),
+        ] {
+            assert_eq!(
+                create_program_address(seeds, &program_id),
+                Some(from_str(address))
+            );
+        }
+
+        // The bump found is the highest one giving an address off the curve.
+        let (address, bump) = try_find_program_address(&[b"counter"], &HOOK_PROGRAM).unwrap();
+        assert_eq!(
+            create_program_address(&[b"counter", &[bump]], &HOOK_PROGRAM),
+            Some(address)
+        );
+        for higher in (bump..=u8::MAX).skip(1) {
+            assert_eq!(
+                create_program_address(&[b"counter", &[higher]], &HOOK_PROGRAM),
+                None
+            );
+        }
+    }
+
+    #[test]
+    fn test_resolve_invalid_seeds() {
+        let account = TestAccount::new([1; 32]).info();
+        let execute_accounts = ExecuteAccounts {
+            source: &account,
+            mint: &account,
+            destination: &account,
+            owner: &account,
+            extra_account_metas: &account,
+        };
+
+        for config in [
+            // Instruction data out of bounds.
+            seeds(&[2, 8, 16]),
+            // Account index without a resolved account.
+            seeds(&[3, 5]),
+            // Literal longer than the config.
+            seeds(&[1, 31]),
+            // Unknown seed tag.
+            seeds(&[5]),
+        ] {
+            let data = validation_data(&[(1, config, false)]);
+            let list = ExtraAccountMetaList::from_bytes(&data).unwrap();
+
+            assert_eq!(
+                resolve_extra_account_metas(
+                    &list,
+                    &execute_accounts,
+                    &[0; 16],
+                    &HOOK_PROGRAM,
+                    &[&account],
+                )
+                .err(),
+                Some(ProgramError::InvalidAccountData)
+            );
+        }
+    }
+}
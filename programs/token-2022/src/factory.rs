@@ -0,0 +1,390 @@
+//! Helpers to initialize a mint with extensions.
+
+use pinocchio::{
+    account_info::AccountInfo, instruction::Signer, program_error::ProgramError, pubkey::Pubkey,
+    ProgramResult,
+};
+
+use crate::{
+    extensions::{
+        default_account_state::InitializeDefaultAccountState, interest_bearing_mint,
+        metadata_pointer, mint_close_authority::InitializeMintCloseAuthority,
+        transfer_fee::InitializeTransferFeeConfig, ExtensionType,
+    },
+    instructions::InitializeMint2,
+    plan::CpiPlan,
+    state::AccountState,
+};
+
+/// Mint extension initialized by [`initialize_mint_with_extensions`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MintExtension<'a> {
+    /// Authority that can close the mint.
+    MintCloseAuthority { close_authority: Option<&'a Pubkey> },
+    /// Pointer to the account holding the token metadata.
+    MetadataPointer {
+        authority: Option<&'a Pubkey>,
+        metadata_address: Option<&'a Pubkey>,
+    },
+    /// Fee assessed on transfers.
+    TransferFeeConfig {
+        transfer_fee_config_authority: Option<&'a Pubkey>,
+        withdraw_withheld_authority: Option<&'a Pubkey>,
+        transfer_fee_basis_points: u16,
+        maximum_fee: u64,
+    },
+    /// State of new token accounts.
+    DefaultAccountState(AccountState),
+    /// Interest accrued by the mint.
+    InterestBearing {
+        rate_authority: Option<&'a Pubkey>,
+        rate: i16,
+    },
+}
+
+impl<'a> MintExtension<'a> {
+    /// Return the extension type, e.g. to calculate the length of the mint
+    /// account with [`try_calculate_account_len`](crate::extensions::try_calculate_account_len).
+    pub fn extension_type(&self) -> ExtensionType {
+        match self {
+            MintExtension::MintCloseAuthority { .. } => ExtensionType::MintCloseAuthority,
+            MintExtension::MetadataPointer { .. } => ExtensionType::MetadataPointer,
+            MintExtension::TransferFeeConfig { .. } => ExtensionType::TransferFeeConfig,
+            MintExtension::DefaultAccountState(_) => ExtensionType::DefaultAccountState,
+            MintExtension::InterestBearing { .. } => ExtensionType::InterestBearingConfig,
+        }
+    }
+
+    fn invoke(&self, mint: &AccountInfo, signers: &[Signer]) -> ProgramResult {
+        match *self {
+            MintExtension::MintCloseAuthority { close_authority } => InitializeMintCloseAuthority {
+                mint,
+                close_authority,
+            }
+            .invoke_signed(signers),
+            MintExtension::MetadataPointer {
+                authority,
+                metadata_address,
+            } => metadata_pointer::Initialize {
+                mint,
+                authority,
+                metadata_address,
+            }
+            .invoke_signed(signers),
+            MintExtension::TransferFeeConfig {
+                transfer_fee_config_authority,
+                withdraw_withheld_authority,
+                transfer_fee_basis_points,
+                maximum_fee,
+            } => InitializeTransferFeeConfig {
+                mint,
+                transfer_fee_config_authority,
+                withdraw_withheld_authority,
+                transfer_fee_basis_points,
+                maximum_fee,
+            }
+            .invoke_signed(signers),
+            MintExtension::DefaultAccountState(state) => {
+                InitializeDefaultAccountState { mint, state }.invoke_signed(signers)
+            }
+            MintExtension::InterestBearing {
+                rate_authority,
+                rate,
+            } => interest_bearing_mint::Initialize {
+                mint,
+                rate_authority,
+                rate,
+            }
+            .invoke_signed(signers),
+        }
+    }
+
+    fn add_to_plan<const MAX_ACCOUNTS: usize>(
+        &self,
+        mint: &'a AccountInfo,
+        plan: &mut CpiPlan<'a, MAX_ACCOUNTS>,
+    ) -> Result<(), ProgramError> {
+        let account_metas = match *self {
+            MintExtension::MintCloseAuthority { close_authority } => InitializeMintCloseAuthority {
+                mint,
+                close_authority,
+            }
+            .account_metas(),
+            MintExtension::MetadataPointer {
+                authority,
+                metadata_address,
+            } => metadata_pointer::Initialize {
+                mint,
+                authority,
+                metadata_address,
+            }
+            .account_metas(),
+            MintExtension::TransferFeeConfig {
+                transfer_fee_config_authority,
+                withdraw_withheld_authority,
+                transfer_fee_basis_points,
+                maximum_fee,
+            } => InitializeTransferFeeConfig {
+                mint,
+                transfer_fee_config_authority,
+                withdraw_withheld_authority,
+                transfer_fee_basis_points,
+                maximum_fee,
+            }
+            .account_metas(),
+            MintExtension::DefaultAccountState(state) => {
+                InitializeDefaultAccountState { mint, state }.account_metas()
+            }
+            MintExtension::InterestBearing {
+                rate_authority,
+                rate,
+            } => interest_bearing_mint::Initialize {
+                mint,
+                rate_authority,
+                rate,
+            }
+            .account_metas(),
+        };
+
+        plan.add_cpi(&account_metas)
+    }
+}
+
+/// Initialize the `extensions` of `mint` and then the mint itself.
+///
+/// The mint account must already be allocated with the length of its
+/// extensions and assigned to Token-2022, typically in the same instruction.
+/// Returns `IncorrectProgramId` without invoking anything if `mint` is owned
+/// by any other program. Extensions are initialized in the given order,
+/// before `InitializeMint2`, since the token program rejects extension
+/// initialization on an initialized mint.
+pub fn initialize_mint_with_extensions(
+    mint: &AccountInfo,
+    extensions: &[MintExtension],
+    decimals: u8,
+    mint_authority: &Pubkey,
+    freeze_authority: Option<&Pubkey>,
+    signers: &[Signer],
+) -> ProgramResult {
+    if mint.owner() != &crate::ID {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    for extension in extensions {
+        extension.invoke(mint, signers)?;
+    }
+
+    InitializeMint2 {
+        mint,
+        decimals,
+        mint_authority,
+        freeze_authority,
+        token_program: &crate::ID,
+    }
+    .invoke()
+}
+
+/// Add the CPIs of [`initialize_mint_with_extensions`] to `plan`, without
+/// invoking them.
+///
+/// Returns `IncorrectProgramId` if `mint` is not owned by Token-2022, as
+/// [`initialize_mint_with_extensions`] does.
+pub fn initialize_mint_with_extensions_dry_run<'a, const MAX_ACCOUNTS: usize>(
+    mint: &'a AccountInfo,
+    extensions: &[MintExtension<'a>],
+    plan: &mut CpiPlan<'a, MAX_ACCOUNTS>,
+) -> Result<(), ProgramError> {
+    if mint.owner() != &crate::ID {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    for extension in extensions {
+        extension.add_to_plan(mint, plan)?;
+    }
+
+    // The metas of `InitializeMint2` do not depend on its data.
+    let initialize = InitializeMint2 {
+        mint,
+        decimals: 0,
+        mint_authority: &[0; 32],
+        freeze_authority: None,
+        token_program: &crate::ID,
+    };
+
+    plan.add_cpi(&initialize.account_metas())
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::vec::Vec;
+
+    use pinocchio::instruction::AccountMeta;
+
+    use super::*;
+    use crate::{
+        extensions::{try_calculate_account_len, BaseState},
+        plan::PlannedAccount,
+        test_utils::{take_captured, CapturedMeta, TestAccount},
+    };
+
+    const EXTENSIONS: [MintExtension<'static>; 3] = [
+        MintExtension::MintCloseAuthority {
+            close_authority: Some(&[2; 32]),
+        },
+        MintExtension::MetadataPointer {
+            authority: Some(&[2; 32]),
+            metadata_address: Some(&[1; 32]),
+        },
+        MintExtension::DefaultAccountState(AccountState::Frozen),
+    ];
+
+    #[test]
+    fn test_initialize_mint_with_extensions() {
+        let mint = TestAccount::new([1; 32]).writable().info();
+
+        initialize_mint_with_extensions(&mint, &EXTENSIONS, 6, &[2; 32], Some(&[3; 32]), &[])
+            .unwrap();
+
+        let instructions = take_captured();
+        let discriminators: Vec<_> = instructions
+            .iter()
+            .map(|instruction| instruction.data[0])
+            .collect();
+
+        // Extensions in order, then `InitializeMint2`.
+        assert_eq!(discriminators, [25, 39, 28, 20]);
+        assert_eq!(instructions[0].data, [&[25, 1][..], &[2; 32]].concat());
+        assert_eq!(
+            instructions[1].data,
+            [&[39, 0][..], &[2; 32], &[1; 32]].concat()
+        );
+        assert_eq!(instructions[2].data, [28, 0, AccountState::Frozen as u8]);
+        for instruction in &instructions {
+            assert_eq!(instruction.program_id, crate::ID);
+            assert_eq!(
+                instruction.accounts,
+                [CapturedMeta::new([1; 32], true, false)]
+            );
+        }
+
+        assert_eq!(
+            EXTENSIONS.map(|extension| extension.extension_type()),
+            [
+                ExtensionType::MintCloseAuthority,
+                ExtensionType::MetadataPointer,
+                ExtensionType::DefaultAccountState,
+            ]
+        );
+        assert_eq!(
+            try_calculate_account_len(
+                BaseState::Mint,
+                &EXTENSIONS.map(|extension| extension.extension_type())
+            ),
+            Ok(166 + 36 + 68 + 5)
+        );
+    }
+
+    #[test]
+    fn test_initialize_mint_with_extensions_dry_run() {
+        let mint = TestAccount::new([1; 32]).writable().info();
+        let mut plan = CpiPlan::<1>::new();
+
+        initialize_mint_with_extensions_dry_run(&mint, &EXTENSIONS, &mut plan).unwrap();
+
+        assert_eq!(plan.cpi_count(), 4);
+        assert_eq!(
+            plan.unique_accounts(),
+            &[PlannedAccount {
+                key: &[1; 32],
+                is_writable: true,
+                is_signer: false,
+            }]
+        );
+        assert_eq!(plan.check(), Ok(()));
+        assert!(take_captured().is_empty());
+
+        // The plan matches the CPIs actually invoked, account by account.
+        initialize_mint_with_extensions(&mint, &EXTENSIONS, 6, &[2; 32], None, &[]).unwrap();
+
+        let instructions = take_captured();
+        let mut invoked = CpiPlan::<1>::new();
+
+        for instruction in &instructions {
+            let metas: Vec<_> = instruction
+                .accounts
+                .iter()
+                .map(|meta| AccountMeta::new(&meta.pubkey, meta.is_writable, meta.is_signer))
+                .collect();
+            invoked.add_cpi(&metas).unwrap();
+        }
+
+        assert_eq!(invoked.cpi_count(), plan.cpi_count());
+        assert_eq!(invoked.unique_accounts(), plan.unique_accounts());
+    }
+
+    #[test]
+    fn test_initialize_transfer_fee_and_interest_bearing() {
+        let mint = TestAccount::new([1; 32]).writable().info();
+
+        initialize_mint_with_extensions(
+            &mint,
+            &[
+                MintExtension::TransferFeeConfig {
+                    transfer_fee_config_authority: Some(&[2; 32]),
+                    withdraw_withheld_authority: None,
+                    transfer_fee_basis_points: 100,
+                    maximum_fee: 5_000,
+                },
+                MintExtension::InterestBearing {
+                    rate_authority: None,
+                    rate: -25,
+                },
+            ],
+            6,
+            &[2; 32],
+            None,
+            &[],
+        )
+        .unwrap();
+
+        let instructions = take_captured();
+
+        assert_eq!(instructions.len(), 3);
+        assert_eq!(
+            instructions[0].data,
+            [
+                &[26, 0, 1][..],
+                &[2; 32],
+                &[0],
+                &100u16.to_le_bytes(),
+                &5_000u64.to_le_bytes(),
+            ]
+            .concat()
+        );
+        assert_eq!(
+            instructions[1].data,
+            [&[33, 0][..], &[0; 32], &(-25i16).to_le_bytes()].concat()
+        );
+    }
+
+    #[test]
+    fn test_reject_foreign_mint() {
+        let mint = TestAccount::with_data([1; 32], [9; 32], &[])
+            .writable()
+            .info();
+        let mut plan = CpiPlan::<1>::new();
+
+        assert_eq!(
+            initialize_mint_with_extensions(&mint, &EXTENSIONS, 6, &[2; 32], None, &[]),
+            Err(ProgramError::IncorrectProgramId)
+        );
+        assert_eq!(
+            initialize_mint_with_extensions_dry_run(&mint, &EXTENSIONS, &mut plan),
+            Err(ProgramError::IncorrectProgramId)
+        );
+        assert!(take_captured().is_empty());
+        assert_eq!(plan.cpi_count(), 0);
+    }
+}
@@ -0,0 +1,561 @@
+//! Garbage collection of program-owned token accounts.
+
+use pinocchio::{
+    account_info::AccountInfo, instruction::Signer, program_error::ProgramError, pubkey::Pubkey,
+    ProgramResult,
+};
+
+use crate::{
+    errors::{classify, FailureClass},
+    extensions::{
+        get_extension_data, get_extension_from_bytes, pausable::PausableConfig, BaseState,
+        ExtensionType,
+    },
+    instructions::{BurnChecked, CloseAccount},
+    state::{classify_account, Mint, Strictness, TokenAccount, TokenAccountKind},
+};
+
+/// How to treat token accounts that still hold tokens.
+#[derive(Clone, Copy)]
+pub enum GcPolicy<'a, 'b> {
+    /// Skip accounts with a nonzero balance.
+    SkipNonEmpty,
+
+    /// Burn the balance of accounts before closing them.
+    ///
+    /// Only accounts owned by the authority can be swept, and the mint of each
+    /// account must be in `mints`; other accounts with a nonzero balance are
+    /// skipped. The lamports of native accounts are closed into the vault
+    /// without burning.
+    BurnNonEmpty { mints: &'b [&'a AccountInfo] },
+}
+
+/// Outcome of a garbage collection batch.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct GcReport {
+    /// Number of accounts closed.
+    pub closed: u32,
+    /// Number of accounts skipped by validation, without invoking any CPI.
+    pub skipped: u32,
+    /// Number of accounts whose burn or close CPI failed.
+    pub failed: u32,
+    /// Number of failed accounts whose CPI error is classified as
+    /// [`FailureClass::RetryLater`], so they can be collected by a later
+    /// batch.
+    pub retryable: u32,
+    /// Lamports transferred to the vault by the closed accounts.
+    pub reclaimed_lamports: u64,
+}
+
+/// Token account that passed validation.
+struct Candidate<'a> {
+    /// Balance to burn before closing, with the mint and its decimals.
+    burn: Option<(u64, &'a AccountInfo, u8)>,
+}
+
+/// Close the token accounts in `accounts` that `authority` can close,
+/// transferring their lamports to `lamport_vault`.
+///
+/// `authority` is the program PDA that owns (or is the close authority of)
+/// the token accounts and `authority_pda_signer` its signer seeds. Accounts
+/// that cannot be closed are skipped and counted in the report instead of
+/// failing the batch:
+///
+///   * accounts not owned by Token-2022, not holding an initialized token
+///     account, or not writable;
+///   * accounts whose close authority is not `authority`;
+///   * frozen accounts;
+///   * accounts with transfer fees withheld, in the clear or confidentially,
+///     which must be harvested to the mint first;
+///   * accounts with a confidential balance;
+///   * accounts with the CPI guard enabled, unless no tokens are burned and
+///     the vault is the account owner;
+///   * accounts with a nonzero balance, unless swept according to `policy`,
+///     in which case the mint must be a writable, initialized and unpaused
+///     Token-2022 mint.
+///
+/// These are the checks of the token program for `BurnChecked` and
+/// `CloseAccount`, so validated accounts are not expected to fail. A CPI
+/// error returned by the token program cannot be recovered on-chain: the
+/// runtime aborts the transaction. Errors returned before the CPI is
+/// invoked (e.g., the account is borrowed) are counted as failed, and the
+/// batch continues with the next account.
+pub fn close_program_owned_accounts(
+    accounts: &[&AccountInfo],
+    authority: &AccountInfo,
+    authority_pda_signer: &[Signer],
+    lamport_vault: &AccountInfo,
+    policy: GcPolicy,
+) -> Result<GcReport, ProgramError> {
+    let mut report = GcReport::default();
+
+    for account in accounts {
+        let Some(candidate) = validate(account, authority.key(), lamport_vault.key(), policy)
+        else {
+            report.skipped += 1;
+            continue;
+        };
+
+        let lamports = account.lamports();
+
+        let result = close(
+            account,
+            candidate,
+            authority,
+            authority_pda_signer,
+            lamport_vault,
+        );
+
+        match result {
+            Ok(()) => {
+                report.closed += 1;
+                report.reclaimed_lamports = report.reclaimed_lamports.saturating_add(lamports);
+            }
+            Err(error) => {
+                report.failed += 1;
+                if classify(&error) == FailureClass::RetryLater {
+                    report.retryable += 1;
+                }
+            }
+        }
+    }
+
+    Ok(report)
+}
+
+/// Burn the balance of a validated account, if any, and close it.
+#[inline(always)]
+fn close(
+    account: &AccountInfo,
+    candidate: Candidate,
+    authority: &AccountInfo,
+    authority_pda_signer: &[Signer],
+    lamport_vault: &AccountInfo,
+) -> ProgramResult {
+    if let Some((amount, mint, decimals)) = candidate.burn {
+        BurnChecked {
+            account,
+            mint,
+            authority,
+            amount,
+            decimals,
+            token_program: &crate::ID,
+        }
+        .invoke_signed(authority_pda_signer)?;
+    }
+
+    CloseAccount {
+        account,
+        destination: lamport_vault,
+        authority,
+        token_program: &crate::ID,
+    }
+    .invoke_signed(authority_pda_signer)
+}
+
+/// Return how to close `account`, or `None` if the token program would
+/// reject closing it with `authority`.
+fn validate<'a>(
+    account: &AccountInfo,
+    authority: &Pubkey,
+    vault: &Pubkey,
+    policy: GcPolicy<'a, '_>,
+) -> Option<Candidate<'a>> {
+    if !account.is_owned_by(&crate::ID) || !account.is_writable() || account.key() == vault {
+        return None;
+    }
+
+    let data = account.try_borrow_data().ok()?;
+
+    if classify_account(&data, Strictness::Strict) != Ok(TokenAccountKind::TokenAccount) {
+        return None;
+    }
+
+    // SAFETY: The data holds a token account.
+    let token_account = unsafe { TokenAccount::from_bytes_unchecked(&data) };
+
+    let close_authority = token_account
+        .close_authority()
+        .unwrap_or(token_account.owner());
+
+    if !token_account.is_initialized() || token_account.is_frozen() || close_authority != authority
+    {
+        return None;
+    }
+
+    // Accounts with withheld transfer fees or confidential balances cannot
+    // be closed.
+    if !is_zero(&data, ExtensionType::TransferFeeAmount, 0..8)
+        || !is_zero(&data, ExtensionType::ConfidentialTransferFeeAmount, 0..64)
+        || !is_zero(&data, ExtensionType::ConfidentialTransferAccount, 33..225)
+    {
+        return None;
+    }
+
+    let cpi_guard = matches!(
+        get_extension_data(&data, BaseState::TokenAccount, ExtensionType::CpiGuard),
+        Some([lock_cpi, ..]) if *lock_cpi != 0
+    );
+
+    // Native accounts are closed with their balance.
+    if token_account.amount() == 0 || token_account.is_native() {
+        return match policy {
+            GcPolicy::SkipNonEmpty if token_account.amount() > 0 => None,
+            // The CPI guard only allows closing into the owner account.
+            _ if cpi_guard && token_account.owner() != vault => None,
+            _ => Some(Candidate { burn: None }),
+        };
+    }
+
+    // The CPI guard blocks burns signed by the owner.
+    let GcPolicy::BurnNonEmpty { mints } = policy else {
+        return None;
+    };
+    if token_account.owner() != authority || cpi_guard {
+        return None;
+    }
+
+    let mint = mints
+        .iter()
+        .find(|mint| mint.key() == token_account.mint())?;
+
+    Some(Candidate {
+        burn: Some((token_account.amount(), mint, burnable_decimals(mint)?)),
+    })
+}
+
+/// Return the decimals of `mint`, or `None` if tokens cannot be burned from
+/// it.
+#[inline(always)]
+fn burnable_decimals(mint: &AccountInfo) -> Option<u8> {
+    if !mint.is_owned_by(&crate::ID) || !mint.is_writable() {
+        return None;
+    }
+
+    let data = mint.try_borrow_data().ok()?;
+
+    if classify_account(&data, Strictness::Strict) != Ok(TokenAccountKind::Mint) {
+        return None;
+    }
+
+    // SAFETY: The data holds a mint.
+    let mint = unsafe { Mint::from_bytes_unchecked(&data) };
+
+    if !mint.is_initialized()
+        || get_extension_from_bytes::<PausableConfig>(&data).is_some_and(|config| config.paused())
+    {
+        return None;
+    }
+
+    Some(mint.decimals())
+}
+
+/// Return whether the bytes at `range` of the extension are zero, or the
+/// account does not have the extension.
+#[inline(always)]
+fn is_zero(data: &[u8], extension_type: ExtensionType, range: core::ops::Range<usize>) -> bool {
+    match get_extension_data(data, BaseState::TokenAccount, extension_type) {
+        Some(value) => value
+            .get(range)
+            .is_some_and(|bytes| bytes.iter().all(|byte| *byte == 0)),
+        None => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::vec;
+
+    use super::*;
+    use crate::{
+        errors::TokenError,
+        test_utils::{
+            account_with_extensions, fail_invocation, mint_base, take_captured, token_account_base,
+            TestAccount,
+        },
+    };
+
+    const AUTHORITY: Pubkey = [1; 32];
+    const MINT: Pubkey = [2; 32];
+
+    fn token_account(key: u8, owner: &Pubkey, amount: u64) -> AccountInfo {
+        token_account_with_extensions(key, owner, amount, &[])
+    }
+
+    fn token_account_with_extensions(
+        key: u8,
+        owner: &Pubkey,
+        amount: u64,
+        extensions: &[(ExtensionType, &[u8])],
+    ) -> AccountInfo {
+        let base = token_account_base(&MINT, owner, amount);
+        let data = if extensions.is_empty() {
+            base.to_vec()
+        } else {
+            account_with_extensions(BaseState::TokenAccount, &base, extensions)
+        };
+
+        TestAccount::with_data([key; 32], crate::ID, &data)
+            .writable()
+            .lamports(2_039_280)
+            .info()
+    }
+
+    fn mint(extensions: &[(ExtensionType, &[u8])]) -> AccountInfo {
+        let base = mint_base(None, 100, 6, None);
+        let data = if extensions.is_empty() {
+            base.to_vec()
+        } else {
+            account_with_extensions(BaseState::Mint, &base, extensions)
+        };
+
+        TestAccount::with_data(MINT, crate::ID, &data)
+            .writable()
+            .info()
+    }
+
+    #[test]
+    fn test_close_mixed_batch() {
+        let authority = TestAccount::new(AUTHORITY).info();
+        let vault = TestAccount::new([3; 32]).writable().info();
+        let mint = mint(&[]);
+
+        let empty = token_account(10, &AUTHORITY, 0);
+        let dusty = token_account(11, &AUTHORITY, 5);
+        let wrong_owner = token_account(12, &[9; 32], 0);
+
+        let mut withheld_fee = vec![0; 8];
+        withheld_fee[0] = 1;
+        let withheld = token_account_with_extensions(
+            13,
+            &AUTHORITY,
+            0,
+            &[(ExtensionType::TransferFeeAmount, &withheld_fee)],
+        );
+
+        let batch = [&empty, &dusty, &wrong_owner, &withheld];
+
+        let report =
+            close_program_owned_accounts(&batch, &authority, &[], &vault, GcPolicy::SkipNonEmpty)
+                .unwrap();
+
+        assert_eq!(
+            report,
+            GcReport {
+                closed: 1,
+                skipped: 3,
+                reclaimed_lamports: 2_039_280,
+                ..GcReport::default()
+            }
+        );
+
+        let instructions = take_captured();
+        assert_eq!(instructions.len(), 1);
+        assert_eq!(instructions[0].data, [9]);
+        assert_eq!(instructions[0].accounts[0].pubkey, [10; 32]);
+
+        let report = close_program_owned_accounts(
+            &batch,
+            &authority,
+            &[],
+            &vault,
+            GcPolicy::BurnNonEmpty { mints: &[&mint] },
+        )
+        .unwrap();
+
+        assert_eq!(
+            report,
+            GcReport {
+                closed: 2,
+                skipped: 2,
+                reclaimed_lamports: 2 * 2_039_280,
+                ..GcReport::default()
+            }
+        );
+
+        let instructions = take_captured();
+        assert_eq!(instructions.len(), 3);
+        // The dusty account is burned before being closed.
+        assert_eq!(instructions[1].data[0], 15);
+        assert_eq!(&instructions[1].data[1..9], &5u64.to_le_bytes());
+        assert_eq!(instructions[1].data[9], 6);
+        assert_eq!(instructions[1].accounts[0].pubkey, [11; 32]);
+        assert_eq!(instructions[2].data, [9]);
+        assert_eq!(instructions[2].accounts[0].pubkey, [11; 32]);
+    }
+
+    #[test]
+    fn test_skip_accounts_without_mint() {
+        let authority = TestAccount::new(AUTHORITY).info();
+        let vault = TestAccount::new([3; 32]).writable().info();
+        let dusty = token_account(11, &AUTHORITY, 5);
+        let not_token = TestAccount::new([14; 32]).writable().info();
+
+        let report = close_program_owned_accounts(
+            &[&dusty, &not_token, &vault],
+            &authority,
+            &[],
+            &vault,
+            GcPolicy::BurnNonEmpty { mints: &[] },
+        )
+        .unwrap();
+
+        assert_eq!(report.closed, 0);
+        assert_eq!(report.skipped, 3);
+        assert!(take_captured().is_empty());
+    }
+
+    #[test]
+    fn test_skip_accounts_rejected_by_token_program() {
+        let authority = TestAccount::new(AUTHORITY).info();
+        let vault = TestAccount::new([3; 32]).writable().info();
+
+        let mut confidential_account = [0; 295];
+        confidential_account[161] = 1;
+
+        let batch = [
+            // The CPI guard blocks burns and closing into the vault.
+            token_account_with_extensions(20, &AUTHORITY, 5, &[(ExtensionType::CpiGuard, &[1])]),
+            token_account_with_extensions(21, &AUTHORITY, 0, &[(ExtensionType::CpiGuard, &[1])]),
+            // Confidential available balance and withheld fees.
+            token_account_with_extensions(
+                22,
+                &AUTHORITY,
+                0,
+                &[(
+                    ExtensionType::ConfidentialTransferAccount,
+                    &confidential_account,
+                )],
+            ),
+            token_account_with_extensions(
+                23,
+                &AUTHORITY,
+                0,
+                &[(ExtensionType::ConfidentialTransferFeeAmount, &[1; 64])],
+            ),
+            // Read-only account.
+            TestAccount::with_data(
+                [24; 32],
+                crate::ID,
+                &token_account_base(&MINT, &AUTHORITY, 0),
+            )
+            .info(),
+        ];
+        let batch = batch.each_ref();
+
+        let report = close_program_owned_accounts(
+            &batch,
+            &authority,
+            &[],
+            &vault,
+            GcPolicy::BurnNonEmpty {
+                mints: &[&mint(&[])],
+            },
+        )
+        .unwrap();
+
+        assert_eq!(report.closed, 0);
+        assert_eq!(report.skipped, 5);
+
+        // Burns require a writable, unpaused mint.
+        let dusty = token_account(11, &AUTHORITY, 5);
+        let paused_mint = mint(&[(
+            ExtensionType::Pausable,
+            &[[5; 32].as_slice(), &[1]].concat(),
+        )]);
+        let read_only_mint =
+            TestAccount::with_data(MINT, crate::ID, &mint_base(None, 100, 6, None)).info();
+
+        for mint in [&paused_mint, &read_only_mint] {
+            let report = close_program_owned_accounts(
+                &[&dusty],
+                &authority,
+                &[],
+                &vault,
+                GcPolicy::BurnNonEmpty { mints: &[mint] },
+            )
+            .unwrap();
+
+            assert_eq!(report.skipped, 1);
+        }
+
+        assert!(take_captured().is_empty());
+    }
+
+    #[test]
+    fn test_failed_cpi_does_not_abort_batch() {
+        let authority = TestAccount::new(AUTHORITY).info();
+        let vault = TestAccount::new([3; 32]).writable().info();
+        let mint = mint(&[]);
+
+        let empty = token_account(10, &AUTHORITY, 0);
+        let dusty = token_account(11, &AUTHORITY, 5);
+        let other = token_account(12, &AUTHORITY, 0);
+
+        // The burn of the dusty account fails, so it is not closed.
+        fail_invocation(1, TokenError::InsufficientFunds.into());
+
+        let report = close_program_owned_accounts(
+            &[&empty, &dusty, &other],
+            &authority,
+            &[],
+            &vault,
+            GcPolicy::BurnNonEmpty { mints: &[&mint] },
+        )
+        .unwrap();
+
+        assert_eq!(
+            report,
+            GcReport {
+                closed: 2,
+                skipped: 0,
+                failed: 1,
+                retryable: 0,
+                reclaimed_lamports: 2 * 2_039_280,
+            }
+        );
+
+        let instructions = take_captured();
+        let accounts = instructions
+            .iter()
+            .map(|instruction| (instruction.data[0], instruction.accounts[0].pubkey[0]))
+            .collect::<std::vec::Vec<_>>();
+        assert_eq!(accounts, [(9, 10), (15, 11), (9, 12)]);
+    }
+
+    #[test]
+    fn test_retryable_accounts_in_use() {
+        let authority = TestAccount::new(AUTHORITY).info();
+        let vault = TestAccount::new([3; 32]).writable().info();
+        let empty = token_account(10, &AUTHORITY, 0);
+        let other = token_account(11, &AUTHORITY, 0);
+
+        // The close CPIs fail to borrow the vault, which is retryable.
+        let vault_lamports = vault.try_borrow_mut_lamports().unwrap();
+
+        let report = close_program_owned_accounts(
+            &[&empty, &other],
+            &authority,
+            &[],
+            &vault,
+            GcPolicy::SkipNonEmpty,
+        )
+        .unwrap();
+
+        drop(vault_lamports);
+
+        assert_eq!(
+            report,
+            GcReport {
+                failed: 2,
+                retryable: 2,
+                ..GcReport::default()
+            }
+        );
+        assert!(take_captured()
+            .iter()
+            .all(|instruction| instruction.data == [9]));
+    }
+}
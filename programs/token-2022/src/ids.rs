@@ -0,0 +1,23 @@
+//! Program ids and native mints of the SPL Token programs.
+//!
+//! Re-exported from `pinocchio-token`, so both crates share a single
+//! definition of the ids and of [`TokenProgram`].
+
+pub use pinocchio_token::ids::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ids() {
+        assert_eq!(crate::ID, TOKEN_2022_PROGRAM_ID);
+        assert!(crate::check_id(&TOKEN_2022_PROGRAM_ID));
+        assert!(!crate::check_id(&TOKEN_PROGRAM_ID));
+        assert_eq!(
+            which_token_program(&crate::ID),
+            Some(TokenProgram::Token2022)
+        );
+        assert_eq!(which_token_program(&NATIVE_MINT_2022), None);
+    }
+}
@@ -1,14 +1,11 @@
-use core::slice::from_raw_parts;
-
 use pinocchio::{
     account_info::AccountInfo,
     instruction::{AccountMeta, Instruction, Signer},
-    program::invoke_signed,
     pubkey::Pubkey,
     ProgramResult,
 };
 
-use crate::{write_bytes, UNINIT_BYTE};
+use crate::cpi::{invoke_signed, IxData};
 
 /// Burns tokens by removing them from an account.
 ///
@@ -29,7 +26,7 @@ pub struct Burn<'a, 'b> {
     pub token_program: &'b Pubkey,
 }
 
-impl Burn<'_, '_> {
+impl<'a> Burn<'a, '_> {
     #[inline(always)]
     pub fn invoke(&self) -> ProgramResult {
         self.invoke_signed(&[])
@@ -37,27 +34,13 @@ impl Burn<'_, '_> {
 
     #[inline(always)]
     pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
-        // Account metadata
-        let account_metas: [AccountMeta; 3] = [
-            AccountMeta::writable(self.account.key()),
-            AccountMeta::writable(self.mint.key()),
-            AccountMeta::readonly_signer(self.authority.key()),
-        ];
-
-        // Instruction data
-        // -  [0]: instruction discriminator (1 byte, u8)
-        // -  [1..9]: amount (8 bytes, u64)
-        let mut instruction_data = [UNINIT_BYTE; 9];
-
-        // Set discriminator as u8 at offset [0]
-        write_bytes(&mut instruction_data, &[8]);
-        // Set amount as u64 at offset [1..9]
-        write_bytes(&mut instruction_data[1..], &self.amount.to_le_bytes());
+        let account_metas = self.account_metas();
+        let instruction_data = self.instruction_data();
 
         let instruction = Instruction {
             program_id: self.token_program,
             accounts: &account_metas,
-            data: unsafe { from_raw_parts(instruction_data.as_ptr() as _, 9) },
+            data: instruction_data.finish(),
         };
 
         invoke_signed(
@@ -66,4 +49,27 @@ impl Burn<'_, '_> {
             signers,
         )
     }
+
+    /// Return the account metas of the instruction.
+    #[inline(always)]
+    pub fn account_metas(&self) -> [AccountMeta<'a>; 3] {
+        self.base().account_metas()
+    }
+
+    /// Return the instruction data.
+    #[inline(always)]
+    pub fn instruction_data(&self) -> IxData<9> {
+        self.base().instruction_data()
+    }
+
+    /// Return the `pinocchio-token` builder of the same instruction.
+    #[inline(always)]
+    fn base(&self) -> pinocchio_token::instructions::Burn<'a> {
+        pinocchio_token::instructions::Burn {
+            account: self.account,
+            mint: self.mint,
+            authority: self.authority,
+            amount: self.amount,
+        }
+    }
 }
@@ -1,11 +1,12 @@
 use pinocchio::{
     account_info::AccountInfo,
-    cpi::invoke,
     instruction::{AccountMeta, Instruction},
     pubkey::Pubkey,
     ProgramResult,
 };
 
+use crate::cpi::{invoke, IxData};
+
 /// Initialize a new Token Account.
 ///
 /// ### Accounts:
@@ -26,21 +27,16 @@ pub struct InitializeAccount<'a, 'b> {
     pub token_program: &'b Pubkey,
 }
 
-impl InitializeAccount<'_, '_> {
+impl<'a> InitializeAccount<'a, '_> {
     #[inline(always)]
     pub fn invoke(&self) -> ProgramResult {
-        // account metadata
-        let account_metas: [AccountMeta; 4] = [
-            AccountMeta::writable(self.account.key()),
-            AccountMeta::readonly(self.mint.key()),
-            AccountMeta::readonly(self.owner.key()),
-            AccountMeta::readonly(self.rent_sysvar.key()),
-        ];
+        let account_metas = self.account_metas();
+        let instruction_data = self.instruction_data();
 
         let instruction = Instruction {
             program_id: self.token_program,
             accounts: &account_metas,
-            data: &[1],
+            data: instruction_data.finish(),
         };
 
         invoke(
@@ -48,4 +44,27 @@ impl InitializeAccount<'_, '_> {
             &[self.account, self.mint, self.owner, self.rent_sysvar],
         )
     }
+
+    /// Return the account metas of the instruction.
+    #[inline(always)]
+    pub fn account_metas(&self) -> [AccountMeta<'a>; 4] {
+        self.base().account_metas()
+    }
+
+    /// Return the instruction data.
+    #[inline(always)]
+    pub fn instruction_data(&self) -> IxData<1> {
+        self.base().instruction_data()
+    }
+
+    /// Return the `pinocchio-token` builder of the same instruction.
+    #[inline(always)]
+    fn base(&self) -> pinocchio_token::instructions::InitializeAccount<'a> {
+        pinocchio_token::instructions::InitializeAccount {
+            account: self.account,
+            mint: self.mint,
+            owner: self.owner,
+            rent_sysvar: self.rent_sysvar,
+        }
+    }
 }
@@ -1,14 +1,11 @@
-use core::slice::from_raw_parts;
-
 use pinocchio::{
     account_info::AccountInfo,
-    cpi::invoke,
     instruction::{AccountMeta, Instruction},
     pubkey::Pubkey,
     ProgramResult,
 };
 
-use crate::{write_bytes, UNINIT_BYTE};
+use crate::cpi::{invoke, IxData};
 
 /// Initialize a new Token Account.
 ///
@@ -29,32 +26,41 @@ pub struct InitializeAccount2<'a, 'b> {
     pub token_program: &'b Pubkey,
 }
 
-impl InitializeAccount2<'_, '_> {
+impl<'a> InitializeAccount2<'a, '_> {
     #[inline(always)]
     pub fn invoke(&self) -> ProgramResult {
-        // account metadata
-        let account_metas: [AccountMeta; 3] = [
-            AccountMeta::writable(self.account.key()),
-            AccountMeta::readonly(self.mint.key()),
-            AccountMeta::readonly(self.rent_sysvar.key()),
-        ];
-
-        // instruction data
-        // -  [0]: instruction discriminator (1 byte, u8)
-        // -  [1..33]: owner (32 bytes, Pubkey)
-        let mut instruction_data = [UNINIT_BYTE; 33];
-
-        // Set discriminator as u8 at offset [0]
-        write_bytes(&mut instruction_data, &[16]);
-        // Set owner as [u8; 32] at offset [1..33]
-        write_bytes(&mut instruction_data[1..], self.owner);
+        let account_metas = self.account_metas();
+        let instruction_data = self.instruction_data();
 
         let instruction = Instruction {
             program_id: self.token_program,
             accounts: &account_metas,
-            data: unsafe { from_raw_parts(instruction_data.as_ptr() as _, 33) },
+            data: instruction_data.finish(),
         };
 
         invoke(&instruction, &[self.account, self.mint, self.rent_sysvar])
     }
+
+    /// Return the account metas of the instruction.
+    #[inline(always)]
+    pub fn account_metas(&self) -> [AccountMeta<'a>; 3] {
+        self.base().account_metas()
+    }
+
+    /// Return the instruction data.
+    #[inline(always)]
+    pub fn instruction_data(&self) -> IxData<33> {
+        self.base().instruction_data()
+    }
+
+    /// Return the `pinocchio-token` builder of the same instruction.
+    #[inline(always)]
+    fn base(&self) -> pinocchio_token::instructions::InitializeAccount2<'a> {
+        pinocchio_token::instructions::InitializeAccount2 {
+            account: self.account,
+            mint: self.mint,
+            rent_sysvar: self.rent_sysvar,
+            owner: self.owner,
+        }
+    }
 }
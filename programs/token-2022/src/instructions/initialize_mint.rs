@@ -1,14 +1,11 @@
-use core::slice::from_raw_parts;
-
 use pinocchio::{
     account_info::AccountInfo,
-    cpi::invoke,
     instruction::{AccountMeta, Instruction},
     pubkey::Pubkey,
     ProgramResult,
 };
 
-use crate::{write_bytes, UNINIT_BYTE};
+use crate::cpi::{invoke, IxData};
 
 /// Initialize a new mint.
 ///
@@ -30,48 +27,42 @@ pub struct InitializeMint<'a, 'b> {
     pub token_program: &'b Pubkey,
 }
 
-impl InitializeMint<'_, '_> {
+impl<'a> InitializeMint<'a, '_> {
     #[inline(always)]
     pub fn invoke(&self) -> ProgramResult {
-        // Account metadata
-        let account_metas: [AccountMeta; 2] = [
-            AccountMeta::writable(self.mint.key()),
-            AccountMeta::readonly(self.rent_sysvar.key()),
-        ];
-
-        // Instruction data layout:
-        // -  [0]: instruction discriminator (1 byte, u8)
-        // -  [1]: decimals (1 byte, u8)
-        // -  [2..34]: mint_authority (32 bytes, Pubkey)
-        // -  [34]: freeze_authority presence flag (1 byte, u8)
-        // -  [35..67]: freeze_authority (optional, 32 bytes, Pubkey)
-        let mut instruction_data = [UNINIT_BYTE; 67];
-        let mut length = instruction_data.len();
-
-        // Set discriminator as u8 at offset [0]
-        write_bytes(&mut instruction_data, &[0]);
-        // Set decimals as u8 at offset [1]
-        write_bytes(&mut instruction_data[1..2], &[self.decimals]);
-        // Set mint_authority as Pubkey at offset [2..34]
-        write_bytes(&mut instruction_data[2..34], self.mint_authority);
-
-        if let Some(freeze_auth) = self.freeze_authority {
-            // Set Option = `true` & freeze_authority at offset [34..67]
-            write_bytes(&mut instruction_data[34..35], &[1]);
-            write_bytes(&mut instruction_data[35..], freeze_auth);
-        } else {
-            // Set Option = `false`
-            write_bytes(&mut instruction_data[34..35], &[0]);
-            // Adjust length if no freeze authority
-            length = 35;
-        }
+        let account_metas = self.account_metas();
+        let instruction_data = self.instruction_data();
 
         let instruction = Instruction {
             program_id: self.token_program,
             accounts: &account_metas,
-            data: unsafe { from_raw_parts(instruction_data.as_ptr() as _, length) },
+            data: instruction_data.finish(),
         };
 
         invoke(&instruction, &[self.mint, self.rent_sysvar])
     }
+
+    /// Return the account metas of the instruction.
+    #[inline(always)]
+    pub fn account_metas(&self) -> [AccountMeta<'a>; 2] {
+        self.base().account_metas()
+    }
+
+    /// Return the instruction data.
+    #[inline(always)]
+    pub fn instruction_data(&self) -> IxData<67> {
+        self.base().instruction_data()
+    }
+
+    /// Return the `pinocchio-token` builder of the same instruction.
+    #[inline(always)]
+    fn base(&self) -> pinocchio_token::instructions::InitializeMint<'a> {
+        pinocchio_token::instructions::InitializeMint {
+            mint: self.mint,
+            rent_sysvar: self.rent_sysvar,
+            decimals: self.decimals,
+            mint_authority: self.mint_authority,
+            freeze_authority: self.freeze_authority,
+        }
+    }
 }
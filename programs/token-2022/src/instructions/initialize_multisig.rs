@@ -1,16 +1,11 @@
-use core::{mem::MaybeUninit, slice};
-
 use pinocchio::{
-    account_info::AccountInfo,
-    cpi::invoke_with_bounds,
-    instruction::{AccountMeta, Instruction},
-    program_error::ProgramError,
-    pubkey::Pubkey,
-    ProgramResult,
+    account_info::AccountInfo, instruction::Instruction, program_error::ProgramError,
+    pubkey::Pubkey, ProgramResult,
 };
 
-/// Maximum number of multisignature signers.
-pub const MAX_MULTISIG_SIGNERS: usize = 11;
+use crate::cpi::{invoke_signed_with_bounds, CpiAccounts, IxData};
+
+pub use pinocchio_token::instructions::MAX_MULTISIG_SIGNERS;
 
 /// Initialize a new Multisig.
 ///
@@ -39,70 +34,44 @@ where
 impl InitializeMultisig<'_, '_, '_> {
     #[inline(always)]
     pub fn invoke(&self) -> ProgramResult {
-        let &Self {
-            multisig,
-            rent_sysvar,
-            signers,
-            m,
-            token_program,
-        } = self;
-
-        if signers.len() > MAX_MULTISIG_SIGNERS {
+        if self.signers.len() > MAX_MULTISIG_SIGNERS {
             return Err(ProgramError::InvalidArgument);
         }
 
-        let num_accounts = 2 + signers.len();
-
         // Account metadata
-        const UNINIT_META: MaybeUninit<AccountMeta> = MaybeUninit::<AccountMeta>::uninit();
-        let mut acc_metas = [UNINIT_META; 2 + MAX_MULTISIG_SIGNERS];
+        let mut accounts = CpiAccounts::<{ 2 + MAX_MULTISIG_SIGNERS }>::new();
 
-        unsafe {
-            // SAFETY:
-            // - `account_metas` is sized to 2 + MAX_MULTISIG_SIGNERS
-            // - Index 0 and 1 are always present
-            acc_metas
-                .get_unchecked_mut(0)
-                .write(AccountMeta::writable(multisig.key()));
-            acc_metas
-                .get_unchecked_mut(1)
-                .write(AccountMeta::readonly(rent_sysvar.key()));
-        }
+        accounts.push(self.multisig, true, false);
+        accounts.push(self.rent_sysvar, false, false);
 
-        for (account_meta, signer) in acc_metas[2..].iter_mut().zip(signers.iter()) {
-            account_meta.write(AccountMeta::readonly(signer.key()));
+        for signer in self.signers {
+            accounts.push(signer, false, false);
         }
 
-        // Instruction data layout:
-        // -  [0]: instruction discriminator (1 byte, u8)
-        // -  [1]: m (1 byte, u8)
-        let data = &[2, m];
+        let instruction_data = self.instruction_data();
 
         let instruction = Instruction {
-            program_id: token_program,
-            accounts: unsafe { slice::from_raw_parts(acc_metas.as_ptr() as _, num_accounts) },
-            data,
+            program_id: self.token_program,
+            accounts: accounts.metas(),
+            data: instruction_data.finish(),
         };
 
-        // Account info array
-        const UNINIT_INFO: MaybeUninit<&AccountInfo> = MaybeUninit::uninit();
-        let mut acc_infos = [UNINIT_INFO; 2 + MAX_MULTISIG_SIGNERS];
-
-        unsafe {
-            // SAFETY:
-            // - `account_infos` is sized to 2 + MAX_MULTISIG_SIGNERS
-            // - Index 0 and 1 are always present
-            acc_infos.get_unchecked_mut(0).write(multisig);
-            acc_infos.get_unchecked_mut(1).write(rent_sysvar);
-        }
+        invoke_signed_with_bounds::<{ 2 + MAX_MULTISIG_SIGNERS }>(
+            &instruction,
+            accounts.infos(),
+            &[],
+        )
+    }
 
-        // Fill signer accounts
-        for (account_info, signer) in acc_infos[2..].iter_mut().zip(signers.iter()) {
-            account_info.write(signer);
+    /// Return the instruction data.
+    #[inline(always)]
+    pub fn instruction_data(&self) -> IxData<2> {
+        pinocchio_token::instructions::InitializeMultisig {
+            multisig: self.multisig,
+            rent_sysvar: self.rent_sysvar,
+            signers: self.signers,
+            m: self.m,
         }
-
-        invoke_with_bounds::<{ 2 + MAX_MULTISIG_SIGNERS }>(&instruction, unsafe {
-            slice::from_raw_parts(acc_infos.as_ptr() as _, num_accounts)
-        })
+        .instruction_data()
     }
 }
@@ -1,14 +1,10 @@
-use core::{mem::MaybeUninit, slice};
-
 use pinocchio::{
-    account_info::AccountInfo,
-    cpi::invoke_with_bounds,
-    instruction::{AccountMeta, Instruction},
-    program_error::ProgramError,
-    pubkey::Pubkey,
-    ProgramResult,
+    account_info::AccountInfo, instruction::Instruction, program_error::ProgramError,
+    pubkey::Pubkey, ProgramResult,
 };
 
+use crate::cpi::{invoke_signed_with_bounds, CpiAccounts, IxData};
+
 use crate::instructions::MAX_MULTISIG_SIGNERS;
 
 /// Initialize a new Multisig.
@@ -35,65 +31,42 @@ where
 impl InitializeMultisig2<'_, '_, '_> {
     #[inline(always)]
     pub fn invoke(&self) -> ProgramResult {
-        let &Self {
-            multisig,
-            signers,
-            m,
-            token_program,
-        } = self;
-
-        if signers.len() > MAX_MULTISIG_SIGNERS {
+        if self.signers.len() > MAX_MULTISIG_SIGNERS {
             return Err(ProgramError::InvalidArgument);
         }
 
-        let num_accounts = 1 + signers.len();
-
         // Account metadata
-        const UNINIT_META: MaybeUninit<AccountMeta> = MaybeUninit::<AccountMeta>::uninit();
-        let mut acc_metas = [UNINIT_META; 1 + MAX_MULTISIG_SIGNERS];
+        let mut accounts = CpiAccounts::<{ 1 + MAX_MULTISIG_SIGNERS }>::new();
 
-        unsafe {
-            // SAFETY:
-            // - `account_metas` is sized to 1 + MAX_MULTISIG_SIGNERS
-            // - Index 0 is always present
-            acc_metas
-                .get_unchecked_mut(0)
-                .write(AccountMeta::writable(multisig.key()));
-        }
+        accounts.push(self.multisig, true, false);
 
-        for (account_meta, signer) in acc_metas[1..].iter_mut().zip(signers.iter()) {
-            account_meta.write(AccountMeta::readonly(signer.key()));
+        for signer in self.signers {
+            accounts.push(signer, false, false);
         }
 
-        // Instruction data layout:
-        // -  [0]: instruction discriminator (1 byte, u8)
-        // -  [1]: m (1 byte, u8)
-        let data = &[19, m];
+        let instruction_data = self.instruction_data();
 
         let instruction = Instruction {
-            program_id: token_program,
-            accounts: unsafe { slice::from_raw_parts(acc_metas.as_ptr() as _, num_accounts) },
-            data,
+            program_id: self.token_program,
+            accounts: accounts.metas(),
+            data: instruction_data.finish(),
         };
 
-        // Account info array
-        const UNINIT_INFO: MaybeUninit<&AccountInfo> = MaybeUninit::uninit();
-        let mut acc_infos = [UNINIT_INFO; 1 + MAX_MULTISIG_SIGNERS];
-
-        unsafe {
-            // SAFETY:
-            // - `account_infos` is sized to 1 + MAX_MULTISIG_SIGNERS
-            // - Index 0 is always present
-            acc_infos.get_unchecked_mut(0).write(multisig);
-        }
+        invoke_signed_with_bounds::<{ 1 + MAX_MULTISIG_SIGNERS }>(
+            &instruction,
+            accounts.infos(),
+            &[],
+        )
+    }
 
-        // Fill signer accounts
-        for (account_info, signer) in acc_infos[1..].iter_mut().zip(signers.iter()) {
-            account_info.write(signer);
+    /// Return the instruction data.
+    #[inline(always)]
+    pub fn instruction_data(&self) -> IxData<2> {
+        pinocchio_token::instructions::InitializeMultisig2 {
+            multisig: self.multisig,
+            signers: self.signers,
+            m: self.m,
         }
-
-        invoke_with_bounds::<{ 1 + MAX_MULTISIG_SIGNERS }>(&instruction, unsafe {
-            slice::from_raw_parts(acc_infos.as_ptr() as _, num_accounts)
-        })
+        .instruction_data()
     }
 }
@@ -1,14 +1,11 @@
-use core::slice::from_raw_parts;
-
 use pinocchio::{
     account_info::AccountInfo,
     instruction::{AccountMeta, Instruction, Signer},
-    program::invoke_signed,
     pubkey::Pubkey,
     ProgramResult,
 };
 
-use crate::{write_bytes, UNINIT_BYTE};
+use crate::cpi::{invoke_signed, IxData};
 
 /// Mints new tokens to an account.
 ///
@@ -31,7 +28,7 @@ pub struct MintToChecked<'a, 'b> {
     pub token_program: &'b Pubkey,
 }
 
-impl MintToChecked<'_, '_> {
+impl<'a> MintToChecked<'a, '_> {
     #[inline(always)]
     pub fn invoke(&self) -> ProgramResult {
         self.invoke_signed(&[])
@@ -39,30 +36,13 @@ impl MintToChecked<'_, '_> {
 
     #[inline(always)]
     pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
-        // account metadata
-        let account_metas: [AccountMeta; 3] = [
-            AccountMeta::writable(self.mint.key()),
-            AccountMeta::writable(self.account.key()),
-            AccountMeta::readonly_signer(self.mint_authority.key()),
-        ];
-
-        // Instruction data layout:
-        // -  [0]: instruction discriminator (1 byte, u8)
-        // -  [1..9]: amount (8 bytes, u64)
-        // -  [9]: decimals (1 byte, u8)
-        let mut instruction_data = [UNINIT_BYTE; 10];
-
-        // Set discriminator as u8 at offset [0]
-        write_bytes(&mut instruction_data, &[14]);
-        // Set amount as u64 at offset [1..9]
-        write_bytes(&mut instruction_data[1..9], &self.amount.to_le_bytes());
-        // Set decimals as u8 at offset [9]
-        write_bytes(&mut instruction_data[9..], &[self.decimals]);
+        let account_metas = self.account_metas();
+        let instruction_data = self.instruction_data();
 
         let instruction = Instruction {
             program_id: self.token_program,
             accounts: &account_metas,
-            data: unsafe { from_raw_parts(instruction_data.as_ptr() as _, 10) },
+            data: instruction_data.finish(),
         };
 
         invoke_signed(
@@ -71,4 +51,28 @@ impl MintToChecked<'_, '_> {
             signers,
         )
     }
+
+    /// Return the account metas of the instruction.
+    #[inline(always)]
+    pub fn account_metas(&self) -> [AccountMeta<'a>; 3] {
+        self.base().account_metas()
+    }
+
+    /// Return the instruction data.
+    #[inline(always)]
+    pub fn instruction_data(&self) -> IxData<10> {
+        self.base().instruction_data()
+    }
+
+    /// Return the `pinocchio-token` builder of the same instruction.
+    #[inline(always)]
+    fn base(&self) -> pinocchio_token::instructions::MintToChecked<'a> {
+        pinocchio_token::instructions::MintToChecked {
+            mint: self.mint,
+            account: self.account,
+            mint_authority: self.mint_authority,
+            amount: self.amount,
+            decimals: self.decimals,
+        }
+    }
 }
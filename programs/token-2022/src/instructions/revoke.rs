@@ -1,11 +1,12 @@
 use pinocchio::{
     account_info::AccountInfo,
     instruction::{AccountMeta, Instruction, Signer},
-    program::invoke_signed,
     pubkey::Pubkey,
     ProgramResult,
 };
 
+use crate::cpi::{invoke_signed, IxData};
+
 /// Revokes the delegate's authority.
 ///
 /// ### Accounts:
@@ -20,7 +21,7 @@ pub struct Revoke<'a, 'b> {
     pub token_program: &'b Pubkey,
 }
 
-impl Revoke<'_, '_> {
+impl<'a> Revoke<'a, '_> {
     #[inline(always)]
     pub fn invoke(&self) -> ProgramResult {
         self.invoke_signed(&[])
@@ -28,18 +29,36 @@ impl Revoke<'_, '_> {
 
     #[inline(always)]
     pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
-        // account metadata
-        let account_metas: [AccountMeta; 2] = [
-            AccountMeta::writable(self.source.key()),
-            AccountMeta::readonly_signer(self.authority.key()),
-        ];
+        let account_metas = self.account_metas();
+        let instruction_data = self.instruction_data();
 
         let instruction = Instruction {
             program_id: self.token_program,
             accounts: &account_metas,
-            data: &[5],
+            data: instruction_data.finish(),
         };
 
         invoke_signed(&instruction, &[self.source, self.authority], signers)
     }
+
+    /// Return the account metas of the instruction.
+    #[inline(always)]
+    pub fn account_metas(&self) -> [AccountMeta<'a>; 2] {
+        self.base().account_metas()
+    }
+
+    /// Return the instruction data.
+    #[inline(always)]
+    pub fn instruction_data(&self) -> IxData<1> {
+        self.base().instruction_data()
+    }
+
+    /// Return the `pinocchio-token` builder of the same instruction.
+    #[inline(always)]
+    fn base(&self) -> pinocchio_token::instructions::Revoke<'a> {
+        pinocchio_token::instructions::Revoke {
+            source: self.source,
+            authority: self.authority,
+        }
+    }
 }
@@ -1,23 +1,13 @@
-use core::slice::from_raw_parts;
-
 use pinocchio::{
     account_info::AccountInfo,
     instruction::{AccountMeta, Instruction, Signer},
-    program::invoke_signed,
     pubkey::Pubkey,
     ProgramResult,
 };
 
-use crate::{write_bytes, UNINIT_BYTE};
+use crate::cpi::{invoke_signed, IxData};
 
-#[repr(u8)]
-#[derive(Clone, Copy)]
-pub enum AuthorityType {
-    MintTokens = 0,
-    FreezeAccount = 1,
-    AccountOwner = 2,
-    CloseAccount = 3,
-}
+pub use pinocchio_token::instructions::AuthorityType;
 
 /// Sets a new authority of a mint or account.
 ///
@@ -37,7 +27,7 @@ pub struct SetAuthority<'a, 'b> {
     pub token_program: &'b Pubkey,
 }
 
-impl SetAuthority<'_, '_> {
+impl<'a> SetAuthority<'a, '_> {
     #[inline(always)]
     pub fn invoke(&self) -> ProgramResult {
         self.invoke_signed(&[])
@@ -45,41 +35,38 @@ impl SetAuthority<'_, '_> {
 
     #[inline(always)]
     pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
-        // account metadata
-        let account_metas: [AccountMeta; 2] = [
-            AccountMeta::writable(self.account.key()),
-            AccountMeta::readonly_signer(self.authority.key()),
-        ];
-
-        // instruction data
-        // -  [0]: instruction discriminator (1 byte, u8)
-        // -  [1]: authority_type (1 byte, u8)
-        // -  [2]: new_authority presence flag (1 byte, AuthorityType)
-        // -  [3..35] new_authority (optional, 32 bytes, Pubkey)
-        let mut instruction_data = [UNINIT_BYTE; 35];
-        let mut length = instruction_data.len();
-
-        // Set discriminator as u8 at offset [0]
-        write_bytes(&mut instruction_data, &[6]);
-        // Set authority_type as u8 at offset [1]
-        write_bytes(&mut instruction_data[1..2], &[self.authority_type as u8]);
-
-        if let Some(new_authority) = self.new_authority {
-            // Set new_authority as [u8; 32] at offset [2..35]
-            write_bytes(&mut instruction_data[2..3], &[1]);
-            write_bytes(&mut instruction_data[3..], new_authority);
-        } else {
-            write_bytes(&mut instruction_data[2..3], &[0]);
-            // Adjust length if no new authority
-            length = 3;
-        }
+        let account_metas = self.account_metas();
+        let instruction_data = self.instruction_data();
 
         let instruction = Instruction {
             program_id: self.token_program,
             accounts: &account_metas,
-            data: unsafe { from_raw_parts(instruction_data.as_ptr() as _, length) },
+            data: instruction_data.finish(),
         };
 
         invoke_signed(&instruction, &[self.account, self.authority], signers)
     }
+
+    /// Return the account metas of the instruction.
+    #[inline(always)]
+    pub fn account_metas(&self) -> [AccountMeta<'a>; 2] {
+        self.base().account_metas()
+    }
+
+    /// Return the instruction data.
+    #[inline(always)]
+    pub fn instruction_data(&self) -> IxData<35> {
+        self.base().instruction_data()
+    }
+
+    /// Return the `pinocchio-token` builder of the same instruction.
+    #[inline(always)]
+    fn base(&self) -> pinocchio_token::instructions::SetAuthority<'a> {
+        pinocchio_token::instructions::SetAuthority {
+            account: self.account,
+            authority: self.authority,
+            authority_type: self.authority_type,
+            new_authority: self.new_authority,
+        }
+    }
 }
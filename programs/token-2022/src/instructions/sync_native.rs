@@ -1,11 +1,12 @@
 use pinocchio::{
     account_info::AccountInfo,
-    cpi::invoke,
     instruction::{AccountMeta, Instruction},
     pubkey::Pubkey,
     ProgramResult,
 };
 
+use crate::cpi::{invoke, IxData};
+
 /// Given a native token account updates its amount field based
 /// on the account's underlying `lamports`.
 ///
@@ -19,18 +20,38 @@ pub struct SyncNative<'a, 'b> {
     pub token_program: &'b Pubkey,
 }
 
-impl SyncNative<'_, '_> {
+impl<'a> SyncNative<'a, '_> {
     #[inline(always)]
     pub fn invoke(&self) -> ProgramResult {
-        // account metadata
-        let account_metas: [AccountMeta; 1] = [AccountMeta::writable(self.native_token.key())];
+        let account_metas = self.account_metas();
+        let instruction_data = self.instruction_data();
 
         let instruction = Instruction {
             program_id: self.token_program,
             accounts: &account_metas,
-            data: &[17],
+            data: instruction_data.finish(),
         };
 
         invoke(&instruction, &[self.native_token])
     }
+
+    /// Return the account metas of the instruction.
+    #[inline(always)]
+    pub fn account_metas(&self) -> [AccountMeta<'a>; 1] {
+        self.base().account_metas()
+    }
+
+    /// Return the instruction data.
+    #[inline(always)]
+    pub fn instruction_data(&self) -> IxData<1> {
+        self.base().instruction_data()
+    }
+
+    /// Return the `pinocchio-token` builder of the same instruction.
+    #[inline(always)]
+    fn base(&self) -> pinocchio_token::instructions::SyncNative<'a> {
+        pinocchio_token::instructions::SyncNative {
+            native_token: self.native_token,
+        }
+    }
 }
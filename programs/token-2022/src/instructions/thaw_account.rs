@@ -1,11 +1,12 @@
 use pinocchio::{
     account_info::AccountInfo,
     instruction::{AccountMeta, Instruction, Signer},
-    program::invoke_signed,
     pubkey::Pubkey,
     ProgramResult,
 };
 
+use crate::cpi::{invoke_signed, IxData};
+
 /// Thaw a Frozen account using the Mint's freeze authority
 ///
 /// ### Accounts:
@@ -23,7 +24,7 @@ pub struct ThawAccount<'a, 'b> {
     pub token_program: &'b Pubkey,
 }
 
-impl ThawAccount<'_, '_> {
+impl<'a> ThawAccount<'a, '_> {
     #[inline(always)]
     pub fn invoke(&self) -> ProgramResult {
         self.invoke_signed(&[])
@@ -31,17 +32,13 @@ impl ThawAccount<'_, '_> {
 
     #[inline(always)]
     pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
-        // account metadata
-        let account_metas: [AccountMeta; 3] = [
-            AccountMeta::writable(self.account.key()),
-            AccountMeta::readonly(self.mint.key()),
-            AccountMeta::readonly_signer(self.freeze_authority.key()),
-        ];
+        let account_metas = self.account_metas();
+        let instruction_data = self.instruction_data();
 
         let instruction = Instruction {
             program_id: self.token_program,
             accounts: &account_metas,
-            data: &[11],
+            data: instruction_data.finish(),
         };
 
         invoke_signed(
@@ -50,4 +47,26 @@ impl ThawAccount<'_, '_> {
             signers,
         )
     }
+
+    /// Return the account metas of the instruction.
+    #[inline(always)]
+    pub fn account_metas(&self) -> [AccountMeta<'a>; 3] {
+        self.base().account_metas()
+    }
+
+    /// Return the instruction data.
+    #[inline(always)]
+    pub fn instruction_data(&self) -> IxData<1> {
+        self.base().instruction_data()
+    }
+
+    /// Return the `pinocchio-token` builder of the same instruction.
+    #[inline(always)]
+    fn base(&self) -> pinocchio_token::instructions::ThawAccount<'a> {
+        pinocchio_token::instructions::ThawAccount {
+            account: self.account,
+            mint: self.mint,
+            freeze_authority: self.freeze_authority,
+        }
+    }
 }
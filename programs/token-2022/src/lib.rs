@@ -1,17 +1,21 @@
 #![no_std]
 
+pub mod batch;
+mod cpi;
+pub mod delegation;
+pub mod errors;
+pub mod extensions;
+pub mod factory;
+pub mod gc;
+pub mod ids;
 pub mod instructions;
+mod math;
+pub mod plan;
+pub mod pod;
 pub mod state;
+pub mod validation;
 
-pinocchio_pubkey::declare_id!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
-
-use core::mem::MaybeUninit;
+#[cfg(test)]
+mod test_utils;
 
-const UNINIT_BYTE: MaybeUninit<u8> = MaybeUninit::<u8>::uninit();
-
-#[inline(always)]
-fn write_bytes(destination: &mut [MaybeUninit<u8>], source: &[u8]) {
-    for (d, s) in destination.iter_mut().zip(source.iter()) {
-        d.write(*s);
-    }
-}
+pinocchio_pubkey::declare_id!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
@@ -0,0 +1,106 @@
+//! Internal floating-point helpers.
+//!
+//! `f64::exp` and `f64::powi` are only available with `std`, so the UI amount
+//! conversions use these implementations instead.
+
+/// High bits of `ln(2)`, exactly representable when multiplied by any
+/// exponent of an `f64`.
+const LN2_HI: f64 = 6.931_471_803_691_238e-1;
+
+/// Low bits of `ln(2)`.
+const LN2_LO: f64 = 1.908_214_929_270_587_7e-10;
+
+/// Largest `x` for which `exp(x)` is finite.
+const EXP_OVERFLOW: f64 = 7.097_827_128_933_84e2;
+
+/// Smallest `x` for which `exp(x)` is not zero.
+const EXP_UNDERFLOW: f64 = -7.451_332_191_019_412e2;
+
+/// Number of terms of the Taylor series of `exp(r)`, with `|r| <= ln(2) / 2`.
+const EXP_TERMS: u32 = 14;
+
+/// Return `e^x`.
+///
+/// The argument is reduced to `x = k * ln(2) + r` and `e^r` is evaluated with
+/// its Taylor series, which is accurate to a few ULPs.
+pub(crate) fn exp(x: f64) -> f64 {
+    if x.is_nan() {
+        return x;
+    }
+    if x > EXP_OVERFLOW {
+        return f64::INFINITY;
+    }
+    if x < EXP_UNDERFLOW {
+        return 0.0;
+    }
+
+    let k = (x * core::f64::consts::LOG2_E + if x < 0.0 { -0.5 } else { 0.5 }) as i32;
+    let r = (x - k as f64 * LN2_HI) - k as f64 * LN2_LO;
+
+    let mut sum = 1.0;
+    let mut n = EXP_TERMS;
+    while n > 0 {
+        sum = 1.0 + sum * r / n as f64;
+        n -= 1;
+    }
+
+    // `2^k` may be out of the range of normal numbers, so it is applied in
+    // two steps.
+    let half = k / 2;
+    sum * pow2(half) * pow2(k - half)
+}
+
+/// Return `2^k`, for `k` in the range of normal exponents.
+#[inline(always)]
+fn pow2(k: i32) -> f64 {
+    f64::from_bits(((k + 1023) as u64) << 52)
+}
+
+/// Return `10^decimals`.
+#[inline]
+pub(crate) fn pow10(decimals: u8) -> f64 {
+    let mut value = 1.0;
+    for _ in 0..decimals {
+        value *= 10.0;
+    }
+    value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exp() {
+        // Values of `f64::exp`.
+        let cases = [
+            (0.0, 1.0),
+            (1.0, core::f64::consts::E),
+            (0.05, 1.051_271_096_376_024_1),
+            (-0.05, 0.951_229_424_500_714),
+            (-10.5, 2.753_644_934_974_715_8e-5),
+            (100.0, 2.688_117_141_816_135_6e43),
+            (700.0, 1.014_232_054_735_004_5e304),
+            (-700.0, 9.859_676_543_759_77e-305),
+        ];
+
+        for (x, expected) in cases {
+            let value = exp(x);
+            assert!(
+                ((value - expected) / expected).abs() < 1e-14,
+                "exp({x}) = {value}, expected {expected}"
+            );
+        }
+
+        assert_eq!(exp(710.0), f64::INFINITY);
+        assert_eq!(exp(-746.0), 0.0);
+        assert!(exp(f64::NAN).is_nan());
+    }
+
+    #[test]
+    fn test_pow10() {
+        assert_eq!(pow10(0), 1.0);
+        assert_eq!(pow10(9), 1e9);
+        assert_eq!(pow10(22), 1e22);
+    }
+}
@@ -0,0 +1,165 @@
+//! Summary of the accounts used by helpers composed of several CPIs.
+
+use core::{mem::MaybeUninit, slice::from_raw_parts};
+
+use pinocchio::{instruction::AccountMeta, program_error::ProgramError, pubkey::Pubkey};
+
+/// Account used by at least one of the CPIs of a plan.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PlannedAccount<'a> {
+    /// Account key.
+    pub key: &'a Pubkey,
+    /// Whether the account is writable in any of the CPIs.
+    pub is_writable: bool,
+    /// Whether the account is a signer in any of the CPIs.
+    pub is_signer: bool,
+}
+
+/// Accumulator of the account metas of the CPIs invoked by a composed
+/// helper.
+///
+/// Composed helpers expose a `*_dry_run` function that populates a plan with
+/// the metas of each CPI, in invocation order, without invoking them. The
+/// plan keeps the unique accounts touched, for budgeting, and detects
+/// accounts used with inconsistent signer roles.
+pub struct CpiPlan<'a, const MAX_ACCOUNTS: usize> {
+    accounts: [MaybeUninit<PlannedAccount<'a>>; MAX_ACCOUNTS],
+    len: usize,
+    cpis: usize,
+    inconsistent: Option<&'a Pubkey>,
+}
+
+impl<'a, const MAX_ACCOUNTS: usize> CpiPlan<'a, MAX_ACCOUNTS> {
+    const UNINIT_ACCOUNT: MaybeUninit<PlannedAccount<'a>> = MaybeUninit::uninit();
+
+    #[inline(always)]
+    pub fn new() -> Self {
+        Self {
+            accounts: [Self::UNINIT_ACCOUNT; MAX_ACCOUNTS],
+            len: 0,
+            cpis: 0,
+            inconsistent: None,
+        }
+    }
+
+    /// Add the account metas of a CPI to the plan.
+    ///
+    /// Returns `InvalidArgument` if the plan would exceed `MAX_ACCOUNTS`
+    /// unique accounts.
+    pub fn add_cpi(&mut self, metas: &[AccountMeta<'a>]) -> Result<(), ProgramError> {
+        for meta in metas {
+            match self.position(meta.pubkey) {
+                Some(index) => {
+                    // SAFETY: The first `len` accounts have been initialized.
+                    let account = unsafe { self.accounts[index].assume_init_mut() };
+
+                    if account.is_signer != meta.is_signer && self.inconsistent.is_none() {
+                        self.inconsistent = Some(meta.pubkey);
+                    }
+
+                    account.is_writable |= meta.is_writable;
+                    account.is_signer |= meta.is_signer;
+                }
+                None => {
+                    if self.len == MAX_ACCOUNTS {
+                        return Err(ProgramError::InvalidArgument);
+                    }
+
+                    self.accounts[self.len].write(PlannedAccount {
+                        key: meta.pubkey,
+                        is_writable: meta.is_writable,
+                        is_signer: meta.is_signer,
+                    });
+                    self.len += 1;
+                }
+            }
+        }
+
+        self.cpis += 1;
+
+        Ok(())
+    }
+
+    /// Return the unique accounts of the plan, in order of first use.
+    #[inline(always)]
+    pub fn unique_accounts(&self) -> &[PlannedAccount<'a>] {
+        // SAFETY: The first `len` accounts have been initialized.
+        unsafe { from_raw_parts(self.accounts.as_ptr() as _, self.len) }
+    }
+
+    /// Return the number of CPIs in the plan.
+    #[inline(always)]
+    pub fn cpi_count(&self) -> usize {
+        self.cpis
+    }
+
+    /// Check that no account is a signer in one CPI and a non-signer in
+    /// another.
+    ///
+    /// Returns the first inconsistent account key as the error.
+    #[inline]
+    pub fn check(&self) -> Result<(), &'a Pubkey> {
+        match self.inconsistent {
+            Some(key) => Err(key),
+            None => Ok(()),
+        }
+    }
+
+    fn position(&self, key: &Pubkey) -> Option<usize> {
+        self.unique_accounts()
+            .iter()
+            .position(|account| account.key == key)
+    }
+}
+
+impl<const MAX_ACCOUNTS: usize> Default for CpiPlan<'_, MAX_ACCOUNTS> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plan_merges_accounts() {
+        let (a, b, c) = ([1; 32], [2; 32], [3; 32]);
+        let mut plan = CpiPlan::<3>::new();
+
+        plan.add_cpi(&[AccountMeta::readonly(&a), AccountMeta::readonly_signer(&b)])
+            .unwrap();
+        plan.add_cpi(&[AccountMeta::writable(&a), AccountMeta::readonly_signer(&b)])
+            .unwrap();
+
+        assert_eq!(plan.cpi_count(), 2);
+        assert_eq!(
+            plan.unique_accounts(),
+            &[
+                PlannedAccount {
+                    key: &a,
+                    is_writable: true,
+                    is_signer: false,
+                },
+                PlannedAccount {
+                    key: &b,
+                    is_writable: false,
+                    is_signer: true,
+                },
+            ]
+        );
+        assert_eq!(plan.check(), Ok(()));
+
+        // `b` is demoted to a non-signer.
+        plan.add_cpi(&[AccountMeta::writable(&b), AccountMeta::readonly(&c)])
+            .unwrap();
+
+        assert_eq!(plan.unique_accounts().len(), 3);
+        assert_eq!(plan.check(), Err(&b));
+
+        assert_eq!(
+            plan.add_cpi(&[AccountMeta::readonly(&[4; 32])]),
+            Err(ProgramError::InvalidArgument)
+        );
+    }
+}
@@ -0,0 +1,21 @@
+//! Plain-old-data types used by the account layouts.
+//!
+//! The types are re-exported from `pinocchio-token`, so both crates share a
+//! single definition.
+
+use pinocchio::pubkey::Pubkey;
+
+pub use pinocchio_token::pod::*;
+
+/// Return `key`, or `None` for the zero key.
+///
+/// Optional keys of the extensions are stored as a `Pubkey`, where the zero
+/// key means `None`.
+#[inline(always)]
+pub(crate) fn non_zero(key: &Pubkey) -> Option<&Pubkey> {
+    if key == &[0; 32] {
+        None
+    } else {
+        Some(key)
+    }
+}
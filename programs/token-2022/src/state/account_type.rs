@@ -0,0 +1,249 @@
+use pinocchio::{
+    account_info::{AccountInfo, Ref},
+    program_error::ProgramError,
+};
+
+use super::{Mint, Multisig, TokenAccount};
+use crate::ID;
+
+/// Length of a `Multisig` account.
+///
+/// Accounts with extensions never have this length: Token-2022 adds an
+/// `Uninitialized` padding entry to accounts that would otherwise be
+/// mistaken for a multisig.
+pub(crate) const MULTISIG_LEN: usize = Multisig::LEN;
+
+/// Offset of the account type discriminator on accounts with extensions.
+///
+/// Mints with extensions are padded to the length of a token account so that
+/// the account type is always at the same offset.
+pub const ACCOUNT_TYPE_OFFSET: usize = TokenAccount::BASE_LEN;
+
+/// Account type discriminator written by Token-2022 on accounts with
+/// extensions.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AccountType {
+    /// Marker for 0 data.
+    Uninitialized,
+
+    /// Mint account with additional extensions.
+    Mint,
+
+    /// Token holding account with additional extensions.
+    Account,
+}
+
+/// Kind of a Token-2022 account, inferred from its data.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TokenAccountKind {
+    Mint,
+    TokenAccount,
+    Multisig,
+    /// Account with an account type discriminator not known by this crate.
+    ///
+    /// Only returned when classifying accounts with [`Strictness::Lenient`].
+    Unknown(u8),
+}
+
+/// How to treat account type discriminators not known by this crate.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Strictness {
+    /// Reject unknown account type discriminators.
+    #[default]
+    Strict,
+
+    /// Report unknown account type discriminators as
+    /// [`TokenAccountKind::Unknown`].
+    Lenient,
+}
+
+/// Classify an account from its data.
+///
+/// Accounts without extensions are identified by their length. Accounts with
+/// extensions are identified by the account type discriminator, which must
+/// be a known value unless `strictness` is [`Strictness::Lenient`].
+pub fn classify_account(
+    data: &[u8],
+    strictness: Strictness,
+) -> Result<TokenAccountKind, ProgramError> {
+    match data.len() {
+        Mint::BASE_LEN => Ok(TokenAccountKind::Mint),
+        TokenAccount::BASE_LEN => Ok(TokenAccountKind::TokenAccount),
+        MULTISIG_LEN => Ok(TokenAccountKind::Multisig),
+        len if len > ACCOUNT_TYPE_OFFSET => match data[ACCOUNT_TYPE_OFFSET] {
+            account_type if account_type == AccountType::Mint as u8 => Ok(TokenAccountKind::Mint),
+            account_type if account_type == AccountType::Account as u8 => {
+                Ok(TokenAccountKind::TokenAccount)
+            }
+            account_type
+                if strictness == Strictness::Lenient
+                    && account_type != AccountType::Uninitialized as u8 =>
+            {
+                Ok(TokenAccountKind::Unknown(account_type))
+            }
+            _ => Err(ProgramError::InvalidAccountData),
+        },
+        _ => Err(ProgramError::InvalidAccountData),
+    }
+}
+
+/// Account of any kind owned by the token program, as returned by
+/// [`from_account_info_any`].
+pub enum AnyAccount<'a> {
+    Mint(Ref<'a, Mint>),
+    TokenAccount(Ref<'a, TokenAccount>),
+    Multisig(Ref<'a, Multisig>),
+    /// Account with an account type discriminator not known by this crate.
+    ///
+    /// Only returned with [`Strictness::Lenient`]; the account data is not
+    /// borrowed.
+    Unknown(u8),
+}
+
+impl AnyAccount<'_> {
+    /// Return the kind of the account.
+    #[inline(always)]
+    pub fn kind(&self) -> TokenAccountKind {
+        match self {
+            AnyAccount::Mint(_) => TokenAccountKind::Mint,
+            AnyAccount::TokenAccount(_) => TokenAccountKind::TokenAccount,
+            AnyAccount::Multisig(_) => TokenAccountKind::Multisig,
+            AnyAccount::Unknown(account_type) => TokenAccountKind::Unknown(*account_type),
+        }
+    }
+}
+
+/// Return the base state of an account of any kind from the given account
+/// info.
+///
+/// This function performs owner validation on `AccountInfo` and classifies
+/// the account with [`classify_account`], safe borrowing the account data.
+///
+/// Returns `InvalidAccountOwner` if the account is not owned by the token
+/// program, `InvalidAccountData` if the account cannot be classified and
+/// `AccountBorrowFailed` if the account data is mutably borrowed.
+#[inline]
+pub fn from_account_info_any(
+    account_info: &AccountInfo,
+    strictness: Strictness,
+) -> Result<AnyAccount, ProgramError> {
+    if !account_info.is_owned_by(&ID) {
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+
+    let data = account_info.try_borrow_data()?;
+
+    // SAFETY: The base states have an alignment of 1, are valid for any byte
+    // pattern and `classify_account` validated the length of the data.
+    Ok(match classify_account(&data, strictness)? {
+        TokenAccountKind::Mint => AnyAccount::Mint(Ref::map(data, |data| unsafe {
+            Mint::from_bytes_unchecked(data)
+        })),
+        TokenAccountKind::TokenAccount => AnyAccount::TokenAccount(Ref::map(data, |data| unsafe {
+            TokenAccount::from_bytes_unchecked(data)
+        })),
+        TokenAccountKind::Multisig => AnyAccount::Multisig(Ref::map(data, |data| unsafe {
+            Multisig::from_bytes_unchecked(data)
+        })),
+        TokenAccountKind::Unknown(account_type) => AnyAccount::Unknown(account_type),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TestAccount;
+
+    fn account_with_type(account_type: u8) -> [u8; ACCOUNT_TYPE_OFFSET + 1] {
+        let mut data = [0; ACCOUNT_TYPE_OFFSET + 1];
+        data[ACCOUNT_TYPE_OFFSET] = account_type;
+        data
+    }
+
+    #[test]
+    fn test_classify_base_accounts() {
+        for strictness in [Strictness::Strict, Strictness::Lenient] {
+            assert_eq!(
+                classify_account(&[0; Mint::BASE_LEN], strictness),
+                Ok(TokenAccountKind::Mint)
+            );
+            assert_eq!(
+                classify_account(&[0; TokenAccount::BASE_LEN], strictness),
+                Ok(TokenAccountKind::TokenAccount)
+            );
+            assert_eq!(
+                classify_account(&[0; MULTISIG_LEN], strictness),
+                Ok(TokenAccountKind::Multisig)
+            );
+            assert_eq!(
+                classify_account(&[0; 100], strictness),
+                Err(ProgramError::InvalidAccountData)
+            );
+        }
+    }
+
+    #[test]
+    fn test_classify_accounts_with_extensions() {
+        assert_eq!(
+            classify_account(&account_with_type(1), Strictness::Strict),
+            Ok(TokenAccountKind::Mint)
+        );
+        assert_eq!(
+            classify_account(&account_with_type(2), Strictness::Strict),
+            Ok(TokenAccountKind::TokenAccount)
+        );
+    }
+
+    #[test]
+    fn test_classify_unknown_account_type() {
+        let data = account_with_type(7);
+
+        assert_eq!(Strictness::default(), Strictness::Strict);
+        assert_eq!(
+            classify_account(&data, Strictness::Strict),
+            Err(ProgramError::InvalidAccountData)
+        );
+        assert_eq!(
+            classify_account(&data, Strictness::Lenient),
+            Ok(TokenAccountKind::Unknown(7))
+        );
+        // An uninitialized account type is never a valid account.
+        assert_eq!(
+            classify_account(&account_with_type(0), Strictness::Lenient),
+            Err(ProgramError::InvalidAccountData)
+        );
+    }
+
+    #[test]
+    fn test_from_account_info_any() {
+        let account = TestAccount::with_data([1; 32], ID, &account_with_type(7)).info();
+
+        assert_eq!(
+            from_account_info_any(&account, Strictness::Strict).err(),
+            Some(ProgramError::InvalidAccountData)
+        );
+        let any = from_account_info_any(&account, Strictness::Lenient).unwrap();
+        assert_eq!(any.kind(), TokenAccountKind::Unknown(7));
+        // Unknown accounts do not hold a borrow of the account data.
+        assert!(account.try_borrow_mut_data().is_ok());
+
+        let account = TestAccount::with_data([1; 32], ID, &account_with_type(2)).info();
+        for strictness in [Strictness::Strict, Strictness::Lenient] {
+            let any = from_account_info_any(&account, strictness).unwrap();
+            assert_eq!(any.kind(), TokenAccountKind::TokenAccount);
+        }
+
+        let account = TestAccount::with_data([1; 32], ID, &[0; MULTISIG_LEN]).info();
+        assert!(matches!(
+            from_account_info_any(&account, Strictness::Strict),
+            Ok(AnyAccount::Multisig(_))
+        ));
+
+        let account = TestAccount::with_data([1; 32], [9; 32], &account_with_type(1)).info();
+        assert_eq!(
+            from_account_info_any(&account, Strictness::Lenient).err(),
+            Some(ProgramError::InvalidAccountOwner)
+        );
+    }
+}
@@ -4,7 +4,8 @@ use pinocchio::{
     pubkey::Pubkey,
 };
 
-use crate::ID;
+use super::{classify_account, window, Strictness, TokenAccountKind};
+use crate::{pod::COption, ID};
 
 /// Mint data.
 #[repr(C)]
@@ -41,7 +42,8 @@ impl Mint {
     /// Return a `Mint` from the given account info.
     ///
     /// This method performs owner and length validation on `AccountInfo`, safe borrowing
-    /// the account data.
+    /// the account data. Mints with extensions are accepted; only the base state is read.
+    /// Token accounts and multisigs are rejected with `InvalidAccountData`.
     #[inline]
     pub fn from_account_info(account_info: &AccountInfo) -> Result<Ref<Mint>, ProgramError> {
         if account_info.data_len() < Self::BASE_LEN {
@@ -50,15 +52,19 @@ impl Mint {
         if !account_info.is_owned_by(&ID) {
             return Err(ProgramError::InvalidAccountOwner);
         }
-        Ok(Ref::map(account_info.try_borrow_data()?, |data| unsafe {
+        let data = account_info.try_borrow_data()?;
+        if classify_account(&data, Strictness::Strict)? != TokenAccountKind::Mint {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(Ref::map(data, |data| unsafe {
             Self::from_bytes_unchecked(data)
         }))
     }
 
     /// Return a `Mint` from the given account info.
     ///
-    /// This method performs owner and length validation on `AccountInfo`, but does not
-    /// perform the borrow check.
+    /// This method performs owner and length validation on `AccountInfo`, and rejects
+    /// token accounts and multisigs, but does not perform the borrow check.
     ///
     /// # Safety
     ///
@@ -74,9 +80,11 @@ impl Mint {
         if account_info.owner() != &ID {
             return Err(ProgramError::InvalidAccountOwner);
         }
-        Ok(Self::from_bytes_unchecked(
-            account_info.borrow_data_unchecked(),
-        ))
+        let data = account_info.borrow_data_unchecked();
+        if classify_account(data, Strictness::Strict)? != TokenAccountKind::Mint {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(Self::from_bytes_unchecked(data))
     }
 
     /// Return a `Mint` from the given bytes.
@@ -92,6 +100,18 @@ impl Mint {
         &*(bytes[..Self::BASE_LEN].as_ptr() as *const Mint)
     }
 
+    /// Return a `Mint` stored at `offset` of `buf`.
+    ///
+    /// `buf` may hold several accounts (e.g., snapshots copied into a single
+    /// buffer); only the `Mint::BASE_LEN` bytes at `offset` are read.
+    #[inline]
+    pub fn from_bytes_at(buf: &[u8], offset: usize) -> Result<&Self, ProgramError> {
+        let data = window(buf, offset, Self::BASE_LEN).ok_or(ProgramError::InvalidAccountData)?;
+        // SAFETY: `Mint` has an alignment of 1, is valid for any byte pattern
+        // and `data` has `Mint::BASE_LEN` bytes.
+        Ok(unsafe { Self::from_bytes_unchecked(data) })
+    }
+
     #[inline(always)]
     pub fn has_mint_authority(&self) -> bool {
         self.mint_authority_flag[0] == 1
@@ -105,6 +125,14 @@ impl Mint {
         }
     }
 
+    /// Return the mint authority, validating the `COption` tag.
+    ///
+    /// Returns `InvalidAccountData` if the tag is neither `0` nor `1`.
+    #[inline]
+    pub fn try_mint_authority(&self) -> Result<Option<&Pubkey>, ProgramError> {
+        COption::new(&self.mint_authority_flag, &self.mint_authority).get()
+    }
+
     /// Return the mint authority.
     ///
     /// This method should be used when the caller knows that the mint will have a mint
@@ -139,6 +167,14 @@ impl Mint {
         }
     }
 
+    /// Return the freeze authority, validating the `COption` tag.
+    ///
+    /// Returns `InvalidAccountData` if the tag is neither `0` nor `1`.
+    #[inline]
+    pub fn try_freeze_authority(&self) -> Result<Option<&Pubkey>, ProgramError> {
+        COption::new(&self.freeze_authority_flag, &self.freeze_authority).get()
+    }
+
     /// Return the freeze authority.
     ///
     /// This method should be used when the caller knows that the mint will have a freeze
@@ -148,3 +184,214 @@ impl Mint {
         &self.freeze_authority
     }
 }
+
+/// Offset of the `supply` field in the `Mint` account data.
+const SUPPLY_OFFSET: usize = 36;
+
+/// Offset of the `decimals` field in the `Mint` account data.
+const DECIMALS_OFFSET: usize = 44;
+
+/// Read the decimals from the given mint account data.
+///
+/// This is a `const fn` that can be used to read the decimals of a mint
+/// embedded in the program at compile time. Panics if `data` is shorter than
+/// the `decimals` offset, which results in a compilation error when used in a
+/// `const` context.
+pub const fn const_read_decimals(data: &[u8]) -> u8 {
+    data[DECIMALS_OFFSET]
+}
+
+/// Read the supply from the given mint account data.
+///
+/// This is a `const fn` that can be used to read the supply of a mint
+/// embedded in the program at compile time. Panics if `data` is too short,
+/// which results in a compilation error when used in a `const` context.
+pub const fn const_read_supply(data: &[u8]) -> u64 {
+    u64::from_le_bytes([
+        data[SUPPLY_OFFSET],
+        data[SUPPLY_OFFSET + 1],
+        data[SUPPLY_OFFSET + 2],
+        data[SUPPLY_OFFSET + 3],
+        data[SUPPLY_OFFSET + 4],
+        data[SUPPLY_OFFSET + 5],
+        data[SUPPLY_OFFSET + 6],
+        data[SUPPLY_OFFSET + 7],
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::vec::Vec;
+
+    use super::*;
+    use crate::{
+        extensions::BaseState,
+        state::{TokenAccount, MULTISIG_LEN},
+        test_utils::{
+            account_with_extensions, mint_base, pyusd_mint_data, token_account_base, TestAccount,
+            PYUSD_UPDATE_AUTHORITY,
+        },
+    };
+
+    #[test]
+    fn test_authorities() {
+        let data = mint_base(Some(&[1; 32]), 0, 6, None);
+        let mint = Mint::from_bytes_at(&data, 0).unwrap();
+
+        assert_eq!(mint.mint_authority(), Some(&[1; 32]));
+        assert_eq!(mint.try_mint_authority(), Ok(Some(&[1; 32])));
+        assert_eq!(mint.freeze_authority(), None);
+        assert_eq!(mint.try_freeze_authority(), Ok(None));
+
+        let data = mint_base(None, 0, 6, Some(&[2; 32]));
+        let mint = Mint::from_bytes_at(&data, 0).unwrap();
+
+        assert_eq!(mint.try_mint_authority(), Ok(None));
+        assert_eq!(mint.try_freeze_authority(), Ok(Some(&[2; 32])));
+
+        // Invalid tags.
+        let mut data = mint_base(Some(&[1; 32]), 0, 6, Some(&[2; 32]));
+        data[0] = 2;
+        data[46..50].copy_from_slice(&[1, 0, 1, 0]);
+        let mint = Mint::from_bytes_at(&data, 0).unwrap();
+
+        assert_eq!(
+            mint.try_mint_authority(),
+            Err(ProgramError::InvalidAccountData)
+        );
+        assert_eq!(
+            mint.try_freeze_authority(),
+            Err(ProgramError::InvalidAccountData)
+        );
+    }
+
+    #[test]
+    fn test_from_account_info_with_extensions() {
+        let mint = TestAccount::with_data([1; 32], ID, &pyusd_mint_data()).info();
+
+        let data = Mint::from_account_info(&mint).unwrap();
+        assert_eq!(data.mint_authority(), Some(&PYUSD_UPDATE_AUTHORITY));
+        assert_eq!(data.freeze_authority(), Some(&PYUSD_UPDATE_AUTHORITY));
+        assert_eq!(data.supply(), 0);
+        assert_eq!(data.decimals(), 6);
+        assert!(data.is_initialized());
+        drop(data);
+
+        let unchecked = unsafe { Mint::from_account_info_unchecked(&mint) }.unwrap();
+        assert_eq!(unchecked.decimals(), 6);
+
+        let token_account = TestAccount::with_data(
+            [2; 32],
+            ID,
+            &account_with_extensions(
+                BaseState::TokenAccount,
+                &token_account_base(&[1; 32], &[3; 32], 42),
+                &[(crate::extensions::ExtensionType::ImmutableOwner, &[])],
+            ),
+        )
+        .info();
+
+        let data = TokenAccount::from_account_info(&token_account).unwrap();
+        assert_eq!(data.mint(), &[1; 32]);
+        assert_eq!(data.owner(), &[3; 32]);
+        assert_eq!(data.amount(), 42);
+        drop(data);
+
+        let unchecked = unsafe { TokenAccount::from_account_info_unchecked(&token_account) };
+        assert_eq!(unchecked.unwrap().amount(), 42);
+
+        // Base state too short or wrong owner.
+        let short = TestAccount::with_data([3; 32], ID, &[0; Mint::BASE_LEN - 1]).info();
+        assert_eq!(
+            Mint::from_account_info(&short).err(),
+            Some(ProgramError::InvalidAccountData)
+        );
+        let foreign = TestAccount::with_data([4; 32], [9; 32], &pyusd_mint_data()).info();
+        assert_eq!(
+            Mint::from_account_info(&foreign).err(),
+            Some(ProgramError::InvalidAccountOwner)
+        );
+    }
+
+    #[test]
+    fn test_from_account_info_rejects_other_kinds() {
+        // A token account, with or without extensions, is not a mint.
+        for data in [
+            token_account_base(&[1; 32], &[3; 32], 42).to_vec(),
+            account_with_extensions(
+                BaseState::TokenAccount,
+                &token_account_base(&[1; 32], &[3; 32], 42),
+                &[(crate::extensions::ExtensionType::ImmutableOwner, &[])],
+            ),
+        ] {
+            let token_account = TestAccount::with_data([2; 32], ID, &data).info();
+
+            assert_eq!(
+                Mint::from_account_info(&token_account).err(),
+                Some(ProgramError::InvalidAccountData)
+            );
+            assert_eq!(
+                unsafe { Mint::from_account_info_unchecked(&token_account) }.err(),
+                Some(ProgramError::InvalidAccountData)
+            );
+        }
+
+        // An extended mint is longer than a token account, but is not one.
+        let mint = TestAccount::with_data([1; 32], ID, &pyusd_mint_data()).info();
+
+        assert_eq!(
+            TokenAccount::from_account_info(&mint).err(),
+            Some(ProgramError::InvalidAccountData)
+        );
+        assert_eq!(
+            unsafe { TokenAccount::from_account_info_unchecked(&mint) }.err(),
+            Some(ProgramError::InvalidAccountData)
+        );
+
+        // Neither is a multisig.
+        let multisig = TestAccount::with_data([3; 32], ID, &[0; MULTISIG_LEN]).info();
+
+        assert_eq!(
+            Mint::from_account_info(&multisig).err(),
+            Some(ProgramError::InvalidAccountData)
+        );
+        assert_eq!(
+            TokenAccount::from_account_info(&multisig).err(),
+            Some(ProgramError::InvalidAccountData)
+        );
+    }
+
+    #[test]
+    fn test_from_bytes_at() {
+        let mut buf = Vec::new();
+        buf.extend_from_slice(&mint_base(Some(&[1; 32]), 100, 6, None));
+        buf.extend_from_slice(&mint_base(None, 200, 9, Some(&[2; 32])));
+        buf.extend_from_slice(&token_account_base(&[3; 32], &[4; 32], 300));
+
+        let first = Mint::from_bytes_at(&buf, 0).unwrap();
+        assert_eq!(first.mint_authority(), Some(&[1; 32]));
+        assert_eq!(first.supply(), 100);
+        assert_eq!(first.decimals(), 6);
+        assert_eq!(first.freeze_authority(), None);
+
+        let second = Mint::from_bytes_at(&buf, Mint::BASE_LEN).unwrap();
+        assert_eq!(second.mint_authority(), None);
+        assert_eq!(second.supply(), 200);
+        assert_eq!(second.decimals(), 9);
+        assert_eq!(second.freeze_authority(), Some(&[2; 32]));
+
+        let token_account = TokenAccount::from_bytes_at(&buf, 2 * Mint::BASE_LEN).unwrap();
+        assert_eq!(token_account.mint(), &[3; 32]);
+        assert_eq!(token_account.amount(), 300);
+
+        // Truncated windows are rejected instead of reading past the buffer.
+        assert_eq!(
+            TokenAccount::from_bytes_at(&buf[..buf.len() - 1], 2 * Mint::BASE_LEN).err(),
+            Some(ProgramError::InvalidAccountData)
+        );
+        assert!(Mint::from_bytes_at(&buf, buf.len() - Mint::BASE_LEN + 1).is_err());
+        assert!(Mint::from_bytes_at(&buf, usize::MAX).is_err());
+    }
+}
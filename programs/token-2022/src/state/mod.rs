@@ -1,7 +1,20 @@
 mod account_state;
+mod account_type;
 mod mint;
+mod multisig;
 mod token;
 
 pub use account_state::*;
+pub use account_type::*;
 pub use mint::*;
+pub use multisig::*;
 pub use token::*;
+
+/// Return the `len` bytes of `buf` starting at `offset`.
+///
+/// Used by the readers that parse accounts stored at an arbitrary offset of a
+/// larger buffer, so all bounds checks are relative to the window.
+#[inline(always)]
+pub(crate) fn window(buf: &[u8], offset: usize, len: usize) -> Option<&[u8]> {
+    buf.get(offset..offset.checked_add(len)?)
+}
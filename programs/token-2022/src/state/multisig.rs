@@ -0,0 +1,199 @@
+use pinocchio::{
+    account_info::{AccountInfo, Ref},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use super::window;
+use crate::{instructions::MAX_MULTISIG_SIGNERS, ID};
+
+/// Minimum number of multisignature signers.
+pub const MIN_SIGNERS: usize = 1;
+
+/// Maximum number of multisignature signers.
+pub const MAX_SIGNERS: usize = MAX_MULTISIG_SIGNERS;
+
+/// Multisignature data.
+#[repr(C)]
+pub struct Multisig {
+    /// Number of signers required.
+    m: u8,
+
+    /// Number of valid signers.
+    n: u8,
+
+    /// Is `true` if this structure has been initialized.
+    is_initialized: u8,
+
+    /// Signer public keys.
+    signers: [Pubkey; MAX_SIGNERS],
+}
+
+impl Multisig {
+    /// The length of the `Multisig` account data.
+    pub const LEN: usize = core::mem::size_of::<Multisig>();
+
+    /// Return a `Multisig` from the given account info.
+    ///
+    /// This method performs owner and length validation on `AccountInfo`, safe borrowing
+    /// the account data. Multisig accounts cannot have extensions, so the length must be
+    /// exactly `Multisig::LEN`.
+    #[inline]
+    pub fn from_account_info(account_info: &AccountInfo) -> Result<Ref<Multisig>, ProgramError> {
+        if account_info.data_len() != Self::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if !account_info.is_owned_by(&ID) {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+        Ok(Ref::map(account_info.try_borrow_data()?, |data| unsafe {
+            Self::from_bytes_unchecked(data)
+        }))
+    }
+
+    /// Return a `Multisig` from the given account info.
+    ///
+    /// This method performs owner and length validation on `AccountInfo`, but does not
+    /// perform the borrow check.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that it is safe to borrow the account data (e.g., there are
+    /// no mutable borrows of the account data).
+    #[inline]
+    pub unsafe fn from_account_info_unchecked(
+        account_info: &AccountInfo,
+    ) -> Result<&Self, ProgramError> {
+        if account_info.data_len() != Self::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if account_info.owner() != &ID {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+        Ok(Self::from_bytes_unchecked(
+            account_info.borrow_data_unchecked(),
+        ))
+    }
+
+    /// Return a `Multisig` from the given bytes.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `bytes` contains a valid representation of `Multisig`, and
+    /// it is properly aligned to be interpreted as an instance of `Multisig`.
+    /// At the moment `Multisig` has an alignment of 1 byte.
+    /// This method does not perform a length validation.
+    #[inline(always)]
+    pub unsafe fn from_bytes_unchecked(bytes: &[u8]) -> &Self {
+        &*(bytes[..Self::LEN].as_ptr() as *const Multisig)
+    }
+
+    /// Return a `Multisig` stored at `offset` of `buf`.
+    ///
+    /// Only the `Multisig::LEN` bytes at `offset` are read.
+    #[inline]
+    pub fn from_bytes_at(buf: &[u8], offset: usize) -> Result<&Self, ProgramError> {
+        let data = window(buf, offset, Self::LEN).ok_or(ProgramError::InvalidAccountData)?;
+        // SAFETY: `Multisig` has an alignment of 1, is valid for any byte pattern
+        // and `data` has `Multisig::LEN` bytes.
+        Ok(unsafe { Self::from_bytes_unchecked(data) })
+    }
+
+    /// Return the number of signers required.
+    #[inline(always)]
+    pub fn m(&self) -> u8 {
+        self.m
+    }
+
+    /// Return the number of valid signers.
+    #[inline(always)]
+    pub fn n(&self) -> u8 {
+        self.n
+    }
+
+    #[inline(always)]
+    pub fn is_initialized(&self) -> bool {
+        self.is_initialized == 1
+    }
+
+    /// Return the valid signer keys, the first `n` entries of the signers.
+    #[inline]
+    pub fn signer_keys(&self) -> &[Pubkey] {
+        &self.signers[..(self.n as usize).min(MAX_SIGNERS)]
+    }
+
+    /// Whether `key` is one of the valid signers.
+    #[inline]
+    pub fn contains_signer(&self, key: &Pubkey) -> bool {
+        self.signer_keys().contains(key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TestAccount;
+
+    /// Pack a multisig with the given signers.
+    fn multisig_data(m: u8, signers: &[Pubkey]) -> [u8; Multisig::LEN] {
+        let mut data = [0; Multisig::LEN];
+        data[0] = m;
+        data[1] = signers.len() as u8;
+        data[2] = 1;
+        for (signer, i) in signers.iter().zip(0..) {
+            data[3 + 32 * i..3 + 32 * (i + 1)].copy_from_slice(signer);
+        }
+        data
+    }
+
+    #[test]
+    fn test_multisig() {
+        // A previous signer left in the unused entries is not a valid signer.
+        let mut data = multisig_data(2, &[[1; 32], [2; 32], [3; 32]]);
+        data[3 + 32 * 3..3 + 32 * 4].copy_from_slice(&[4; 32]);
+
+        let multisig = Multisig::from_bytes_at(&data, 0).unwrap();
+
+        assert_eq!(Multisig::LEN, 355);
+        assert_eq!(multisig.m(), 2);
+        assert_eq!(multisig.n(), 3);
+        assert!(multisig.is_initialized());
+        assert_eq!(multisig.signer_keys(), &[[1; 32], [2; 32], [3; 32]]);
+        assert!(multisig.contains_signer(&[2; 32]));
+        assert!(!multisig.contains_signer(&[4; 32]));
+        assert!(!multisig.contains_signer(&[0; 32]));
+    }
+
+    #[test]
+    fn test_multisig_with_invalid_n() {
+        let mut data = multisig_data(1, &[[1; 32]; MAX_SIGNERS]);
+        data[1] = u8::MAX;
+
+        let multisig = Multisig::from_bytes_at(&data, 0).unwrap();
+        assert_eq!(multisig.signer_keys().len(), MAX_SIGNERS);
+    }
+
+    #[test]
+    fn test_from_account_info() {
+        let data = multisig_data(MIN_SIGNERS as u8, &[[1; 32]]);
+
+        let account = TestAccount::with_data([9; 32], ID, &data).info();
+        let multisig = Multisig::from_account_info(&account).unwrap();
+        assert_eq!(multisig.signer_keys(), &[[1; 32]]);
+
+        let foreign = TestAccount::with_data([9; 32], [8; 32], &data).info();
+        assert_eq!(
+            Multisig::from_account_info(&foreign).err(),
+            Some(ProgramError::InvalidAccountOwner)
+        );
+
+        let mut longer = data.to_vec();
+        longer.push(0);
+        let longer = TestAccount::with_data([9; 32], ID, &longer).info();
+        assert_eq!(
+            Multisig::from_account_info(&longer).err(),
+            Some(ProgramError::InvalidAccountData)
+        );
+        assert!(Multisig::from_bytes_at(&data[..Multisig::LEN - 1], 0).is_err());
+    }
+}
@@ -1,4 +1,4 @@
-use super::AccountState;
+use super::{classify_account, window, AccountState, Strictness, TokenAccountKind};
 use pinocchio::{
     account_info::{AccountInfo, Ref},
     program_error::ProgramError,
@@ -54,7 +54,8 @@ impl TokenAccount {
     /// Return a `TokenAccount` from the given account info.
     ///
     /// This method performs owner and length validation on `AccountInfo`, safe borrowing
-    /// the account data.
+    /// the account data. Token accounts with extensions are accepted; only the base state
+    /// is read. Mints and multisigs are rejected with `InvalidAccountData`.
     #[inline]
     pub fn from_account_info(
         account_info: &AccountInfo,
@@ -65,15 +66,19 @@ impl TokenAccount {
         if !account_info.is_owned_by(&ID) {
             return Err(ProgramError::InvalidAccountData);
         }
-        Ok(Ref::map(account_info.try_borrow_data()?, |data| unsafe {
+        let data = account_info.try_borrow_data()?;
+        if classify_account(&data, Strictness::Strict)? != TokenAccountKind::TokenAccount {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(Ref::map(data, |data| unsafe {
             Self::from_bytes_unchecked(data)
         }))
     }
 
     /// Return a `TokenAccount` from the given account info.
     ///
-    /// This method performs owner and length validation on `AccountInfo`, but does not
-    /// perform the borrow check.
+    /// This method performs owner and length validation on `AccountInfo`, and rejects
+    /// mints and multisigs, but does not perform the borrow check.
     ///
     /// # Safety
     ///
@@ -89,9 +94,11 @@ impl TokenAccount {
         if account_info.owner() != &ID {
             return Err(ProgramError::InvalidAccountData);
         }
-        Ok(Self::from_bytes_unchecked(
-            account_info.borrow_data_unchecked(),
-        ))
+        let data = account_info.borrow_data_unchecked();
+        if classify_account(data, Strictness::Strict)? != TokenAccountKind::TokenAccount {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(Self::from_bytes_unchecked(data))
     }
 
     /// Return a `TokenAccount` from the given bytes.
@@ -107,6 +114,18 @@ impl TokenAccount {
         &*(bytes[..Self::BASE_LEN].as_ptr() as *const TokenAccount)
     }
 
+    /// Return a `TokenAccount` stored at `offset` of `buf`.
+    ///
+    /// `buf` may hold several accounts (e.g., snapshots copied into a single
+    /// buffer); only the `TokenAccount::BASE_LEN` bytes at `offset` are read.
+    #[inline]
+    pub fn from_bytes_at(buf: &[u8], offset: usize) -> Result<&Self, ProgramError> {
+        let data = window(buf, offset, Self::BASE_LEN).ok_or(ProgramError::InvalidAccountData)?;
+        // SAFETY: `TokenAccount` has an alignment of 1, is valid for any byte pattern
+        // and `data` has `TokenAccount::BASE_LEN` bytes.
+        Ok(unsafe { Self::from_bytes_unchecked(data) })
+    }
+
     pub fn mint(&self) -> &Pubkey {
         &self.mint
     }
@@ -156,6 +175,15 @@ impl TokenAccount {
         }
     }
 
+    /// Return the rent-exempt reserve of a native (wrapped SOL) account.
+    ///
+    /// The reserve is the value of the native amount field, so this is the
+    /// same as [`TokenAccount::native_amount`].
+    #[inline(always)]
+    pub fn native_reserve(&self) -> Option<u64> {
+        self.native_amount()
+    }
+
     /// Return the native amount.
     ///
     /// This method should be used when the caller knows that the token is native since it
@@ -201,3 +229,69 @@ impl TokenAccount {
         self.state == AccountState::Frozen as u8
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::token_account_base;
+
+    /// Offsets of the optional fields of a token account.
+    const DELEGATE_OFFSET: usize = 72;
+    const STATE_OFFSET: usize = 108;
+    const IS_NATIVE_OFFSET: usize = 109;
+    const DELEGATED_AMOUNT_OFFSET: usize = 121;
+    const CLOSE_AUTHORITY_OFFSET: usize = 129;
+
+    #[test]
+    fn test_getters() {
+        let data = token_account_base(&[1; 32], &[2; 32], 100);
+        let account = TokenAccount::from_bytes_at(&data, 0).unwrap();
+
+        assert_eq!(account.mint(), &[1; 32]);
+        assert_eq!(account.owner(), &[2; 32]);
+        assert_eq!(account.amount(), 100);
+        assert!(account.is_initialized());
+        assert!(!account.is_frozen());
+        assert!(!account.is_native());
+        assert_eq!(account.native_reserve(), None);
+        assert_eq!(account.delegate(), None);
+        assert_eq!(account.delegated_amount(), 0);
+        assert_eq!(account.close_authority(), None);
+    }
+
+    #[test]
+    fn test_native_account() {
+        let mut data = token_account_base(&[1; 32], &[2; 32], 3_000_000);
+        data[IS_NATIVE_OFFSET] = 1;
+        data[IS_NATIVE_OFFSET + 4..IS_NATIVE_OFFSET + 12]
+            .copy_from_slice(&2_039_280u64.to_le_bytes());
+        data[CLOSE_AUTHORITY_OFFSET] = 1;
+        data[CLOSE_AUTHORITY_OFFSET + 4..].copy_from_slice(&[5; 32]);
+
+        let account = TokenAccount::from_bytes_at(&data, 0).unwrap();
+
+        assert!(account.is_native());
+        assert_eq!(account.native_reserve(), Some(2_039_280));
+        assert_eq!(account.native_amount(), Some(2_039_280));
+        assert_eq!(account.amount(), 3_000_000);
+        assert_eq!(account.close_authority(), Some(&[5; 32]));
+    }
+
+    #[test]
+    fn test_delegated_and_frozen_account() {
+        let mut data = token_account_base(&[1; 32], &[2; 32], 100);
+        data[DELEGATE_OFFSET] = 1;
+        data[DELEGATE_OFFSET + 4..DELEGATE_OFFSET + 36].copy_from_slice(&[3; 32]);
+        data[DELEGATED_AMOUNT_OFFSET..DELEGATED_AMOUNT_OFFSET + 8]
+            .copy_from_slice(&40u64.to_le_bytes());
+        data[STATE_OFFSET] = AccountState::Frozen as u8;
+
+        let account = TokenAccount::from_bytes_at(&data, 0).unwrap();
+
+        assert_eq!(account.delegate(), Some(&[3; 32]));
+        assert_eq!(account.delegated_amount(), 40);
+        assert!(account.is_frozen());
+        assert!(account.is_initialized());
+        assert_eq!(account.state(), AccountState::Frozen);
+    }
+}
@@ -0,0 +1,493 @@
+//! Test helpers to create `AccountInfo`s and capture the instructions
+//! invoked by the CPI builders.
+//!
+//! This module is compiled only when `cfg(test)` is active so `std` can be used.
+
+// Not every helper is used under every feature combination.
+#![allow(dead_code)]
+
+extern crate std;
+
+use core::cell::RefCell;
+use std::{vec, vec::Vec};
+
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{AccountMeta, Instruction},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    ProgramResult,
+};
+use pinocchio_pubkey::from_str;
+
+use crate::{
+    extensions::{
+        BaseState, ExtensionType, ExtensionWriter, EXTENSION_HEADER_LEN, EXTENSION_START_OFFSET,
+    },
+    state::{AccountState, Mint, TokenAccount},
+};
+
+/// Size of the account header preceding the account data in the input buffer.
+const ACCOUNT_HEADER_LEN: usize = 88;
+
+/// Value of the borrow state for an account without outstanding borrows.
+const NOT_BORROWED: u8 = u8::MAX;
+
+/// An account backed by an 8-byte aligned buffer using the same memory layout
+/// as the runtime input.
+pub struct TestAccount {
+    buffer: Vec<u64>,
+}
+
+impl TestAccount {
+    /// Create an account with the given key and no data.
+    pub fn new(key: Pubkey) -> Self {
+        Self::with_data(key, crate::ID, &[])
+    }
+
+    /// Create an account with the given key, owner and data.
+    pub fn with_data(key: Pubkey, owner: Pubkey, data: &[u8]) -> Self {
+        let mut buffer = vec![0u64; (ACCOUNT_HEADER_LEN + data.len()).div_ceil(8)];
+        let bytes = Self::bytes_mut(&mut buffer);
+
+        bytes[0] = NOT_BORROWED;
+        bytes[8..40].copy_from_slice(&key);
+        bytes[40..72].copy_from_slice(&owner);
+        bytes[80..88].copy_from_slice(&(data.len() as u64).to_le_bytes());
+        bytes[ACCOUNT_HEADER_LEN..ACCOUNT_HEADER_LEN + data.len()].copy_from_slice(data);
+
+        Self { buffer }
+    }
+
+    /// Mark the account as a signer of the transaction.
+    pub fn signer(mut self) -> Self {
+        Self::bytes_mut(&mut self.buffer)[1] = 1;
+        self
+    }
+
+    /// Set the lamports of the account.
+    pub fn lamports(mut self, lamports: u64) -> Self {
+        Self::bytes_mut(&mut self.buffer)[72..80].copy_from_slice(&lamports.to_le_bytes());
+        self
+    }
+
+    /// Mark the account as writable.
+    pub fn writable(mut self) -> Self {
+        Self::bytes_mut(&mut self.buffer)[2] = 1;
+        self
+    }
+
+    /// Return an `AccountInfo` pointing to this account.
+    ///
+    /// The account memory is leaked so the `AccountInfo` remains valid for
+    /// the rest of the test.
+    pub fn info(self) -> AccountInfo {
+        let buffer = self.buffer.leak();
+        // SAFETY: `AccountInfo` is a `repr(C)` wrapper around a pointer to the
+        // account header, which is what the buffer holds.
+        unsafe { core::mem::transmute::<*mut u64, AccountInfo>(buffer.as_mut_ptr()) }
+    }
+
+    fn bytes_mut(buffer: &mut [u64]) -> &mut [u8] {
+        // SAFETY: The buffer is valid for `len * 8` bytes.
+        unsafe { core::slice::from_raw_parts_mut(buffer.as_mut_ptr() as *mut u8, buffer.len() * 8) }
+    }
+}
+
+/// Account meta captured from an invoked instruction.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CapturedMeta {
+    pub pubkey: Pubkey,
+    pub is_writable: bool,
+    pub is_signer: bool,
+}
+
+impl CapturedMeta {
+    pub fn new(pubkey: Pubkey, is_writable: bool, is_signer: bool) -> Self {
+        Self {
+            pubkey,
+            is_writable,
+            is_signer,
+        }
+    }
+}
+
+impl From<&AccountMeta<'_>> for CapturedMeta {
+    fn from(meta: &AccountMeta<'_>) -> Self {
+        Self::new(*meta.pubkey, meta.is_writable, meta.is_signer)
+    }
+}
+
+/// Owned copy of an invoked instruction.
+#[derive(Clone, Debug)]
+pub struct CapturedInstruction {
+    pub program_id: Pubkey,
+    pub accounts: Vec<CapturedMeta>,
+    pub data: Vec<u8>,
+    pub account_infos: Vec<Pubkey>,
+}
+
+std::thread_local! {
+    static CAPTURED: RefCell<Vec<CapturedInstruction>> = const { RefCell::new(Vec::new()) };
+    static FAILURES: RefCell<Vec<(usize, ProgramError)>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Make the invocation at `index` of the instructions captured on the
+/// current thread fail with `error`, until the next [`take_captured`].
+///
+/// The failing instruction is still captured.
+pub fn fail_invocation(index: usize, error: ProgramError) {
+    FAILURES.with(|failures| failures.borrow_mut().push((index, error)));
+}
+
+/// Record an instruction about to be invoked.
+///
+/// Returns the error set with [`fail_invocation`] for this instruction, if
+/// any.
+///
+/// Panics if the account infos do not match the account metas of the
+/// instruction one to one, which the runtime requires to find the accounts.
+pub fn record(instruction: &Instruction, account_infos: &[&AccountInfo]) -> ProgramResult {
+    assert!(
+        instruction.accounts.len() == account_infos.len()
+            && instruction
+                .accounts
+                .iter()
+                .zip(account_infos)
+                .all(|(meta, info)| meta.pubkey == info.key()),
+        "account infos do not match the account metas"
+    );
+
+    CAPTURED.with(|captured| {
+        captured.borrow_mut().push(CapturedInstruction {
+            program_id: *instruction.program_id,
+            accounts: instruction
+                .accounts
+                .iter()
+                .map(CapturedMeta::from)
+                .collect(),
+            data: instruction.data.to_vec(),
+            account_infos: account_infos.iter().map(|info| *info.key()).collect(),
+        })
+    });
+
+    let index = CAPTURED.with(|captured| captured.borrow().len() - 1);
+
+    FAILURES.with(|failures| {
+        let mut failures = failures.borrow_mut();
+        match failures.iter().position(|(failing, _)| *failing == index) {
+            Some(position) => Err(failures.remove(position).1),
+            None => Ok(()),
+        }
+    })
+}
+
+/// Return (and clear) the instructions invoked on the current thread.
+///
+/// Failures set with [`fail_invocation`] are also cleared.
+pub fn take_captured() -> Vec<CapturedInstruction> {
+    FAILURES.with(|failures| failures.take());
+    CAPTURED.with(|captured| captured.take())
+}
+
+/// Return (and clear) the single instruction invoked on the current thread.
+pub fn take_single() -> CapturedInstruction {
+    let mut captured = take_captured();
+    assert_eq!(captured.len(), 1, "expected a single invoked instruction");
+    captured.remove(0)
+}
+
+/// Off-chain version of `try_find_program_address`, whose syscall is only
+/// available on-chain.
+///
+/// Returns the first address, with bumps from `255` down, that is not a
+/// valid curve point.
+pub fn try_find_program_address(seeds: &[&[u8]], program_id: &Pubkey) -> Option<(Pubkey, u8)> {
+    (0..=u8::MAX).rev().find_map(|bump| {
+        let mut seeds_with_bump = seeds.to_vec();
+        seeds_with_bump.push(core::slice::from_ref(&bump));
+        create_program_address(&seeds_with_bump, program_id).map(|address| (address, bump))
+    })
+}
+
+/// Off-chain version of `create_program_address`.
+///
+/// Returns `None` if the derived address is a valid curve point.
+pub fn create_program_address(seeds: &[&[u8]], program_id: &Pubkey) -> Option<Pubkey> {
+    // Seeds are hashed sequentially, so hashing their concatenation is
+    // equivalent.
+    let seeds = seeds.concat();
+    let address = pinocchio_pubkey::derive_address_const(&[&seeds], None, program_id);
+
+    (!is_on_curve(&address)).then_some(address)
+}
+
+/// Element of the field of integers modulo `2^255 - 19`, as little-endian
+/// 64-bit limbs.
+type FieldElement = [u64; 4];
+
+const FIELD_PRIME: FieldElement = [
+    0xffff_ffff_ffff_ffed,
+    u64::MAX,
+    u64::MAX,
+    0x7fff_ffff_ffff_ffff,
+];
+
+/// Check whether `bytes` are the compressed form of a point of the ed25519
+/// curve, following the decompression of `curve25519-dalek`.
+///
+/// The point is on the curve if `(y^2 - 1) / (d * y^2 + 1)` is a square,
+/// where `d = -121665 / 121666`.
+fn is_on_curve(bytes: &[u8; 32]) -> bool {
+    let mut y = [0; 4];
+    for (limb, chunk) in y.iter_mut().zip(bytes.chunks_exact(8)) {
+        *limb = u64::from_le_bytes(chunk.try_into().unwrap());
+    }
+    // The last bit is the sign of `x`.
+    y[3] &= 0x7fff_ffff_ffff_ffff;
+    let y = field_reduce(y);
+
+    let one = [1, 0, 0, 0];
+    let d = field_mul(
+        &field_sub(&[0; 4], &[121_665, 0, 0, 0]),
+        &field_invert(&[121_666, 0, 0, 0]),
+    );
+
+    let y2 = field_mul(&y, &y);
+    let u = field_sub(&y2, &one);
+    let v = field_add(&field_mul(&d, &y2), &one);
+    let ratio = field_mul(&u, &field_invert(&v));
+
+    // Euler's criterion: `ratio^((p - 1) / 2)` is `1` for non-zero squares.
+    let legendre = field_pow(
+        &ratio,
+        &[
+            0xffff_ffff_ffff_fff6,
+            u64::MAX,
+            u64::MAX,
+            0x3fff_ffff_ffff_ffff,
+        ],
+    );
+
+    legendre == [0; 4] || legendre == one
+}
+
+/// Reduce a value lower than `2^256` to its canonical form.
+fn field_reduce(mut value: FieldElement) -> FieldElement {
+    // Limbs are compared from the most significant one.
+    while value.iter().rev().ge(FIELD_PRIME.iter().rev()) {
+        value = wrapping_sub(&value, &FIELD_PRIME);
+    }
+    value
+}
+
+fn wrapping_sub(a: &FieldElement, b: &FieldElement) -> FieldElement {
+    let mut result = [0; 4];
+    let mut borrow = false;
+    for i in 0..4 {
+        let (value, borrow_a) = a[i].overflowing_sub(b[i]);
+        let (value, borrow_b) = value.overflowing_sub(borrow as u64);
+        result[i] = value;
+        borrow = borrow_a || borrow_b;
+    }
+    result
+}
+
+fn field_add(a: &FieldElement, b: &FieldElement) -> FieldElement {
+    // Canonical values are lower than `2^255`, so the sum cannot overflow.
+    let mut result = [0; 4];
+    let mut carry = 0;
+    for i in 0..4 {
+        let value = a[i] as u128 + b[i] as u128 + carry;
+        result[i] = value as u64;
+        carry = value >> 64;
+    }
+    field_reduce(result)
+}
+
+fn field_sub(a: &FieldElement, b: &FieldElement) -> FieldElement {
+    field_add(a, &wrapping_sub(&FIELD_PRIME, b))
+}
+
+fn field_mul(a: &FieldElement, b: &FieldElement) -> FieldElement {
+    let mut product = [0u64; 8];
+    for i in 0..4 {
+        let mut carry = 0;
+        for j in 0..4 {
+            let value = product[i + j] as u128 + a[i] as u128 * b[j] as u128 + carry;
+            product[i + j] = value as u64;
+            carry = value >> 64;
+        }
+        product[i + 4] = carry as u64;
+    }
+
+    // `2^256` is `38` modulo `2^255 - 19`.
+    let mut result = [0; 4];
+    let mut carry = 0;
+    for i in 0..4 {
+        let value = product[i] as u128 + 38 * product[i + 4] as u128 + carry;
+        result[i] = value as u64;
+        carry = value >> 64;
+    }
+    while carry != 0 {
+        let mut overflow = 38 * carry;
+        carry = 0;
+        for limb in result.iter_mut() {
+            let value = *limb as u128 + overflow;
+            *limb = value as u64;
+            overflow = value >> 64;
+        }
+        carry += overflow;
+    }
+    field_reduce(result)
+}
+
+fn field_pow(base: &FieldElement, exponent: &FieldElement) -> FieldElement {
+    let mut result = [1, 0, 0, 0];
+    for bit in (0..256).rev() {
+        result = field_mul(&result, &result);
+        if exponent[bit / 64] >> (bit % 64) & 1 == 1 {
+            result = field_mul(&result, base);
+        }
+    }
+    result
+}
+
+fn field_invert(value: &FieldElement) -> FieldElement {
+    field_pow(value, &wrapping_sub(&FIELD_PRIME, &[2, 0, 0, 0]))
+}
+
+/// Build the data of an account with extensions.
+///
+/// The `base` data is padded to the length of a token account, followed by
+/// the account type and the TLV entries written by an `ExtensionWriter`.
+pub fn account_with_extensions(
+    base_state: BaseState,
+    base: &[u8],
+    extensions: &[(ExtensionType, &[u8])],
+) -> Vec<u8> {
+    let len = extensions
+        .iter()
+        .map(|(_, value)| EXTENSION_HEADER_LEN + value.len())
+        .sum::<usize>();
+
+    let mut data = vec![0; EXTENSION_START_OFFSET + len];
+    data[..base.len()].copy_from_slice(base);
+
+    let mut writer = ExtensionWriter::new(&mut data, base_state).unwrap();
+
+    for (extension_type, value) in extensions {
+        writer.write(*extension_type, value).unwrap();
+    }
+
+    data
+}
+
+/// Build the data of an initialized mint without extensions.
+pub fn mint_base(
+    mint_authority: Option<&Pubkey>,
+    supply: u64,
+    decimals: u8,
+    freeze_authority: Option<&Pubkey>,
+) -> [u8; Mint::BASE_LEN] {
+    let mut data = [0; Mint::BASE_LEN];
+
+    if let Some(mint_authority) = mint_authority {
+        data[0] = 1;
+        data[4..36].copy_from_slice(mint_authority);
+    }
+    data[36..44].copy_from_slice(&supply.to_le_bytes());
+    data[44] = decimals;
+    data[45] = 1;
+    if let Some(freeze_authority) = freeze_authority {
+        data[46] = 1;
+        data[50..82].copy_from_slice(freeze_authority);
+    }
+
+    data
+}
+
+/// Build the data of an initialized token account without extensions.
+pub fn token_account_base(
+    mint: &Pubkey,
+    owner: &Pubkey,
+    amount: u64,
+) -> [u8; TokenAccount::BASE_LEN] {
+    let mut data = [0; TokenAccount::BASE_LEN];
+
+    data[..32].copy_from_slice(mint);
+    data[32..64].copy_from_slice(owner);
+    data[64..72].copy_from_slice(&amount.to_le_bytes());
+    data[108] = AccountState::Initialized as u8;
+
+    data
+}
+
+/// Serialize a string using the borsh encoding (`u32` length prefix).
+pub fn borsh_str(value: &str) -> Vec<u8> {
+    let mut data = (value.len() as u32).to_le_bytes().to_vec();
+    data.extend_from_slice(value.as_bytes());
+    data
+}
+
+/// PYUSD mint address `2b1kV6DkPAnxd5ixfnxCpjxmKwqjjaYmCZfHsFu24GXo`.
+pub const PYUSD_MINT: Pubkey = from_str("2b1kV6DkPAnxd5ixfnxCpjxmKwqjjaYmCZfHsFu24GXo");
+
+/// Authority used for all PYUSD fixture authorities.
+pub const PYUSD_UPDATE_AUTHORITY: Pubkey = [7; 32];
+
+/// Data of a mint with the same extensions as PYUSD.
+///
+/// The `TokenMetadata` extension is the last TLV entry.
+pub fn pyusd_mint_data() -> Vec<u8> {
+    pyusd_mint_data_with_additional_metadata(&[])
+}
+
+/// Data of a mint with the same extensions as PYUSD and the given additional
+/// metadata key/value pairs.
+pub fn pyusd_mint_data_with_additional_metadata(additional_metadata: &[(&str, &str)]) -> Vec<u8> {
+    let authority = PYUSD_UPDATE_AUTHORITY;
+
+    let mut transfer_fee_config = vec![0; 108];
+    transfer_fee_config[..32].copy_from_slice(&authority);
+    transfer_fee_config[32..64].copy_from_slice(&authority);
+
+    let mut transfer_hook = vec![0; 64];
+    transfer_hook[..32].copy_from_slice(&authority);
+
+    let mut confidential_transfer_mint = vec![0; 65];
+    confidential_transfer_mint[..32].copy_from_slice(&authority);
+
+    let mut metadata_pointer = authority.to_vec();
+    metadata_pointer.extend_from_slice(&PYUSD_MINT);
+
+    let mut token_metadata = authority.to_vec();
+    token_metadata.extend_from_slice(&PYUSD_MINT);
+    token_metadata.extend(borsh_str("PayPal USD"));
+    token_metadata.extend(borsh_str("PYUSD"));
+    token_metadata.extend(borsh_str(
+        "https://token-metadata.paxos.com/pyusd_metadata/prod/solana/pyusd_metadata.json",
+    ));
+    token_metadata.extend_from_slice(&(additional_metadata.len() as u32).to_le_bytes());
+    for (key, value) in additional_metadata {
+        token_metadata.extend(borsh_str(key));
+        token_metadata.extend(borsh_str(value));
+    }
+
+    account_with_extensions(
+        BaseState::Mint,
+        &mint_base(Some(&authority), 0, 6, Some(&authority)),
+        &[
+            (ExtensionType::TransferFeeConfig, &transfer_fee_config),
+            (ExtensionType::MintCloseAuthority, &authority),
+            (
+                ExtensionType::ConfidentialTransferMint,
+                &confidential_transfer_mint,
+            ),
+            (ExtensionType::PermanentDelegate, &authority),
+            (ExtensionType::TransferHook, &transfer_hook),
+            (ExtensionType::MetadataPointer, &metadata_pointer),
+            (ExtensionType::TokenMetadata, &token_metadata),
+        ],
+    )
+}
@@ -0,0 +1,345 @@
+//! Checks of whether token accounts can send or receive transfers.
+//!
+//! These helpers evaluate the state of the accounts before invoking a
+//! transfer, so programs can report why a transfer would fail instead of the
+//! token program error.
+
+use pinocchio::{program_error::ProgramError, pubkey::Pubkey};
+
+use crate::{
+    errors::TokenError,
+    extensions::{
+        get_extension_data, get_extension_from_bytes, non_transferable::NonTransferable,
+        pausable::PausableConfig, BaseState, ExtensionType,
+    },
+    state::{classify_account, Strictness, TokenAccount, TokenAccountKind},
+};
+
+/// Reason a transfer is rejected by the token program.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TransferBlocked {
+    /// The source or destination account was frozen by the freeze authority.
+    FrozenByAuthority,
+
+    /// The mint is paused by its pause authority.
+    MintPaused,
+
+    /// The mint is non-transferable.
+    NonTransferableMint,
+
+    /// The source account has the CPI guard enabled and the transfer is
+    /// invoked through a CPI without a delegate.
+    CpiGuard,
+
+    /// The destination account requires a memo on incoming transfers.
+    MemoRequired,
+}
+
+impl TransferBlocked {
+    /// Return the token program error returned for the blocked transfer.
+    #[inline(always)]
+    pub const fn token_error(self) -> TokenError {
+        match self {
+            TransferBlocked::FrozenByAuthority => TokenError::AccountFrozen,
+            TransferBlocked::MintPaused => TokenError::MintPaused,
+            TransferBlocked::NonTransferableMint => TokenError::NonTransferable,
+            TransferBlocked::CpiGuard => TokenError::CpiGuardTransferBlocked,
+            TransferBlocked::MemoRequired => TokenError::NoMemo,
+        }
+    }
+}
+
+impl From<TransferBlocked> for ProgramError {
+    fn from(blocked: TransferBlocked) -> Self {
+        blocked.token_error().into()
+    }
+}
+
+/// Status of a token account.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TokenAccountStatus {
+    /// The account can send and receive transfers, subject to the
+    /// transfer-specific checks of [`evaluate_transfer`].
+    Active,
+
+    /// Transfers from and to the account are blocked.
+    Blocked(TransferBlocked),
+}
+
+/// How a transfer is invoked.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TransferContext {
+    /// Whether the transfer is invoked through a CPI.
+    pub is_cpi: bool,
+    /// Whether the transfer is signed by a delegate of the source account.
+    pub by_delegate: bool,
+    /// Whether the transfer is preceded by a memo instruction.
+    pub with_memo: bool,
+}
+
+/// Return the status of a token account of the mint at `mint_key`, whose
+/// data is `mint`.
+///
+/// Returns `InvalidAccountData` if `mint` or `token_account` do not hold the
+/// data of a mint and a token account, and the `MintMismatch` token error if
+/// the token account belongs to another mint.
+pub fn token_account_status(
+    mint_key: &Pubkey,
+    mint: &[u8],
+    token_account: &[u8],
+) -> Result<TokenAccountStatus, ProgramError> {
+    let token_account = parse_token_account(token_account)?;
+
+    if token_account.mint() != mint_key {
+        return Err(TokenError::MintMismatch.into());
+    }
+
+    if classify_account(mint, Strictness::Strict)? != TokenAccountKind::Mint {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let blocked = if token_account.is_frozen() {
+        Some(TransferBlocked::FrozenByAuthority)
+    } else if get_extension_from_bytes::<PausableConfig>(mint).is_some_and(|c| c.paused()) {
+        Some(TransferBlocked::MintPaused)
+    } else if get_extension_from_bytes::<NonTransferable>(mint).is_some() {
+        Some(TransferBlocked::NonTransferableMint)
+    } else {
+        None
+    };
+
+    Ok(match blocked {
+        Some(blocked) => TokenAccountStatus::Blocked(blocked),
+        None => TokenAccountStatus::Active,
+    })
+}
+
+/// Evaluate whether a transfer from `source` to `destination` would be
+/// rejected by the token program.
+///
+/// Returns the first reason the transfer is blocked, or `None` if it is
+/// allowed. Balances and authorities are not checked, and errors are
+/// returned as by [`token_account_status`].
+pub fn evaluate_transfer(
+    mint_key: &Pubkey,
+    mint: &[u8],
+    source: &[u8],
+    destination: &[u8],
+    context: TransferContext,
+) -> Result<Option<TransferBlocked>, ProgramError> {
+    for account in [source, destination] {
+        if let TokenAccountStatus::Blocked(blocked) = token_account_status(mint_key, mint, account)?
+        {
+            return Ok(Some(blocked));
+        }
+    }
+
+    if context.is_cpi && !context.by_delegate && is_flag_set(source, ExtensionType::CpiGuard) {
+        return Ok(Some(TransferBlocked::CpiGuard));
+    }
+
+    if !context.with_memo && is_flag_set(destination, ExtensionType::MemoTransfer) {
+        return Ok(Some(TransferBlocked::MemoRequired));
+    }
+
+    Ok(None)
+}
+
+#[inline(always)]
+fn parse_token_account(data: &[u8]) -> Result<&TokenAccount, ProgramError> {
+    if classify_account(data, Strictness::Strict)? != TokenAccountKind::TokenAccount {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    TokenAccount::from_bytes_at(data, 0)
+}
+
+/// Return whether the single-byte flag extension of a token account is set.
+#[inline(always)]
+fn is_flag_set(token_account: &[u8], extension_type: ExtensionType) -> bool {
+    matches!(
+        get_extension_data(token_account, BaseState::TokenAccount, extension_type),
+        Some([flag, ..]) if *flag != 0
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::vec::Vec;
+
+    use super::*;
+    use crate::{
+        state::AccountState,
+        test_utils::{account_with_extensions, mint_base, token_account_base},
+    };
+
+    const MINT: [u8; 32] = [1; 32];
+
+    fn mint(extensions: &[(ExtensionType, &[u8])]) -> Vec<u8> {
+        account_with_extensions(
+            BaseState::Mint,
+            &mint_base(Some(&[2; 32]), 0, 6, Some(&[3; 32])),
+            extensions,
+        )
+    }
+
+    fn token_account(state: AccountState, extensions: &[(ExtensionType, &[u8])]) -> Vec<u8> {
+        let mut base = token_account_base(&MINT, &[4; 32], 100);
+        base[108] = state as u8;
+        account_with_extensions(BaseState::TokenAccount, &base, extensions)
+    }
+
+    fn paused(paused: u8) -> Vec<u8> {
+        let mut config = [5; 32].to_vec();
+        config.push(paused);
+        config
+    }
+
+    #[test]
+    fn test_blocking_sources() {
+        let plain_mint = mint(&[]);
+        let active = token_account(AccountState::Initialized, &[]);
+        let cpi = TransferContext {
+            is_cpi: true,
+            ..Default::default()
+        };
+
+        let cases = [
+            (
+                plain_mint.clone(),
+                token_account(AccountState::Frozen, &[]),
+                active.clone(),
+                TransferContext::default(),
+                TransferBlocked::FrozenByAuthority,
+            ),
+            (
+                mint(&[(ExtensionType::Pausable, &paused(1))]),
+                active.clone(),
+                active.clone(),
+                TransferContext::default(),
+                TransferBlocked::MintPaused,
+            ),
+            (
+                mint(&[(ExtensionType::NonTransferable, &[])]),
+                active.clone(),
+                active.clone(),
+                TransferContext::default(),
+                TransferBlocked::NonTransferableMint,
+            ),
+            (
+                plain_mint.clone(),
+                token_account(
+                    AccountState::Initialized,
+                    &[(ExtensionType::CpiGuard, &[1])],
+                ),
+                active.clone(),
+                cpi,
+                TransferBlocked::CpiGuard,
+            ),
+            (
+                plain_mint.clone(),
+                active.clone(),
+                token_account(
+                    AccountState::Initialized,
+                    &[(ExtensionType::MemoTransfer, &[1])],
+                ),
+                TransferContext::default(),
+                TransferBlocked::MemoRequired,
+            ),
+        ];
+
+        for (mint, source, destination, context, blocked) in cases {
+            assert_eq!(
+                evaluate_transfer(&MINT, &mint, &source, &destination, context),
+                Ok(Some(blocked))
+            );
+        }
+
+        assert_eq!(
+            evaluate_transfer(&MINT, &plain_mint, &active, &active, cpi),
+            Ok(None)
+        );
+    }
+
+    #[test]
+    fn test_blocking_conditions_lifted() {
+        let unpaused = mint(&[(ExtensionType::Pausable, &paused(0))]);
+        let guarded = token_account(
+            AccountState::Initialized,
+            &[(ExtensionType::CpiGuard, &[1])],
+        );
+        let memo = token_account(
+            AccountState::Initialized,
+            &[(ExtensionType::MemoTransfer, &[1])],
+        );
+
+        assert_eq!(
+            token_account_status(&MINT, &unpaused, &guarded),
+            Ok(TokenAccountStatus::Active)
+        );
+
+        // The CPI guard only applies to CPIs without a delegate.
+        let context = TransferContext {
+            is_cpi: true,
+            by_delegate: true,
+            with_memo: true,
+        };
+        assert_eq!(
+            evaluate_transfer(&MINT, &unpaused, &guarded, &memo, context),
+            Ok(None)
+        );
+
+        // Accounts without extensions are accepted.
+        let base_mint = mint_base(None, 0, 0, None);
+        let base_account = token_account_base(&MINT, &[4; 32], 0);
+        assert_eq!(
+            token_account_status(&MINT, &base_mint, &base_account),
+            Ok(TokenAccountStatus::Active)
+        );
+        assert_eq!(
+            token_account_status(&MINT, &base_account, &base_mint),
+            Err(ProgramError::InvalidAccountData)
+        );
+
+        // The account must belong to the mint.
+        assert_eq!(
+            token_account_status(&[9; 32], &base_mint, &base_account),
+            Err(TokenError::MintMismatch.into())
+        );
+        assert_eq!(
+            evaluate_transfer(
+                &[9; 32],
+                &base_mint,
+                &base_account,
+                &base_account,
+                TransferContext::default()
+            ),
+            Err(TokenError::MintMismatch.into())
+        );
+    }
+
+    #[test]
+    fn test_blocked_error_codes() {
+        let blocked = [
+            TransferBlocked::FrozenByAuthority,
+            TransferBlocked::MintPaused,
+            TransferBlocked::NonTransferableMint,
+            TransferBlocked::CpiGuard,
+            TransferBlocked::MemoRequired,
+        ];
+
+        let codes = blocked.map(ProgramError::from);
+
+        assert_eq!(
+            codes,
+            [
+                ProgramError::Custom(17),
+                ProgramError::Custom(67),
+                ProgramError::Custom(37),
+                ProgramError::Custom(42),
+                ProgramError::Custom(36),
+            ]
+        );
+    }
+}
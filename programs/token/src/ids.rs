@@ -0,0 +1,71 @@
+//! Program ids and native mints of the SPL Token programs.
+//!
+//! This module is re-exported by `pinocchio-token-2022`, so programs using
+//! either crate can detect which token program owns an account.
+
+use pinocchio::pubkey::Pubkey;
+use pinocchio_pubkey::pubkey;
+
+/// Program id of the SPL Token program.
+pub const TOKEN_PROGRAM_ID: Pubkey = pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+
+/// Program id of the SPL Token-2022 program.
+pub const TOKEN_2022_PROGRAM_ID: Pubkey = pubkey!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+
+/// Mint of wrapped SOL for the SPL Token program.
+pub const NATIVE_MINT: Pubkey = pubkey!("So11111111111111111111111111111111111111112");
+
+/// Mint of wrapped SOL for the SPL Token-2022 program.
+pub const NATIVE_MINT_2022: Pubkey = pubkey!("9pan9bMn5HatX4EJdBwg9VgCa7Uz5HL8N1m5D3NdXejP");
+
+/// SPL Token programs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TokenProgram {
+    Token,
+    Token2022,
+}
+
+impl TokenProgram {
+    /// Return the program id.
+    #[inline(always)]
+    pub const fn id(self) -> &'static Pubkey {
+        match self {
+            TokenProgram::Token => &TOKEN_PROGRAM_ID,
+            TokenProgram::Token2022 => &TOKEN_2022_PROGRAM_ID,
+        }
+    }
+
+    /// Return the mint of wrapped SOL.
+    #[inline(always)]
+    pub const fn native_mint(self) -> &'static Pubkey {
+        match self {
+            TokenProgram::Token => &NATIVE_MINT,
+            TokenProgram::Token2022 => &NATIVE_MINT_2022,
+        }
+    }
+}
+
+/// Return the token program with the given id, typically the owner of a
+/// mint or token account.
+#[inline]
+pub fn which_token_program(owner: &Pubkey) -> Option<TokenProgram> {
+    if owner == &TOKEN_PROGRAM_ID {
+        Some(TokenProgram::Token)
+    } else if owner == &TOKEN_2022_PROGRAM_ID {
+        Some(TokenProgram::Token2022)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ids() {
+        assert_eq!(crate::ID, TOKEN_PROGRAM_ID);
+        assert_eq!(which_token_program(&crate::ID), Some(TokenProgram::Token));
+        assert_eq!(which_token_program(&NATIVE_MINT), None);
+    }
+}
@@ -0,0 +1,158 @@
+//! Instruction data writer used by the instruction builders.
+
+use core::{mem::MaybeUninit, slice::from_raw_parts};
+
+use pinocchio::pubkey::Pubkey;
+
+const UNINIT_BYTE: MaybeUninit<u8> = MaybeUninit::<u8>::uninit();
+
+/// Stack allocated instruction data.
+///
+/// Values are appended in instruction order, so builders do not need to
+/// track the offset of each field. Shared by the instruction builders of
+/// `pinocchio-token` and `pinocchio-token-2022`.
+pub struct IxData<const MAX_LEN: usize> {
+    data: [MaybeUninit<u8>; MAX_LEN],
+    len: usize,
+}
+
+impl<const MAX_LEN: usize> IxData<MAX_LEN> {
+    #[inline(always)]
+    pub fn new() -> Self {
+        Self {
+            data: [UNINIT_BYTE; MAX_LEN],
+            len: 0,
+        }
+    }
+
+    /// Append raw bytes to the instruction data.
+    ///
+    /// Panics if the data would exceed `MAX_LEN` bytes; the layout of each
+    /// instruction is fixed, so this is a bug in the builder.
+    #[inline(always)]
+    pub fn write_bytes(&mut self, source: &[u8]) {
+        debug_assert!(
+            self.len + source.len() <= MAX_LEN,
+            "instruction data exceeds {MAX_LEN} bytes"
+        );
+        for (d, s) in self.data[self.len..self.len + source.len()]
+            .iter_mut()
+            .zip(source)
+        {
+            d.write(*s);
+        }
+        self.len += source.len();
+    }
+
+    #[inline(always)]
+    pub fn write_u8(&mut self, value: u8) {
+        self.write_bytes(&[value]);
+    }
+
+    #[inline(always)]
+    pub fn write_u16_le(&mut self, value: u16) {
+        self.write_bytes(&value.to_le_bytes());
+    }
+
+    #[inline(always)]
+    pub fn write_u32_le(&mut self, value: u32) {
+        self.write_bytes(&value.to_le_bytes());
+    }
+
+    #[inline(always)]
+    pub fn write_u64_le(&mut self, value: u64) {
+        self.write_bytes(&value.to_le_bytes());
+    }
+
+    #[inline(always)]
+    pub fn write_i64_le(&mut self, value: i64) {
+        self.write_bytes(&value.to_le_bytes());
+    }
+
+    #[inline(always)]
+    pub fn write_pubkey(&mut self, pubkey: &Pubkey) {
+        self.write_bytes(pubkey);
+    }
+
+    /// Append an optional pubkey as a 1-byte presence flag followed by the
+    /// key, which is omitted if `None`.
+    #[inline(always)]
+    pub fn write_coption_pubkey(&mut self, pubkey: Option<&Pubkey>) {
+        match pubkey {
+            Some(pubkey) => {
+                self.write_u8(1);
+                self.write_pubkey(pubkey);
+            }
+            None => self.write_u8(0),
+        }
+    }
+
+    /// Return the number of bytes that can still be written.
+    #[inline(always)]
+    pub fn remaining(&self) -> usize {
+        MAX_LEN - self.len
+    }
+
+    /// Return the bytes written so far.
+    #[inline(always)]
+    pub fn finish(&self) -> &[u8] {
+        // SAFETY: The first `len` bytes have been initialized.
+        unsafe { from_raw_parts(self.data.as_ptr() as _, self.len) }
+    }
+}
+
+impl<const MAX_LEN: usize> Default for IxData<MAX_LEN> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::vec;
+
+    use super::*;
+
+    #[test]
+    fn test_ix_data() {
+        let mut data = IxData::<64>::new();
+        assert!(data.finish().is_empty());
+
+        data.write_u8(7);
+        data.write_u16_le(0x0102);
+        data.write_u64_le(0x0304_0506_0708_090a);
+        data.write_i64_le(-2);
+        data.write_pubkey(&[5; 32]);
+
+        let mut expected = vec![
+            7, 0x02, 0x01, 0x0a, 0x09, 0x08, 0x07, 0x06, 0x05, 0x04, 0x03,
+        ];
+        expected.extend_from_slice(&[0xfe, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff]);
+        expected.extend_from_slice(&[5; 32]);
+
+        assert_eq!(data.finish(), expected);
+    }
+
+    #[test]
+    fn test_ix_data_set_authority_layout() {
+        // Layout previously written by hand in `SetAuthority`.
+        let mut data = IxData::<35>::new();
+        data.write_u8(6);
+        data.write_u8(2);
+        data.write_coption_pubkey(Some(&[3; 32]));
+
+        let mut expected = vec![6, 2, 1];
+        expected.extend_from_slice(&[3; 32]);
+
+        assert_eq!(data.finish(), expected);
+
+        let mut data = IxData::<35>::new();
+        data.write_u8(6);
+        data.write_u8(2);
+        data.write_coption_pubkey(None);
+
+        assert_eq!(data.finish(), [6, 2, 0]);
+    }
+}
@@ -1,5 +1,3 @@
-use core::slice::from_raw_parts;
-
 use pinocchio::{
     account_info::AccountInfo,
     instruction::{AccountMeta, Instruction, Signer},
@@ -7,7 +5,7 @@ use pinocchio::{
     ProgramResult,
 };
 
-use crate::{write_bytes, UNINIT_BYTE};
+use crate::instruction_data::IxData;
 
 /// Approves a delegate.
 ///
@@ -26,7 +24,7 @@ pub struct Approve<'a> {
     pub amount: u64,
 }
 
-impl Approve<'_> {
+impl<'a> Approve<'a> {
     #[inline(always)]
     pub fn invoke(&self) -> ProgramResult {
         self.invoke_signed(&[])
@@ -34,27 +32,13 @@ impl Approve<'_> {
 
     #[inline(always)]
     pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
-        // Account metadata
-        let account_metas: [AccountMeta; 3] = [
-            AccountMeta::writable(self.source.key()),
-            AccountMeta::readonly(self.delegate.key()),
-            AccountMeta::readonly_signer(self.authority.key()),
-        ];
-
-        // Instruction data
-        // -  [0]: instruction discriminator (1 byte, u8)
-        // -  [1..9]: amount (8 bytes, u64)
-        let mut instruction_data = [UNINIT_BYTE; 9];
-
-        // Set discriminator as u8 at offset [0]
-        write_bytes(&mut instruction_data, &[4]);
-        // Set amount as u64 at offset [1..9]
-        write_bytes(&mut instruction_data[1..], &self.amount.to_le_bytes());
+        let account_metas = self.account_metas();
+        let instruction_data = self.instruction_data();
 
         let instruction = Instruction {
             program_id: &crate::ID,
             accounts: &account_metas,
-            data: unsafe { from_raw_parts(instruction_data.as_ptr() as _, 9) },
+            data: instruction_data.finish(),
         };
 
         invoke_signed(
@@ -63,4 +47,30 @@ impl Approve<'_> {
             signers,
         )
     }
+
+    /// Return the account metas of the instruction.
+    #[inline(always)]
+    pub fn account_metas(&self) -> [AccountMeta<'a>; 3] {
+        [
+            AccountMeta::writable(self.source.key()),
+            AccountMeta::readonly(self.delegate.key()),
+            AccountMeta::readonly_signer(self.authority.key()),
+        ]
+    }
+
+    /// Return the instruction data.
+    #[inline(always)]
+    pub fn instruction_data(&self) -> IxData<9> {
+        // Instruction data
+        // -  [0]: instruction discriminator (1 byte, u8)
+        // -  [1..9]: amount (8 bytes, u64)
+        let mut instruction_data = IxData::<9>::new();
+
+        // Set discriminator as u8 at offset [0]
+        instruction_data.write_u8(4);
+        // Set amount as u64 at offset [1..9]
+        instruction_data.write_u64_le(self.amount);
+
+        instruction_data
+    }
 }
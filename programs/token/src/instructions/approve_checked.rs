@@ -1,5 +1,3 @@
-use core::slice::from_raw_parts;
-
 use pinocchio::{
     account_info::AccountInfo,
     instruction::{AccountMeta, Instruction, Signer},
@@ -7,7 +5,7 @@ use pinocchio::{
     ProgramResult,
 };
 
-use crate::{write_bytes, UNINIT_BYTE};
+use crate::instruction_data::IxData;
 
 /// Approves a delegate.
 ///
@@ -31,7 +29,7 @@ pub struct ApproveChecked<'a> {
     pub decimals: u8,
 }
 
-impl ApproveChecked<'_> {
+impl<'a> ApproveChecked<'a> {
     #[inline(always)]
     pub fn invoke(&self) -> ProgramResult {
         self.invoke_signed(&[])
@@ -39,37 +37,49 @@ impl ApproveChecked<'_> {
 
     #[inline(always)]
     pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
-        // Account metadata
-        let account_metas: [AccountMeta; 4] = [
+        let account_metas = self.account_metas();
+        let instruction_data = self.instruction_data();
+
+        let instruction = Instruction {
+            program_id: &crate::ID,
+            accounts: &account_metas,
+            data: instruction_data.finish(),
+        };
+
+        invoke_signed(
+            &instruction,
+            &[self.source, self.mint, self.delegate, self.authority],
+            signers,
+        )
+    }
+
+    /// Return the account metas of the instruction.
+    #[inline(always)]
+    pub fn account_metas(&self) -> [AccountMeta<'a>; 4] {
+        [
             AccountMeta::writable(self.source.key()),
             AccountMeta::readonly(self.mint.key()),
             AccountMeta::readonly(self.delegate.key()),
             AccountMeta::readonly_signer(self.authority.key()),
-        ];
+        ]
+    }
 
+    /// Return the instruction data.
+    #[inline(always)]
+    pub fn instruction_data(&self) -> IxData<10> {
         // Instruction data
         // -  [0]  : instruction discriminator (1 byte, u8)
         // -  [1..9]: amount (8 bytes, u64)
         // -  [9]   : decimals (1 byte, u8)
-        let mut instruction_data = [UNINIT_BYTE; 10];
+        let mut instruction_data = IxData::<10>::new();
 
         // Set discriminator as u8 at offset [0]
-        write_bytes(&mut instruction_data, &[13]);
+        instruction_data.write_u8(13);
         // Set amount as u64 at offset [1..9]
-        write_bytes(&mut instruction_data[1..9], &self.amount.to_le_bytes());
+        instruction_data.write_u64_le(self.amount);
         // Set decimals as u8 at offset [9]
-        write_bytes(&mut instruction_data[9..], &[self.decimals]);
+        instruction_data.write_u8(self.decimals);
 
-        let instruction = Instruction {
-            program_id: &crate::ID,
-            accounts: &account_metas,
-            data: unsafe { from_raw_parts(instruction_data.as_ptr() as _, 10) },
-        };
-
-        invoke_signed(
-            &instruction,
-            &[self.source, self.mint, self.delegate, self.authority],
-            signers,
-        )
+        instruction_data
     }
 }
@@ -5,6 +5,8 @@ use pinocchio::{
     ProgramResult,
 };
 
+use crate::instruction_data::IxData;
+
 /// Freeze an initialized account using the Mint's freeze authority.
 ///
 /// ### Accounts:
@@ -20,7 +22,7 @@ pub struct FreezeAccount<'a> {
     pub freeze_authority: &'a AccountInfo,
 }
 
-impl FreezeAccount<'_> {
+impl<'a> FreezeAccount<'a> {
     #[inline(always)]
     pub fn invoke(&self) -> ProgramResult {
         self.invoke_signed(&[])
@@ -28,17 +30,13 @@ impl FreezeAccount<'_> {
 
     #[inline(always)]
     pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
-        // account metadata
-        let account_metas: [AccountMeta; 3] = [
-            AccountMeta::writable(self.account.key()),
-            AccountMeta::readonly(self.mint.key()),
-            AccountMeta::readonly_signer(self.freeze_authority.key()),
-        ];
+        let account_metas = self.account_metas();
+        let instruction_data = self.instruction_data();
 
         let instruction = Instruction {
             program_id: &crate::ID,
             accounts: &account_metas,
-            data: &[10],
+            data: instruction_data.finish(),
         };
 
         invoke_signed(
@@ -47,4 +45,26 @@ impl FreezeAccount<'_> {
             signers,
         )
     }
+
+    /// Return the account metas of the instruction.
+    #[inline(always)]
+    pub fn account_metas(&self) -> [AccountMeta<'a>; 3] {
+        [
+            AccountMeta::writable(self.account.key()),
+            AccountMeta::readonly(self.mint.key()),
+            AccountMeta::readonly_signer(self.freeze_authority.key()),
+        ]
+    }
+
+    /// Return the instruction data.
+    #[inline(always)]
+    pub fn instruction_data(&self) -> IxData<1> {
+        // Instruction data layout:
+        // -  [0]: instruction discriminator (1 byte, u8)
+        let mut instruction_data = IxData::<1>::new();
+
+        instruction_data.write_u8(10);
+
+        instruction_data
+    }
 }
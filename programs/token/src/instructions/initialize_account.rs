@@ -5,6 +5,8 @@ use pinocchio::{
     ProgramResult,
 };
 
+use crate::instruction_data::IxData;
+
 /// Initialize a new Token Account.
 ///
 /// ### Accounts:
@@ -23,21 +25,16 @@ pub struct InitializeAccount<'a> {
     pub rent_sysvar: &'a AccountInfo,
 }
 
-impl InitializeAccount<'_> {
+impl<'a> InitializeAccount<'a> {
     #[inline(always)]
     pub fn invoke(&self) -> ProgramResult {
-        // account metadata
-        let account_metas: [AccountMeta; 4] = [
-            AccountMeta::writable(self.account.key()),
-            AccountMeta::readonly(self.mint.key()),
-            AccountMeta::readonly(self.owner.key()),
-            AccountMeta::readonly(self.rent_sysvar.key()),
-        ];
+        let account_metas = self.account_metas();
+        let instruction_data = self.instruction_data();
 
         let instruction = Instruction {
             program_id: &crate::ID,
             accounts: &account_metas,
-            data: &[1],
+            data: instruction_data.finish(),
         };
 
         invoke(
@@ -45,4 +42,27 @@ impl InitializeAccount<'_> {
             &[self.account, self.mint, self.owner, self.rent_sysvar],
         )
     }
+
+    /// Return the account metas of the instruction.
+    #[inline(always)]
+    pub fn account_metas(&self) -> [AccountMeta<'a>; 4] {
+        [
+            AccountMeta::writable(self.account.key()),
+            AccountMeta::readonly(self.mint.key()),
+            AccountMeta::readonly(self.owner.key()),
+            AccountMeta::readonly(self.rent_sysvar.key()),
+        ]
+    }
+
+    /// Return the instruction data.
+    #[inline(always)]
+    pub fn instruction_data(&self) -> IxData<1> {
+        // Instruction data layout:
+        // -  [0]: instruction discriminator (1 byte, u8)
+        let mut instruction_data = IxData::<1>::new();
+
+        instruction_data.write_u8(1);
+
+        instruction_data
+    }
 }
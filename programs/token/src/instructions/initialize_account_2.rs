@@ -1,5 +1,3 @@
-use core::slice::from_raw_parts;
-
 use pinocchio::{
     account_info::AccountInfo,
     cpi::invoke,
@@ -8,7 +6,7 @@ use pinocchio::{
     ProgramResult,
 };
 
-use crate::{write_bytes, UNINIT_BYTE};
+use crate::instruction_data::IxData;
 
 /// Initialize a new Token Account.
 ///
@@ -27,32 +25,44 @@ pub struct InitializeAccount2<'a> {
     pub owner: &'a Pubkey,
 }
 
-impl InitializeAccount2<'_> {
+impl<'a> InitializeAccount2<'a> {
     #[inline(always)]
     pub fn invoke(&self) -> ProgramResult {
-        // account metadata
-        let account_metas: [AccountMeta; 3] = [
+        let account_metas = self.account_metas();
+        let instruction_data = self.instruction_data();
+
+        let instruction = Instruction {
+            program_id: &crate::ID,
+            accounts: &account_metas,
+            data: instruction_data.finish(),
+        };
+
+        invoke(&instruction, &[self.account, self.mint, self.rent_sysvar])
+    }
+
+    /// Return the account metas of the instruction.
+    #[inline(always)]
+    pub fn account_metas(&self) -> [AccountMeta<'a>; 3] {
+        [
             AccountMeta::writable(self.account.key()),
             AccountMeta::readonly(self.mint.key()),
             AccountMeta::readonly(self.rent_sysvar.key()),
-        ];
+        ]
+    }
 
+    /// Return the instruction data.
+    #[inline(always)]
+    pub fn instruction_data(&self) -> IxData<33> {
         // instruction data
         // -  [0]: instruction discriminator (1 byte, u8)
         // -  [1..33]: owner (32 bytes, Pubkey)
-        let mut instruction_data = [UNINIT_BYTE; 33];
+        let mut instruction_data = IxData::<33>::new();
 
         // Set discriminator as u8 at offset [0]
-        write_bytes(&mut instruction_data, &[16]);
+        instruction_data.write_u8(16);
         // Set owner as [u8; 32] at offset [1..33]
-        write_bytes(&mut instruction_data[1..], self.owner);
+        instruction_data.write_pubkey(self.owner);
 
-        let instruction = Instruction {
-            program_id: &crate::ID,
-            accounts: &account_metas,
-            data: unsafe { from_raw_parts(instruction_data.as_ptr() as _, 33) },
-        };
-
-        invoke(&instruction, &[self.account, self.mint, self.rent_sysvar])
+        instruction_data
     }
 }
@@ -1,5 +1,3 @@
-use core::slice::from_raw_parts;
-
 use pinocchio::{
     account_info::AccountInfo,
     cpi::invoke,
@@ -8,7 +6,7 @@ use pinocchio::{
     ProgramResult,
 };
 
-use crate::{write_bytes, UNINIT_BYTE};
+use crate::instruction_data::IxData;
 
 /// Initialize a new mint.
 ///
@@ -28,48 +26,51 @@ pub struct InitializeMint<'a> {
     pub freeze_authority: Option<&'a Pubkey>,
 }
 
-impl InitializeMint<'_> {
+impl<'a> InitializeMint<'a> {
     #[inline(always)]
     pub fn invoke(&self) -> ProgramResult {
-        // Account metadata
-        let account_metas: [AccountMeta; 2] = [
+        let account_metas = self.account_metas();
+        let instruction_data = self.instruction_data();
+
+        let instruction = Instruction {
+            program_id: &crate::ID,
+            accounts: &account_metas,
+            data: instruction_data.finish(),
+        };
+
+        invoke(&instruction, &[self.mint, self.rent_sysvar])
+    }
+
+    /// Return the account metas of the instruction.
+    #[inline(always)]
+    pub fn account_metas(&self) -> [AccountMeta<'a>; 2] {
+        [
             AccountMeta::writable(self.mint.key()),
             AccountMeta::readonly(self.rent_sysvar.key()),
-        ];
+        ]
+    }
 
+    /// Return the instruction data.
+    #[inline(always)]
+    pub fn instruction_data(&self) -> IxData<67> {
         // Instruction data layout:
         // -  [0]: instruction discriminator (1 byte, u8)
         // -  [1]: decimals (1 byte, u8)
         // -  [2..34]: mint_authority (32 bytes, Pubkey)
         // -  [34]: freeze_authority presence flag (1 byte, u8)
         // -  [35..67]: freeze_authority (optional, 32 bytes, Pubkey)
-        let mut instruction_data = [UNINIT_BYTE; 67];
-        let mut length = instruction_data.len();
+        let mut instruction_data = IxData::<67>::new();
 
         // Set discriminator as u8 at offset [0]
-        write_bytes(&mut instruction_data, &[0]);
+        instruction_data.write_u8(0);
         // Set decimals as u8 at offset [1]
-        write_bytes(&mut instruction_data[1..2], &[self.decimals]);
+        instruction_data.write_u8(self.decimals);
         // Set mint_authority as Pubkey at offset [2..34]
-        write_bytes(&mut instruction_data[2..34], self.mint_authority);
+        instruction_data.write_pubkey(self.mint_authority);
 
-        if let Some(freeze_auth) = self.freeze_authority {
-            // Set Option = `true` & freeze_authority at offset [34..67]
-            write_bytes(&mut instruction_data[34..35], &[1]);
-            write_bytes(&mut instruction_data[35..], freeze_auth);
-        } else {
-            // Set Option = `false`
-            write_bytes(&mut instruction_data[34..35], &[0]);
-            // Adjust length if no freeze authority
-            length = 35;
-        }
+        // Set freeze_authority as COption<Pubkey> at offset [34..67]
+        instruction_data.write_coption_pubkey(self.freeze_authority);
 
-        let instruction = Instruction {
-            program_id: &crate::ID,
-            accounts: &account_metas,
-            data: unsafe { from_raw_parts(instruction_data.as_ptr() as _, length) },
-        };
-
-        invoke(&instruction, &[self.mint, self.rent_sysvar])
+        instruction_data
     }
 }
@@ -8,6 +8,8 @@ use pinocchio::{
     ProgramResult,
 };
 
+use crate::instruction_data::IxData;
+
 /// Maximum number of multisignature signers.
 pub const MAX_MULTISIG_SIGNERS: usize = 11;
 
@@ -39,7 +41,7 @@ impl InitializeMultisig<'_, '_> {
             multisig,
             rent_sysvar,
             signers,
-            m,
+            ..
         } = self;
 
         if signers.len() > MAX_MULTISIG_SIGNERS {
@@ -68,15 +70,12 @@ impl InitializeMultisig<'_, '_> {
             account_meta.write(AccountMeta::readonly(signer.key()));
         }
 
-        // Instruction data layout:
-        // -  [0]: instruction discriminator (1 byte, u8)
-        // -  [1]: m (1 byte, u8)
-        let data = &[2, m];
+        let instruction_data = self.instruction_data();
 
         let instruction = Instruction {
             program_id: &crate::ID,
             accounts: unsafe { slice::from_raw_parts(acc_metas.as_ptr() as _, num_accounts) },
-            data,
+            data: instruction_data.finish(),
         };
 
         // Account info array
@@ -100,4 +99,18 @@ impl InitializeMultisig<'_, '_> {
             slice::from_raw_parts(acc_infos.as_ptr() as _, num_accounts)
         })
     }
+
+    /// Return the instruction data.
+    #[inline(always)]
+    pub fn instruction_data(&self) -> IxData<2> {
+        // Instruction data layout:
+        // -  [0]: instruction discriminator (1 byte, u8)
+        // -  [1]: m (1 byte, u8)
+        let mut instruction_data = IxData::<2>::new();
+
+        instruction_data.write_u8(2);
+        instruction_data.write_u8(self.m);
+
+        instruction_data
+    }
 }
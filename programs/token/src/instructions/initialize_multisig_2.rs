@@ -8,7 +8,7 @@ use pinocchio::{
     ProgramResult,
 };
 
-use crate::instructions::MAX_MULTISIG_SIGNERS;
+use crate::{instruction_data::IxData, instructions::MAX_MULTISIG_SIGNERS};
 
 /// Initialize a new Multisig.
 ///
@@ -32,9 +32,7 @@ impl InitializeMultisig2<'_, '_> {
     #[inline(always)]
     pub fn invoke(&self) -> ProgramResult {
         let &Self {
-            multisig,
-            signers,
-            m,
+            multisig, signers, ..
         } = self;
 
         if signers.len() > MAX_MULTISIG_SIGNERS {
@@ -60,15 +58,12 @@ impl InitializeMultisig2<'_, '_> {
             account_meta.write(AccountMeta::readonly(signer.key()));
         }
 
-        // Instruction data layout:
-        // -  [0]: instruction discriminator (1 byte, u8)
-        // -  [1]: m (1 byte, u8)
-        let data = &[19, m];
+        let instruction_data = self.instruction_data();
 
         let instruction = Instruction {
             program_id: &crate::ID,
             accounts: unsafe { slice::from_raw_parts(acc_metas.as_ptr() as _, num_accounts) },
-            data,
+            data: instruction_data.finish(),
         };
 
         // Account info array
@@ -91,4 +86,18 @@ impl InitializeMultisig2<'_, '_> {
             slice::from_raw_parts(acc_infos.as_ptr() as _, num_accounts)
         })
     }
+
+    /// Return the instruction data.
+    #[inline(always)]
+    pub fn instruction_data(&self) -> IxData<2> {
+        // Instruction data layout:
+        // -  [0]: instruction discriminator (1 byte, u8)
+        // -  [1]: m (1 byte, u8)
+        let mut instruction_data = IxData::<2>::new();
+
+        instruction_data.write_u8(19);
+        instruction_data.write_u8(self.m);
+
+        instruction_data
+    }
 }
@@ -5,6 +5,8 @@ use pinocchio::{
     ProgramResult,
 };
 
+use crate::instruction_data::IxData;
+
 /// Revokes the delegate's authority.
 ///
 /// ### Accounts:
@@ -17,7 +19,7 @@ pub struct Revoke<'a> {
     pub authority: &'a AccountInfo,
 }
 
-impl Revoke<'_> {
+impl<'a> Revoke<'a> {
     #[inline(always)]
     pub fn invoke(&self) -> ProgramResult {
         self.invoke_signed(&[])
@@ -25,18 +27,36 @@ impl Revoke<'_> {
 
     #[inline(always)]
     pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
-        // account metadata
-        let account_metas: [AccountMeta; 2] = [
-            AccountMeta::writable(self.source.key()),
-            AccountMeta::readonly_signer(self.authority.key()),
-        ];
+        let account_metas = self.account_metas();
+        let instruction_data = self.instruction_data();
 
         let instruction = Instruction {
             program_id: &crate::ID,
             accounts: &account_metas,
-            data: &[5],
+            data: instruction_data.finish(),
         };
 
         invoke_signed(&instruction, &[self.source, self.authority], signers)
     }
+
+    /// Return the account metas of the instruction.
+    #[inline(always)]
+    pub fn account_metas(&self) -> [AccountMeta<'a>; 2] {
+        [
+            AccountMeta::writable(self.source.key()),
+            AccountMeta::readonly_signer(self.authority.key()),
+        ]
+    }
+
+    /// Return the instruction data.
+    #[inline(always)]
+    pub fn instruction_data(&self) -> IxData<1> {
+        // Instruction data layout:
+        // -  [0]: instruction discriminator (1 byte, u8)
+        let mut instruction_data = IxData::<1>::new();
+
+        instruction_data.write_u8(5);
+
+        instruction_data
+    }
 }
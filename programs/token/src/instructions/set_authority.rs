@@ -1,5 +1,3 @@
-use core::slice::from_raw_parts;
-
 use pinocchio::{
     account_info::AccountInfo,
     instruction::{AccountMeta, Instruction, Signer},
@@ -8,7 +6,7 @@ use pinocchio::{
     ProgramResult,
 };
 
-use crate::{write_bytes, UNINIT_BYTE};
+use crate::instruction_data::IxData;
 
 #[repr(u8)]
 #[derive(Clone, Copy)]
@@ -35,7 +33,7 @@ pub struct SetAuthority<'a> {
     pub new_authority: Option<&'a Pubkey>,
 }
 
-impl SetAuthority<'_> {
+impl<'a> SetAuthority<'a> {
     #[inline(always)]
     pub fn invoke(&self) -> ProgramResult {
         self.invoke_signed(&[])
@@ -43,41 +41,41 @@ impl SetAuthority<'_> {
 
     #[inline(always)]
     pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
-        // account metadata
-        let account_metas: [AccountMeta; 2] = [
+        let account_metas = self.account_metas();
+        let instruction_data = self.instruction_data();
+
+        let instruction = Instruction {
+            program_id: &crate::ID,
+            accounts: &account_metas,
+            data: instruction_data.finish(),
+        };
+
+        invoke_signed(&instruction, &[self.account, self.authority], signers)
+    }
+
+    /// Return the account metas of the instruction.
+    #[inline(always)]
+    pub fn account_metas(&self) -> [AccountMeta<'a>; 2] {
+        [
             AccountMeta::writable(self.account.key()),
             AccountMeta::readonly_signer(self.authority.key()),
-        ];
+        ]
+    }
 
+    /// Return the instruction data.
+    #[inline(always)]
+    pub fn instruction_data(&self) -> IxData<35> {
         // instruction data
         // -  [0]: instruction discriminator (1 byte, u8)
         // -  [1]: authority_type (1 byte, u8)
         // -  [2]: new_authority presence flag (1 byte, AuthorityType)
         // -  [3..35] new_authority (optional, 32 bytes, Pubkey)
-        let mut instruction_data = [UNINIT_BYTE; 35];
-        let mut length = instruction_data.len();
-
-        // Set discriminator as u8 at offset [0]
-        write_bytes(&mut instruction_data, &[6]);
-        // Set authority_type as u8 at offset [1]
-        write_bytes(&mut instruction_data[1..2], &[self.authority_type as u8]);
-
-        if let Some(new_authority) = self.new_authority {
-            // Set new_authority as [u8; 32] at offset [2..35]
-            write_bytes(&mut instruction_data[2..3], &[1]);
-            write_bytes(&mut instruction_data[3..], new_authority);
-        } else {
-            write_bytes(&mut instruction_data[2..3], &[0]);
-            // Adjust length if no new authority
-            length = 3;
-        }
+        let mut instruction_data = IxData::<35>::new();
 
-        let instruction = Instruction {
-            program_id: &crate::ID,
-            accounts: &account_metas,
-            data: unsafe { from_raw_parts(instruction_data.as_ptr() as _, length) },
-        };
+        instruction_data.write_u8(6);
+        instruction_data.write_u8(self.authority_type as u8);
+        instruction_data.write_coption_pubkey(self.new_authority);
 
-        invoke_signed(&instruction, &[self.account, self.authority], signers)
+        instruction_data
     }
 }
@@ -5,6 +5,8 @@ use pinocchio::{
     ProgramResult,
 };
 
+use crate::instruction_data::IxData;
+
 /// Given a native token account updates its amount field based
 /// on the account's underlying `lamports`.
 ///
@@ -16,18 +18,36 @@ pub struct SyncNative<'a> {
     pub native_token: &'a AccountInfo,
 }
 
-impl SyncNative<'_> {
+impl<'a> SyncNative<'a> {
     #[inline(always)]
     pub fn invoke(&self) -> ProgramResult {
-        // account metadata
-        let account_metas: [AccountMeta; 1] = [AccountMeta::writable(self.native_token.key())];
+        let account_metas = self.account_metas();
+        let instruction_data = self.instruction_data();
 
         let instruction = Instruction {
             program_id: &crate::ID,
             accounts: &account_metas,
-            data: &[17],
+            data: instruction_data.finish(),
         };
 
         invoke(&instruction, &[self.native_token])
     }
+
+    /// Return the account metas of the instruction.
+    #[inline(always)]
+    pub fn account_metas(&self) -> [AccountMeta<'a>; 1] {
+        [AccountMeta::writable(self.native_token.key())]
+    }
+
+    /// Return the instruction data.
+    #[inline(always)]
+    pub fn instruction_data(&self) -> IxData<1> {
+        // Instruction data layout:
+        // -  [0]: instruction discriminator (1 byte, u8)
+        let mut instruction_data = IxData::<1>::new();
+
+        instruction_data.write_u8(17);
+
+        instruction_data
+    }
 }
@@ -1,17 +1,13 @@
 #![no_std]
 
+pub mod ids;
+#[cfg(feature = "shared")]
+pub mod instruction_data;
+#[cfg(not(feature = "shared"))]
+mod instruction_data;
 pub mod instructions;
+#[cfg(feature = "shared")]
+pub mod pod;
 pub mod state;
 
 pinocchio_pubkey::declare_id!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
-
-use core::mem::MaybeUninit;
-
-const UNINIT_BYTE: MaybeUninit<u8> = MaybeUninit::<u8>::uninit();
-
-#[inline(always)]
-fn write_bytes(destination: &mut [MaybeUninit<u8>], source: &[u8]) {
-    for (d, s) in destination.iter_mut().zip(source.iter()) {
-        d.write(*s);
-    }
-}
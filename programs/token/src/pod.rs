@@ -0,0 +1,198 @@
+//! Plain-old-data types used by the account layouts.
+//!
+//! Account fields are stored without padding, so every type in this module
+//! has an alignment of 1 and can be read from any offset of the account data.
+
+use pinocchio::{program_error::ProgramError, pubkey::Pubkey};
+
+/// Tag of a `COption` without a value.
+const COPTION_NONE: [u8; 4] = [0; 4];
+
+/// Tag of a `COption` with a value.
+const COPTION_SOME: [u8; 4] = [1, 0, 0, 0];
+
+/// Reader of a `COption`, stored as a 4-byte little-endian tag (`0` for
+/// `None`, `1` for `Some`) followed by the value.
+///
+/// The value is always present in the layout, even when the tag is `0`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct COption<'a, T = Pubkey> {
+    tag: &'a [u8; 4],
+    value: &'a T,
+}
+
+impl<'a, T> COption<'a, T> {
+    /// Create a reader from the tag and the value of the `COption`.
+    #[inline(always)]
+    pub fn new(tag: &'a [u8; 4], value: &'a T) -> Self {
+        Self { tag, value }
+    }
+
+    /// Return the value of the `COption`.
+    ///
+    /// Returns `InvalidAccountData` if the tag is neither `0` nor `1`.
+    #[inline]
+    pub fn get(&self) -> Result<Option<&'a T>, ProgramError> {
+        match *self.tag {
+            COPTION_NONE => Ok(None),
+            COPTION_SOME => Ok(Some(self.value)),
+            _ => Err(ProgramError::InvalidAccountData),
+        }
+    }
+}
+
+/// Define a little-endian integer stored as a byte array.
+macro_rules! pod_int {
+    ($(#[$meta:meta])* $name:ident, $int:ty, $len:literal) => {
+        $(#[$meta])*
+        #[repr(transparent)]
+        #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+        pub struct $name(pub [u8; $len]);
+
+        impl $name {
+            /// Return the value of the integer.
+            #[inline(always)]
+            pub const fn get(&self) -> $int {
+                <$int>::from_le_bytes(self.0)
+            }
+
+            /// Set the value of the integer.
+            #[inline(always)]
+            pub fn set(&mut self, value: $int) {
+                self.0 = value.to_le_bytes();
+            }
+        }
+
+        impl From<$int> for $name {
+            #[inline(always)]
+            fn from(value: $int) -> Self {
+                Self(value.to_le_bytes())
+            }
+        }
+
+        impl From<$name> for $int {
+            #[inline(always)]
+            fn from(value: $name) -> Self {
+                value.get()
+            }
+        }
+    };
+}
+
+pod_int!(
+    /// `u16` stored as 2 little-endian bytes.
+    PodU16,
+    u16,
+    2
+);
+
+pod_int!(
+    /// `i16` stored as 2 little-endian bytes.
+    PodI16,
+    i16,
+    2
+);
+
+pod_int!(
+    /// `u64` stored as 8 little-endian bytes.
+    PodU64,
+    u64,
+    8
+);
+
+pod_int!(
+    /// `i64` stored as 8 little-endian bytes.
+    PodI64,
+    i64,
+    8
+);
+
+/// `bool` stored as a single byte.
+///
+/// Any nonzero byte is read as `true`, so the type is valid for every byte
+/// pattern, unlike `bool`.
+#[repr(transparent)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PodBool(pub u8);
+
+impl PodBool {
+    /// Return the value of the flag.
+    #[inline(always)]
+    pub const fn get(&self) -> bool {
+        self.0 != 0
+    }
+
+    /// Set the value of the flag.
+    #[inline(always)]
+    pub fn set(&mut self, value: bool) {
+        self.0 = value as u8;
+    }
+}
+
+impl From<bool> for PodBool {
+    #[inline(always)]
+    fn from(value: bool) -> Self {
+        Self(value as u8)
+    }
+}
+
+impl From<PodBool> for bool {
+    #[inline(always)]
+    fn from(value: PodBool) -> Self {
+        value.get()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_coption() {
+        let key = [7; 32];
+
+        assert_eq!(COption::new(&[0; 4], &key).get(), Ok(None));
+        assert_eq!(COption::new(&[1, 0, 0, 0], &key).get(), Ok(Some(&key)));
+
+        for tag in [[2, 0, 0, 0], [1, 0, 0, 1], [0, 1, 0, 0], [u8::MAX; 4]] {
+            assert_eq!(
+                COption::new(&tag, &key).get(),
+                Err(ProgramError::InvalidAccountData)
+            );
+        }
+
+        let amount = 5u64.to_le_bytes();
+        assert_eq!(
+            COption::new(&[1, 0, 0, 0], &amount).get(),
+            Ok(Some(&amount))
+        );
+    }
+
+    #[test]
+    fn test_pod_int() {
+        let mut value = PodU64::from(u64::MAX - 1);
+        assert_eq!(value.0, (u64::MAX - 1).to_le_bytes());
+        value.set(7);
+        assert_eq!(u64::from(value), 7);
+
+        assert_eq!(PodI64::from(-2).get(), -2);
+        assert_eq!(PodI64([0xff; 8]).get(), -1);
+        assert_eq!(PodU16([0x10, 0x27]).get(), 10_000);
+        assert_eq!(PodI16::from(-500).0, (-500i16).to_le_bytes());
+
+        assert_eq!(core::mem::align_of::<PodU64>(), 1);
+        assert_eq!(core::mem::align_of::<PodI16>(), 1);
+    }
+
+    #[test]
+    fn test_pod_bool() {
+        assert!(!PodBool(0).get());
+        assert!(PodBool(1).get());
+        assert!(PodBool(2).get());
+
+        let mut flag = PodBool::from(true);
+        assert_eq!(flag, PodBool(1));
+        flag.set(false);
+        assert!(!bool::from(flag));
+    }
+}